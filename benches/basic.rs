@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
 use serde::Deserialize;
-use serde_ply::PlyReader;
+use serde_ply::{PlyReader, PodLayout, ScalarType};
 use std::io::{BufReader, Cursor};
 
 #[derive(Deserialize)]
@@ -195,5 +195,95 @@ fn benchmark_parsing(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, benchmark_parsing);
+fn benchmark_skipping(c: &mut Criterion) {
+    let vertex_count = 1_000_000;
+
+    let mut group = c.benchmark_group("skipping");
+
+    let realistic_data = generate_realistic_ply(vertex_count);
+    group.throughput(Throughput::Bytes(realistic_data.len() as u64));
+    group.sample_size(20);
+    group.bench_function("read_1m_rows", |b| {
+        b.iter(|| {
+            let cursor = Cursor::new(&realistic_data);
+            let mut file = PlyReader::from_reader(BufReader::new(cursor)).unwrap();
+            let vertices: Vec<VertexWithColor> = file.next_element().unwrap();
+            black_box(vertices);
+        });
+    });
+    group.bench_function("skip_1m_rows", |b| {
+        b.iter(|| {
+            let cursor = Cursor::new(&realistic_data);
+            let mut file = PlyReader::from_reader(BufReader::new(cursor)).unwrap();
+            let rows: Vec<()> = file.next_element().unwrap();
+            black_box(rows);
+        });
+    });
+
+    group.finish();
+}
+
+/// Big endian element of about 100MB, with rows of 12 floats, 2 ushorts and a double.
+fn generate_big_endian_ply(vertex_count: usize) -> Vec<u8> {
+    let mut header = format!("ply\nformat binary_big_endian 1.0\nelement vertex {vertex_count}\n");
+    for i in 0..12 {
+        header += &format!("property float f{i}\n");
+    }
+    header += "property ushort u0\nproperty ushort u1\nproperty double d\nend_header\n";
+
+    let mut data = header.into_bytes();
+    for i in 0..vertex_count {
+        for j in 0..12 {
+            data.extend_from_slice(&((i * 12 + j) as f32 * 0.01).to_be_bytes());
+        }
+        data.extend_from_slice(&(i as u16).to_be_bytes());
+        data.extend_from_slice(&((i >> 16) as u16).to_be_bytes());
+        data.extend_from_slice(&(i as f64 * 0.5).to_be_bytes());
+    }
+    data
+}
+
+fn benchmark_pod_big_endian(c: &mut Criterion) {
+    let vertex_count = 1_750_000;
+    let data = generate_big_endian_ply(vertex_count);
+    let row_size = 12 * 4 + 2 * 2 + 8;
+    let layout = |stride: usize| {
+        let mut layout = PodLayout::new(stride);
+        for i in 0..12 {
+            layout = layout.field(&format!("f{i}"), i * 4, ScalarType::F32);
+        }
+        layout
+            .field("u0", 48, ScalarType::U16)
+            .field("u1", 50, ScalarType::U16)
+            .field("d", 52, ScalarType::F64)
+    };
+
+    let mut group = c.benchmark_group("pod_big_endian");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.sample_size(10);
+    // The layout matches the file, so rows are copied and byte swapped in bulk.
+    let packed = layout(row_size);
+    group.bench_function("bulk_swap", |b| {
+        b.iter(|| {
+            let mut file = PlyReader::from_reader(Cursor::new(&data)).unwrap();
+            black_box(file.next_element_pod_bytes(&packed).unwrap());
+        });
+    });
+    // Spare bytes at the end of each row make every value go through the converting path.
+    let padded = layout(row_size + 4);
+    group.bench_function("per_value", |b| {
+        b.iter(|| {
+            let mut file = PlyReader::from_reader(Cursor::new(&data)).unwrap();
+            black_box(file.next_element_pod_bytes(&padded).unwrap());
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_parsing,
+    benchmark_skipping,
+    benchmark_pod_big_endian
+);
 criterion_main!(benches);
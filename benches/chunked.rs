@@ -80,5 +80,35 @@ fn benchmark_chunked_parsing(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, benchmark_chunked_parsing);
+fn benchmark_small_feeds(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chunked_small_feeds");
+
+    // Large enough that moving the backlog on every read dominates.
+    let vertex_count = 1_000_000;
+    let binary_data = generate_binary_ply(vertex_count);
+    group.throughput(Throughput::Bytes(binary_data.len() as u64));
+    group.sample_size(10);
+
+    group.bench_function("next_rows_backlog", |b| {
+        b.iter(|| {
+            let mut ply_file = serde_ply::PlyChunkedReader::new();
+            let mut count = 0;
+
+            // Feed 4 KiB at a time but read fewer rows than arrive, so a backlog of
+            // megabytes builds up behind the rows being read.
+            for chunk in binary_data.chunks(4096) {
+                ply_file.buffer_mut().extend_from_slice(black_box(chunk));
+                count += ply_file.next_rows::<Vertex>(64).unwrap().len();
+            }
+            while ply_file.buffered_len() > 0 {
+                count += ply_file.next_rows::<Vertex>(64).unwrap().len();
+            }
+            assert_eq!(count, vertex_count);
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_chunked_parsing, benchmark_small_feeds);
 criterion_main!(benches);
@@ -0,0 +1,109 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use serde::Serialize;
+use serde_ply::SerializeOptions;
+
+#[derive(Serialize)]
+struct Vertex {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+#[derive(Serialize)]
+struct Ply {
+    vertex: Vec<Vertex>,
+}
+
+fn generate_ply(vertex_count: usize) -> Ply {
+    Ply {
+        vertex: (0..vertex_count)
+            .map(|i| {
+                let base = i as f32 * 0.01;
+                Vertex {
+                    x: base,
+                    y: base + 1.0,
+                    z: base + 2.0,
+                }
+            })
+            .collect(),
+    }
+}
+
+fn benchmark_serialize_file(c: &mut Criterion) {
+    let vertex_count = 100_000;
+    let ply = generate_ply(vertex_count);
+    let path = std::env::temp_dir().join("serde_ply_bench_serialize.ply");
+
+    let mut group = c.benchmark_group("serialize_file");
+    group.throughput(Throughput::Elements(vertex_count as u64));
+    group.sample_size(10);
+
+    // An unbuffered file turns every value into a system call.
+    for (name, options) in [
+        ("ascii_100k_rows_unbuffered", SerializeOptions::ascii()),
+        (
+            "ascii_100k_rows_buffer_hint",
+            SerializeOptions::ascii().buffer_hint(64 * 1024),
+        ),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let file = std::fs::File::create(&path).unwrap();
+                serde_ply::to_writer(black_box(&ply), options.clone(), file).unwrap();
+            });
+        });
+    }
+
+    group.finish();
+    let _ = std::fs::remove_file(&path);
+}
+
+// Run with `--features parallel`.
+#[cfg(feature = "parallel")]
+fn benchmark_serialize_parallel(c: &mut Criterion) {
+    let vertex_count = 1_000_000;
+    let ply = generate_ply(vertex_count);
+
+    let mut group = c.benchmark_group("serialize_parallel");
+    group.throughput(Throughput::Elements(vertex_count as u64));
+    group.sample_size(10);
+
+    for (name, options) in [
+        ("ascii", SerializeOptions::ascii as fn() -> SerializeOptions),
+        ("binary", SerializeOptions::binary_le),
+    ] {
+        // Writes to a sink can skip formatting entirely, so both write to a buffer.
+        let mut out = Vec::new();
+        group.bench_function(format!("{name}_1m_rows_sequential"), |b| {
+            b.iter(|| {
+                out.clear();
+                serde_ply::to_writer(black_box(&ply), options(), &mut out).unwrap();
+            });
+        });
+        group.bench_function(format!("{name}_1m_rows_parallel"), |b| {
+            b.iter(|| {
+                out.clear();
+                serde_ply::to_writer_parallel(
+                    &mut out,
+                    options(),
+                    "vertex",
+                    black_box(&ply.vertex),
+                    16 * 1024,
+                )
+                .unwrap();
+            });
+        });
+    }
+
+    group.finish();
+}
+
+#[cfg(not(feature = "parallel"))]
+criterion_group!(benches, benchmark_serialize_file);
+#[cfg(feature = "parallel")]
+criterion_group!(
+    benches,
+    benchmark_serialize_file,
+    benchmark_serialize_parallel
+);
+criterion_main!(benches);
@@ -57,8 +57,8 @@ end_header
     println!("\nParse with header element by element: \n");
     let cursor = Cursor::new(ply_data);
     let mut file = PlyReader::from_reader(BufReader::new(cursor))?;
-    let vertices: Vec<Vertex> = file.next_element()?;
-    let faces: Vec<Face> = file.next_element()?;
+    let vertices: Vec<Vertex> = file.next_element_named("vertex")?;
+    let faces: Vec<Face> = file.next_element_named("face")?;
 
     println!(
         "Parsed {} vertices and {} faces",
@@ -0,0 +1,50 @@
+use serde::{de::DeserializeSeed, Deserialize};
+use serde_ply::{DeserializeError, PlyChunkedReader, RowVisitor};
+
+#[derive(Deserialize, Debug)]
+struct Vertex {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+fn point_cloud(vertex_count: usize) -> Vec<u8> {
+    let mut data = format!(
+        "ply\nformat binary_little_endian 1.0\nelement vertex {vertex_count}\nproperty float x\nproperty float y\nproperty float z\nend_header\n"
+    )
+    .into_bytes();
+    for i in 0..vertex_count {
+        for coord in [i as f32, 0.0, 1.0] {
+            data.extend_from_slice(&coord.to_le_bytes());
+        }
+    }
+    data
+}
+
+fn main() -> Result<(), DeserializeError> {
+    let data = point_cloud(10_000);
+
+    // Keep every 10th point while streaming, without collecting the full cloud first.
+    let keep_every = 10;
+    let mut kept = Vec::new();
+
+    let mut file = PlyChunkedReader::new();
+    // Reuse a single visitor for all chunks so the row index passed to the filter
+    // counts from the start of the element.
+    let mut visitor = RowVisitor::new(|vertex: Vertex| kept.push(vertex))
+        .with_row_filter(|index| index % keep_every == 0);
+
+    for chunk in data.chunks(4096) {
+        file.buffer_mut().extend_from_slice(chunk);
+        if file.current_element().is_some() {
+            visitor.deserialize(&mut file)?;
+        }
+    }
+    file.finish()?;
+
+    println!("Kept {} of 10000 vertices", kept.len());
+    if let Some(last) = kept.last() {
+        println!("Last kept vertex: ({}, {}, {})", last.x, last.y, last.z);
+    }
+    Ok(())
+}
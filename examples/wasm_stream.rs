@@ -0,0 +1,100 @@
+//! Streams a PLY file into a `PlyChunkedReader` the way a browser fetch would, in chunks
+//! of whatever size the network delivers, while only parsing a bounded number of rows per
+//! frame.
+//!
+//! Builds for `wasm32-unknown-unknown` without any bindings:
+//! `cargo build --example wasm_stream --features wasm --target wasm32-unknown-unknown`.
+//! The chunk arrival is simulated, in a real app the chunks come from a
+//! `ReadableStream` reader.
+
+use serde::Deserialize;
+use serde_ply::{DeserializeError, PlyChunkedReader};
+
+#[derive(Deserialize)]
+struct Splat {
+    x: f32,
+    y: f32,
+    z: f32,
+    opacity: f32,
+}
+
+/// Stop pulling from the stream while this many bytes wait to be parsed.
+const HIGH_WATER_MARK: usize = 64 * 1024;
+/// Rows to parse per frame, so a large file doesn't stall rendering.
+const ROWS_PER_FRAME: usize = 1000;
+
+/// Stands in for the network: hands out the file in chunks of irregular sizes.
+struct SimulatedStream {
+    data: Vec<u8>,
+    pos: usize,
+    chunk: usize,
+}
+
+impl SimulatedStream {
+    /// Copy the next chunk straight into `buf`, returning false at the end of the stream.
+    fn read_into(&mut self, buf: &mut Vec<u8>) -> bool {
+        if self.pos == self.data.len() {
+            return false;
+        }
+        self.chunk = self.chunk * 7 % 16_381 + 512;
+        let end = (self.pos + self.chunk).min(self.data.len());
+        buf.extend_from_slice(&self.data[self.pos..end]);
+        self.pos = end;
+        true
+    }
+}
+
+fn splat_file(count: usize) -> Vec<u8> {
+    let mut data = format!(
+        "ply\nformat binary_little_endian 1.0\nelement vertex {count}\nproperty float x\nproperty float y\nproperty float z\nproperty float opacity\nend_header\n"
+    )
+    .into_bytes();
+    for i in 0..count {
+        for v in [i as f32, 0.0, 1.0, 0.5] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+    data
+}
+
+fn main() -> Result<(), DeserializeError> {
+    let count = 100_000;
+    let mut stream = SimulatedStream {
+        data: splat_file(count),
+        pos: 0,
+        chunk: 1,
+    };
+    let mut file = PlyChunkedReader::new();
+    let mut splats: Vec<Splat> = Vec::with_capacity(count);
+    let mut frames = 0;
+    let mut stream_done = false;
+
+    loop {
+        // Backpressure: only pull more data while the parser keeps up. The chunk is
+        // written into the parser's own buffer, no intermediate copy is kept around.
+        while !stream_done && file.buffered_len() < HIGH_WATER_MARK {
+            stream_done = !stream.read_into(file.buffer_mut());
+        }
+
+        // One frame worth of rows. Rows that are still incomplete stay buffered.
+        if file.current_element().is_some() {
+            let rows = file.next_rows::<Splat>(ROWS_PER_FRAME)?;
+            if rows.is_empty() && stream_done {
+                break;
+            }
+            splats.extend(rows);
+        } else if stream_done {
+            break;
+        }
+        frames += 1;
+    }
+    file.finish()?;
+
+    let opacity: f32 = splats.iter().map(|s| s.opacity).sum();
+    let last = splats.last().map(|s| (s.x, s.y, s.z));
+    println!(
+        "Loaded {} splats over {frames} frames, total opacity {opacity}, last at {last:?}",
+        splats.len()
+    );
+    Ok(())
+}
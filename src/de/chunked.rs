@@ -1,13 +1,16 @@
 use crate::{
     de::{
+        progress::{ElementState, Progress},
+        stats::PrecisionWarning,
         val_reader::{AsciiValReader, BinValReader, ScalarReader},
         RowDeserializer,
     },
-    DeserializeError, ElementDef, PlyFormat, PlyHeader,
+    DeserializeError, DeserializeOptions, ElementDef, LoadReport, PlyFormat, PlyHeader,
+    PropertyType,
 };
 use byteorder::{BigEndian, LittleEndian};
 use serde::{
-    de::{DeserializeSeed, Error, SeqAccess, Visitor},
+    de::{DeserializeSeed, Error, IgnoredAny, SeqAccess, Visitor},
     Deserialize, Deserializer,
 };
 use std::{io::Cursor, marker::PhantomData};
@@ -56,6 +59,17 @@ pub struct PlyChunkedReader {
     current_element_index: usize,
     rows_parsed: usize,
     data_buffer: Vec<u8>,
+    // Bytes at the front of `data_buffer` that were already parsed. They're dropped once
+    // they make up half the buffer, so reading a few rows at a time doesn't move the rest.
+    consumed: usize,
+    // Most rows the next read may return, see `next_rows`.
+    row_limit: Option<usize>,
+    options: DeserializeOptions,
+    // Set when the header was handed in, until the data is known not to start with another header.
+    check_no_header: bool,
+    report: Option<LoadReport>,
+    precision_warnings: Vec<PrecisionWarning>,
+    progress: Progress,
 }
 
 impl PlyChunkedReader {
@@ -66,17 +80,147 @@ impl PlyChunkedReader {
             current_element_index: 0,
             rows_parsed: 0,
             data_buffer: Vec::new(),
+            consumed: 0,
+            row_limit: None,
+            options: DeserializeOptions::default(),
+            check_no_header: false,
+            report: None,
+            precision_warnings: Vec::new(),
+            progress: Progress::default(),
         }
     }
 
+    /// Create a chunked parser for a body whose header was already parsed elsewhere.
+    ///
+    /// The buffer should only receive element data, starting with the first row of
+    /// the first element. Feeding data that starts with another `ply` header is an error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::{
+    ///     ElementDef, PlyChunkedReader, PlyFormat, PlyHeader, PlyProperty, PropertyType,
+    ///     ScalarType,
+    /// };
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let header = PlyHeader::new(
+    ///     PlyFormat::Ascii,
+    ///     vec![ElementDef {
+    ///         name: "vertex".to_string(),
+    ///         count: 1,
+    ///         properties: vec![PlyProperty::new("x", PropertyType::Scalar(ScalarType::F32))],
+    ///     }],
+    /// );
+    ///
+    /// let mut file = PlyChunkedReader::with_header(header);
+    /// file.buffer_mut().extend_from_slice(b"1.5\n");
+    /// let vertices: Vec<Vertex> = file.next_chunk()?;
+    /// assert_eq!(vertices[0].x, 1.5);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_header(header: PlyHeader) -> Self {
+        Self::from_parts(header, Vec::new())
+    }
+
+    /// Create a chunked parser from a parsed header and any body bytes that were read
+    /// past the end of the header.
+    ///
+    /// Useful to hand over to this parser after parsing the header by other means.
+    /// See [`Self::with_header`].
+    pub fn from_parts(header: PlyHeader, leftover: Vec<u8>) -> Self {
+        Self {
+            progress: Progress::new(&header),
+            header: Some(header),
+            data_buffer: leftover,
+            check_no_header: true,
+            ..Self::new()
+        }
+    }
+
+    /// Use the given [`DeserializeOptions`] for all subsequent reads.
+    pub fn with_options(mut self, mut options: DeserializeOptions) -> Self {
+        if let Some(header) = &mut self.header {
+            options.apply_to_header(header);
+        }
+        self.options = options;
+        self.init_report();
+        self
+    }
+
+    /// Continue at the start of the element with index `element_index`, adding to `report`,
+    /// `precision_warnings` and `progress`.
+    pub(crate) fn resume_at(
+        mut self,
+        element_index: usize,
+        report: Option<LoadReport>,
+        precision_warnings: Vec<PrecisionWarning>,
+        progress: Progress,
+    ) -> Self {
+        self.current_element_index = element_index;
+        self.precision_warnings = precision_warnings;
+        self.progress = progress;
+        if report.is_some() {
+            self.report = report;
+        }
+        // Past the first element, data could start with anything.
+        self.check_no_header &= element_index == 0;
+        self
+    }
+
     /// Get mutable access to the internal buffer.
     ///
     /// Allows writing data directly into the parser's buffer without copies.
     /// Useful for async readers or when feeding data in chunks.
+    ///
+    /// Only append to the buffer. Its front can still hold bytes that were already
+    /// parsed, which are dropped in bulk rather than on every read; use
+    /// [`Self::buffered_len`] for the number of bytes waiting to be parsed.
     pub fn buffer_mut(&mut self) -> &mut Vec<u8> {
         &mut self.data_buffer
     }
 
+    /// Number of bytes fed in but not parsed yet.
+    ///
+    /// Useful to apply backpressure, eg. only feed more data while this is below a limit.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyChunkedReader;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let mut file = PlyChunkedReader::new();
+    /// file.buffer_mut().extend_from_slice(
+    ///     b"ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nend_header\n1.0\n2."
+    /// );
+    /// let vertices: Vec<Vertex> = file.next_chunk()?;
+    /// assert_eq!(vertices.len(), 1);
+    /// assert_eq!(file.buffered_len(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn buffered_len(&self) -> usize {
+        self.data_buffer.len() - self.consumed
+    }
+
+    fn unparsed(&self) -> &[u8] {
+        &self.data_buffer[self.consumed..]
+    }
+
+    /// Mark `len` more bytes as parsed, dropping the parsed bytes once they take up at
+    /// least half the buffer. Every byte is then moved at most once on average.
+    fn consume(&mut self, len: usize) {
+        self.consumed += len;
+        if self.consumed * 2 >= self.data_buffer.len() {
+            self.data_buffer.drain(..self.consumed);
+            self.consumed = 0;
+        }
+    }
+
     /// Get the parsed PLY header if available.
     ///
     /// Returns `None` if there isn't enough buffered data to parse the complete header.
@@ -95,16 +239,52 @@ impl PlyChunkedReader {
     /// assert!(file.header().is_some());
     /// ```
     pub fn header(&mut self) -> Option<&PlyHeader> {
+        let _ = self.try_parse_header();
+        self.header.as_ref()
+    }
+
+    /// Statistics of the data read so far.
+    ///
+    /// Returns `None` unless [`DeserializeOptions::collect_stats`] is enabled, or while the
+    /// header isn't parsed yet.
+    pub fn report(&self) -> Option<&LoadReport> {
+        self.report.as_ref()
+    }
+
+    /// Properties whose `double` values lost precision in `f32` fields so far.
+    ///
+    /// Empty unless the policy is [`crate::PrecisionLoss::Warn`], the default. See
+    /// [`DeserializeOptions::with_precision_loss`].
+    pub fn precision_warnings(&self) -> &[PrecisionWarning] {
+        &self.precision_warnings
+    }
+
+    fn init_report(&mut self) {
+        self.report = match &self.header {
+            Some(header) if self.options.collect_stats => Some(LoadReport::new(header)),
+            _ => None,
+        };
+    }
+
+    /// Parse the header if enough data is buffered. Running out of data is not an
+    /// error, but a malformed header is.
+    fn try_parse_header(&mut self) -> Result<(), DeserializeError> {
         if self.header.is_none() {
-            let available_data = &self.data_buffer;
-            let mut cursor = Cursor::new(available_data);
-            let header = PlyHeader::parse(&mut cursor);
-            if let Ok(header) = header {
-                self.header = Some(header);
-                self.data_buffer.drain(..cursor.position() as usize);
+            let mut cursor = Cursor::new(self.unparsed());
+            match PlyHeader::parse(&mut cursor) {
+                Ok(mut header) => {
+                    let header_len = cursor.position() as usize;
+                    self.options.apply_to_header(&mut header);
+                    self.progress = Progress::new(&header);
+                    self.header = Some(header);
+                    self.consume(header_len);
+                    self.init_report();
+                }
+                Err(e) if e.0.kind() == std::io::ErrorKind::UnexpectedEof => {}
+                Err(e) => return Err(e),
             }
         }
-        self.header.as_ref()
+        Ok(())
     }
 
     /// Deserialize as many complete elements as possible from the current buffer.
@@ -118,6 +298,39 @@ impl PlyChunkedReader {
         T::deserialize(self)
     }
 
+    /// Deserialize at most `max_rows` complete rows of the current element from the
+    /// current buffer.
+    ///
+    /// Like [`Self::next_chunk`], but leaves the rest of the buffered rows for later
+    /// calls, eg. to process a large buffer in bounded batches.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyChunkedReader;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let mut file = PlyChunkedReader::new();
+    /// file.buffer_mut().extend_from_slice(
+    ///     b"ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nend_header\n1\n2\n3\n"
+    /// );
+    /// let first: Vec<Vertex> = file.next_rows(2)?;
+    /// let rest: Vec<Vertex> = file.next_rows(2)?;
+    /// assert_eq!((first.len(), rest.len()), (2, 1));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn next_rows<T>(&mut self, max_rows: usize) -> Result<Vec<T>, DeserializeError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.row_limit = Some(max_rows);
+        let rows = Vec::<T>::deserialize(&mut *self);
+        self.row_limit = None;
+        rows
+    }
+
     /// Get the current element definition being processed.
     ///
     /// Returns `None` when the header isn't parsed yet, or when all elements
@@ -146,6 +359,63 @@ impl PlyChunkedReader {
     pub fn rows_done(&self) -> usize {
         self.rows_parsed
     }
+
+    /// State of every element of the file, see [`crate::PlyReader::element_states`].
+    ///
+    /// Empty until the header is parsed.
+    pub fn element_states(&self) -> impl Iterator<Item = ElementState> + '_ {
+        self.progress.states()
+    }
+
+    /// Check that the whole file was read, once no more data can arrive.
+    ///
+    /// Errors with [`std::io::ErrorKind::UnexpectedEof`] if the header is incomplete, or if
+    /// any element has fewer rows than declared. No data at all gives an error for which
+    /// [`DeserializeError::is_empty_input`] holds, and an element with missing rows one
+    /// with [`DeserializeError::as_missing_data`]. Unread data after the last element is
+    /// ignored.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyChunkedReader;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let mut file = PlyChunkedReader::new();
+    /// file.buffer_mut().extend_from_slice(
+    ///     b"ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nend_header\n1.0\n"
+    /// );
+    /// let vertices: Vec<Vertex> = file.next_chunk()?;
+    /// assert_eq!(vertices.len(), 1);
+    /// assert!(file.finish().is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn finish(&mut self) -> Result<(), DeserializeError> {
+        self.try_parse_header()?;
+        let Some(header) = &self.header else {
+            if self.buffered_len() == 0 {
+                return Err(DeserializeError::empty_input());
+            }
+            return Err(DeserializeError::header(
+                std::io::ErrorKind::UnexpectedEof,
+                "File ended before the end of the header",
+            ));
+        };
+        let mut rows_done = self.rows_parsed;
+        for elem_def in header.elem_defs.iter().skip(self.current_element_index) {
+            if rows_done < elem_def.count {
+                return Err(DeserializeError::missing_data(
+                    &elem_def.name,
+                    elem_def.count,
+                    rows_done,
+                ));
+            }
+            rows_done = 0;
+        }
+        Ok(())
+    }
 }
 
 impl<'de> Deserializer<'de> for &'_ mut PlyChunkedReader {
@@ -162,63 +432,111 @@ impl<'de> Deserializer<'de> for &'_ mut PlyChunkedReader {
     where
         V: serde::de::Visitor<'de>,
     {
-        let _ = self.header();
         // Make sure header is parsed
+        self.try_parse_header()?;
+
+        if self.check_no_header && self.buffered_len() > 0 {
+            const MAGIC: &[u8] = b"ply";
+            let buf = self.unparsed();
+            if buf.len() <= MAGIC.len() && MAGIC.starts_with(buf) {
+                // Can't tell yet, wait for more data.
+                return visitor.visit_seq(EmptySeq);
+            }
+            if buf.starts_with(MAGIC) && matches!(buf[MAGIC.len()], b'\n' | b'\r') {
+                return Err(DeserializeError::custom(
+                    "Data starts with a ply header, but the header was already provided",
+                ));
+            }
+            self.check_no_header = false;
+        }
         let Some(header) = &self.header else {
             return visitor.visit_seq(EmptySeq);
         };
 
         // Check if we've moved past all elements, if so error that we've run out of elements.
         if self.current_element_index >= header.elem_defs.len() {
-            return Err(DeserializeError::custom("Ran out of elements"));
+            return Err(DeserializeError::elements_exhausted(None));
         }
 
         let elem_def = &header.elem_defs[self.current_element_index];
-
-        let mut cursor = Cursor::new(&self.data_buffer);
-        let remaining = elem_def.count - self.rows_parsed;
+        let mut stats = self
+            .report
+            .as_mut()
+            .map(|r| &mut r.elements[self.current_element_index]);
+
+        let mut cursor = Cursor::new(&self.data_buffer[self.consumed..]);
+        let count = elem_def.count;
+        let remaining = count - self.rows_parsed;
+        let batch = self
+            .row_limit
+            .map_or(remaining, |limit| remaining.min(limit));
+        // Warnings of the row being read, kept apart until the row is complete.
+        let mut row_warnings = Vec::new();
+        let min_row_size = min_row_size(elem_def, header.format);
 
         let (res, rows_remaining) = match header.format {
             PlyFormat::Ascii => {
                 let mut seq = ChunkPlyReaderSeqVisitor {
-                    remaining,
+                    remaining: batch,
+                    min_row_size,
                     row: RowDeserializer::<_, AsciiValReader>::new(
                         &mut cursor,
-                        &elem_def.properties,
-                    ),
+                        elem_def,
+                        &self.options,
+                    )
+                    .with_short_rows_pending()
+                    .with_stats(stats.take())
+                    .with_warnings(&mut row_warnings),
+                    row_index: count - remaining,
+                    warnings: &mut self.precision_warnings,
                 };
                 let res = visitor.visit_seq(&mut seq)?;
                 (res, seq.remaining)
             }
             PlyFormat::BinaryLittleEndian => {
                 let mut seq = ChunkPlyReaderSeqVisitor {
-                    remaining,
+                    remaining: batch,
+                    min_row_size,
                     row: RowDeserializer::<_, BinValReader<LittleEndian>>::new(
                         &mut cursor,
-                        &elem_def.properties,
-                    ),
+                        elem_def,
+                        &self.options,
+                    )
+                    .with_stats(stats.take())
+                    .with_warnings(&mut row_warnings),
+                    row_index: count - remaining,
+                    warnings: &mut self.precision_warnings,
                 };
                 let res = visitor.visit_seq(&mut seq)?;
                 (res, seq.remaining)
             }
             PlyFormat::BinaryBigEndian => {
                 let mut seq = ChunkPlyReaderSeqVisitor {
-                    remaining,
+                    remaining: batch,
+                    min_row_size,
                     row: RowDeserializer::<_, BinValReader<BigEndian>>::new(
                         &mut cursor,
-                        &elem_def.properties,
-                    ),
+                        elem_def,
+                        &self.options,
+                    )
+                    .with_stats(stats.take())
+                    .with_warnings(&mut row_warnings),
+                    row_index: count - remaining,
+                    warnings: &mut self.precision_warnings,
                 };
                 let res = visitor.visit_seq(&mut seq)?;
                 (res, seq.remaining)
             }
         };
 
-        self.rows_parsed = elem_def.count - rows_remaining;
-        self.data_buffer.drain(..cursor.position() as usize);
+        self.rows_parsed += batch - rows_remaining;
+        let parsed_len = cursor.position() as usize;
+        self.consume(parsed_len);
+        self.progress
+            .rows_done(self.current_element_index, self.rows_parsed);
 
         // If we've parsed all elements move to the next element.
-        if self.rows_parsed >= elem_def.count {
+        if self.rows_parsed >= count {
             self.rows_parsed = 0;
             self.current_element_index += 1;
         }
@@ -257,9 +575,30 @@ impl<'de> SeqAccess<'de> for EmptySeq {
     }
 }
 
+/// Fewest bytes a row of `elem_def` can take, to estimate how many rows a buffer holds.
+fn min_row_size(elem_def: &ElementDef, format: PlyFormat) -> usize {
+    let size = match format {
+        // Every value is at least a digit and a separator.
+        PlyFormat::Ascii => 2 * elem_def.properties.len(),
+        PlyFormat::BinaryLittleEndian | PlyFormat::BinaryBigEndian => elem_def
+            .properties
+            .iter()
+            .map(|p| match p.property_type {
+                PropertyType::Scalar(ty) => ty.size_bytes(),
+                PropertyType::List { count_type, .. } => count_type.size_bytes(),
+            })
+            .sum(),
+    };
+    size.max(1)
+}
+
 struct ChunkPlyReaderSeqVisitor<'a, D: AsRef<[u8]>, S: ScalarReader> {
     remaining: usize,
+    min_row_size: usize,
+    // Index of the next row in the element.
+    row_index: usize,
     row: RowDeserializer<'a, Cursor<D>, S>,
+    warnings: &'a mut Vec<PrecisionWarning>,
 }
 
 impl<'de, D: AsRef<[u8]>, S: ScalarReader> SeqAccess<'de>
@@ -276,14 +615,22 @@ impl<'de, D: AsRef<[u8]>, S: ScalarReader> SeqAccess<'de>
         }
 
         let last_pos = self.row.reader.position();
+        self.row.row_index = self.row_index;
         match seed.deserialize(&mut self.row) {
             Ok(element) => {
                 self.remaining -= 1;
+                self.row_index += 1;
+                if let Some(row_warnings) = self.row.warnings_mut() {
+                    PrecisionWarning::merge(self.warnings, row_warnings);
+                }
                 Ok(Some(element))
             }
             // Not enough data for this element, stop here
             Err(e) if e.0.kind() == std::io::ErrorKind::UnexpectedEof => {
                 self.row.reader.set_position(last_pos);
+                if let Some(row_warnings) = self.row.warnings_mut() {
+                    row_warnings.clear();
+                }
                 Ok(None)
             }
             Err(e) => Err(e)?,
@@ -291,7 +638,12 @@ impl<'de, D: AsRef<[u8]>, S: ScalarReader> SeqAccess<'de>
     }
 
     fn size_hint(&self) -> Option<usize> {
-        Some(self.remaining)
+        // Elements can declare far more rows than are buffered, so only hint at the rows
+        // that could be complete. Otherwise the first chunk of a huge element allocates
+        // for all of its rows.
+        let reader = &self.row.reader;
+        let buffered = reader.get_ref().as_ref().len() - reader.position() as usize;
+        Some(self.remaining.min(buffered / self.min_row_size))
     }
 }
 
@@ -327,8 +679,10 @@ impl Default for PlyChunkedReader {
 /// assert_eq!(count, 1);
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
-pub struct RowVisitor<T, F: FnMut(T)> {
+pub struct RowVisitor<T, F: FnMut(T), P: FnMut(usize) -> bool = fn(usize) -> bool> {
     row_callback: F,
+    row_filter: P,
+    rows_read: usize,
     _row: PhantomData<T>,
 }
 
@@ -341,12 +695,56 @@ impl<T, F: FnMut(T)> RowVisitor<T, F> {
     pub fn new(row_callback: F) -> Self {
         Self {
             row_callback,
+            row_filter: |_| true,
+            rows_read: 0,
             _row: PhantomData,
         }
     }
 }
 
-impl<'de, T: Deserialize<'de>, F: FnMut(T)> DeserializeSeed<'de> for &mut RowVisitor<T, F> {
+impl<T, F: FnMut(T), P: FnMut(usize) -> bool> RowVisitor<T, F, P> {
+    /// Only pass rows to the callback for which `row_filter` returns `true`.
+    ///
+    /// The filter gets the index of the row among all rows read by this visitor, and is
+    /// called before the row is deserialized, so skipped rows are never converted to `T`.
+    /// To count rows across chunks, reuse the same visitor for each chunk. The filter may
+    /// be called more than once for a row that is cut off at the end of a chunk, so it
+    /// should only depend on the index.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::{Deserialize, de::DeserializeSeed};
+    /// use serde_ply::{PlyChunkedReader, RowVisitor};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let data = b"ply\nformat ascii 1.0\nelement vertex 5\nproperty float x\nend_header\n0\n1\n2\n3\n4\n";
+    /// let mut file = PlyChunkedReader::new();
+    /// let mut xs = Vec::new();
+    /// let mut every_other = RowVisitor::new(|v: Vertex| xs.push(v.x)).with_row_filter(|i| i % 2 == 0);
+    /// for chunk in data.chunks(7) {
+    ///     file.buffer_mut().extend_from_slice(chunk);
+    ///     if file.current_element().is_some() {
+    ///         every_other.deserialize(&mut file)?;
+    ///     }
+    /// }
+    /// assert_eq!(xs, [0.0, 2.0, 4.0]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_row_filter<P2: FnMut(usize) -> bool>(self, row_filter: P2) -> RowVisitor<T, F, P2> {
+        RowVisitor {
+            row_callback: self.row_callback,
+            row_filter,
+            rows_read: self.rows_read,
+            _row: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>, F: FnMut(T), P: FnMut(usize) -> bool> DeserializeSeed<'de>
+    for &mut RowVisitor<T, F, P>
+{
     type Value = ();
 
     fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
@@ -354,14 +752,24 @@ impl<'de, T: Deserialize<'de>, F: FnMut(T)> DeserializeSeed<'de> for &mut RowVis
     }
 }
 
-impl<'de, T: Deserialize<'de>, F: FnMut(T)> Visitor<'de> for &mut RowVisitor<T, F> {
+impl<'de, T: Deserialize<'de>, F: FnMut(T), P: FnMut(usize) -> bool> Visitor<'de>
+    for &mut RowVisitor<T, F, P>
+{
     type Value = ();
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
         formatter.write_str("a sequence of rows")
     }
     fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
-        while let Some(row) = seq.next_element()? {
-            (self.row_callback)(row);
+        loop {
+            if (self.row_filter)(self.rows_read) {
+                let Some(row) = seq.next_element()? else {
+                    break;
+                };
+                (self.row_callback)(row);
+            } else if seq.next_element::<IgnoredAny>()?.is_none() {
+                break;
+            }
+            self.rows_read += 1;
         }
         Ok(())
     }
@@ -0,0 +1,304 @@
+//! Which property types can be read into which field types, and how.
+
+use std::fmt::{self, Display};
+
+use serde::de::Visitor;
+
+use crate::{DeserializeError, DeserializeOptions, PrecisionLoss, ScalarType};
+
+/// Scalar type of a field that a property is read into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    Bool,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    I128,
+    U128,
+    F32,
+    F64,
+}
+
+impl FieldType {
+    /// All field types, in the order of the columns of the matrix.
+    pub const ALL: [FieldType; 13] = [
+        FieldType::Bool,
+        FieldType::I8,
+        FieldType::U8,
+        FieldType::I16,
+        FieldType::U16,
+        FieldType::I32,
+        FieldType::U32,
+        FieldType::I64,
+        FieldType::U64,
+        FieldType::I128,
+        FieldType::U128,
+        FieldType::F32,
+        FieldType::F64,
+    ];
+
+    /// The field type a property of type `ty` is visited as without any conversion.
+    pub(crate) fn native(ty: ScalarType) -> Self {
+        match ty {
+            ScalarType::I8 => FieldType::I8,
+            ScalarType::U8 => FieldType::U8,
+            ScalarType::I16 => FieldType::I16,
+            ScalarType::U16 => FieldType::U16,
+            ScalarType::I32 => FieldType::I32,
+            ScalarType::U32 => FieldType::U32,
+            ScalarType::F32 => FieldType::F32,
+            ScalarType::F64 => FieldType::F64,
+        }
+    }
+}
+
+impl Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FieldType::Bool => "bool",
+            FieldType::I8 => "i8",
+            FieldType::U8 => "u8",
+            FieldType::I16 => "i16",
+            FieldType::U16 => "u16",
+            FieldType::I32 => "i32",
+            FieldType::U32 => "u32",
+            FieldType::I64 => "i64",
+            FieldType::U64 => "u64",
+            FieldType::I128 => "i128",
+            FieldType::U128 => "u128",
+            FieldType::F32 => "f32",
+            FieldType::F64 => "f64",
+        };
+        f.write_str(name)
+    }
+}
+
+/// How the values of a property are converted when read into a field, see
+/// [`coercion_matrix`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Coercion {
+    /// Every value converts exactly.
+    Lossless,
+    /// Values convert exactly, values the field can't hold are an error.
+    Checked,
+    /// Values are rounded to the nearest value the field can hold, eg. `uint` to `f32`.
+    Rounded,
+    /// A `double` read into an `f32`, which rounds the value. Values that change by more
+    /// than the tolerance are handled by [`DeserializeOptions::with_precision_loss`].
+    Narrowed,
+    /// Zero is `false`, any other value is `true`.
+    NonZero,
+    /// The field can't be read from the property, eg. a `float` into an integer field.
+    Rejected,
+}
+
+use Coercion::{
+    Checked as C, Lossless as L, Narrowed as N, NonZero as Z, Rejected as X, Rounded as R,
+};
+
+/// Rows are property types in the order of [`ScalarType`], columns are [`FieldType::ALL`].
+#[rustfmt::skip]
+const MATRIX: [[Coercion; 13]; 8] = [
+    //        bool i8 u8 i16 u16 i32 u32 i64 u64 i128 u128 f32 f64
+    /* i8  */ [Z,  L, C, L,  C,  L,  C,  L,  C,  L,   C,   L,  L],
+    /* u8  */ [Z,  C, L, L,  L,  L,  L,  L,  L,  L,   L,   L,  L],
+    /* i16 */ [Z,  C, C, L,  C,  L,  C,  L,  C,  L,   C,   L,  L],
+    /* u16 */ [Z,  C, C, C,  L,  L,  L,  L,  L,  L,   L,   L,  L],
+    /* i32 */ [Z,  C, C, C,  C,  L,  C,  L,  C,  L,   C,   R,  L],
+    /* u32 */ [Z,  C, C, C,  C,  C,  L,  L,  L,  L,   L,   R,  L],
+    /* f32 */ [Z,  X, X, X,  X,  X,  X,  X,  X,  X,   X,   L,  L],
+    /* f64 */ [Z,  X, X, X,  X,  X,  X,  X,  X,  X,   X,   N,  L],
+];
+
+fn row(ty: ScalarType) -> usize {
+    match ty {
+        ScalarType::I8 => 0,
+        ScalarType::U8 => 1,
+        ScalarType::I16 => 2,
+        ScalarType::U16 => 3,
+        ScalarType::I32 => 4,
+        ScalarType::U32 => 5,
+        ScalarType::F32 => 6,
+        ScalarType::F64 => 7,
+    }
+}
+
+/// How a property of type `from` is read into a field of type `to`.
+///
+/// Both ASCII and binary files are read by these rules, so a value reads the same
+/// whatever the format it's stored in.
+pub fn coercion(from: ScalarType, to: FieldType) -> Coercion {
+    MATRIX[row(from)][to as usize]
+}
+
+/// Every pair of property type and field type, with how values are converted.
+///
+/// Scalars and list entries follow the same rules. Fields that aren't one of the
+/// [`FieldType`]s, eg. strings, can't be read from any property.
+///
+/// # Example
+/// ```rust
+/// use serde_ply::{coercion_matrix, Coercion, FieldType, ScalarType};
+///
+/// let (_, _, coercion) = coercion_matrix()
+///     .find(|&(from, to, _)| from == ScalarType::U16 && to == FieldType::U8)
+///     .unwrap();
+/// assert_eq!(coercion, Coercion::Checked);
+/// assert_eq!(serde_ply::coercion(ScalarType::F32, FieldType::I32), Coercion::Rejected);
+/// ```
+pub fn coercion_matrix() -> impl Iterator<Item = (ScalarType, FieldType, Coercion)> {
+    const TYPES: [ScalarType; 8] = [
+        ScalarType::I8,
+        ScalarType::U8,
+        ScalarType::I16,
+        ScalarType::U16,
+        ScalarType::I32,
+        ScalarType::U32,
+        ScalarType::F32,
+        ScalarType::F64,
+    ];
+    TYPES.into_iter().flat_map(|from| {
+        FieldType::ALL
+            .into_iter()
+            .map(move |to| (from, to, coercion(from, to)))
+    })
+}
+
+/// A property value as read from the file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Scalar {
+    Int(i64),
+    Float(f64),
+}
+
+/// A value converted to the type a field asked for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum FieldValue {
+    Bool(bool),
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    F32(f32),
+    F64(f64),
+}
+
+impl FieldValue {
+    pub(crate) fn visit<'de, V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        match self {
+            FieldValue::Bool(v) => visitor.visit_bool(v),
+            FieldValue::I8(v) => visitor.visit_i8(v),
+            FieldValue::U8(v) => visitor.visit_u8(v),
+            FieldValue::I16(v) => visitor.visit_i16(v),
+            FieldValue::U16(v) => visitor.visit_u16(v),
+            FieldValue::I32(v) => visitor.visit_i32(v),
+            FieldValue::U32(v) => visitor.visit_u32(v),
+            FieldValue::I64(v) => visitor.visit_i64(v),
+            FieldValue::U64(v) => visitor.visit_u64(v),
+            FieldValue::I128(v) => visitor.visit_i128(v),
+            FieldValue::U128(v) => visitor.visit_u128(v),
+            FieldValue::F32(v) => visitor.visit_f32(v),
+            FieldValue::F64(v) => visitor.visit_f64(v),
+        }
+    }
+}
+
+/// Why a value couldn't be read into a field.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum CoerceError {
+    /// The pair is [`Coercion::Rejected`].
+    Rejected,
+    /// The value doesn't fit a [`Coercion::Checked`] field.
+    OutOfRange(i64),
+    /// A [`Coercion::Narrowed`] value lost precision under [`PrecisionLoss::Error`].
+    PrecisionLoss(f64),
+}
+
+/// Convert `value`, a property of type `from`, to a field of type `to`.
+///
+/// Returns the value and whether it lost precision beyond the tolerance, which is only
+/// possible for [`Coercion::Narrowed`] under [`PrecisionLoss::Warn`].
+#[inline]
+pub(crate) fn coerce(
+    value: Scalar,
+    from: ScalarType,
+    to: FieldType,
+    options: &DeserializeOptions,
+) -> Result<(FieldValue, bool), CoerceError> {
+    if to == FieldType::native(from) {
+        return Ok((native(value, to), false));
+    }
+    let checked = |v: Result<FieldValue, _>, i: i64| v.map_err(|_| CoerceError::OutOfRange(i));
+    let value = match (coercion(from, to), value) {
+        (Coercion::Rejected, _) => return Err(CoerceError::Rejected),
+        (Coercion::NonZero, Scalar::Int(v)) => FieldValue::Bool(v != 0),
+        (Coercion::NonZero, Scalar::Float(v)) => FieldValue::Bool(v != 0.0),
+        (Coercion::Narrowed, Scalar::Float(v)) => return narrow(v, options),
+        (_, Scalar::Int(v)) => match to {
+            FieldType::I8 => checked(v.try_into().map(FieldValue::I8), v)?,
+            FieldType::U8 => checked(v.try_into().map(FieldValue::U8), v)?,
+            FieldType::I16 => checked(v.try_into().map(FieldValue::I16), v)?,
+            FieldType::U16 => checked(v.try_into().map(FieldValue::U16), v)?,
+            FieldType::I32 => checked(v.try_into().map(FieldValue::I32), v)?,
+            FieldType::U32 => checked(v.try_into().map(FieldValue::U32), v)?,
+            FieldType::I64 => FieldValue::I64(v),
+            FieldType::U64 => checked(v.try_into().map(FieldValue::U64), v)?,
+            FieldType::I128 => FieldValue::I128(v.into()),
+            FieldType::U128 => checked(v.try_into().map(FieldValue::U128), v)?,
+            FieldType::F32 => FieldValue::F32(v as f32),
+            FieldType::F64 => FieldValue::F64(v as f64),
+            FieldType::Bool => unreachable!("bool fields are NonZero"),
+        },
+        (_, Scalar::Float(v)) => match to {
+            FieldType::F32 => FieldValue::F32(v as f32),
+            FieldType::F64 => FieldValue::F64(v),
+            _ => unreachable!("float properties only convert to floats"),
+        },
+    };
+    Ok((value, false))
+}
+
+/// `value` as the field type matching its property type.
+fn native(value: Scalar, to: FieldType) -> FieldValue {
+    // The value was read as the property type, so these casts are exact.
+    match value {
+        Scalar::Int(v) => match to {
+            FieldType::I8 => FieldValue::I8(v as i8),
+            FieldType::U8 => FieldValue::U8(v as u8),
+            FieldType::I16 => FieldValue::I16(v as i16),
+            FieldType::U16 => FieldValue::U16(v as u16),
+            FieldType::I32 => FieldValue::I32(v as i32),
+            _ => FieldValue::U32(v as u32),
+        },
+        Scalar::Float(v) => match to {
+            FieldType::F32 => FieldValue::F32(v as f32),
+            _ => FieldValue::F64(v),
+        },
+    }
+}
+
+/// Narrow a double to an f32 under the precision loss policy of `options`.
+fn narrow(v: f64, options: &DeserializeOptions) -> Result<(FieldValue, bool), CoerceError> {
+    let narrowed = v as f32;
+    let lossy = options.precision_loss != PrecisionLoss::Allow
+        && (narrowed.is_infinite() && v.is_finite()
+            || (narrowed as f64 - v).abs() > options.f32_relative_tolerance * v.abs());
+    if lossy && options.precision_loss == PrecisionLoss::Error {
+        return Err(CoerceError::PrecisionLoss(v));
+    }
+    Ok((FieldValue::F32(narrowed), lossy))
+}
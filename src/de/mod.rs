@@ -3,21 +3,433 @@
 pub(crate) mod ply_file;
 pub(crate) use row::*;
 pub(crate) mod chunked;
+pub(crate) mod coerce;
+pub(crate) mod pod;
+pub(crate) mod progress;
+pub(crate) mod raw;
+pub(crate) mod read_buffer;
 mod row;
+pub(crate) mod stats;
+pub(crate) mod tee;
 
 pub mod val_reader;
 use std::io::{BufRead, BufReader, Cursor};
 
+use byteorder::{BigEndian, LittleEndian};
 pub use ply_file::PlyReader;
-use serde::Deserialize;
+use serde::{
+    de::{Error, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    Deserialize,
+};
 
-use crate::DeserializeError;
+use crate::{
+    de::{
+        ply_file::ElementSeqDeserializer,
+        val_reader::{AsciiValReader, BinValReader},
+    },
+    DeserializeError, ElementDef, PlyFormat, PlyHeader, PlyProperty, PropertyType, ScalarType,
+};
+
+/// Options for PLY file deserialization.
+///
+/// Builder struct for configuring how leniently element data is interpreted.
+/// Pass to [`PlyReader::with_options`] or [`crate::PlyChunkedReader::with_options`].
+///
+/// Apart from [`DeserializeOptions::pad_short_rows_with_default`], none of the options
+/// relax the row structure: every property declared in the header must be present in every
+/// row. `#[serde(default)]` on a field only applies when the property is absent from the
+/// header, never to values missing from a row. An ASCII row that is cut short is an error
+/// for [`PlyReader`], and is treated as not yet complete by [`crate::PlyChunkedReader`].
+#[derive(Debug, Clone)]
+pub struct DeserializeOptions {
+    pub(crate) single_element_list_as_scalar: bool,
+    pub(crate) collect_stats: bool,
+    pub(crate) precision_loss: PrecisionLoss,
+    pub(crate) f32_relative_tolerance: f64,
+    pub(crate) decimal_comma: bool,
+    pub(crate) pad_short_rows_with_default: bool,
+    pub(crate) allow_extra_whitespace_in_binary: bool,
+    pub(crate) deny_extra_properties: bool,
+    pub(crate) diagnose_misalignment: bool,
+    pub(crate) max_plausible_list_len: usize,
+    pub(crate) list_count_overrides: Vec<(String, String, ScalarType)>,
+    // Number of vertices in the file, set by the reader when diagnosing misalignment.
+    pub(crate) vertex_count: Option<usize>,
+}
+
+impl Default for DeserializeOptions {
+    fn default() -> Self {
+        Self {
+            single_element_list_as_scalar: false,
+            collect_stats: false,
+            precision_loss: PrecisionLoss::default(),
+            f32_relative_tolerance: 1e-7,
+            decimal_comma: false,
+            pad_short_rows_with_default: false,
+            allow_extra_whitespace_in_binary: false,
+            deny_extra_properties: false,
+            diagnose_misalignment: false,
+            max_plausible_list_len: 1024,
+            list_count_overrides: Vec::new(),
+            vertex_count: None,
+        }
+    }
+}
+
+/// What to do when a `double` property is read into an `f32` field and the value changes
+/// by more than the tolerance, see [`DeserializeOptions::with_precision_loss`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PrecisionLoss {
+    /// Convert the value and count it in [`PlyReader::precision_warnings`] or
+    /// [`crate::PlyChunkedReader::precision_warnings`].
+    ///
+    /// Only those two readers keep warnings. [`from_reader`], [`from_bytes`], [`from_str`]
+    /// and [`from_element_bytes`] have nowhere to report them, so under `Warn` they
+    /// convert lossy values silently, just like [`PrecisionLoss::Allow`]. Use
+    /// [`PrecisionLoss::Error`] with a [`PlyReader`] when lost precision must not go
+    /// unnoticed.
+    #[default]
+    Warn,
+    /// Return an error.
+    Error,
+    /// Convert the value without checking.
+    Allow,
+}
+
+impl DeserializeOptions {
+    /// Create [`DeserializeOptions`] with the default (strict) behavior.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow reading a list property into a scalar field.
+    ///
+    /// When enabled, a list that holds exactly one entry is read as that entry. Lists
+    /// with any other length still produce an error. Disabled by default.
+    pub fn with_single_element_list_as_scalar(mut self, allow: bool) -> Self {
+        self.single_element_list_as_scalar = allow;
+        self
+    }
+
+    /// Collect per element and per property byte counts and list lengths while reading.
+    ///
+    /// The statistics are available from [`PlyReader::report`] and
+    /// [`crate::PlyChunkedReader::report`]. Disabled by default, as it adds a little work to
+    /// every value read.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::{DeserializeOptions, PlyReader};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Face { vertex_indices: Vec<u32> }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement face 2\nproperty list uchar uint vertex_indices\nend_header\n3 0 1 2\n4 0 1 2 3\n";
+    /// let mut reader = PlyReader::from_reader(ply_data.as_bytes())?
+    ///     .with_options(DeserializeOptions::new().collect_stats(true));
+    /// let _faces: Vec<Face> = reader.next_element()?;
+    ///
+    /// let report = reader.report().unwrap();
+    /// let lists = report.elements[0].properties[0].list.as_ref().unwrap();
+    /// assert_eq!((lists.min_len, lists.max_len, lists.mean_len()), (3, 4, 3.5));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn collect_stats(mut self, collect: bool) -> Self {
+        self.collect_stats = collect;
+        self
+    }
+
+    /// Choose what happens when a `double` value loses precision in an `f32` field.
+    ///
+    /// An `f32` only has 24 bits of mantissa, so large coordinates such as UTM positions
+    /// lose their fractional part, eg. `500000.123` becomes `500000.125`. A value counts as
+    /// lossy when it changes by more than [`Self::with_f32_relative_tolerance`] times its
+    /// magnitude, or no longer fits in an `f32`. Defaults to [`PrecisionLoss::Warn`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::{DeserializeOptions, PlyReader, PrecisionLoss};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty double x\nend_header\n0.5\n500000.123\n";
+    /// let options = DeserializeOptions::new().with_f32_relative_tolerance(1e-9);
+    /// let mut reader = PlyReader::from_reader(ply_data.as_bytes())?.with_options(options.clone());
+    /// let _vertices: Vec<Vertex> = reader.next_element()?;
+    /// let warning = &reader.precision_warnings()[0];
+    /// assert_eq!((warning.property.as_str(), warning.count, warning.example), ("x", 1, 500000.123));
+    ///
+    /// let mut reader = PlyReader::from_reader(ply_data.as_bytes())?
+    ///     .with_options(options.with_precision_loss(PrecisionLoss::Error));
+    /// assert!(reader.next_element::<Vec<Vertex>>().is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_precision_loss(mut self, policy: PrecisionLoss) -> Self {
+        self.precision_loss = policy;
+        self
+    }
+
+    /// Largest change, relative to the value, that a `double` may undergo when read into an
+    /// `f32` field before it counts as lossy: a value `v` is lossy if it changes by more
+    /// than `tolerance * |v|`.
+    ///
+    /// Rounding to an `f32` changes a value by at most `2^-24` (about `6e-8`) of its
+    /// magnitude, so the default of `1e-7` only catches values that no longer fit, or that
+    /// are so small they lose most of their digits. Ordinary coordinates like `4096.1` are
+    /// read without a warning. A smaller tolerance catches rounding itself: with `1e-9`
+    /// UTM-scale values like `500000.123` count, as do values like `0.1` that an `f32` can't
+    /// hold exactly, while values it holds exactly, like `0.25`, never count.
+    pub fn with_f32_relative_tolerance(mut self, tolerance: f64) -> Self {
+        self.f32_relative_tolerance = tolerance;
+        self
+    }
+
+    /// Accept a ',' as decimal separator in ASCII float values, eg. `1,5` for `1.5`.
+    ///
+    /// This is a workaround for exporters that format numbers with the system locale. A
+    /// token with a single ',' and no '.' has the comma replaced before parsing, tokens
+    /// with both are still rejected. Integer properties and binary files are unaffected.
+    /// Disabled by default: PLY separates values with whitespace, so a file whose values
+    /// are separated by commas instead would silently read as different numbers.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::{DeserializeOptions, PlyReader};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\n1,5\n";
+    /// let mut reader = PlyReader::from_reader(ply_data.as_bytes())?
+    ///     .with_options(DeserializeOptions::new().decimal_comma(true));
+    /// let vertices: Vec<Vertex> = reader.next_element()?;
+    /// assert_eq!(vertices[0].x, 1.5);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn decimal_comma(mut self, allow: bool) -> Self {
+        self.decimal_comma = allow;
+        self
+    }
+
+    /// Let ASCII rows that end their line early leave the trailing properties to the
+    /// defaults of their fields.
+    ///
+    /// Some exporters omit trailing optional columns on some rows. With this enabled, each
+    /// ASCII row ends at its line, and the properties after the last value on the line are
+    /// left out of the row, so `Option` fields are `None` and `#[serde(default)]` fields get
+    /// their default. A field without a default is still an error. Rows read as tuples
+    /// must be complete. Disabled by default, as a row can then no longer continue on the
+    /// next line. Has no effect on binary files.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::{DeserializeOptions, PlyReader};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32, #[serde(default)] confidence: f32 }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float confidence\nend_header\n1.0 0.5\n2.0\n";
+    /// let mut reader = PlyReader::from_reader(ply_data.as_bytes())?
+    ///     .with_options(DeserializeOptions::new().pad_short_rows_with_default(true));
+    /// let vertices: Vec<Vertex> = reader.next_element()?;
+    /// assert_eq!((vertices[1].x, vertices[1].confidence), (2.0, 0.0));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn pad_short_rows_with_default(mut self, pad: bool) -> Self {
+        self.pad_short_rows_with_default = pad;
+        self
+    }
+
+    /// Skip a few whitespace bytes between `end_header` and the first record of a binary
+    /// file.
+    ///
+    /// Binary data starts right after the newline ending `end_header`, but some broken
+    /// exporters write an extra space or newline there. With this enabled,
+    /// [`PlyReader::with_options`] skips up to 4 of `' '`, `'\t'`,
+    /// `'\r'` and `'\n'` before the data. Disabled by default, as these are valid bytes
+    /// of the first record, eg. a `uchar` of 10 or 32, and skipping them would shift every
+    /// value after. Has no effect on ASCII files or [`crate::PlyChunkedReader`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::{DeserializeOptions, PlyReader};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let mut ply_data = b"ply\nformat binary_little_endian 1.0\nelement vertex 1\nproperty float x\nend_header\n\n".to_vec();
+    /// ply_data.extend_from_slice(&1.5f32.to_le_bytes());
+    /// let mut reader = PlyReader::from_reader(ply_data.as_slice())?
+    ///     .with_options(DeserializeOptions::new().allow_extra_whitespace_in_binary(true));
+    /// let vertices: Vec<Vertex> = reader.next_element()?;
+    /// assert_eq!(vertices[0].x, 1.5);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn allow_extra_whitespace_in_binary(mut self, allow: bool) -> Self {
+        self.allow_extra_whitespace_in_binary = allow;
+        self
+    }
+
+    /// Error when an element has properties that the struct rows are read into have no
+    /// field for.
+    ///
+    /// By default such properties are skipped without converting their values, and
+    /// `#[serde(deny_unknown_fields)]` on the row type errors on the first one. With this
+    /// enabled the error lists all of them and is returned before any row is read. Rows
+    /// read as maps, including structs with a `#[serde(flatten)]` field, take every
+    /// property and are unaffected. Disabled by default.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::{DeserializeOptions, PlyReader};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float nx\nproperty float ny\nend_header\n1 0 1\n";
+    /// let mut reader = PlyReader::from_reader(ply_data.as_bytes())?
+    ///     .with_options(DeserializeOptions::new().deny_extra_properties(true));
+    /// let err = reader.next_element::<Vec<Vertex>>().err().unwrap();
+    /// assert!(err.to_string().contains("'nx', 'ny'"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn deny_extra_properties(mut self, deny: bool) -> Self {
+        self.deny_extra_properties = deny;
+        self
+    }
+
+    /// Check binary lists for signs that their count type doesn't match the data.
+    ///
+    /// Some exporters declare eg. `property list uchar int vertex_indices` but write
+    /// 2 byte counts, which shifts every value after the first count. With this enabled,
+    /// reading a binary list fails with a [`crate::MisalignedList`] error explaining the
+    /// likely mismatch when its count is above [`Self::with_max_plausible_list_len`], or
+    /// when a `vertex_indices` or `vertex_index` entry isn't a valid index into the
+    /// `vertex` element. Use [`Self::override_list_count_type`] to read such files.
+    /// Disabled by default. Rows that are skipped entirely, and properties the row type has
+    /// no field for, aren't checked.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::{DeserializeOptions, PlyReader};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Face { vertex_indices: Vec<u32> }
+    ///
+    /// // Declared as a uchar count, written as a ushort.
+    /// let mut ply_data = b"ply\nformat binary_little_endian 1.0\nelement vertex 3\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n".to_vec();
+    /// ply_data.extend_from_slice(&3u16.to_le_bytes());
+    /// for i in 0u32..3 {
+    ///     ply_data.extend_from_slice(&i.to_le_bytes());
+    /// }
+    /// let mut reader = PlyReader::from_reader(ply_data.as_slice())?
+    ///     .with_options(DeserializeOptions::new().diagnose_misalignment(true));
+    /// reader.next_element::<serde::de::IgnoredAny>()?;
+    /// let err = reader.next_element::<Vec<Face>>().err().unwrap();
+    /// assert!(err.is_misaligned_list());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn diagnose_misalignment(mut self, diagnose: bool) -> Self {
+        self.diagnose_misalignment = diagnose;
+        self
+    }
+
+    /// Longest binary list considered plausible by [`Self::diagnose_misalignment`].
+    /// Defaults to 1024.
+    pub fn with_max_plausible_list_len(mut self, len: usize) -> Self {
+        self.max_plausible_list_len = len;
+        self
+    }
+
+    /// Read the counts of a list property as `count_type` instead of the type the header
+    /// declares, to fix files whose exporter wrote them with another type.
+    ///
+    /// The override changes the header of the reader, so [`PlyReader::header`] shows the
+    /// type the data is read with. An override for a property that doesn't exist or isn't
+    /// a list has no effect.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::{DeserializeOptions, PlyReader, ScalarType};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Face { vertex_indices: Vec<u32> }
+    ///
+    /// // Declared as a uchar count, written as a ushort.
+    /// let mut ply_data = b"ply\nformat binary_little_endian 1.0\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n".to_vec();
+    /// ply_data.extend_from_slice(&3u16.to_le_bytes());
+    /// for i in 0u32..3 {
+    ///     ply_data.extend_from_slice(&i.to_le_bytes());
+    /// }
+    /// let options = DeserializeOptions::new().override_list_count_type("face", "vertex_indices", ScalarType::U16);
+    /// let mut reader = PlyReader::from_reader(ply_data.as_slice())?.with_options(options);
+    /// let faces: Vec<Face> = reader.next_element()?;
+    /// assert_eq!(faces[0].vertex_indices, [0, 1, 2]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn override_list_count_type(
+        mut self,
+        element: impl Into<String>,
+        property: impl Into<String>,
+        count_type: ScalarType,
+    ) -> Self {
+        self.list_count_overrides
+            .push((element.into(), property.into(), count_type));
+        self
+    }
+
+    /// Apply the list count overrides to `header`, and remember what the checks of
+    /// [`Self::diagnose_misalignment`] need from it.
+    pub(crate) fn apply_to_header(&mut self, header: &mut PlyHeader) {
+        for (element, property, count_type) in &self.list_count_overrides {
+            let prop = header
+                .elem_defs
+                .iter_mut()
+                .filter(|e| e.name == *element)
+                .flat_map(|e| e.properties.iter_mut())
+                .find(|p| p.name == *property);
+            if let Some(PlyProperty {
+                property_type: PropertyType::List { count_type: ty, .. },
+                ..
+            }) = prop
+            {
+                *ty = *count_type;
+            }
+        }
+        self.vertex_count = self
+            .diagnose_misalignment
+            .then(|| header.elem_defs.iter().find(|e| e.name == "vertex"))
+            .flatten()
+            .map(|e| e.count);
+    }
+}
 
 /// Deserialize PLY data from a reader.
 ///
 /// This is the primary entry point for deserializing complete PLY files.
 /// The reader should contain the full PLY file including header.
 ///
+/// Elements usually map to struct fields. When the element names aren't known up front,
+/// eg. `camera_0`, `camera_1`, ..., read into a `Vec<(String, Vec<Row>)>` instead to get
+/// every element with its name in file order. Serializing such a `Vec` writes the
+/// elements back in the same order. That is also the way to get all elements of a file
+/// that repeats an element name, as a struct field only gets the first element with its
+/// name.
+///
+/// Lossy `double` to `f32` conversions are not reported here: the default
+/// [`PrecisionLoss::Warn`] keeps its warnings on the [`PlyReader`], which is dropped before
+/// this returns. Read through a [`PlyReader`] and check [`PlyReader::precision_warnings`]
+/// if they matter.
+///
 /// # Example
 /// ```rust
 /// use serde::Deserialize;
@@ -43,10 +455,166 @@ where
     Ok(t)
 }
 
+/// Deserialize PLY data from a reader, and return the reader positioned after the data.
+///
+/// Like [`from_reader`], but hands the reader back so any bytes following the PLY data
+/// can be read as well.
+///
+/// # Example
+/// ```rust
+/// use serde::Deserialize;
+/// use std::io::{Cursor, Read};
+///
+/// #[derive(Deserialize)]
+/// struct Vertex { x: f32 }
+///
+/// #[derive(Deserialize)]
+/// struct Mesh { vertex: Vec<Vertex> }
+///
+/// let data = b"ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\n1.0\ntrailer";
+/// let (mesh, mut reader): (Mesh, _) = serde_ply::from_reader_keep(Cursor::new(data))?;
+/// let mut rest = String::new();
+/// reader.read_to_string(&mut rest)?;
+/// assert_eq!(rest, "trailer");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn from_reader_keep<'a, T, R>(reader: R) -> Result<(T, R), DeserializeError>
+where
+    T: Deserialize<'a>,
+    R: BufRead,
+{
+    let mut deserializer = PlyReader::from_reader(reader)?;
+    let t: T = T::deserialize(&mut deserializer)?;
+    Ok((t, deserializer.into_inner()))
+}
+
+/// Check a whole PLY file without building any values, and return its header.
+///
+/// Every element must have exactly as many rows as the header declares, and every value
+/// must be well formed: ASCII values must parse as their property type, and binary data
+/// must be long enough for the rows and their lists. Data after the last element is an
+/// error too, apart from trailing whitespace in ASCII files. Errors name the element
+/// and row at fault.
+///
+/// # Example
+/// ```rust
+/// let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty uchar flag\nend_header\n1.5 1\n2.5 2\n";
+/// let header = serde_ply::validate(ply_data.as_bytes())?;
+/// assert_eq!(header.elem_defs[0].count, 2);
+///
+/// let corrupt = ply_data.replace("2.5 2", "2.5 256");
+/// assert!(serde_ply::validate(corrupt.as_bytes()).is_err());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn validate(reader: impl BufRead) -> Result<PlyHeader, DeserializeError> {
+    let mut reader = PlyReader::from_reader(reader)?;
+    let format = reader.header().format;
+    while reader.has_next_element() {
+        let element = reader.element()?;
+        let elem_def = element.def().clone();
+        for (row, result) in element.rows::<CheckedRow>().enumerate() {
+            result.map_err(|e| locate_row_error(e, format, &elem_def, row))?;
+        }
+    }
+    let header = reader.clone_header();
+    let mut rest = reader.into_inner();
+    loop {
+        let buf = rest.fill_buf()?;
+        let Some(&byte) = buf.first() else {
+            return Ok(header);
+        };
+        if format.is_binary() || !byte.is_ascii_whitespace() {
+            return Err(DeserializeError::custom(
+                "data continues after the last element, the header may declare too few rows",
+            ));
+        }
+        rest.consume(1);
+    }
+}
+
+/// Name the element and row of an error from reading a row, unless it already does.
+fn locate_row_error(
+    err: DeserializeError,
+    format: PlyFormat,
+    elem_def: &ElementDef,
+    row: usize,
+) -> DeserializeError {
+    if err.0.kind() != std::io::ErrorKind::UnexpectedEof {
+        let message = format!("row {row} of element '{}': {}", elem_def.name, err.0);
+        return DeserializeError(std::io::Error::new(err.0.kind(), message));
+    }
+    // ASCII rows that run out of data already say where.
+    if format.is_binary() {
+        return DeserializeError::missing_data(&elem_def.name, elem_def.count, row);
+    }
+    err
+}
+
+/// A row whose values are all read, and then dropped.
+struct CheckedRow;
+
+impl<'de> Deserialize<'de> for CheckedRow {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RowVisitor;
+
+        impl<'de> Visitor<'de> for RowVisitor {
+            type Value = CheckedRow;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a row")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<CheckedRow, A::Error> {
+                while map.next_entry::<IgnoredAny, CheckedValue>()?.is_some() {}
+                Ok(CheckedRow)
+            }
+        }
+
+        deserializer.deserialize_map(RowVisitor)
+    }
+}
+
+/// A scalar or list value, read in full.
+struct CheckedValue;
+
+impl<'de> Deserialize<'de> for CheckedValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ValueVisitor;
+
+        impl<'de> Visitor<'de> for ValueVisitor {
+            type Value = CheckedValue;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a scalar or a list")
+            }
+
+            fn visit_i64<E>(self, _v: i64) -> Result<CheckedValue, E> {
+                Ok(CheckedValue)
+            }
+
+            fn visit_u64<E>(self, _v: u64) -> Result<CheckedValue, E> {
+                Ok(CheckedValue)
+            }
+
+            fn visit_f64<E>(self, _v: f64) -> Result<CheckedValue, E> {
+                Ok(CheckedValue)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<CheckedValue, A::Error> {
+                while seq.next_element::<CheckedValue>()?.is_some() {}
+                Ok(CheckedValue)
+            }
+        }
+
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
 /// Deserialize PLY data from bytes.
 ///
 /// Convenience function for parsing PLY data from a byte slice.
-/// Works with both ASCII and binary format PLY files.
+/// Works with both ASCII and binary format PLY files. Like [`from_reader`], precision
+/// warnings are dropped.
 pub fn from_bytes<'a, T>(bytes: &[u8]) -> Result<T, DeserializeError>
 where
     T: Deserialize<'a>,
@@ -82,3 +650,117 @@ where
 {
     from_bytes(str.as_bytes())
 }
+
+/// Parse a PLY header from the start of a file.
+///
+/// Returns the header and its length in bytes, so that the element data can be located
+/// with [`PlyHeader::element_byte_ranges`]. The prefix may contain (part of) the element
+/// data after the header, but must contain the full header, otherwise an
+/// [`std::io::ErrorKind::UnexpectedEof`] error is returned.
+pub fn read_header_prefix(prefix: &[u8]) -> Result<(PlyHeader, u64), DeserializeError> {
+    let mut cursor = Cursor::new(prefix);
+    let header = PlyHeader::parse(&mut cursor)?;
+    Ok((header, cursor.position()))
+}
+
+/// Find where the element data starts in a buffer holding the start of a PLY file.
+///
+/// Returns the offset just past the `end_header` line, which may end in `\n` or `\r\n`.
+/// Only a line holding just `end_header` counts, so a comment mentioning it doesn't end
+/// the header. Returns `None` if the buffer doesn't hold a complete `end_header` line
+/// yet. Unlike [`read_header_prefix`] the header isn't parsed or validated, which makes
+/// this cheap enough to split many files before handing them to workers.
+///
+/// # Example
+/// ```rust
+/// let file = b"ply\r\nformat ascii 1.0\r\ncomment end_header\r\nend_header\r\n1 2 3\r\n";
+/// let start = serde_ply::find_data_start(file).unwrap();
+/// assert_eq!(&file[start..], b"1 2 3\r\n");
+/// assert_eq!(serde_ply::find_data_start(b"ply\nend_header"), None);
+/// ```
+pub fn find_data_start(buf: &[u8]) -> Option<usize> {
+    const END_HEADER: &[u8] = b"end_header";
+    for pos in memchr::memmem::find_iter(buf, END_HEADER) {
+        // Allow surrounding whitespace like the header parser does, including a `\r`.
+        let before = buf[..pos].iter().rposition(|&b| !matches!(b, b' ' | b'\t'));
+        if before.is_some_and(|i| buf[i] != b'\n') {
+            continue;
+        }
+        let rest = &buf[pos + END_HEADER.len()..];
+        let line_end = rest
+            .iter()
+            .position(|&b| !matches!(b, b' ' | b'\t' | b'\r'));
+        match line_end {
+            Some(i) if rest[i] == b'\n' => return Some(pos + END_HEADER.len() + i + 1),
+            Some(_) => continue,
+            None => return None,
+        }
+    }
+    None
+}
+
+/// Deserialize a single element from just its data.
+///
+/// `bytes` should hold exactly the data of the element called `element_name`, for example
+/// a slice fetched with a range from [`PlyHeader::element_byte_ranges`]. The type `T`
+/// should be a sequence of rows, like with [`PlyReader::next_element`].
+///
+/// The default [`DeserializeOptions`] are used, and as there is no reader to hold
+/// precision warnings, `double` values read into `f32` fields are narrowed without any
+/// warning.
+///
+/// # Example
+/// ```rust
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Vertex { x: f32 }
+///
+/// let mut file = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nproperty float x\nend_header\n".to_vec();
+/// file.extend_from_slice(&1.0f32.to_le_bytes());
+/// file.extend_from_slice(&2.0f32.to_le_bytes());
+///
+/// let (header, header_len) = serde_ply::read_header_prefix(&file)?;
+/// let range = header.element_byte_ranges(header_len)[0].clone().unwrap();
+/// let data = &file[range.start as usize..range.end as usize];
+/// let vertices: Vec<Vertex> = serde_ply::from_element_bytes(&header, "vertex", data)?;
+/// assert_eq!(vertices[1].x, 2.0);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn from_element_bytes<'a, T>(
+    header: &PlyHeader,
+    element_name: &str,
+    bytes: &[u8],
+) -> Result<T, DeserializeError>
+where
+    T: Deserialize<'a>,
+{
+    let elem_def = header
+        .elem_defs
+        .iter()
+        .find(|e| e.name == element_name)
+        .ok_or_else(|| {
+            DeserializeError::custom(format!("header has no element '{element_name}'"))
+        })?;
+    let options = Default::default();
+    let mut cursor = Cursor::new(bytes);
+    match header.format {
+        PlyFormat::Ascii => T::deserialize(ElementSeqDeserializer::<_, AsciiValReader>::new(
+            elem_def,
+            &mut cursor,
+            &options,
+        )),
+        PlyFormat::BinaryLittleEndian => T::deserialize(ElementSeqDeserializer::<
+            _,
+            BinValReader<LittleEndian>,
+        >::new(
+            elem_def, &mut cursor, &options
+        )),
+        PlyFormat::BinaryBigEndian => T::deserialize(ElementSeqDeserializer::<
+            _,
+            BinValReader<BigEndian>,
+        >::new(
+            elem_def, &mut cursor, &options
+        )),
+    }
+}
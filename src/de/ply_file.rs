@@ -1,13 +1,25 @@
 use core::fmt;
-use serde::de::value::BytesDeserializer;
-use serde::de::{DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::de::value::{BytesDeserializer, StrDeserializer};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer};
-use std::io::{BufRead, Read};
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+use std::iter::FusedIterator;
 use std::marker::PhantomData;
 
+use crate::de::pod::{read_pod_rows, PodLayout};
+use crate::de::progress::{ElementState, ElementStatus, Progress};
+use crate::de::raw::{RawRow, RawRows};
+use crate::de::read_buffer::ReadBuffer;
+use crate::de::stats::{CountingReader, ElementStats, LoadReport, PrecisionWarning};
+use crate::de::tee::TeeReader;
 use crate::de::val_reader::{AsciiValReader, BinValReader, ScalarReader};
 use crate::de::RowDeserializer;
-use crate::{DeserializeError, ElementDef, PlyFormat, PlyHeader, PlyProperty};
+use crate::PlyChunkedReader;
+use crate::{
+    ByteOrderKind, DeserializeError, DeserializeOptions, ElementDef, PlyFormat, PlyHeader,
+    PropertyType, ScalarType,
+};
 use byteorder::{BigEndian, LittleEndian};
 
 /// PLY file deserializer for element-by-element processing.
@@ -31,28 +43,126 @@ use byteorder::{BigEndian, LittleEndian};
 /// let ply_data = b"ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n0.0 0.0 0.0\n3 0 1 2\n";
 /// let mut reader = PlyReader::from_reader(BufReader::new(Cursor::new(ply_data)))?;
 ///
-/// let vertices: Vec<Vertex> = reader.next_element()?;
-/// let faces: Vec<Face> = reader.next_element()?;
+/// let vertices: Vec<Vertex> = reader.next_element_named("vertex")?;
+/// let faces: Vec<Face> = reader.next_element_named("face")?;
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 pub struct PlyReader<R> {
     reader: R,
     header: PlyHeader,
     current_element: usize,
+    last_element_bytes: usize,
+    options: DeserializeOptions,
+    report: Option<LoadReport>,
+    precision_warnings: Vec<PrecisionWarning>,
+    progress: Progress,
+    // The header as it was read, for `tee`. Unknown for readers made with `from_parts`.
+    header_bytes: Option<Vec<u8>>,
+}
+
+/// Parse the header of the whole input in `reader`, keeping the bytes it was read from.
+fn parse_header(reader: impl BufRead) -> Result<(PlyHeader, Vec<u8>), DeserializeError> {
+    let mut reader = TeeReader::new(reader, Vec::new());
+    let header = PlyHeader::parse_with(&mut reader, true)?;
+    Ok((header, reader.into_writer()?))
 }
 
 impl<R: BufRead> PlyReader<R> {
     /// Create PLY deserializer from reader.
     ///
     /// Parses the PLY header immediately but doesn't read any element data.
-    /// Use [`Self::next_element`] to process elements sequentially.
+    /// Use [`Self::next_element`] to process elements sequentially. Binary data that
+    /// directly follows `end_header`, without a line break, is accepted.
     pub fn from_reader(mut reader: R) -> Result<Self, DeserializeError> {
-        let header = PlyHeader::parse(&mut reader)?;
+        let (header, header_bytes) = parse_header(&mut reader)?;
         Ok(Self {
+            header_bytes: Some(header_bytes),
+            ..Self::from_parts(header, reader)
+        })
+    }
+
+    /// Create PLY deserializer from a header and a reader positioned at the start of the
+    /// element data.
+    ///
+    /// Use this to read the data of a file whose header had to be repaired. Errors in
+    /// the [`crate::ErrorPhase::Header`] phase don't consume any element data.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::{ErrorPhase, PlyReader};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// // The element count isn't a number.
+    /// let header = "ply\nformat ascii 1.0\nelement vertex one\nproperty float x\nend_header\n";
+    /// let data = "2.5\n";
+    /// let err = PlyReader::from_reader(format!("{header}{data}").as_bytes()).err().unwrap();
+    /// assert_eq!(err.phase(), ErrorPhase::Header);
+    ///
+    /// let fixed = header.replace("vertex one", "vertex 1");
+    /// let (header, _) = serde_ply::read_header_prefix(fixed.as_bytes())?;
+    /// let mut reader = PlyReader::from_parts(header, data.as_bytes());
+    /// let vertices: Vec<Vertex> = reader.next_element()?;
+    /// assert_eq!(vertices[0].x, 2.5);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_parts(header: PlyHeader, reader: R) -> Self {
+        Self {
             reader,
+            progress: Progress::new(&header),
             header,
             current_element: 0,
-        })
+            last_element_bytes: 0,
+            options: DeserializeOptions::default(),
+            report: None,
+            precision_warnings: Vec::new(),
+            header_bytes: None,
+        }
+    }
+
+    /// Use the given [`DeserializeOptions`] for all subsequent reads.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::{DeserializeOptions, PlyReader};
+    /// use std::io::Cursor;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { weight: f32 }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty list uchar float weight\nend_header\n1 0.5\n";
+    /// let mut reader = PlyReader::from_reader(Cursor::new(ply_data))?
+    ///     .with_options(DeserializeOptions::new().with_single_element_list_as_scalar(true));
+    /// let vertices: Vec<Vertex> = reader.next_element()?;
+    /// assert_eq!(vertices[0].weight, 0.5);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_options(mut self, mut options: DeserializeOptions) -> Self {
+        options.apply_to_header(&mut self.header);
+        self.report = options.collect_stats.then(|| LoadReport::new(&self.header));
+        if options.allow_extra_whitespace_in_binary
+            && self.header.format.is_binary()
+            && self.current_element == 0
+        {
+            self.skip_binary_padding();
+        }
+        self.options = options;
+        self
+    }
+
+    /// Skip stray whitespace some exporters write between `end_header` and binary data.
+    fn skip_binary_padding(&mut self) {
+        const MAX_PADDING: usize = 4;
+        for _ in 0..MAX_PADDING {
+            // A read error here is returned again by the first element read.
+            match self.reader.fill_buf() {
+                Ok([b' ' | b'\t' | b'\r' | b'\n', ..]) => self.reader.consume(1),
+                _ => break,
+            }
+        }
     }
 
     /// Get the parsed PLY header.
@@ -60,6 +170,140 @@ impl<R: BufRead> PlyReader<R> {
         &self.header
     }
 
+    /// An owned copy of the header, to keep around independently of the reader.
+    pub fn clone_header(&self) -> PlyHeader {
+        self.header.clone()
+    }
+
+    /// Consume the reader and return its header, without copying it.
+    pub fn into_header(self) -> PlyHeader {
+        self.header
+    }
+
+    /// Statistics of the data read so far.
+    ///
+    /// Returns `None` unless [`DeserializeOptions::collect_stats`] is enabled.
+    pub fn report(&self) -> Option<&LoadReport> {
+        self.report.as_ref()
+    }
+
+    /// Properties whose `double` values lost precision in `f32` fields so far.
+    ///
+    /// Empty unless the policy is [`crate::PrecisionLoss::Warn`], the default. See
+    /// [`DeserializeOptions::with_precision_loss`].
+    pub fn precision_warnings(&self) -> &[PrecisionWarning] {
+        &self.precision_warnings
+    }
+
+    /// Unwrap the underlying reader, positioned after the data read so far.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Switch to a [`PlyChunkedReader`] for the rest of the file.
+    ///
+    /// The chunked reader continues at the next unread element, with the same header,
+    /// options, [`Self::report`] and [`Self::precision_warnings`]. Bytes the reader had already buffered are moved into
+    /// its buffer, feed it the rest of the data after that. If nothing is buffered, this
+    /// reads once to fill the buffer, so any error from that read is returned. To keep
+    /// using the source afterwards, wrap a mutable reference to it, eg.
+    /// `BufReader::new(&mut file)`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyReader;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let data = b"ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nend_header\n1.0\n2.0\n";
+    /// let (first, rest) = data.split_at(data.len() - 4);
+    ///
+    /// let reader = PlyReader::from_reader(first)?;
+    /// assert_eq!(reader.header().elem_defs[0].count, 2);
+    ///
+    /// let mut chunked = reader.into_chunked()?;
+    /// chunked.buffer_mut().extend_from_slice(rest);
+    /// let vertices: Vec<Vertex> = chunked.next_chunk()?;
+    /// assert_eq!(vertices.len(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn into_chunked(mut self) -> Result<PlyChunkedReader, DeserializeError> {
+        let leftover = self.reader.fill_buf()?.to_vec();
+        Ok(PlyChunkedReader::from_parts(self.header, leftover)
+            .with_options(self.options)
+            .resume_at(
+                self.current_element,
+                self.report,
+                self.precision_warnings,
+                self.progress,
+            ))
+    }
+
+    /// Copy the file to `writer` as it's read.
+    ///
+    /// The header is written right away, and from then on every byte the reader consumes
+    /// is written as well, including the rows of skipped elements. Once all elements are
+    /// read `writer` holds an exact copy of the file, up to the end of the last element.
+    /// Useful to archive a file from a stream that can only be read once, while
+    /// validating it. Errors once any element was read. For readers made with
+    /// [`Self::from_parts`] the header bytes aren't known, so only the data is copied.
+    ///
+    /// Get the writer back with [`Self::into_inner`] and [`TeeReader::into_writer`], which
+    /// also reports errors from writing to it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyReader;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\ncomment scanned\nelement vertex 2\nproperty float x\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n1\n2\n3 0 1 1\n";
+    /// let mut reader = PlyReader::from_reader(ply_data.as_bytes())?.tee(Vec::new())?;
+    /// let vertices: Vec<Vertex> = reader.next_element()?;
+    /// reader.element()?.skip()?;
+    /// assert_eq!(vertices.len(), 2);
+    ///
+    /// let archive = reader.into_inner().into_writer()?;
+    /// assert_eq!(archive, ply_data.as_bytes());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn tee<W: Write>(
+        self,
+        mut writer: W,
+    ) -> Result<PlyReader<TeeReader<R, W>>, DeserializeError> {
+        if self.current_element > 0 {
+            return Err(DeserializeError::custom(
+                "tee must be set up before reading any element, as earlier bytes can't be copied",
+            ));
+        }
+        if let Some(bytes) = &self.header_bytes {
+            writer.write_all(bytes)?;
+        }
+        Ok(PlyReader {
+            reader: TeeReader::new(self.reader, writer),
+            header: self.header,
+            current_element: self.current_element,
+            last_element_bytes: self.last_element_bytes,
+            options: self.options,
+            report: self.report,
+            precision_warnings: self.precision_warnings,
+            progress: self.progress,
+            header_bytes: self.header_bytes,
+        })
+    }
+
+    /// Number of bytes the most recently read element occupied in the source.
+    ///
+    /// Together with the header length this can be used to build an offset table
+    /// of the elements in a file. Returns 0 before any element has been read.
+    pub fn last_element_bytes(&self) -> usize {
+        self.last_element_bytes
+    }
+
     /// Get the current element definition.
     ///
     /// Returns the element that will be deserialized by the next call to
@@ -69,6 +313,61 @@ impl<R: BufRead> PlyReader<R> {
         self.header.elem_defs.get(self.current_element)
     }
 
+    /// Whether there are elements left to read.
+    ///
+    /// Once this returns `false`, reading another element fails with an error for which
+    /// [`DeserializeError::is_elements_exhausted`] holds.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::de::IgnoredAny;
+    /// use serde_ply::PlyReader;
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nelement face 0\nproperty list uchar uint vertex_indices\nend_header\n1.0\n";
+    /// let mut reader = PlyReader::from_reader(ply_data.as_bytes())?;
+    /// let mut count = 0;
+    /// while reader.has_next_element() {
+    ///     reader.next_element::<IgnoredAny>()?;
+    ///     count += 1;
+    /// }
+    /// assert_eq!(count, 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn has_next_element(&self) -> bool {
+        self.current_element < self.header.elem_defs.len()
+    }
+
+    /// State of every element of the file, in file order, eg. to show progress.
+    ///
+    /// The states are a snapshot, they don't borrow the reader. While rows are read with
+    /// [`Self::element`] or [`Self::rows`], get them from [`ElementReader::element_states`]
+    /// or [`Rows::element_states`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::{ElementStatus, PlyReader};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 4\nproperty float x\nelement face 0\nproperty list uchar uint vertex_indices\nend_header\n1\n2\n3\n4\n";
+    /// let mut reader = PlyReader::from_reader(ply_data.as_bytes())?;
+    /// let mut rows = reader.rows::<Vertex>()?;
+    /// rows.next().unwrap()?;
+    /// let vertex = rows.element_states().next().unwrap();
+    /// assert_eq!((vertex.status, vertex.rows_done), (ElementStatus::Reading, 1));
+    /// assert_eq!(vertex.fraction_done(), 0.25);
+    /// drop(rows);
+    ///
+    /// let statuses: Vec<_> = reader.element_states().map(|s| s.status).collect();
+    /// assert_eq!(statuses, [ElementStatus::Skipped, ElementStatus::Pending]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn element_states(&self) -> impl Iterator<Item = ElementState> + '_ {
+        self.progress.states()
+    }
+
     /// Deserialize the next element.
     ///
     /// The type `T` should typically be a sequence of rows. for example `Vec<RowType>` where `RowType`
@@ -119,6 +418,710 @@ impl<R: BufRead> PlyReader<R> {
         }
         self.deserialize_map(FirstValueVisitor(PhantomData))
     }
+
+    /// Skip `n` elements and deserialize the one after, like [`Iterator::nth`].
+    ///
+    /// Elements are counted in file order, so this also reaches an element whose name
+    /// repeats an earlier one, eg. the second of two `vertex` elements, which a struct
+    /// field can't.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyReader;
+    /// use std::io::Cursor;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nelement vertex 1\nproperty float x\nend_header\n1\n2\n";
+    /// let mut reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    /// let second: Vec<Vertex> = reader.nth_element(1)?;
+    /// assert_eq!(second[0].x, 2.0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn nth_element<'a, T>(&mut self, n: usize) -> Result<T, DeserializeError>
+    where
+        T: Deserialize<'a>,
+    {
+        let remaining = self.header.elem_defs.len() - self.current_element;
+        if n >= remaining {
+            return Err(DeserializeError::custom(format!(
+                "can't read element {n} from here, only {remaining} elements are left"
+            )));
+        }
+        for _ in 0..n {
+            let index = self.current_element;
+            self.next_element::<IgnoredAny>()?;
+            self.progress.set(index, ElementStatus::Skipped);
+        }
+        self.next_element()
+    }
+
+    /// Name of the element that will be deserialized next, or `None` if all elements
+    /// have been read.
+    pub fn peek_element_name(&self) -> Option<&str> {
+        self.current_element().map(|e| e.name.as_str())
+    }
+
+    /// Deserialize the next element, checking that it is called `expected`.
+    ///
+    /// Unlike [`Self::next_element`] this catches reading elements out of order. If the
+    /// next element has a different name an error is returned and nothing is consumed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyReader;
+    /// use std::io::Cursor;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Face { vertex_indices: Vec<u32> }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 0\nproperty float x\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n3 0 1 2\n";
+    /// let mut reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    /// assert!(reader.next_element_named::<Vec<Face>>("face").is_err());
+    /// assert_eq!(reader.peek_element_name(), Some("vertex"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn next_element_named<'a, T>(&mut self, expected: &str) -> Result<T, DeserializeError>
+    where
+        T: Deserialize<'a>,
+    {
+        self.expect_element(expected)?;
+        self.next_element()
+    }
+
+    /// Iterate over the rows of the next element one at a time.
+    ///
+    /// Unlike [`Self::next_element`] this doesn't collect the element, so rows can be
+    /// processed as they are read. The iterator stops for good after the last row or after
+    /// the first error. Dropping it early skips the remaining rows, so the reader continues
+    /// with the next element. After an error the position in the stream is unknown and the
+    /// reader shouldn't be used any further.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyReader;
+    /// use std::io::Cursor;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nend_header\n1\n2\n3\n";
+    /// let mut reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    /// let mut rows = reader.rows::<Vertex>()?;
+    /// assert_eq!(rows.len(), 3);
+    /// let sum = rows.try_fold(0.0, |sum, v| v.map(|v| sum + v.x))?;
+    /// assert_eq!(sum, 6.0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn rows<T>(&mut self) -> Result<Rows<'_, R, T>, DeserializeError>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(self.element()?.rows())
+    }
+
+    /// Start reading the next element row by row.
+    ///
+    /// The returned [`ElementReader`] borrows this reader until it is dropped, so rows of
+    /// one element can't be mixed up with reads of the next. Dropping it early skips the
+    /// rows that weren't read.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyReader;
+    /// use std::io::Cursor;
+    ///
+    /// #[derive(Deserialize, Debug, PartialEq)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nelement face 0\nproperty list uchar uint vertex_indices\nend_header\n1\n2\n3\n";
+    /// let mut reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    ///
+    /// let mut vertices = reader.element()?;
+    /// assert_eq!(vertices.def().name, "vertex");
+    /// assert_eq!(vertices.peek_row::<Vertex>()?, Vertex { x: 1.0 });
+    /// let first: Vec<Vertex> = vertices.take(2)?;
+    /// assert_eq!(first.len(), 2);
+    /// drop(vertices);
+    ///
+    /// assert_eq!(reader.peek_element_name(), Some("face"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn element(&mut self) -> Result<ElementReader<'_, R>, DeserializeError> {
+        let element = self.current_element;
+        if element >= self.header.elem_defs.len() {
+            return Err(DeserializeError::elements_exhausted(None));
+        }
+        self.current_element += 1;
+        self.last_element_bytes = 0;
+        self.progress.rows_done(element, 0);
+        check_element_data(
+            &mut self.reader,
+            self.header.format,
+            &self.header.elem_defs[element],
+        )?;
+        Ok(ElementReader {
+            reader: self,
+            element,
+            row: 0,
+            failed: false,
+        })
+    }
+
+    /// Get the current element, erroring if it isn't called `name`.
+    fn expect_element(&self, name: &str) -> Result<&ElementDef, DeserializeError> {
+        let Some(elem_def) = self.current_element() else {
+            return Err(DeserializeError::elements_exhausted(Some(name)));
+        };
+        if elem_def.name != name {
+            return Err(DeserializeError::custom(format!(
+                "expected element '{name}' but the next element is '{0}' (did you forget to read or skip '{0}'?)",
+                elem_def.name
+            )));
+        }
+        Ok(elem_def)
+    }
+
+    /// Deserialize the next element as rows of `T`, and return the element's definition.
+    ///
+    /// Pass the definition to [`crate::SerializeOptions::with_element_def`] to write the rows
+    /// back with the exact property types of the source file, even if `T` uses wider types.
+    pub fn next_element_preserving<'a, T>(
+        &mut self,
+    ) -> Result<(Vec<T>, ElementDef), DeserializeError>
+    where
+        T: Deserialize<'a>,
+    {
+        let elem_def = self
+            .current_element()
+            .cloned()
+            .ok_or_else(|| DeserializeError::elements_exhausted(None))?;
+        let rows = self.next_element()?;
+        Ok((rows, elem_def))
+    }
+
+    /// Read the rows of the next element as [`RawRows`], without a type for them.
+    ///
+    /// Look at the values or the element's definition first, then convert the rows with
+    /// [`RawRows::deserialize_into`]. They convert with the options of this reader, to the
+    /// same rows as [`Self::next_element`] would have read.
+    pub fn next_element_raw(&mut self) -> Result<RawRows, DeserializeError> {
+        let (rows, elem_def) = self.next_element_preserving::<RawRow>()?;
+        let rows = rows.into_iter().map(|row| row.0).collect();
+        Ok(RawRows::new(elem_def, rows).with_options(self.options.clone()))
+    }
+
+    /// Read the rows of the next element of an ASCII file as raw lines, without parsing
+    /// any values.
+    ///
+    /// Useful for tools that filter or edit rows as text and pass the rest through. Each
+    /// row is expected on a line of its own, the line break is left out. The next element
+    /// must be called `name`, and binary files return an error, without consuming any data.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::PlyReader;
+    /// use std::io::Cursor;
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\nend_header\n1 2\n3.5 4\n";
+    /// let mut reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    /// let lines = reader.next_element_lines("vertex")?;
+    /// assert_eq!(lines, ["1 2", "3.5 4"]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn next_element_lines(&mut self, name: &str) -> Result<Vec<String>, DeserializeError> {
+        let elem_def = self.expect_element(name)?;
+        if self.header.format.is_binary() {
+            return Err(DeserializeError::custom(format!(
+                "element '{name}' is {}, only ASCII rows can be read as lines",
+                self.header.format
+            )));
+        }
+
+        let count = elem_def.count;
+        let index = self.current_element;
+        self.current_element += 1;
+        self.progress.rows_done(index, 0);
+        check_element_data(
+            &mut self.reader,
+            self.header.format,
+            &self.header.elem_defs[index],
+        )?;
+        let mut lines = Vec::with_capacity(count);
+        let mut bytes = 0;
+        let mut line = Vec::new();
+        while lines.len() < count {
+            line.clear();
+            let read = self.reader.read_until(b'\n', &mut line)?;
+            if read == 0 {
+                return Err(DeserializeError::missing_data(name, count, lines.len()));
+            }
+            bytes += read;
+            let end = line.strip_suffix(b"\n").unwrap_or(&line);
+            let end = end.strip_suffix(b"\r").unwrap_or(end);
+            let text = std::str::from_utf8(end).map_err(|_| {
+                DeserializeError::custom(format!(
+                    "row {} of element '{name}' is not valid UTF-8",
+                    lines.len()
+                ))
+            })?;
+            lines.push(text.to_string());
+        }
+        self.last_element_bytes = bytes;
+        self.record_bulk(index, count);
+        self.progress.set(index, ElementStatus::Done);
+        Ok(lines)
+    }
+
+    /// Read the next element as rows of `N` `double` properties.
+    ///
+    /// A fast path for high precision point data (eg. geodetic coordinates) that skips
+    /// the per-property serde machinery. The next element must be called `name` and
+    /// consist of exactly `N` scalar `double` properties, otherwise an error is returned
+    /// without consuming any data.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::PlyReader;
+    /// use std::io::Cursor;
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty double x\nproperty double y\nproperty double z\nend_header\n1.5 2.5 3.5\n";
+    /// let mut reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    /// let positions = reader.element_columns_f64::<3>("vertex")?;
+    /// assert_eq!(positions, vec![[1.5, 2.5, 3.5]]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn element_columns_f64<const N: usize>(
+        &mut self,
+        name: &str,
+    ) -> Result<Vec<[f64; N]>, DeserializeError> {
+        let elem_def = self.expect_element(name)?;
+        if elem_def.properties.len() != N {
+            return Err(DeserializeError::custom(format!(
+                "element '{name}' has {} properties, expected {N}",
+                elem_def.properties.len()
+            )));
+        }
+        if let Some(prop) = elem_def
+            .properties
+            .iter()
+            .find(|p| !matches!(p.property_type, PropertyType::Scalar(ScalarType::F64)))
+        {
+            return Err(DeserializeError::custom(format!(
+                "property '{}' of element '{name}' is not a double",
+                prop.name
+            )));
+        }
+
+        let count = elem_def.count;
+        let index = self.current_element;
+        self.current_element += 1;
+        self.progress.rows_done(index, 0);
+        check_element_data(
+            &mut self.reader,
+            self.header.format,
+            &self.header.elem_defs[index],
+        )?;
+        let mut reader = CountingReader::new(&mut self.reader);
+        let rows = match self.header.format {
+            PlyFormat::Ascii => read_f64_rows::<_, AsciiValReader, N>(&mut reader, count),
+            PlyFormat::BinaryLittleEndian => {
+                read_f64_rows::<_, BinValReader<LittleEndian>, N>(&mut reader, count)
+            }
+            PlyFormat::BinaryBigEndian => {
+                read_f64_rows::<_, BinValReader<BigEndian>, N>(&mut reader, count)
+            }
+        }?;
+        self.last_element_bytes = reader.count;
+        self.record_bulk(index, count);
+        self.progress.set(index, ElementStatus::Done);
+        Ok(rows)
+    }
+
+    /// Read the next element into a buffer of `#[repr(C)]` rows laid out by `layout`.
+    ///
+    /// The buffer can be uploaded as is to eg. a GPU, and uses the native byte order. If the
+    /// file is binary and `layout` matches the element exactly the rows are read in bulk,
+    /// otherwise each property is converted and written at its offset. Properties not in
+    /// the layout are skipped.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::{PlyReader, PodLayout, ScalarType};
+    /// use std::io::Cursor;
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty uchar red\nend_header\n1.5 10\n";
+    /// let mut reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    ///
+    /// // #[repr(C)] struct GpuVertex { x: f32, col: [u8; 4] }
+    /// let layout = PodLayout::new(8)
+    ///     .field("x", 0, ScalarType::F32)
+    ///     .field("red", 4, ScalarType::U8)
+    ///     .field_with_default("alpha", 7, ScalarType::U8, 255.0);
+    /// let bytes = reader.next_element_pod_bytes(&layout)?;
+    /// assert_eq!(bytes[4..], [10, 0, 0, 255]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn next_element_pod_bytes(
+        &mut self,
+        layout: &PodLayout,
+    ) -> Result<Vec<u8>, DeserializeError> {
+        let elem_def = self
+            .header
+            .elem_defs
+            .get(self.current_element)
+            .ok_or_else(|| DeserializeError::elements_exhausted(None))?;
+        let index = self.current_element;
+        self.current_element += 1;
+        self.progress.rows_done(index, 0);
+        check_element_data(&mut self.reader, self.header.format, elem_def)?;
+
+        let len = elem_def.count.checked_mul(layout.stride()).ok_or_else(|| {
+            DeserializeError::custom(format!(
+                "element '{}' has {} rows of {} bytes, more than fit in memory",
+                elem_def.name,
+                elem_def.count,
+                layout.stride()
+            ))
+        })?;
+        let mut reader = CountingReader::new(&mut self.reader);
+        let rows = if self.header.format.is_binary() && layout.is_packed_match(elem_def) {
+            // Read what's there rather than trusting the header with the allocation.
+            let mut rows = Vec::new();
+            (&mut reader).take(len as u64).read_to_end(&mut rows)?;
+            if rows.len() != len {
+                return Err(DeserializeError(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "element '{}' ended after {} of {len} bytes",
+                        elem_def.name,
+                        rows.len()
+                    ),
+                )));
+            }
+            let file_is_le = self.header.format.byte_order() == Some(ByteOrderKind::Little);
+            if file_is_le != cfg!(target_endian = "little") {
+                layout.swap_bytes(&mut rows);
+            }
+            rows
+        } else {
+            match self.header.format {
+                PlyFormat::Ascii => read_pod_rows::<_, AsciiValReader>(
+                    &mut reader,
+                    elem_def,
+                    layout,
+                    &self.options,
+                    &mut self.precision_warnings,
+                )?,
+                PlyFormat::BinaryLittleEndian => read_pod_rows::<_, BinValReader<LittleEndian>>(
+                    &mut reader,
+                    elem_def,
+                    layout,
+                    &self.options,
+                    &mut self.precision_warnings,
+                )?,
+                PlyFormat::BinaryBigEndian => read_pod_rows::<_, BinValReader<BigEndian>>(
+                    &mut reader,
+                    elem_def,
+                    layout,
+                    &self.options,
+                    &mut self.precision_warnings,
+                )?,
+            }
+        };
+        self.last_element_bytes = reader.count;
+        self.record_bulk(index, elem_def.count);
+        self.progress.set(index, ElementStatus::Done);
+        Ok(rows)
+    }
+
+    /// Add an element read without the row deserializer to the report.
+    fn record_bulk(&mut self, index: usize, rows: usize) {
+        if let Some(report) = &mut self.report {
+            report.elements[index].record_bulk(
+                &self.header.elem_defs[index],
+                self.header.format,
+                rows,
+                self.last_element_bytes as u64,
+            );
+        }
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<R: BufRead> PlyReader<R> {
+    /// Read the next element as a `(rows, properties)` matrix of `f32`.
+    ///
+    /// Every property is converted to `f32`, whatever its type in the header, so large
+    /// integers and `double` values may be rounded. The next element must be called `name`
+    /// and only have scalar properties, otherwise an error is returned without consuming
+    /// any data.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::PlyReader;
+    /// use std::io::Cursor;
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty double y\nproperty uchar z\nend_header\n1.5 2.5 3\n4 5 6\n";
+    /// let mut reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    /// let positions = reader.element_array2("vertex")?;
+    /// assert_eq!(positions, ndarray::array![[1.5, 2.5, 3.0], [4.0, 5.0, 6.0]]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn element_array2(&mut self, name: &str) -> Result<ndarray::Array2<f32>, DeserializeError> {
+        let elem_def = self.expect_element(name)?;
+        let mut types = Vec::with_capacity(elem_def.properties.len());
+        for prop in &elem_def.properties {
+            match prop.property_type {
+                PropertyType::Scalar(ty) => types.push(ty),
+                PropertyType::List { .. } => {
+                    return Err(DeserializeError::custom(format!(
+                        "property '{}' of element '{name}' is a list, but a matrix needs scalars",
+                        prop.name
+                    )))
+                }
+            }
+        }
+
+        let count = elem_def.count;
+        let index = self.current_element;
+        self.current_element += 1;
+        self.progress.rows_done(index, 0);
+        check_element_data(
+            &mut self.reader,
+            self.header.format,
+            &self.header.elem_defs[index],
+        )?;
+        let mut reader = CountingReader::new(&mut self.reader);
+        let values = match self.header.format {
+            PlyFormat::Ascii => read_f32_matrix::<_, AsciiValReader>(&mut reader, &types, count),
+            PlyFormat::BinaryLittleEndian => {
+                read_f32_matrix::<_, BinValReader<LittleEndian>>(&mut reader, &types, count)
+            }
+            PlyFormat::BinaryBigEndian => {
+                read_f32_matrix::<_, BinValReader<BigEndian>>(&mut reader, &types, count)
+            }
+        }?;
+        self.last_element_bytes = reader.count;
+        self.record_bulk(index, count);
+        self.progress.set(index, ElementStatus::Done);
+        ndarray::Array2::from_shape_vec((count, types.len()), values)
+            .map_err(|e| DeserializeError::custom(e.to_string()))
+    }
+}
+
+#[cfg(feature = "ndarray")]
+fn read_f32_matrix<R: Read, S: ScalarReader>(
+    reader: &mut R,
+    types: &[ScalarType],
+    count: usize,
+) -> Result<Vec<f32>, DeserializeError> {
+    let mut values = Vec::with_capacity(count * types.len());
+    for _ in 0..count {
+        for &ty in types {
+            values.push(crate::de::pod::read_f64::<S>(&mut *reader, ty)? as f32);
+        }
+    }
+    Ok(values)
+}
+
+fn read_f64_rows<R: Read, S: ScalarReader, const N: usize>(
+    reader: &mut R,
+    count: usize,
+) -> Result<Vec<[f64; N]>, DeserializeError> {
+    let mut rows = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut row = [0.0; N];
+        for val in &mut row {
+            *val = S::read_f64(&mut *reader)?;
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+impl<R: Read> PlyReader<ReadBuffer<R>> {
+    /// Create PLY deserializer from a source that doesn't implement [`BufRead`].
+    ///
+    /// The reader buffers the source itself, with a buffer sized from the header: small
+    /// binary files are buffered whole, larger files in chunks of up to 1 MiB. Prefer this
+    /// over wrapping the source in a [`std::io::BufReader`] of your own, and use
+    /// [`Self::from_reader`] for sources that already buffer.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyReader;
+    /// use std::io::Read;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// // A source that only implements Read, like a socket or pipe.
+    /// struct Source<'a>(&'a [u8]);
+    ///
+    /// impl Read for Source<'_> {
+    ///     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    ///         self.0.read(buf)
+    ///     }
+    /// }
+    ///
+    /// let ply_data = b"ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nend_header\n1\n2\n";
+    /// let mut reader = PlyReader::from_read(Source(ply_data))?;
+    /// let vertices: Vec<Vertex> = reader.next_element()?;
+    /// assert_eq!(vertices.len(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_read(reader: R) -> Result<Self, DeserializeError> {
+        let mut reader = ReadBuffer::new(reader);
+        let (header, header_bytes) = parse_header(&mut reader)?;
+        reader.fit_to(&header);
+        Ok(Self {
+            header_bytes: Some(header_bytes),
+            ..Self::from_parts(header, reader)
+        })
+    }
+}
+
+impl<R: BufRead + Seek> PlyReader<R> {
+    /// Create PLY deserializer from a reader that knows its length, like a file or cursor.
+    ///
+    /// Like [`Self::from_reader`], but for binary files also checks up front that the
+    /// input is long enough for the data the header declares. A truncated file then fails
+    /// immediately with the expected and actual sizes, instead of with an end of file error
+    /// partway through an element. Only elements before the first list property are
+    /// counted, as the size of list data isn't known without reading it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::PlyReader;
+    /// use std::io::Cursor;
+    ///
+    /// let mut ply_data = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nproperty float x\nend_header\n".to_vec();
+    /// ply_data.extend_from_slice(&1.0f32.to_le_bytes());
+    ///
+    /// let err = PlyReader::from_seekable(Cursor::new(ply_data)).err().unwrap();
+    /// assert!(err.to_string().contains("at least 8 bytes of data but only 4 bytes remain"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_seekable(reader: R) -> Result<Self, DeserializeError> {
+        let mut ply = Self::from_reader(reader)?;
+        ply.check_data_len()?;
+        Ok(ply)
+    }
+
+    /// Create PLY deserializer for a file embedded `offset` bytes into `reader`.
+    ///
+    /// Seeks to `offset` and then reads the header like [`Self::from_reader`]. Useful for
+    /// containers that store a PLY file after a prefix of their own.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyReader;
+    /// use std::io::Cursor;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let data = b"MYFORMAT v1\nply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\n2.5\n";
+    /// let mut reader = PlyReader::from_reader_at(Cursor::new(data), 12)?;
+    /// let vertices: Vec<Vertex> = reader.next_element()?;
+    /// assert_eq!(vertices[0].x, 2.5);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_reader_at(mut reader: R, offset: u64) -> Result<Self, DeserializeError> {
+        reader.seek(SeekFrom::Start(offset))?;
+        Self::from_reader(reader)
+    }
+
+    /// Check up front that the input holds data for the elements left to read.
+    ///
+    /// Fails with a [`crate::MissingData`] error, see [`DeserializeError::as_missing_data`],
+    /// when no data follows the header but an element declares rows, as left behind by a
+    /// writer that crashed. For binary files the size of the data is checked as well, up
+    /// to the first element with list properties, and the error names the first element
+    /// that is cut short. ASCII data can't be checked without reading it. Doesn't move the
+    /// read position.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::PlyReader;
+    /// use std::io::Cursor;
+    ///
+    /// let mut ply_data = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nproperty float x\nelement face 1\nproperty uchar flag\nend_header\n".to_vec();
+    /// ply_data.extend_from_slice(&[0; 8]);
+    ///
+    /// let mut reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    /// let err = reader.validate_complete().err().unwrap();
+    /// let missing = err.as_missing_data().unwrap();
+    /// assert_eq!((missing.element.as_str(), missing.rows_read), ("face", 0));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn validate_complete(&mut self) -> Result<(), DeserializeError> {
+        let data_start = self.reader.stream_position()?;
+        let end = self.reader.seek(SeekFrom::End(0))?;
+        self.reader.seek(SeekFrom::Start(data_start))?;
+        let available = end.saturating_sub(data_start);
+        let elements =
+            &self.header.elem_defs[self.current_element.min(self.header.elem_defs.len())..];
+        if available == 0 {
+            return match elements.iter().find(|e| e.count > 0) {
+                Some(e) => Err(DeserializeError::missing_data(&e.name, e.count, 0)),
+                None => Ok(()),
+            };
+        }
+        if !self.header.format.is_binary() {
+            return Ok(());
+        }
+        let mut offset = 0;
+        for elem_def in elements {
+            let Some(row_size) = elem_def.row_byte_size() else {
+                break;
+            };
+            let size = (row_size * elem_def.count) as u64;
+            if offset + size > available {
+                let rows_read = ((available - offset) / row_size as u64) as usize;
+                return Err(DeserializeError::missing_data(
+                    &elem_def.name,
+                    elem_def.count,
+                    rows_read,
+                ));
+            }
+            offset += size;
+        }
+        Ok(())
+    }
+
+    fn check_data_len(&mut self) -> Result<(), DeserializeError> {
+        let data_start = self.reader.stream_position()?;
+        let Some(expected) = self
+            .header
+            .element_byte_ranges(0)
+            .into_iter()
+            .map_while(|range| range.map(|r| r.end))
+            .last()
+        else {
+            return Ok(());
+        };
+        let end = self.reader.seek(SeekFrom::End(0))?;
+        self.reader.seek(SeekFrom::Start(data_start))?;
+        let actual = end.saturating_sub(data_start);
+        if actual < expected {
+            return Err(DeserializeError(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!(
+                    "File is truncated, the header declares at least {expected} bytes of data but only {actual} bytes remain"
+                ),
+            )));
+        }
+        Ok(())
+    }
 }
 
 impl<'de, R: BufRead> Deserializer<'de> for &mut PlyReader<R> {
@@ -161,20 +1164,112 @@ impl<'de, R: BufRead> Deserializer<'de> for &mut PlyReader<R> {
         visitor.visit_newtype_struct(self)
     }
 
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        // Elements as (name, rows) pairs, eg. for Vec<(String, Vec<Row>)>. Unlike a map
+        // this keeps the file order.
+        visitor.visit_seq(ElementEntries(self))
+    }
+
     serde::forward_to_deserialize_any! {
         bool i8 u8 i16 u16 i32 u32 i64 u64 f32 f64 char str string
         bytes byte_buf option unit unit_struct tuple
-        tuple_struct enum identifier ignored_any seq
+        tuple_struct enum identifier ignored_any
+    }
+}
+
+/// The remaining elements of a file as a sequence of `(name, rows)` pairs.
+struct ElementEntries<'a, R>(&'a mut PlyReader<R>);
+
+impl<'de, R: BufRead> SeqAccess<'de> for ElementEntries<'_, R> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.0.current_element >= self.0.header.elem_defs.len() {
+            return Ok(None);
+        }
+        seed.deserialize(ElementEntry(&mut *self.0)).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.header.elem_defs.len() - self.0.current_element)
     }
 }
 
-impl<'de, R: Read> MapAccess<'de> for &mut PlyReader<R> {
+/// A single `(name, rows)` pair of the current element.
+struct ElementEntry<'a, R>(&'a mut PlyReader<R>);
+
+impl<'de, R: BufRead> Deserializer<'de> for ElementEntry<'_, R> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(ElementEntryAccess {
+            reader: self.0,
+            index: 0,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 u8 i16 u16 i32 u32 i64 u64 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct ElementEntryAccess<'a, R> {
+    reader: &'a mut PlyReader<R>,
+    index: usize,
+}
+
+impl<'de, R: BufRead> SeqAccess<'de> for ElementEntryAccess<'_, R> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.index += 1;
+        match self.index {
+            1 => {
+                let name = &self.reader.header.elem_defs[self.reader.current_element].name;
+                seed.deserialize(StrDeserializer::new(name)).map(Some)
+            }
+            2 => self.reader.next_value_seed(seed).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(2usize.saturating_sub(self.index))
+    }
+}
+
+impl<'de, R: BufRead> MapAccess<'de> for &mut PlyReader<R> {
     type Error = DeserializeError;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
     where
         K: DeserializeSeed<'de>,
     {
+        // A name that repeats an earlier element's name would be a duplicate key, so only
+        // the first element of each name is visited. Skip the later ones.
+        while let Some(elem_def) = self.header.elem_defs.get(self.current_element) {
+            let earlier = &self.header.elem_defs[..self.current_element];
+            if !earlier.iter().any(|e| e.name == elem_def.name) {
+                break;
+            }
+            let index = self.current_element;
+            self.next_value_seed(PhantomData::<IgnoredAny>)?;
+            self.progress.set(index, ElementStatus::Skipped);
+        }
         if self.current_element >= self.header.elem_defs.len() {
             return Ok(None);
         }
@@ -187,49 +1282,357 @@ impl<'de, R: Read> MapAccess<'de> for &mut PlyReader<R> {
     where
         V: DeserializeSeed<'de>,
     {
-        let elem_def = &self.header.elem_defs[self.current_element];
+        let Some(elem_def) = self.header.elem_defs.get(self.current_element) else {
+            return Err(DeserializeError::elements_exhausted(None));
+        };
+        let index = self.current_element;
+        let stats = self.report.as_mut().map(|r| &mut r.elements[index]);
         self.current_element += 1;
+        self.progress.rows_done(index, 0);
+        check_element_data(&mut self.reader, self.header.format, elem_def)?;
 
-        match self.header.format {
-            PlyFormat::Ascii => seed.deserialize(ElementSeqDeserializer::<_, AsciiValReader>::new(
-                &elem_def.properties,
-                &mut self.reader,
-                elem_def.count,
-            )),
-            PlyFormat::BinaryLittleEndian => seed.deserialize(ElementSeqDeserializer::<
-                _,
-                BinValReader<LittleEndian>,
-            >::new(
-                &elem_def.properties,
-                &mut self.reader,
-                elem_def.count,
-            )),
-            PlyFormat::BinaryBigEndian => {
-                seed.deserialize(ElementSeqDeserializer::<_, BinValReader<BigEndian>>::new(
-                    &elem_def.properties,
-                    &mut self.reader,
-                    elem_def.count,
+        let mut reader = CountingReader::new(&mut self.reader);
+        let value = match self.header.format {
+            PlyFormat::Ascii => seed.deserialize(
+                ElementSeqDeserializer::<_, AsciiValReader>::new(
+                    elem_def,
+                    &mut reader,
+                    &self.options,
+                )
+                .with_stats(stats)
+                .with_warnings(&mut self.precision_warnings),
+            ),
+            PlyFormat::BinaryLittleEndian => seed.deserialize(
+                ElementSeqDeserializer::<_, BinValReader<LittleEndian>>::new(
+                    elem_def,
+                    &mut reader,
+                    &self.options,
+                )
+                .with_stats(stats)
+                .with_warnings(&mut self.precision_warnings),
+            ),
+            PlyFormat::BinaryBigEndian => seed.deserialize(
+                ElementSeqDeserializer::<_, BinValReader<BigEndian>>::new(
+                    elem_def,
+                    &mut reader,
+                    &self.options,
+                )
+                .with_stats(stats)
+                .with_warnings(&mut self.precision_warnings),
+            ),
+        };
+        self.last_element_bytes = reader.count;
+        if value.is_ok() {
+            self.progress.set(index, ElementStatus::Done);
+        }
+        value
+    }
+}
+
+/// Access to the rows of a single element, created by [`PlyReader::element`].
+///
+/// Borrows the [`PlyReader`], so the next element can only be started once this one is
+/// done with. Dropping it before all rows are read skips the rest of the element, so the
+/// parent reader continues with the next element either way. Use [`Self::skip`] to see
+/// errors from skipping. After a row fails to read the position in the stream is unknown,
+/// no more rows are returned and the parent reader shouldn't be used any further.
+pub struct ElementReader<'r, R: BufRead> {
+    reader: &'r mut PlyReader<R>,
+    element: usize,
+    row: usize,
+    failed: bool,
+}
+
+impl<'r, R: BufRead> ElementReader<'r, R> {
+    /// Definition of the element.
+    pub fn def(&self) -> &ElementDef {
+        &self.reader.header.elem_defs[self.element]
+    }
+
+    /// Number of rows left in the element. Zero once a row failed to read.
+    pub fn remaining(&self) -> usize {
+        if self.failed {
+            0
+        } else {
+            self.def().count - self.row
+        }
+    }
+
+    /// Statistics of the element so far, see [`PlyReader::report`].
+    pub fn stats(&self) -> Option<&ElementStats> {
+        let report = self.reader.report.as_ref()?;
+        Some(&report.elements[self.element])
+    }
+
+    /// Iterate over the remaining rows.
+    pub fn rows<T: DeserializeOwned>(self) -> Rows<'r, R, T> {
+        Rows {
+            element: self,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Read all remaining rows.
+    pub fn collect<T: DeserializeOwned>(self) -> Result<Vec<T>, DeserializeError> {
+        self.rows().collect()
+    }
+
+    /// Read the next `n` rows, or all remaining rows if there are fewer.
+    pub fn take<T: DeserializeOwned>(&mut self, n: usize) -> Result<Vec<T>, DeserializeError> {
+        (0..n.min(self.remaining()))
+            .map(|_| self.read_row())
+            .collect()
+    }
+
+    /// Skip the remaining rows without converting them.
+    pub fn skip(mut self) -> Result<(), DeserializeError> {
+        self.skip_rest()
+    }
+
+    /// State of every element of the file, see [`PlyReader::element_states`].
+    pub fn element_states(&self) -> impl Iterator<Item = ElementState> + '_ {
+        self.reader.element_states()
+    }
+
+    fn skip_rest(&mut self) -> Result<(), DeserializeError> {
+        if self.remaining() == 0 {
+            return Ok(());
+        }
+        while self.remaining() > 0 {
+            self.read_row::<IgnoredAny>()?;
+        }
+        self.reader
+            .progress
+            .set(self.element, ElementStatus::Skipped);
+        Ok(())
+    }
+
+    /// Deserialize the next row without consuming it.
+    ///
+    /// The row is read from the data the reader has buffered, see [`BufRead::fill_buf`].
+    /// Returns an error if the row isn't buffered in full. For ASCII data only complete
+    /// lines are used.
+    pub fn peek_row<T: DeserializeOwned>(&mut self) -> Result<T, DeserializeError> {
+        if self.remaining() == 0 {
+            return Err(DeserializeError::custom(format!(
+                "element '{}' has no rows left to peek",
+                self.def().name
+            )));
+        }
+        let reader = &mut *self.reader;
+        let elem_def = &reader.header.elem_defs[self.element];
+        let mut buf = reader.reader.fill_buf()?;
+        // A value at the end of the buffer might continue in the unbuffered data.
+        if !reader.header.format.is_binary() {
+            if let Some(end) = buf.iter().rposition(|&b| b == b'\n') {
+                buf = &buf[..=end];
+            }
+        }
+        // Peeked values are counted once they are read for real.
+        let mut warnings = Vec::new();
+        let row = match reader.header.format {
+            PlyFormat::Ascii => deserialize_row::<_, AsciiValReader, T>(
+                &mut buf,
+                elem_def,
+                &reader.options,
+                None,
+                &mut warnings,
+                self.row,
+            ),
+            PlyFormat::BinaryLittleEndian => deserialize_row::<_, BinValReader<LittleEndian>, T>(
+                &mut buf,
+                elem_def,
+                &reader.options,
+                None,
+                &mut warnings,
+                self.row,
+            ),
+            PlyFormat::BinaryBigEndian => deserialize_row::<_, BinValReader<BigEndian>, T>(
+                &mut buf,
+                elem_def,
+                &reader.options,
+                None,
+                &mut warnings,
+                self.row,
+            ),
+        };
+        row.map_err(|e| {
+            if e.0.kind() == std::io::ErrorKind::UnexpectedEof {
+                DeserializeError::custom(format!(
+                    "row {} of element '{}' isn't fully buffered, so it can't be peeked",
+                    self.row, elem_def.name
                 ))
+            } else {
+                e
             }
+        })
+    }
+
+    fn read_row<U: DeserializeOwned>(&mut self) -> Result<U, DeserializeError> {
+        let reader = &mut *self.reader;
+        let elem_def = &reader.header.elem_defs[self.element];
+        let stats = reader
+            .report
+            .as_mut()
+            .map(|r| &mut r.elements[self.element]);
+        let mut counting = CountingReader::new(&mut reader.reader);
+        let row = match reader.header.format {
+            PlyFormat::Ascii => deserialize_row::<_, AsciiValReader, U>(
+                &mut counting,
+                elem_def,
+                &reader.options,
+                stats,
+                &mut reader.precision_warnings,
+                self.row,
+            ),
+            PlyFormat::BinaryLittleEndian => deserialize_row::<_, BinValReader<LittleEndian>, U>(
+                &mut counting,
+                elem_def,
+                &reader.options,
+                stats,
+                &mut reader.precision_warnings,
+                self.row,
+            ),
+            PlyFormat::BinaryBigEndian => deserialize_row::<_, BinValReader<BigEndian>, U>(
+                &mut counting,
+                elem_def,
+                &reader.options,
+                stats,
+                &mut reader.precision_warnings,
+                self.row,
+            ),
+        };
+        reader.last_element_bytes += counting.count;
+        self.row += 1;
+        self.failed = row.is_err();
+        if !self.failed {
+            reader.progress.rows_done(self.element, self.row);
         }
+        row
     }
 }
 
-pub(crate) struct ElementSeqDeserializer<'a, R: Read, S: ScalarReader> {
+impl<R: BufRead> Drop for ElementReader<'_, R> {
+    fn drop(&mut self) {
+        // Skip the rest of the element so the reader is at the start of the next one.
+        let _ = self.skip_rest();
+    }
+}
+
+/// Iterator over the rows of an element, created by [`PlyReader::rows`] and
+/// [`ElementReader::rows`].
+pub struct Rows<'r, R: BufRead, T> {
+    element: ElementReader<'r, R>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<R: BufRead, T> Rows<'_, R, T> {
+    /// Number of rows left in the element. Zero once the iterator has stopped.
+    pub fn remaining(&self) -> usize {
+        self.element.remaining()
+    }
+
+    /// State of every element of the file, see [`PlyReader::element_states`].
+    pub fn element_states(&self) -> impl Iterator<Item = ElementState> + '_ {
+        self.element.element_states()
+    }
+}
+
+/// Fail with [`crate::MissingData`] when the data ends before an element with rows, as
+/// for a file with a header but no data.
+fn check_element_data(
+    reader: &mut impl BufRead,
+    format: PlyFormat,
+    elem_def: &ElementDef,
+) -> Result<(), DeserializeError> {
+    if elem_def.count == 0 {
+        return Ok(());
+    }
+    loop {
+        let buf = reader.fill_buf()?;
+        // Whitespace before the first ASCII row would be skipped anyway.
+        let space = match format {
+            PlyFormat::Ascii => buf.iter().take_while(|b| b.is_ascii_whitespace()).count(),
+            _ => 0,
+        };
+        if buf.is_empty() {
+            return Err(DeserializeError::missing_data(
+                &elem_def.name,
+                elem_def.count,
+                0,
+            ));
+        }
+        if space == 0 {
+            return Ok(());
+        }
+        reader.consume(space);
+    }
+}
+
+fn deserialize_row<R: BufRead, S: ScalarReader, T: DeserializeOwned>(
+    reader: &mut R,
+    elem_def: &ElementDef,
+    options: &DeserializeOptions,
+    stats: Option<&mut ElementStats>,
+    warnings: &mut Vec<PrecisionWarning>,
+    row_index: usize,
+) -> Result<T, DeserializeError> {
+    let mut row = RowDeserializer::<_, S>::new(reader, elem_def, options)
+        .with_stats(stats)
+        .with_warnings(warnings);
+    row.row_index = row_index;
+    T::deserialize(&mut row)
+}
+
+impl<R: BufRead, T: DeserializeOwned> Iterator for Rows<'_, R, T> {
+    type Item = Result<T, DeserializeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining() == 0 {
+            return None;
+        }
+        Some(self.element.read_row())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<R: BufRead, T: DeserializeOwned> ExactSizeIterator for Rows<'_, R, T> {}
+
+impl<R: BufRead, T: DeserializeOwned> FusedIterator for Rows<'_, R, T> {}
+
+pub(crate) struct ElementSeqDeserializer<'a, R: BufRead, S: ScalarReader> {
     row: RowDeserializer<'a, R, S>,
     remaining: usize,
 }
 
-impl<'a, R: Read, S: ScalarReader> ElementSeqDeserializer<'a, R, S> {
-    pub(crate) fn new(properties: &'a [PlyProperty], reader: &'a mut R, row_count: usize) -> Self {
+impl<'a, R: BufRead, S: ScalarReader> ElementSeqDeserializer<'a, R, S> {
+    pub(crate) fn new(
+        elem_def: &'a ElementDef,
+        reader: &'a mut R,
+        options: &'a DeserializeOptions,
+    ) -> Self {
         Self {
-            row: RowDeserializer::new(reader, properties),
-            remaining: row_count,
+            row: RowDeserializer::new(reader, elem_def, options),
+            remaining: elem_def.count,
         }
     }
+
+    pub(crate) fn with_stats(mut self, stats: Option<&'a mut ElementStats>) -> Self {
+        self.row = self.row.with_stats(stats);
+        self
+    }
+
+    pub(crate) fn with_warnings(mut self, warnings: &'a mut Vec<PrecisionWarning>) -> Self {
+        self.row = self.row.with_warnings(warnings);
+        self
+    }
 }
 
-impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for ElementSeqDeserializer<'_, R, S> {
+impl<'de, R: BufRead, S: ScalarReader> Deserializer<'de> for ElementSeqDeserializer<'_, R, S> {
     type Error = DeserializeError;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -271,7 +1674,7 @@ impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for ElementSeqDeserializer
     }
 }
 
-impl<'de, R: Read, S: ScalarReader> SeqAccess<'de> for ElementSeqDeserializer<'_, R, S> {
+impl<'de, R: BufRead, S: ScalarReader> SeqAccess<'de> for ElementSeqDeserializer<'_, R, S> {
     type Error = DeserializeError;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
@@ -281,6 +1684,7 @@ impl<'de, R: Read, S: ScalarReader> SeqAccess<'de> for ElementSeqDeserializer<'_
         if self.remaining == 0 {
             return Ok(None);
         }
+        self.row.row_index = self.row.elem_def.count - self.remaining;
         self.remaining -= 1;
         seed.deserialize(&mut self.row).map(Some)
     }
@@ -0,0 +1,382 @@
+//! Decoding elements straight into plain old data buffers.
+
+use std::io::Read;
+
+use serde::de::Error;
+
+use crate::{
+    de::{
+        coerce::{coerce, coercion, CoerceError, Coercion, FieldType, FieldValue, Scalar},
+        stats::PrecisionWarning,
+        val_reader::ScalarReader,
+    },
+    DeserializeError, DeserializeOptions, ElementDef, PropertyType, ScalarType,
+};
+
+/// Byte layout of a `#[repr(C)]` row type, for [`crate::PlyReader::next_element_pod_bytes`].
+///
+/// Each field maps a property to an offset in the row and the type stored there. Values
+/// are converted by the same rules as fields of a serde row, see [`crate::coercion`]: eg.
+/// a `float` property can't be stored in an integer field, an `int` value that doesn't fit
+/// a `U8` field is an error, and a `double` stored in an `F32` field follows
+/// [`crate::PrecisionLoss`].
+///
+/// # Example
+/// ```rust
+/// use serde_ply::{PodLayout, ScalarType};
+///
+/// // #[repr(C)] struct GpuVertex { pos: [f32; 3], col: [u8; 4] }
+/// let layout = PodLayout::new(16)
+///     .field("x", 0, ScalarType::F32)
+///     .field("y", 4, ScalarType::F32)
+///     .field("z", 8, ScalarType::F32)
+///     .field("red", 12, ScalarType::U8)
+///     .field("green", 13, ScalarType::U8)
+///     .field("blue", 14, ScalarType::U8)
+///     .field_with_default("alpha", 15, ScalarType::U8, 255.0);
+/// assert_eq!(layout.stride(), 16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PodLayout {
+    stride: usize,
+    fields: Vec<PodField>,
+}
+
+#[derive(Debug, Clone)]
+struct PodField {
+    property: String,
+    offset: usize,
+    ty: ScalarType,
+    default: Option<f64>,
+}
+
+impl PodLayout {
+    /// Create a layout for rows of `stride` bytes, eg. `size_of::<GpuVertex>()`.
+    pub fn new(stride: usize) -> Self {
+        Self {
+            stride,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Store `property` as a `ty` at `offset` bytes into each row.
+    pub fn field(mut self, property: &str, offset: usize, ty: ScalarType) -> Self {
+        self.fields.push(PodField {
+            property: property.to_string(),
+            offset,
+            ty,
+            default: None,
+        });
+        self
+    }
+
+    /// Like [`Self::field`], but use `default` for files that don't have `property`.
+    ///
+    /// For integer fields the default must be a whole number the type can hold, otherwise
+    /// reading an element without `property` is an error.
+    pub fn field_with_default(
+        mut self,
+        property: &str,
+        offset: usize,
+        ty: ScalarType,
+        default: f64,
+    ) -> Self {
+        self.fields.push(PodField {
+            property: property.to_string(),
+            offset,
+            ty,
+            default: Some(default),
+        });
+        self
+    }
+
+    /// Layout of a `#[repr(C)]` struct with one field per property, in the same order
+    /// and with the same types as the element.
+    ///
+    /// Returns `None` if the element has list properties.
+    pub fn from_element(elem_def: &ElementDef) -> Option<Self> {
+        let mut layout = Self::new(0);
+        let mut offset = 0usize;
+        let mut align = 1;
+        for prop in &elem_def.properties {
+            let PropertyType::Scalar(ty) = prop.property_type else {
+                return None;
+            };
+            let size = ty.size_bytes();
+            offset = offset.next_multiple_of(size);
+            layout = layout.field(&prop.name, offset, ty);
+            offset += size;
+            align = align.max(size);
+        }
+        layout.stride = offset.next_multiple_of(align);
+        Some(layout)
+    }
+
+    /// Size of a single row in bytes.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Whether binary rows of the element are stored exactly like this layout, so they
+    /// can be copied without converting anything.
+    pub(crate) fn is_packed_match(&self, elem_def: &ElementDef) -> bool {
+        if elem_def.row_byte_size() != Some(self.stride)
+            || elem_def.properties.len() != self.fields.len()
+        {
+            return false;
+        }
+        let mut offset = 0;
+        elem_def
+            .properties
+            .iter()
+            .zip(&self.fields)
+            .all(|(prop, field)| {
+                let matches = prop.name == field.property
+                    && prop.property_type == PropertyType::Scalar(field.ty)
+                    && field.offset == offset;
+                offset += field.ty.size_bytes();
+                matches
+            })
+    }
+
+    /// Swap the byte order of every field in `rows`.
+    ///
+    /// Neighbouring fields of the same size are swapped as one run of lanes, and rows that
+    /// are a single run are swapped in one pass over the whole buffer. Both loops are
+    /// simple enough for the compiler to vectorize.
+    pub(crate) fn swap_bytes(&self, rows: &mut [u8]) {
+        // (offset, length, lane size) of each run of same sized fields.
+        let mut runs: Vec<(usize, usize, usize)> = Vec::new();
+        for field in &self.fields {
+            let size = field.ty.size_bytes();
+            match runs.last_mut() {
+                Some((offset, len, lane)) if *lane == size && *offset + *len == field.offset => {
+                    *len += size;
+                }
+                _ => runs.push((field.offset, size, size)),
+            }
+        }
+        if let [(0, len, lane)] = runs[..] {
+            if len == self.stride {
+                swap_lanes(rows, lane);
+                return;
+            }
+        }
+        for row in rows.chunks_exact_mut(self.stride) {
+            for &(offset, len, lane) in &runs {
+                swap_lanes(&mut row[offset..offset + len], lane);
+            }
+        }
+    }
+}
+
+/// Swap the byte order of each `lane` sized value in `bytes`.
+fn swap_lanes(bytes: &mut [u8], lane: usize) {
+    macro_rules! swap {
+        ($t:ty) => {
+            for value in bytes.chunks_exact_mut(size_of::<$t>()) {
+                let swapped = <$t>::from_ne_bytes(value.try_into().unwrap()).swap_bytes();
+                value.copy_from_slice(&swapped.to_ne_bytes());
+            }
+        };
+    }
+    match lane {
+        2 => swap!(u16),
+        4 => swap!(u32),
+        8 => swap!(u64),
+        _ => {}
+    }
+}
+
+/// Decode all rows of `elem_def` into `layout`, converting each property.
+///
+/// Rows are added as they are read, so a header that claims more rows than the data holds
+/// doesn't allocate for them up front.
+pub(crate) fn read_pod_rows<R: Read, S: ScalarReader>(
+    reader: &mut R,
+    elem_def: &ElementDef,
+    layout: &PodLayout,
+    options: &DeserializeOptions,
+    warnings: &mut Vec<PrecisionWarning>,
+) -> Result<Vec<u8>, DeserializeError> {
+    // Row with all defaults filled in, and where each property goes.
+    let mut template = vec![0; layout.stride];
+    let mut targets: Vec<Option<&PodField>> = vec![None; elem_def.properties.len()];
+    for field in &layout.fields {
+        if field.offset + field.ty.size_bytes() > layout.stride {
+            return Err(DeserializeError::custom(format!(
+                "field for property '{}' at offset {} doesn't fit in a row of {} bytes",
+                field.property, field.offset, layout.stride
+            )));
+        }
+        match elem_def
+            .properties
+            .iter()
+            .position(|p| p.name == field.property)
+        {
+            Some(i) => {
+                let PropertyType::Scalar(ty) = elem_def.properties[i].property_type else {
+                    return Err(DeserializeError::custom(format!(
+                        "property '{}' of element '{}' is a list and can't be stored in a POD field",
+                        field.property, elem_def.name
+                    )));
+                };
+                if coercion(ty, FieldType::native(field.ty)) == Coercion::Rejected {
+                    return Err(DeserializeError::custom(format!(
+                        "property '{}' of element '{}' is {} and can't be stored in a {} field",
+                        field.property,
+                        elem_def.name,
+                        ty.header_name(),
+                        FieldType::native(field.ty)
+                    )));
+                }
+                targets[i] = Some(field);
+            }
+            None => {
+                let Some(default) = field.default else {
+                    return Err(DeserializeError::custom(format!(
+                        "element '{}' has no property '{}' and the field has no default",
+                        elem_def.name, field.property
+                    )));
+                };
+                if !write_default(&mut template, field.offset, field.ty, default) {
+                    return Err(DeserializeError::custom(format!(
+                        "default {default} for property '{}' doesn't fit a {} field",
+                        field.property,
+                        FieldType::native(field.ty)
+                    )));
+                }
+            }
+        }
+    }
+
+    let mut rows = Vec::new();
+    for row_index in 0..elem_def.count {
+        let start = rows.len();
+        rows.extend_from_slice(&template);
+        let row = &mut rows[start..];
+        for (prop, target) in elem_def.properties.iter().zip(&targets) {
+            match prop.property_type {
+                PropertyType::Scalar(ty) => {
+                    let Some(field) = target else {
+                        read_f64::<S>(&mut *reader, ty)?;
+                        continue;
+                    };
+                    let value = read_scalar::<S>(&mut *reader, ty, options.decimal_comma)?;
+                    let to = FieldType::native(field.ty);
+                    let error = |msg: String| {
+                        DeserializeError::custom(format!(
+                            "property '{}' of element '{}' in row {row_index}: {msg}",
+                            prop.name, elem_def.name
+                        ))
+                    };
+                    match coerce(value, ty, to, options) {
+                        Ok((converted, lossy)) => {
+                            if let (true, Scalar::Float(v)) = (lossy, value) {
+                                PrecisionWarning::record(warnings, &elem_def.name, &prop.name, v);
+                            }
+                            write_value(row, field.offset, converted);
+                        }
+                        Err(CoerceError::OutOfRange(v)) => {
+                            return Err(error(format!("{v} is out of range for {to}")))
+                        }
+                        Err(CoerceError::PrecisionLoss(v)) => {
+                            return Err(error(format!(
+                                "{v} is a double and loses precision in an f32 field"
+                            )))
+                        }
+                        Err(CoerceError::Rejected) => unreachable!("checked with the layout"),
+                    }
+                }
+                PropertyType::List {
+                    count_type,
+                    data_type,
+                } => {
+                    let len = read_f64::<S>(&mut *reader, count_type)? as usize;
+                    for _ in 0..len {
+                        read_f64::<S>(&mut *reader, data_type)?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(rows)
+}
+
+/// Read a value of type `ty` as it is in the file.
+fn read_scalar<S: ScalarReader>(
+    reader: impl Read,
+    ty: ScalarType,
+    decimal_comma: bool,
+) -> std::io::Result<Scalar> {
+    Ok(match ty {
+        ScalarType::I8 => Scalar::Int(S::read_i8(reader)?.into()),
+        ScalarType::U8 => Scalar::Int(S::read_u8(reader)?.into()),
+        ScalarType::I16 => Scalar::Int(S::read_i16(reader)?.into()),
+        ScalarType::U16 => Scalar::Int(S::read_u16(reader)?.into()),
+        ScalarType::I32 => Scalar::Int(S::read_i32(reader)?.into()),
+        ScalarType::U32 => Scalar::Int(S::read_u32(reader)?.into()),
+        ScalarType::F32 => Scalar::Float(S::read_f32_decimal(reader, decimal_comma)?.into()),
+        ScalarType::F64 => Scalar::Float(S::read_f64_decimal(reader, decimal_comma)?),
+    })
+}
+
+/// Read a value of type `ty` and convert it to `f64`.
+pub(crate) fn read_f64<S: ScalarReader>(reader: impl Read, ty: ScalarType) -> std::io::Result<f64> {
+    Ok(match ty {
+        ScalarType::I8 => S::read_i8(reader)? as f64,
+        ScalarType::U8 => S::read_u8(reader)? as f64,
+        ScalarType::I16 => S::read_i16(reader)? as f64,
+        ScalarType::U16 => S::read_u16(reader)? as f64,
+        ScalarType::I32 => S::read_i32(reader)? as f64,
+        ScalarType::U32 => S::read_u32(reader)? as f64,
+        ScalarType::F32 => S::read_f32(reader)? as f64,
+        ScalarType::F64 => S::read_f64(reader)?,
+    })
+}
+
+/// Write `value` in native byte order at `offset`.
+fn write_value(row: &mut [u8], offset: usize, value: FieldValue) {
+    macro_rules! put {
+        ($v:expr) => {{
+            let bytes = $v.to_ne_bytes();
+            row[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        }};
+    }
+    match value {
+        FieldValue::I8(v) => put!(v),
+        FieldValue::U8(v) => put!(v),
+        FieldValue::I16(v) => put!(v),
+        FieldValue::U16(v) => put!(v),
+        FieldValue::I32(v) => put!(v),
+        FieldValue::U32(v) => put!(v),
+        FieldValue::F32(v) => put!(v),
+        FieldValue::F64(v) => put!(v),
+        _ => unreachable!("POD fields are PLY scalar types"),
+    }
+}
+
+/// Write `v` as a native endian `ty` at `offset`. Returns `false`, without writing, if
+/// `ty` is an integer type that can't hold `v` exactly.
+fn write_default(row: &mut [u8], offset: usize, ty: ScalarType, v: f64) -> bool {
+    macro_rules! put_int {
+        ($t:ty, $variant:ident) => {{
+            let int = v as $t;
+            if int as f64 != v {
+                return false;
+            }
+            write_value(row, offset, FieldValue::$variant(int));
+        }};
+    }
+    match ty {
+        ScalarType::I8 => put_int!(i8, I8),
+        ScalarType::U8 => put_int!(u8, U8),
+        ScalarType::I16 => put_int!(i16, I16),
+        ScalarType::U16 => put_int!(u16, U16),
+        ScalarType::I32 => put_int!(i32, I32),
+        ScalarType::U32 => put_int!(u32, U32),
+        ScalarType::F32 => write_value(row, offset, FieldValue::F32(v as f32)),
+        ScalarType::F64 => write_value(row, offset, FieldValue::F64(v)),
+    }
+    true
+}
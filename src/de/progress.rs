@@ -0,0 +1,91 @@
+//! How far the readers have got through the elements of a file.
+
+use crate::PlyHeader;
+
+/// Status of an element in an [`ElementState`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementStatus {
+    /// No rows have been read yet.
+    Pending,
+    /// Reading has started but not finished. An element whose rows failed to read stays
+    /// in this state.
+    Reading,
+    /// All rows were read.
+    Done,
+    /// The element was passed over, and some or all of its rows weren't read into values.
+    Skipped,
+}
+
+/// Snapshot of the progress of one element, see [`crate::PlyReader::element_states`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementState {
+    /// Name of the element.
+    pub name: String,
+    /// Number of rows the header declares.
+    pub count: usize,
+    /// How far reading the element has got.
+    pub status: ElementStatus,
+    /// Number of rows read so far, or `count` once the element is done or skipped.
+    pub rows_done: usize,
+}
+
+impl ElementState {
+    /// Fraction of the rows done, from 0 to 1. An element without rows is at 1 once it
+    /// is done or skipped.
+    pub fn fraction_done(&self) -> f64 {
+        match self.status {
+            ElementStatus::Done | ElementStatus::Skipped => 1.0,
+            _ if self.count == 0 => 0.0,
+            _ => self.rows_done as f64 / self.count as f64,
+        }
+    }
+}
+
+/// The state of every element, updated by the readers as they go.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Progress(Vec<ElementState>);
+
+impl Progress {
+    pub(crate) fn new(header: &PlyHeader) -> Self {
+        Self(
+            header
+                .elem_defs
+                .iter()
+                .map(|e| ElementState {
+                    name: e.name.clone(),
+                    count: e.count,
+                    status: ElementStatus::Pending,
+                    rows_done: 0,
+                })
+                .collect(),
+        )
+    }
+
+    pub(crate) fn states(&self) -> impl Iterator<Item = ElementState> + '_ {
+        self.0.iter().cloned()
+    }
+
+    /// Record that `rows` rows of element `index` are read, finishing it once all are.
+    pub(crate) fn rows_done(&mut self, index: usize, rows: usize) {
+        let Some(state) = self.0.get_mut(index) else {
+            return;
+        };
+        state.rows_done = rows;
+        state.status = if rows >= state.count {
+            ElementStatus::Done
+        } else {
+            ElementStatus::Reading
+        };
+    }
+
+    pub(crate) fn set(&mut self, index: usize, status: ElementStatus) {
+        let Some(state) = self.0.get_mut(index) else {
+            return;
+        };
+        state.status = status;
+        state.rows_done = match status {
+            ElementStatus::Pending | ElementStatus::Reading => 0,
+            ElementStatus::Done | ElementStatus::Skipped => state.count,
+        };
+    }
+}
@@ -0,0 +1,650 @@
+//! Rows read without a type for them, converted to one later.
+
+use std::fmt;
+
+use serde::{
+    de::{
+        value::{BytesDeserializer, MapDeserializer},
+        DeserializeOwned, DeserializeSeed, Error as _, IgnoredAny, MapAccess, SeqAccess, Visitor,
+    },
+    ser::{Error as _, SerializeMap},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    de::{
+        check_fields,
+        coerce::{FieldType, Scalar},
+        expected_list_error, visit_coerced, PropertyContext,
+    },
+    ser::SingleElement,
+    to_bytes, DeserializeError, DeserializeOptions, ElementDef, PlyReader, PropertyType,
+    ScalarType, SerializeError, SerializeOptions,
+};
+
+/// A property value of a row in [`RawRows`].
+///
+/// Values keep the type of their property: integer properties hold an `Int`, `float` and
+/// `double` properties a `Float`, and list properties a `List` of either.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlyValue {
+    Int(i64),
+    Float(f64),
+    List(Vec<PlyValue>),
+}
+
+/// The rows of an element, read without a type for them.
+///
+/// Read with [`PlyReader::next_element_raw`] to look at the values first, and convert
+/// them to rows of a type with [`Self::deserialize_into`] once it's known, without reading
+/// the file again. Values are converted by the same rules as when reading the file into
+/// that type directly, see [`crate::coercion`], so the rows come out the same. Precision
+/// lost under [`crate::PrecisionLoss::Warn`] isn't recorded.
+///
+/// # Example
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_ply::{PlyReader, PlyValue};
+///
+/// #[derive(Deserialize)]
+/// struct Face { vertex_indices: Vec<u32> }
+///
+/// let ply_data = "ply\nformat ascii 1.0\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n3 0 1 2\n";
+/// let mut reader = PlyReader::from_reader(ply_data.as_bytes())?;
+/// let raw = reader.next_element_raw()?;
+/// assert_eq!(raw.rows[0][0], PlyValue::List(vec![PlyValue::Int(0), PlyValue::Int(1), PlyValue::Int(2)]));
+///
+/// let faces: Vec<Face> = raw.deserialize_into()?;
+/// assert_eq!(faces[0].vertex_indices, [0, 1, 2]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RawRows {
+    /// The element the rows belong to, with the types of the values.
+    pub elem_def: ElementDef,
+    /// The values of each row, in the order of the properties.
+    pub rows: Vec<Vec<PlyValue>>,
+    options: DeserializeOptions,
+}
+
+impl RawRows {
+    /// Rows of `elem_def`, converted with the default [`DeserializeOptions`].
+    pub fn new(elem_def: ElementDef, rows: Vec<Vec<PlyValue>>) -> Self {
+        Self {
+            elem_def,
+            rows,
+            options: DeserializeOptions::default(),
+        }
+    }
+
+    /// Convert the values with `options`. Rows read with [`PlyReader::next_element_raw`]
+    /// have the options of the reader.
+    pub fn with_options(mut self, options: DeserializeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Convert the rows to rows of `T`.
+    ///
+    /// Errors like reading the file into `T` would, and for values that don't have the
+    /// type of their property, eg. a `Float` for an `int` property.
+    pub fn deserialize_into<T: DeserializeOwned>(&self) -> Result<Vec<T>, DeserializeError> {
+        let mut deserializer = RawRowDeserializer {
+            elem_def: &self.elem_def,
+            options: &self.options,
+            values: &[],
+            row: 0,
+            current: 0,
+            fields_checked: false,
+        };
+        let properties = self.elem_def.properties.len();
+        let mut rows = Vec::with_capacity(self.rows.len());
+        for (row, values) in self.rows.iter().enumerate() {
+            if values.len() != properties {
+                return Err(DeserializeError::custom(format!(
+                    "row {row} of element '{}' has {} values, but the element has {properties} \
+                     properties",
+                    self.elem_def.name,
+                    values.len()
+                )));
+            }
+            deserializer.values = values;
+            deserializer.row = row;
+            rows.push(T::deserialize(&mut deserializer)?);
+        }
+        Ok(rows)
+    }
+
+    /// Rows of element `name` with the values of `rows`, typed like [`crate::to_writer`]
+    /// would write them.
+    pub fn from_typed<T: Serialize>(name: &str, rows: &[T]) -> Result<Self, SerializeError> {
+        // Written as a file and read back, so the values are exactly those of the file.
+        let bytes = to_bytes(&SingleElement(name, rows), SerializeOptions::binary_le())?;
+        let mut reader =
+            PlyReader::from_reader(bytes.as_slice()).map_err(|e| SerializeError(e.0))?;
+        reader.next_element_raw().map_err(|e| SerializeError(e.0))
+    }
+}
+
+/// A row read as its values, in the order of the properties.
+pub(crate) struct RawRow(pub Vec<PlyValue>);
+
+impl<'de> Deserialize<'de> for RawRow {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RowVisitor;
+
+        impl<'de> Visitor<'de> for RowVisitor {
+            type Value = RawRow;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a row")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<RawRow, A::Error> {
+                let mut values = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some((_, value)) = map.next_entry::<IgnoredAny, _>()? {
+                    values.push(value);
+                }
+                Ok(RawRow(values))
+            }
+        }
+
+        deserializer.deserialize_map(RowVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for PlyValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ValueVisitor;
+
+        impl<'de> Visitor<'de> for ValueVisitor {
+            type Value = PlyValue;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a scalar or a list")
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<PlyValue, E> {
+                Ok(PlyValue::Int(v))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<PlyValue, E> {
+                i64::try_from(v)
+                    .map(PlyValue::Int)
+                    .map_err(|_| E::custom(format!("{v} is out of range for a PLY integer")))
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<PlyValue, E> {
+                Ok(PlyValue::Float(v))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<PlyValue, A::Error> {
+                let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(v) = seq.next_element()? {
+                    values.push(v);
+                }
+                Ok(PlyValue::List(values))
+            }
+        }
+
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// Serializes the values of a row as a map of its properties.
+pub(crate) struct NamedRow<'a>(pub &'a ElementDef, pub &'a [PlyValue]);
+
+impl Serialize for NamedRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.1.len()))?;
+        for (prop, value) in self.0.properties.iter().zip(self.1) {
+            map.serialize_entry(&prop.name, value)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for PlyValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            // PLY integers are at most 32 bits, which is all rows are written with.
+            &PlyValue::Int(v) => match (i32::try_from(v), u32::try_from(v)) {
+                (Ok(v), _) => serializer.serialize_i32(v),
+                (_, Ok(v)) => serializer.serialize_u32(v),
+                _ => Err(S::Error::custom(format!(
+                    "{v} is out of range for a PLY integer"
+                ))),
+            },
+            PlyValue::Float(v) => serializer.serialize_f64(*v),
+            PlyValue::List(values) => serializer.collect_seq(values),
+        }
+    }
+}
+
+/// Deserializes rows of [`RawRows`] like [`crate::de::RowDeserializer`] does rows of a
+/// file.
+struct RawRowDeserializer<'a> {
+    elem_def: &'a ElementDef,
+    options: &'a DeserializeOptions,
+    values: &'a [PlyValue],
+    row: usize,
+    current: usize,
+    // Whether the struct fields were checked against the properties.
+    fields_checked: bool,
+}
+
+impl<'de> Deserializer<'de> for &mut RawRowDeserializer<'_> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(DeserializeError::custom(
+            "Rows must be deserialized as maps",
+        ))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if self.options.deny_extra_properties && !self.fields_checked {
+            self.fields_checked = true;
+            check_fields(self.elem_def, fields)?;
+        }
+        if fields.is_empty() {
+            return visitor.visit_map(MapDeserializer::new(std::iter::empty::<((), ())>()));
+        }
+        self.current = 0;
+        visitor.visit_map(self)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.current = 0;
+        visitor.visit_map(self)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if len != self.elem_def.properties.len() {
+            return Err(DeserializeError::custom(format!(
+                "element '{}' has {} properties but rows are deserialized as a tuple of {len}",
+                self.elem_def.name,
+                self.elem_def.properties.len()
+            )));
+        }
+        self.current = 0;
+        visitor.visit_seq(self)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 u8 i16 u16 i32 u32 i64 u64 f32 f64 char str string
+        bytes byte_buf option seq enum identifier
+    }
+}
+
+impl<'de> SeqAccess<'de> for RawRowDeserializer<'_> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.current >= self.values.len() {
+            return Ok(None);
+        }
+        self.next_value_seed(seed).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len() - self.current)
+    }
+}
+
+impl<'de> MapAccess<'de> for RawRowDeserializer<'_> {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some(prop) = self.elem_def.properties.get(self.current) else {
+            return Ok(None);
+        };
+        seed.deserialize(BytesDeserializer::new(prop.name.as_bytes()))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let elem_def = self.elem_def;
+        let prop = &elem_def.properties[self.current];
+        let value = &self.values[self.current];
+        self.current += 1;
+        let ctx = PropertyContext::new(&elem_def.name, &prop.name, self.row, self.options);
+        match (prop.property_type, value) {
+            (PropertyType::Scalar(data_type), value) => seed.deserialize(ScalarValue {
+                value: scalar(value, data_type, ctx)?,
+                data_type,
+                ctx,
+                list_entry: false,
+            }),
+            (PropertyType::List { data_type, .. }, PlyValue::List(entries)) => {
+                seed.deserialize(ListValue {
+                    entries,
+                    data_type,
+                    ctx,
+                })
+            }
+            (PropertyType::List { .. }, _) => {
+                Err(ctx.error("holds a scalar, but the property is a list"))
+            }
+        }
+    }
+}
+
+/// `value` as it would be read from a property of type `data_type`.
+fn scalar(
+    value: &PlyValue,
+    data_type: ScalarType,
+    ctx: PropertyContext<'_>,
+) -> Result<Scalar, DeserializeError> {
+    let fits = match (value, data_type) {
+        (PlyValue::Float(v), ScalarType::F32) => return Ok(Scalar::Float(*v as f32 as f64)),
+        (PlyValue::Float(v), ScalarType::F64) => return Ok(Scalar::Float(*v)),
+        (&PlyValue::Int(v), ScalarType::I8) => i8::try_from(v).is_ok(),
+        (&PlyValue::Int(v), ScalarType::U8) => u8::try_from(v).is_ok(),
+        (&PlyValue::Int(v), ScalarType::I16) => i16::try_from(v).is_ok(),
+        (&PlyValue::Int(v), ScalarType::U16) => u16::try_from(v).is_ok(),
+        (&PlyValue::Int(v), ScalarType::I32) => i32::try_from(v).is_ok(),
+        (&PlyValue::Int(v), ScalarType::U32) => u32::try_from(v).is_ok(),
+        _ => false,
+    };
+    match value {
+        &PlyValue::Int(v) if fits => Ok(Scalar::Int(v)),
+        _ => Err(ctx.error(format_args!(
+            "holds {value:?}, which isn't a {} value",
+            data_type.header_name()
+        ))),
+    }
+}
+
+/// A scalar property, or an entry of a list property.
+struct ScalarValue<'a> {
+    value: Scalar,
+    data_type: ScalarType,
+    ctx: PropertyContext<'a>,
+    list_entry: bool,
+}
+
+impl ScalarValue<'_> {
+    fn deserialize_field<'de, V: Visitor<'de>>(
+        self,
+        to: FieldType,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        let (value, data_type) = (self.value, self.data_type);
+        visit_coerced(
+            value,
+            data_type,
+            to,
+            self.ctx,
+            self.list_entry,
+            None,
+            visitor,
+        )
+    }
+}
+
+macro_rules! deserialize_fields {
+    ($($method:ident => $to:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.deserialize_field(FieldType::$to, visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for ScalarValue<'_> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let to = FieldType::native(self.data_type);
+        self.deserialize_field(to, visitor)
+    }
+
+    deserialize_fields! {
+        deserialize_bool => Bool,
+        deserialize_i8 => I8,
+        deserialize_u8 => U8,
+        deserialize_i16 => I16,
+        deserialize_u16 => U16,
+        deserialize_i32 => I32,
+        deserialize_u32 => U32,
+        deserialize_i64 => I64,
+        deserialize_u64 => U64,
+        deserialize_i128 => I128,
+        deserialize_u128 => U128,
+        deserialize_f32 => F32,
+        deserialize_f64 => F64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(expected_list_error(self.ctx, self.list_entry))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(expected_list_error(self.ctx, self.list_entry))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(expected_list_error(self.ctx, self.list_entry))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct map struct enum identifier
+    }
+}
+
+/// The entries of a list property.
+struct ListValue<'a> {
+    entries: &'a [PlyValue],
+    data_type: ScalarType,
+    ctx: PropertyContext<'a>,
+}
+
+impl ListValue<'_> {
+    /// Read the list as a single scalar, if the options allow it.
+    fn deserialize_scalar<'de, V: Visitor<'de>>(
+        self,
+        to: FieldType,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        if !self.ctx.options.single_element_list_as_scalar {
+            return Err(self.ctx.error("is a list but field expects a scalar"));
+        }
+        let [entry] = self.entries else {
+            return Err(self.ctx.error(format_args!(
+                "is a list with {} entries but field expects a scalar",
+                self.entries.len()
+            )));
+        };
+        ScalarValue {
+            value: scalar(entry, self.data_type, self.ctx)?,
+            data_type: self.data_type,
+            ctx: self.ctx,
+            list_entry: true,
+        }
+        .deserialize_field(to, visitor)
+    }
+}
+
+macro_rules! forward_to_deserialize_scalar {
+    ($($method:ident => $to:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.deserialize_scalar(FieldType::$to, visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for ListValue<'_> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(Entries {
+            entries: self.entries.iter(),
+            data_type: self.data_type,
+            ctx: self.ctx,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let count = self.entries.len();
+        if count != len {
+            return Err(self.ctx.error(format_args!(
+                "is a list with {count} entries but field is an array of {len}"
+            )));
+        }
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_scalar! {
+        deserialize_i8 => I8,
+        deserialize_u8 => U8,
+        deserialize_i16 => I16,
+        deserialize_u16 => U16,
+        deserialize_i32 => I32,
+        deserialize_u32 => U32,
+        deserialize_i64 => I64,
+        deserialize_u64 => U64,
+        deserialize_i128 => I128,
+        deserialize_u128 => U128,
+        deserialize_f32 => F32,
+        deserialize_f64 => F64,
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool char str string bytes byte_buf unit unit_struct
+        tuple_struct map struct enum identifier
+    }
+}
+
+struct Entries<'a> {
+    entries: std::slice::Iter<'a, PlyValue>,
+    data_type: ScalarType,
+    ctx: PropertyContext<'a>,
+}
+
+impl<'de> SeqAccess<'de> for Entries<'_> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some(entry) = self.entries.next() else {
+            return Ok(None);
+        };
+        seed.deserialize(ScalarValue {
+            value: scalar(entry, self.data_type, self.ctx)?,
+            data_type: self.data_type,
+            ctx: self.ctx,
+            list_entry: true,
+        })
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
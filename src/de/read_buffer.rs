@@ -0,0 +1,96 @@
+//! Buffering for sources that only implement [`Read`].
+
+use std::io::{BufRead, ErrorKind, Read};
+
+use crate::PlyHeader;
+
+/// Capacity while reading the header, which is usually a few hundred bytes.
+const HEADER_CAPACITY: usize = 8 * 1024;
+/// Capacity for data whose size isn't known from the header, ie. ASCII or list data.
+const DEFAULT_DATA_CAPACITY: usize = 64 * 1024;
+const MAX_DATA_CAPACITY: usize = 1024 * 1024;
+
+/// Buffer between a [`Read`] source and a [`crate::PlyReader`], see
+/// [`crate::PlyReader::from_read`].
+///
+/// Works like [`std::io::BufReader`], but the reader sizes it from the header: small
+/// binary files are buffered whole, large ones in chunks of up to 1 MiB.
+pub struct ReadBuffer<R> {
+    inner: R,
+    buf: Vec<u8>,
+    pos: usize,
+    filled: usize,
+}
+
+impl<R: Read> ReadBuffer<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: vec![0; HEADER_CAPACITY],
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    /// Grow the buffer to suit the data the header declares. Buffered bytes are kept.
+    pub(crate) fn fit_to(&mut self, header: &PlyHeader) {
+        let ranges = header.element_byte_ranges(0);
+        let capacity = match ranges.last() {
+            Some(Some(range)) => (range.end as usize).clamp(HEADER_CAPACITY, MAX_DATA_CAPACITY),
+            Some(None) => DEFAULT_DATA_CAPACITY,
+            None => HEADER_CAPACITY,
+        };
+        if capacity > self.buf.len() {
+            self.buf.copy_within(self.pos..self.filled, 0);
+            self.filled -= self.pos;
+            self.pos = 0;
+            self.buf.resize(capacity, 0);
+        }
+    }
+
+    /// Size of the buffer in bytes.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// The bytes read from the source but not consumed yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.filled]
+    }
+
+    /// Unwrap the source. Bytes still in [`Self::buffer`] are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for ReadBuffer<R> {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        // Large reads skip the buffer when it's empty.
+        if self.pos == self.filled && out.len() >= self.buf.len() {
+            return self.inner.read(out);
+        }
+        let n = self.fill_buf()?.read(out)?;
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: Read> BufRead for ReadBuffer<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.pos == self.filled {
+            self.filled = loop {
+                match self.inner.read(&mut self.buf) {
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    res => break res?,
+                }
+            };
+            self.pos = 0;
+        }
+        Ok(self.buffer())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.filled);
+    }
+}
@@ -1,31 +1,356 @@
 use crate::{
-    de::val_reader::ScalarReader, DeserializeError, PlyProperty, PropertyType, ScalarType,
+    de::{
+        coerce::{coerce, CoerceError, FieldType, Scalar},
+        stats::{CountingReader, ElementStats, PrecisionWarning, RowStats},
+        val_reader::{read_count, ScalarReader},
+    },
+    DeserializeError, DeserializeOptions, ElementDef, PropertyType, ScalarType,
 };
 use serde::{
-    de::{value::BytesDeserializer, DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
+    de::{
+        value::{BytesDeserializer, MapDeserializer},
+        DeserializeSeed, Error, IgnoredAny, MapAccess, SeqAccess, Visitor,
+    },
     Deserializer,
 };
-use std::{io::Read, marker::PhantomData};
+use std::{
+    fmt::Display,
+    io::{BufRead, Read},
+    marker::PhantomData,
+};
 
-pub(crate) struct RowDeserializer<'a, R: Read, S: ScalarReader> {
+pub(crate) struct RowDeserializer<'a, R: BufRead, S: ScalarReader> {
     pub reader: &'a mut R,
-    properties: &'a [PlyProperty],
+    pub elem_def: &'a ElementDef,
+    /// Index of the row currently being deserialized, used for error context.
+    pub row_index: usize,
+    options: &'a DeserializeOptions,
     current_property: u32,
+    report_short_rows: bool,
+    tokens: TokenState,
+    // First row that ended its line before its last value, and the values it had.
+    short_line: Option<(usize, usize)>,
+    stats: Option<RowStats<'a>>,
+    warnings: Option<&'a mut Vec<PrecisionWarning>>,
+    // Whether the struct fields were checked against the properties.
+    fields_checked: bool,
     _marker: PhantomData<S>,
 }
 
-impl<'a, R: Read, S: ScalarReader> RowDeserializer<'a, R, S> {
-    pub fn new(reader: &'a mut R, properties: &'a [PlyProperty]) -> Self {
+impl<'a, R: BufRead, S: ScalarReader> RowDeserializer<'a, R, S> {
+    pub fn new(
+        reader: &'a mut R,
+        elem_def: &'a ElementDef,
+        options: &'a DeserializeOptions,
+    ) -> Self {
         Self {
             current_property: 0,
+            report_short_rows: true,
+            tokens: TokenState::default(),
+            short_line: None,
+            row_index: 0,
             reader,
-            properties,
+            elem_def,
+            options,
+            stats: None,
+            warnings: None,
+            fields_checked: false,
             _marker: PhantomData,
         }
     }
+
+    /// Record the bytes and list lengths of the rows read in `stats`.
+    pub fn with_stats(mut self, stats: Option<&'a mut ElementStats>) -> Self {
+        self.stats = stats.map(RowStats::new);
+        self
+    }
+
+    /// Keep the plain end of data error for a short ASCII row, for readers that wait for
+    /// the rest of the row instead of reporting it.
+    pub fn with_short_rows_pending(mut self) -> Self {
+        self.report_short_rows = false;
+        self
+    }
+
+    /// Count `double` values that lose precision in `f32` fields in `warnings`.
+    pub fn with_warnings(mut self, warnings: &'a mut Vec<PrecisionWarning>) -> Self {
+        self.warnings = Some(warnings);
+        self
+    }
+
+    /// The warnings passed to [`Self::with_warnings`].
+    pub fn warnings_mut(&mut self) -> Option<&mut Vec<PrecisionWarning>> {
+        self.warnings.as_deref_mut()
+    }
+}
+
+impl<R: BufRead, S: ScalarReader> RowDeserializer<'_, R, S> {
+    fn start_row(&mut self) {
+        self.current_property = 0;
+        self.tokens = TokenState::default();
+        if let Some(stats) = &mut self.stats {
+            stats.start_row();
+            if self.elem_def.properties.is_empty() {
+                stats.finish_row();
+            }
+        }
+    }
+
+    /// Read past the row without converting any values.
+    fn skip_row(&mut self) -> Result<(), DeserializeError> {
+        if self.stats.is_some() || self.pads_short_rows() {
+            // Read the values one by one so they are counted, and short rows end at their line.
+            self.start_row();
+            while self.next_key_seed(PhantomData::<IgnoredAny>)?.is_some() {
+                self.next_value_seed(PhantomData::<IgnoredAny>)?;
+            }
+            return Ok(());
+        }
+        self.current_property = self.elem_def.properties.len() as u32;
+        if S::TEXT && self.report_short_rows {
+            self.tokens = TokenState::default();
+            let mut reader = TokenCounter {
+                inner: &mut *self.reader,
+                tokens: &mut self.tokens,
+            };
+            let skipped = S::skip_row(&mut reader, self.elem_def).map_err(DeserializeError::from);
+            return self.check_short_row(skipped);
+        }
+        Ok(S::skip_row(&mut self.reader, self.elem_def)?)
+    }
+
+    /// Error for the properties that have no field in a struct row, if the options deny
+    /// them. Checked once, on the first row.
+    ///
+    /// Properties are matched to the fields of the row type in this order:
+    /// 1. A property named like a field, or one of its aliases, is read into it.
+    /// 2. A row type read as a map, like a `HashMap` or a struct with a
+    ///    `#[serde(flatten)]` field, takes every other property as an entry. Map rows have
+    ///    no fixed fields, so no property is extra for them.
+    /// 3. With [`DeserializeOptions::deny_extra_properties`] any other property is an
+    ///    error listing all of them, before any value of the element is read.
+    /// 4. With `#[serde(deny_unknown_fields)]` serde errors on the first other property.
+    /// 5. Otherwise other properties are skipped without converting their values.
+    fn check_extra_properties(&mut self, fields: &[&str]) -> Result<(), DeserializeError> {
+        if !self.options.deny_extra_properties || self.fields_checked {
+            return Ok(());
+        }
+        self.fields_checked = true;
+        check_fields(self.elem_def, fields)
+    }
+
+    fn pads_short_rows(&self) -> bool {
+        S::TEXT && self.options.pad_short_rows_with_default
+    }
+
+    /// Whether the line of the current ASCII row ends before the next value, skipping the
+    /// spaces up to the line break.
+    fn at_line_end(&mut self) -> Result<bool, DeserializeError> {
+        if self.tokens.line_end.is_some() {
+            return Ok(true);
+        }
+        loop {
+            match self.reader.fill_buf()?.first() {
+                Some(b' ' | b'\t') => self.reader.consume(1),
+                Some(b'\r' | b'\n') => return Ok(true),
+                Some(_) => return Ok(false),
+                // The chunked reader waits for more data instead.
+                None => return Ok(self.report_short_rows),
+            }
+        }
+    }
+
+    /// Report running out of data in the middle of a row with the number of values it had.
+    ///
+    /// Rows may share lines, so a short row takes values of the rows after it and the
+    /// data only runs out later. The first row that ends its line early is reported too,
+    /// as it is usually the one missing values.
+    fn check_short_row<T>(
+        &mut self,
+        result: Result<T, DeserializeError>,
+    ) -> Result<T, DeserializeError> {
+        let properties = self.elem_def.properties.len();
+        if self.short_line.is_none() {
+            self.short_line = self
+                .tokens
+                .line_end
+                .filter(|&values| values < properties)
+                .map(|values| (self.row_index, values));
+        }
+        let e = match result {
+            Err(e) => e,
+            result => return result,
+        };
+        let name = &self.elem_def.name;
+        let short_line = self.short_line.map(|(row, values)| {
+            format!("row {row} of element '{name}' ends its line after {values} values, but the element has {properties} properties")
+        });
+        let (kind, msg) = match (e.0.kind(), short_line) {
+            (std::io::ErrorKind::UnexpectedEof, Some(short_line))
+                if self.short_line.is_some_and(|(row, _)| row < self.row_index) =>
+            {
+                (
+                    std::io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "{short_line}, so the rows after it are misaligned and the data runs out in row {}",
+                        self.row_index
+                    ),
+                )
+            }
+            (std::io::ErrorKind::UnexpectedEof, _) => (
+                std::io::ErrorKind::UnexpectedEof,
+                format!(
+                    "row {} of element '{name}' ends after {} values, but the element has {properties} properties",
+                    self.row_index, self.tokens.count
+                ),
+            ),
+            (std::io::ErrorKind::InvalidData, Some(short_line)) => (
+                std::io::ErrorKind::InvalidData,
+                format!("{} ({short_line})", e.0),
+            ),
+            _ => return Err(e),
+        };
+        Err(DeserializeError(std::io::Error::new(kind, msg)))
+    }
+}
+
+/// Error for the properties of `elem_def` that aren't one of `fields`.
+pub(crate) fn check_fields(elem_def: &ElementDef, fields: &[&str]) -> Result<(), DeserializeError> {
+    let extra: Vec<String> = elem_def
+        .properties
+        .iter()
+        .filter(|p| !fields.contains(&p.name.as_str()))
+        .map(|p| format!("'{}'", p.name))
+        .collect();
+    if extra.is_empty() {
+        return Ok(());
+    }
+    Err(DeserializeError::custom(format!(
+        "element '{}' has properties without a field in the row type: {}",
+        elem_def.name,
+        extra.join(", ")
+    )))
+}
+
+/// Number of whitespace separated values read in the current ASCII row.
+#[derive(Default)]
+struct TokenState {
+    count: usize,
+    in_token: bool,
+    // Number of values before the first line break in the row, '\r' or '\n'.
+    line_end: Option<usize>,
+}
+
+/// Counts the values passing through, so a short row can say how many it had.
+struct TokenCounter<'a, R> {
+    inner: &'a mut R,
+    tokens: &'a mut TokenState,
+}
+
+impl<R: Read> Read for TokenCounter<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        for &b in &buf[..n] {
+            let in_token = !b.is_ascii_whitespace();
+            if in_token && !self.tokens.in_token {
+                self.tokens.count += 1;
+            }
+            self.tokens.in_token = in_token;
+            if matches!(b, b'\r' | b'\n') && self.tokens.count > 0 && self.tokens.line_end.is_none()
+            {
+                self.tokens.line_end = Some(self.tokens.count);
+            }
+        }
+        Ok(n)
+    }
+}
+
+/// Where a value being deserialized lives in the file, for error messages.
+#[derive(Clone, Copy)]
+pub(crate) struct PropertyContext<'a> {
+    element: &'a str,
+    property: &'a str,
+    row: usize,
+    pub(crate) options: &'a DeserializeOptions,
+    // Number of vertices the entries of a binary vertex index list must stay below and the
+    // list's count type, when diagnosing misalignment.
+    index_limit: Option<(usize, ScalarType)>,
+}
+
+impl<'a> PropertyContext<'a> {
+    pub(crate) fn new(
+        element: &'a str,
+        property: &'a str,
+        row: usize,
+        options: &'a DeserializeOptions,
+    ) -> Self {
+        Self {
+            element,
+            property,
+            row,
+            options,
+            index_limit: None,
+        }
+    }
+
+    pub(crate) fn error(&self, msg: impl Display) -> DeserializeError {
+        DeserializeError::custom(format!(
+            "property '{}' of element '{}' {} (row {})",
+            self.property, self.element, msg, self.row
+        ))
+    }
+
+    /// Check a binary list count against the plausible length when diagnosing
+    /// misalignment.
+    fn check_count<S: ScalarReader>(
+        &self,
+        count_type: ScalarType,
+        count: usize,
+    ) -> Result<usize, DeserializeError> {
+        let max = self.options.max_plausible_list_len;
+        if S::TEXT || !self.options.diagnose_misalignment || count <= max {
+            return Ok(count);
+        }
+        // A huge count usually means the file writes narrower counts than declared.
+        let suggestion = (count_type.size_bytes() > 1).then_some(ScalarType::U8);
+        Err(DeserializeError::misaligned_list(
+            self.element,
+            self.property,
+            self.row,
+            count_type,
+            format!("has {count} entries, more than the plausible {max}"),
+            suggestion,
+        ))
+    }
+
+    /// Check a vertex index list entry against the number of vertices.
+    #[inline]
+    fn check_index(&self, index: i64) -> Result<(), DeserializeError> {
+        match self.index_limit {
+            Some((limit, count_type)) if index < 0 || index >= limit as i64 => {
+                // Entries out of range usually mean the file writes wider counts than declared.
+                let suggestion = match count_type {
+                    ScalarType::U8 => Some(ScalarType::U16),
+                    ScalarType::I8 => Some(ScalarType::I16),
+                    ScalarType::U16 => Some(ScalarType::U32),
+                    ScalarType::I16 => Some(ScalarType::I32),
+                    _ => None,
+                };
+                Err(DeserializeError::misaligned_list(
+                    self.element,
+                    self.property,
+                    self.row,
+                    count_type,
+                    format!("has entry {index}, but there are only {limit} vertices"),
+                    suggestion,
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
-impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for &mut RowDeserializer<'_, R, S> {
+impl<'de, R: BufRead, S: ScalarReader> Deserializer<'de> for &mut RowDeserializer<'_, R, S> {
     type Error = DeserializeError;
 
     fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
@@ -40,13 +365,19 @@ impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for &mut RowDeserializer<'
     fn deserialize_struct<V>(
         self,
         _name: &'static str,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.current_property = 0;
+        self.check_extra_properties(fields)?;
+        // A struct without fields doesn't need any of the values.
+        if fields.is_empty() {
+            self.skip_row()?;
+            return visitor.visit_map(MapDeserializer::new(std::iter::empty::<((), ())>()));
+        }
+        self.start_row();
         visitor.visit_map(self)
     }
 
@@ -54,10 +385,38 @@ impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for &mut RowDeserializer<'
     where
         V: Visitor<'de>,
     {
-        self.current_property = 0;
+        self.start_row();
         visitor.visit_map(self)
     }
 
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        // Tuples map to the properties by position.
+        if len != self.elem_def.properties.len() {
+            return Err(DeserializeError::custom(format!(
+                "element '{}' has {} properties but rows are deserialized as a tuple of {len}",
+                self.elem_def.name,
+                self.elem_def.properties.len()
+            )));
+        }
+        self.start_row();
+        visitor.visit_seq(self)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
     fn deserialize_newtype_struct<V>(
         self,
         _name: &'static str,
@@ -69,23 +428,75 @@ impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for &mut RowDeserializer<'
         visitor.visit_newtype_struct(self)
     }
 
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.skip_row()?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.skip_row()?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
     serde::forward_to_deserialize_any! {
         bool i8 u8 i16 u16 i32 u32 i64 u64 f32 f64 char str string
-        bytes byte_buf option unit unit_struct seq tuple
-        tuple_struct enum identifier ignored_any
+        bytes byte_buf option seq enum identifier
+    }
+}
+
+impl<'de, R: BufRead, S: ScalarReader> SeqAccess<'de> for RowDeserializer<'_, R, S> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.current_property as usize >= self.elem_def.properties.len() {
+            return Ok(None);
+        }
+        self.next_value_seed(seed).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elem_def.properties.len() - self.current_property as usize)
     }
 }
 
-impl<'de, R: Read, S: ScalarReader> MapAccess<'de> for RowDeserializer<'_, R, S> {
+impl<'de, R: BufRead, S: ScalarReader> MapAccess<'de> for RowDeserializer<'_, R, S> {
     type Error = DeserializeError;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
     where
         K: DeserializeSeed<'de>,
     {
-        let Some(prop) = self.properties.get(self.current_property as usize) else {
+        let Some(prop) = self.elem_def.properties.get(self.current_property as usize) else {
             return Ok(None);
         };
+        if self.current_property > 0 && self.pads_short_rows() && self.at_line_end()? {
+            // Leave the rest of the properties to the defaults of their fields.
+            self.current_property = self.elem_def.properties.len() as u32;
+            if let Some(stats) = &mut self.stats {
+                stats.finish_row();
+            }
+            return Ok(None);
+        }
         seed.deserialize(BytesDeserializer::new(prop.name.as_bytes()))
             .map(Some)
     }
@@ -98,37 +509,347 @@ impl<'de, R: Read, S: ScalarReader> MapAccess<'de> for RowDeserializer<'_, R, S>
         // I really hope the bounds check here gets optimized out (next_key_seed already checks).
         // Could use unsafe here to avoid this but let's not use any unsafe code in a data format,
         // I am not smart enough :)
-        match self.properties[self.current_property as usize].property_type {
-            PropertyType::Scalar(data_type) => {
-                self.current_property += 1;
-                seed.deserialize(ScalarDeserializer {
-                    reader: &mut self.reader,
-                    data_type,
-                    _marker: PhantomData::<S>,
-                })
-            }
-            PropertyType::List {
-                count_type,
-                data_type,
-            } => {
-                self.current_property += 1;
-                seed.deserialize(ListDeserializer {
-                    reader: &mut self.reader,
-                    count_type,
-                    data_type,
-                    _marker: PhantomData::<S>,
-                })
-            }
+        let elem_def = self.elem_def;
+        let prop = &elem_def.properties[self.current_property as usize];
+        let ctx = PropertyContext {
+            element: &elem_def.name,
+            property: &prop.name,
+            row: self.row_index,
+            options: self.options,
+            index_limit: match (self.options.vertex_count, prop.property_type) {
+                (Some(limit), PropertyType::List { count_type, .. })
+                    if !S::TEXT
+                        && matches!(prop.name.as_str(), "vertex_indices" | "vertex_index") =>
+                {
+                    Some((limit, count_type))
+                }
+                _ => None,
+            },
+        };
+        self.current_property += 1;
+        let last = self.current_property as usize == elem_def.properties.len();
+
+        // Count the values of ASCII rows to report short ones, and to see where their lines
+        // end when padding them.
+        if S::TEXT && (self.report_short_rows || self.options.pad_short_rows_with_default) {
+            let mut reader = TokenCounter {
+                inner: &mut *self.reader,
+                tokens: &mut self.tokens,
+            };
+            let value = read_value::<_, S, _>(
+                &mut reader,
+                prop.property_type,
+                ctx,
+                seed,
+                self.stats.as_mut().map(|stats| (stats, last)),
+                self.warnings.as_deref_mut(),
+            );
+            return self.check_short_row(value);
         }
+        read_value::<_, S, _>(
+            &mut *self.reader,
+            prop.property_type,
+            ctx,
+            seed,
+            self.stats.as_mut().map(|stats| (stats, last)),
+            self.warnings.as_deref_mut(),
+        )
     }
 }
 
+/// Read a property, recording its bytes and list length in `stats` if given. The flag
+/// marks the last property of the row.
+#[inline]
+fn read_value<'de, R: Read, S: ScalarReader, V: DeserializeSeed<'de>>(
+    reader: &mut R,
+    property_type: PropertyType,
+    ctx: PropertyContext<'_>,
+    seed: V,
+    stats: Option<(&mut RowStats<'_>, bool)>,
+    warnings: Option<&mut Vec<PrecisionWarning>>,
+) -> Result<V::Value, DeserializeError> {
+    match stats {
+        Some((stats, last)) => {
+            read_counted::<_, S, _>(reader, property_type, ctx, seed, stats, last, warnings)
+        }
+        None => read_property::<_, S, _>(reader, property_type, ctx, seed, None, warnings),
+    }
+}
+
+/// Read a property while recording its bytes and list length in the row stats.
+#[inline(never)]
+fn read_counted<'de, R: Read, S: ScalarReader, V: DeserializeSeed<'de>>(
+    reader: &mut R,
+    property_type: PropertyType,
+    ctx: PropertyContext<'_>,
+    seed: V,
+    stats: &mut RowStats<'_>,
+    last: bool,
+    mut warnings: Option<&mut Vec<PrecisionWarning>>,
+) -> Result<V::Value, DeserializeError> {
+    let mut reader = CountingReader::new(reader);
+    let (value, len) = match property_type {
+        PropertyType::Scalar(_) => (
+            read_property::<_, S, _>(
+                &mut reader,
+                property_type,
+                ctx,
+                seed,
+                None,
+                warnings.as_deref_mut(),
+            )?,
+            0,
+        ),
+        PropertyType::List { count_type, .. } => {
+            let len =
+                ctx.check_count::<S>(count_type, read_count::<S>(&mut reader, count_type)?)?;
+            (
+                read_property::<_, S, _>(
+                    &mut reader,
+                    property_type,
+                    ctx,
+                    seed,
+                    Some(len),
+                    warnings,
+                )?,
+                len,
+            )
+        }
+    };
+    stats.record(reader.count as u64, len);
+    if last {
+        stats.finish_row();
+    }
+    Ok(value)
+}
+
+/// Deserialize a single property. `count` is the length of a list whose count was
+/// already read.
+#[inline]
+fn read_property<'de, R: Read, S: ScalarReader, V: DeserializeSeed<'de>>(
+    reader: &mut R,
+    property_type: PropertyType,
+    ctx: PropertyContext<'_>,
+    seed: V,
+    count: Option<usize>,
+    warnings: Option<&mut Vec<PrecisionWarning>>,
+) -> Result<V::Value, DeserializeError> {
+    match property_type {
+        PropertyType::Scalar(data_type) => seed.deserialize(ScalarDeserializer {
+            reader,
+            data_type,
+            ctx,
+            list_entry: false,
+            warnings,
+            _marker: PhantomData::<S>,
+        }),
+        PropertyType::List {
+            count_type,
+            data_type,
+        } => seed.deserialize(ListDeserializer {
+            reader,
+            count_type,
+            data_type,
+            count,
+            ctx,
+            warnings,
+            _marker: PhantomData::<S>,
+        }),
+    }
+}
+
+/// Say which property it was when the field rejects a value.
+fn field_error(
+    ctx: PropertyContext<'_>,
+    data_type: ScalarType,
+    list_entry: bool,
+    msg: impl Display,
+) -> DeserializeError {
+    let kind = if list_entry { "a list of " } else { "" };
+    ctx.error(format_args!(
+        "is {kind}{} but doesn't fit the field: {msg}",
+        data_type.header_name()
+    ))
+}
+
 struct ScalarDeserializer<'a, R: Read, S: ScalarReader> {
     reader: &'a mut R,
     data_type: ScalarType,
+    ctx: PropertyContext<'a>,
+    list_entry: bool,
+    warnings: Option<&'a mut Vec<PrecisionWarning>>,
     _marker: PhantomData<S>,
 }
 
+impl<R: Read, S: ScalarReader> ScalarDeserializer<'_, R, S> {
+    fn expected_list_error(&self) -> DeserializeError {
+        expected_list_error(self.ctx, self.list_entry)
+    }
+
+    /// Read the value as the type in the header.
+    #[inline]
+    fn read(&mut self) -> Result<Scalar, DeserializeError> {
+        let comma = self.ctx.options.decimal_comma;
+        let v = match self.data_type {
+            ScalarType::I8 => S::read_i8(&mut *self.reader)? as i64,
+            ScalarType::U8 => S::read_u8(&mut *self.reader)? as i64,
+            ScalarType::I16 => S::read_i16(&mut *self.reader)? as i64,
+            ScalarType::U16 => S::read_u16(&mut *self.reader)? as i64,
+            ScalarType::I32 => S::read_i32(&mut *self.reader)? as i64,
+            ScalarType::U32 => S::read_u32(&mut *self.reader)? as i64,
+            ScalarType::F32 => {
+                return Ok(Scalar::Float(
+                    S::read_f32_decimal(&mut *self.reader, comma)? as f64,
+                ))
+            }
+            ScalarType::F64 => {
+                return Ok(Scalar::Float(S::read_f64_decimal(
+                    &mut *self.reader,
+                    comma,
+                )?))
+            }
+        };
+        self.ctx.check_index(v)?;
+        Ok(Scalar::Int(v))
+    }
+
+    /// Read the value and visit it as the type in the header, skipping the conversion.
+    #[inline]
+    fn visit_native<'de, V>(self, visitor: V) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'de>,
+    {
+        let (ctx, comma) = (self.ctx, self.ctx.options.decimal_comma);
+        let (data_type, list_entry) = (self.data_type, self.list_entry);
+        let fail = |e: DeserializeError| field_error(ctx, data_type, list_entry, e.0);
+        match data_type {
+            ScalarType::I8 => {
+                let v = S::read_i8(self.reader)?;
+                ctx.check_index(v as i64)?;
+                visitor.visit_i8(v).map_err(fail)
+            }
+            ScalarType::U8 => {
+                let v = S::read_u8(self.reader)?;
+                ctx.check_index(v as i64)?;
+                visitor.visit_u8(v).map_err(fail)
+            }
+            ScalarType::I16 => {
+                let v = S::read_i16(self.reader)?;
+                ctx.check_index(v as i64)?;
+                visitor.visit_i16(v).map_err(fail)
+            }
+            ScalarType::U16 => {
+                let v = S::read_u16(self.reader)?;
+                ctx.check_index(v as i64)?;
+                visitor.visit_u16(v).map_err(fail)
+            }
+            ScalarType::I32 => {
+                let v = S::read_i32(self.reader)?;
+                ctx.check_index(v as i64)?;
+                visitor.visit_i32(v).map_err(fail)
+            }
+            ScalarType::U32 => {
+                let v = S::read_u32(self.reader)?;
+                ctx.check_index(v as i64)?;
+                visitor.visit_u32(v).map_err(fail)
+            }
+            ScalarType::F32 => visitor
+                .visit_f32(S::read_f32_decimal(self.reader, comma)?)
+                .map_err(fail),
+            ScalarType::F64 => visitor
+                .visit_f64(S::read_f64_decimal(self.reader, comma)?)
+                .map_err(fail),
+        }
+    }
+
+    /// Read the value into a field of type `to`, converted as [`crate::coercion`] says.
+    #[inline]
+    fn deserialize_field<'de, V>(
+        mut self,
+        to: FieldType,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'de>,
+    {
+        if to == FieldType::native(self.data_type) {
+            return self.visit_native(visitor);
+        }
+        let value = self.read()?;
+        let (ctx, list_entry) = (self.ctx, self.list_entry);
+        visit_coerced(
+            value,
+            self.data_type,
+            to,
+            ctx,
+            list_entry,
+            self.warnings,
+            visitor,
+        )
+    }
+}
+
+/// Error for a scalar, or an entry of a list, read into a list field.
+pub(crate) fn expected_list_error(ctx: PropertyContext<'_>, list_entry: bool) -> DeserializeError {
+    if list_entry {
+        ctx.error("is a list of scalars but field expects a list of lists")
+    } else {
+        ctx.error("is a scalar but field expects a list (Vec)")
+    }
+}
+
+/// Visit `value`, a property of type `data_type`, as a field of type `to`, converted as
+/// [`crate::coercion`] says. Lossy conversions are counted in `warnings`, if given.
+pub(crate) fn visit_coerced<'de, V: Visitor<'de>>(
+    value: Scalar,
+    data_type: ScalarType,
+    to: FieldType,
+    ctx: PropertyContext<'_>,
+    list_entry: bool,
+    warnings: Option<&mut Vec<PrecisionWarning>>,
+    visitor: V,
+) -> Result<V::Value, DeserializeError> {
+    let error = |msg: std::fmt::Arguments| field_error(ctx, data_type, list_entry, msg);
+    let field = match coerce(value, data_type, to, ctx.options) {
+        Ok((field, lossy)) => {
+            if let (true, Some(warnings), Scalar::Float(v)) = (lossy, warnings, value) {
+                PrecisionWarning::record(warnings, ctx.element, ctx.property, v);
+            }
+            field
+        }
+        Err(CoerceError::PrecisionLoss(v)) => {
+            return Err(ctx.error(format_args!(
+                "is a double, and {v} loses precision in an f32 field"
+            )))
+        }
+        Err(CoerceError::OutOfRange(v)) => {
+            return Err(error(format_args!("{v} is out of range for {to}")))
+        }
+        Err(CoerceError::Rejected) => {
+            return Err(error(format_args!(
+                "{} values can't be converted, expected {to}",
+                data_type.header_name()
+            )))
+        }
+    };
+    field
+        .visit(visitor)
+        .map_err(|e| field_error(ctx, data_type, list_entry, e.0))
+}
+
+macro_rules! deserialize_fields {
+    ($($method:ident => $to:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.deserialize_field(FieldType::$to, visitor)
+            }
+        )*
+    };
+}
+
 impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for ScalarDeserializer<'_, R, S> {
     type Error = DeserializeError;
 
@@ -136,16 +857,25 @@ impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for ScalarDeserializer<'_,
     where
         V: Visitor<'de>,
     {
-        match self.data_type {
-            ScalarType::I8 => visitor.visit_i8(S::read_i8(self.reader)?),
-            ScalarType::U8 => visitor.visit_u8(S::read_u8(self.reader)?),
-            ScalarType::I16 => visitor.visit_i16(S::read_i16(self.reader)?),
-            ScalarType::U16 => visitor.visit_u16(S::read_u16(self.reader)?),
-            ScalarType::I32 => visitor.visit_i32(S::read_i32(self.reader)?),
-            ScalarType::U32 => visitor.visit_u32(S::read_u32(self.reader)?),
-            ScalarType::F32 => visitor.visit_f32(S::read_f32(self.reader)?),
-            ScalarType::F64 => visitor.visit_f64(S::read_f64(self.reader)?),
-        }
+        // Visited as the type in the header, the visitor decides what it accepts.
+        let to = FieldType::native(self.data_type);
+        self.deserialize_field(to, visitor)
+    }
+
+    deserialize_fields! {
+        deserialize_bool => Bool,
+        deserialize_i8 => I8,
+        deserialize_u8 => U8,
+        deserialize_i16 => I16,
+        deserialize_u16 => U16,
+        deserialize_i32 => I32,
+        deserialize_u32 => U32,
+        deserialize_i64 => I64,
+        deserialize_u64 => U64,
+        deserialize_i128 => I128,
+        deserialize_u128 => U128,
+        deserialize_f32 => F32,
+        deserialize_f64 => F64,
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -166,21 +896,109 @@ impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for ScalarDeserializer<'_,
         visitor.visit_newtype_struct(self)
     }
 
+    fn deserialize_seq<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(self.expected_list_error())
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(self.expected_list_error())
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(self.expected_list_error())
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        S::skip(self.reader, self.data_type, 1)?;
+        visitor.visit_unit()
+    }
+
     serde::forward_to_deserialize_any! {
-        bool i8 u8 i16 u16 i32 u32 f32 f64 i128 i64 u128 u64 char str string
-        bytes byte_buf unit unit_struct seq tuple tuple_struct
-        map struct enum identifier ignored_any
+        char str string bytes byte_buf unit unit_struct map struct enum identifier
     }
 }
 
-struct ListDeserializer<R: Read, S: ScalarReader> {
+struct ListDeserializer<'a, R: Read, S: ScalarReader> {
     reader: R,
     count_type: ScalarType,
     data_type: ScalarType,
+    // Length of the list, if the count was already read.
+    count: Option<usize>,
+    ctx: PropertyContext<'a>,
+    warnings: Option<&'a mut Vec<PrecisionWarning>>,
     _marker: PhantomData<S>,
 }
 
-impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for ListDeserializer<R, S> {
+impl<'a, R: Read, S: ScalarReader> ListDeserializer<'a, R, S> {
+    fn read_count(&mut self) -> Result<usize, DeserializeError> {
+        if let Some(count) = self.count.take() {
+            return Ok(count);
+        }
+        let count = read_count::<S>(&mut self.reader, self.count_type)?;
+        self.ctx.check_count::<S>(self.count_type, count)
+    }
+
+    /// Read the list as a single scalar, if the options allow it.
+    fn deserialize_scalar<'de, V>(
+        mut self,
+        to: FieldType,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'de>,
+    {
+        if !self.ctx.options.single_element_list_as_scalar {
+            return Err(self.ctx.error("is a list but field expects a scalar"));
+        }
+        let count = self.read_count()?;
+        if count != 1 {
+            return Err(self.ctx.error(format_args!(
+                "is a list with {count} entries but field expects a scalar"
+            )));
+        }
+        ScalarDeserializer {
+            reader: &mut self.reader,
+            data_type: self.data_type,
+            ctx: self.ctx,
+            list_entry: true,
+            warnings: self.warnings,
+            _marker: PhantomData::<S>,
+        }
+        .deserialize_field(to, visitor)
+    }
+}
+
+macro_rules! forward_to_deserialize_scalar {
+    ($($method:ident => $to:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.deserialize_scalar(FieldType::$to, visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for ListDeserializer<'_, R, S> {
     type Error = DeserializeError;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -202,28 +1020,33 @@ impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for ListDeserializer<R, S>
     where
         V: Visitor<'de>,
     {
-        let count = match self.count_type {
-            ScalarType::I8 => S::read_i8(&mut self.reader)? as usize,
-            ScalarType::U8 => S::read_u8(&mut self.reader)? as usize,
-            ScalarType::I16 => S::read_i16(&mut self.reader)? as usize,
-            ScalarType::U16 => S::read_u16(&mut self.reader)? as usize,
-            ScalarType::I32 => S::read_i32(&mut self.reader)? as usize,
-            ScalarType::U32 => S::read_u32(&mut self.reader)? as usize,
-            ScalarType::F32 => {
-                return Err(DeserializeError::custom("List count cannot be a float"))
-            }
-            ScalarType::F64 => {
-                return Err(DeserializeError::custom("List count cannot be a float"))
-            }
-        };
+        let count = self.read_count()?;
         visitor.visit_seq(ListSeqAccess {
             reader: &mut self.reader,
             remaining: count,
             data_type: self.data_type,
+            ctx: self.ctx,
+            warnings: self.warnings,
             _marker: PhantomData::<S>,
         })
     }
 
+    fn deserialize_tuple<V>(mut self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        // Fixed size arrays take exactly their length, anything else would leave entries
+        // behind or come up short.
+        let count = self.read_count()?;
+        if count != len {
+            return Err(self.ctx.error(format_args!(
+                "is a list with {count} entries but field is an array of {len}"
+            )));
+        }
+        self.count = Some(count);
+        self.deserialize_seq(visitor)
+    }
+
     fn deserialize_newtype_struct<V>(
         self,
         _name: &'static str,
@@ -235,21 +1058,46 @@ impl<'de, R: Read, S: ScalarReader> Deserializer<'de> for ListDeserializer<R, S>
         visitor.visit_newtype_struct(self)
     }
 
+    fn deserialize_ignored_any<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let count = self.read_count()?;
+        S::skip(&mut self.reader, self.data_type, count)?;
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_scalar! {
+        deserialize_i8 => I8,
+        deserialize_u8 => U8,
+        deserialize_i16 => I16,
+        deserialize_u16 => U16,
+        deserialize_i32 => I32,
+        deserialize_u32 => U32,
+        deserialize_i64 => I64,
+        deserialize_u64 => U64,
+        deserialize_i128 => I128,
+        deserialize_u128 => U128,
+        deserialize_f32 => F32,
+        deserialize_f64 => F64,
+    }
+
     serde::forward_to_deserialize_any! {
-        bool i8 u8 i16 u16 i32 u32 f32 f64 i128 i64 u128 u64 char str string
-        bytes byte_buf unit unit_struct tuple
-        tuple_struct map struct enum identifier ignored_any
+        bool char str string bytes byte_buf unit unit_struct
+        tuple_struct map struct enum identifier
     }
 }
 
-struct ListSeqAccess<R: Read, S> {
+struct ListSeqAccess<'a, R: Read, S> {
     reader: R,
     data_type: ScalarType,
     remaining: usize,
+    ctx: PropertyContext<'a>,
+    warnings: Option<&'a mut Vec<PrecisionWarning>>,
     _marker: PhantomData<S>,
 }
 
-impl<'de, R: Read, S: ScalarReader> SeqAccess<'de> for ListSeqAccess<R, S> {
+impl<'de, R: Read, S: ScalarReader> SeqAccess<'de> for ListSeqAccess<'_, R, S> {
     type Error = DeserializeError;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
@@ -263,6 +1111,9 @@ impl<'de, R: Read, S: ScalarReader> SeqAccess<'de> for ListSeqAccess<R, S> {
         seed.deserialize(ScalarDeserializer {
             reader: &mut self.reader,
             data_type: self.data_type,
+            ctx: self.ctx,
+            list_entry: true,
+            warnings: self.warnings.as_deref_mut(),
             _marker: PhantomData::<S>,
         })
         .map(Some)
@@ -0,0 +1,266 @@
+//! Byte and row statistics collected while reading, see [`DeserializeOptions::collect_stats`],
+//! and warnings about values that lost precision.
+//!
+//! [`DeserializeOptions::collect_stats`]: crate::DeserializeOptions::collect_stats
+
+use std::io::{BufRead, Read};
+
+use crate::{ElementDef, PlyFormat, PlyHeader, PropertyType};
+
+/// Statistics of the data read from a file, per element and per property.
+///
+/// Returned by [`crate::PlyReader::report`] and [`crate::PlyChunkedReader::report`] when
+/// [`crate::DeserializeOptions::collect_stats`] is enabled. Elements that haven't been
+/// read yet have zero rows.
+///
+/// Bytes are counted as they are consumed from the data section. For ASCII files this
+/// includes the whitespace around each value, so the bytes of all properties add up to
+/// the size of the data that was read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadReport {
+    /// Statistics of each element, in file order.
+    pub elements: Vec<ElementStats>,
+}
+
+/// Statistics of a single element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementStats {
+    /// Name of the element.
+    pub name: String,
+    /// Number of rows read.
+    pub rows: usize,
+    /// Bytes of element data read.
+    pub data_bytes: u64,
+    /// Statistics of each property, in header order.
+    pub properties: Vec<PropertyStats>,
+}
+
+/// Statistics of a single property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyStats {
+    /// Name of the property.
+    pub name: String,
+    /// Bytes of data read for this property, including list counts.
+    pub bytes: u64,
+    /// Lengths of the lists read, for list properties.
+    pub list: Option<ListStats>,
+}
+
+/// Lengths of the lists of a list property.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListStats {
+    /// Number of lists read.
+    pub lists: usize,
+    /// Shortest list, 0 if no lists were read.
+    pub min_len: usize,
+    /// Longest list.
+    pub max_len: usize,
+    /// Sum of all list lengths.
+    pub total_len: u64,
+}
+
+/// A property whose `double` values lost precision when read into `f32` fields.
+///
+/// Returned by [`crate::PlyReader::precision_warnings`] and
+/// [`crate::PlyChunkedReader::precision_warnings`] under [`crate::PrecisionLoss::Warn`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrecisionWarning {
+    /// Name of the element.
+    pub element: String,
+    /// Name of the property.
+    pub property: String,
+    /// Number of values that lost precision.
+    pub count: usize,
+    /// The first value that lost precision, as stored in the file.
+    pub example: f64,
+}
+
+impl PrecisionWarning {
+    /// Count a lossy `value` of the property in `warnings`.
+    pub(crate) fn record(
+        warnings: &mut Vec<PrecisionWarning>,
+        element: &str,
+        property: &str,
+        value: f64,
+    ) {
+        match warnings
+            .iter_mut()
+            .find(|w| w.element == element && w.property == property)
+        {
+            Some(warning) => warning.count += 1,
+            None => warnings.push(PrecisionWarning {
+                element: element.to_string(),
+                property: property.to_string(),
+                count: 1,
+                example: value,
+            }),
+        }
+    }
+
+    /// Add the counts of `from` to `warnings`.
+    pub(crate) fn merge(warnings: &mut Vec<PrecisionWarning>, from: &mut Vec<PrecisionWarning>) {
+        for warning in from.drain(..) {
+            match warnings
+                .iter_mut()
+                .find(|w| w.element == warning.element && w.property == warning.property)
+            {
+                Some(existing) => existing.count += warning.count,
+                None => warnings.push(warning),
+            }
+        }
+    }
+}
+
+impl LoadReport {
+    pub(crate) fn new(header: &PlyHeader) -> Self {
+        Self {
+            elements: header.elem_defs.iter().map(ElementStats::new).collect(),
+        }
+    }
+
+    /// Statistics of the element with the given name.
+    pub fn element(&self, name: &str) -> Option<&ElementStats> {
+        self.elements.iter().find(|e| e.name == name)
+    }
+
+    /// Total bytes of element data read.
+    pub fn data_bytes(&self) -> u64 {
+        self.elements.iter().map(|e| e.data_bytes).sum()
+    }
+}
+
+impl ElementStats {
+    fn new(def: &ElementDef) -> Self {
+        Self {
+            name: def.name.clone(),
+            rows: 0,
+            data_bytes: 0,
+            properties: def
+                .properties
+                .iter()
+                .map(|p| PropertyStats {
+                    name: p.name.clone(),
+                    bytes: 0,
+                    list: p.is_list().then(ListStats::default),
+                })
+                .collect(),
+        }
+    }
+
+    /// Statistics of the property with the given name.
+    pub fn property(&self, name: &str) -> Option<&PropertyStats> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    /// Record rows that were read in bulk, without going through the row deserializer.
+    ///
+    /// Per property bytes are only known for binary rows without lists.
+    pub(crate) fn record_bulk(
+        &mut self,
+        def: &ElementDef,
+        format: PlyFormat,
+        rows: usize,
+        bytes: u64,
+    ) {
+        self.rows += rows;
+        self.data_bytes += bytes;
+        if !format.is_binary() || def.row_byte_size().is_none() {
+            return;
+        }
+        for (stats, prop) in self.properties.iter_mut().zip(&def.properties) {
+            if let PropertyType::Scalar(ty) = prop.property_type {
+                stats.bytes += (ty.size_bytes() * rows) as u64;
+            }
+        }
+    }
+}
+
+impl ListStats {
+    /// Mean list length, 0 if no lists were read.
+    pub fn mean_len(&self) -> f64 {
+        if self.lists == 0 {
+            0.0
+        } else {
+            self.total_len as f64 / self.lists as f64
+        }
+    }
+
+    fn record(&mut self, len: usize) {
+        self.min_len = if self.lists == 0 {
+            len
+        } else {
+            self.min_len.min(len)
+        };
+        self.max_len = self.max_len.max(len);
+        self.total_len += len as u64;
+        self.lists += 1;
+    }
+}
+
+/// Samples of the row being read, added to the element once the row is complete so a
+/// row that is retried later isn't counted twice.
+pub(crate) struct RowStats<'a> {
+    element: &'a mut ElementStats,
+    // Bytes and list length of each property read so far in this row.
+    pending: Vec<(u64, usize)>,
+}
+
+impl<'a> RowStats<'a> {
+    pub(crate) fn new(element: &'a mut ElementStats) -> Self {
+        Self {
+            element,
+            pending: Vec::new(),
+        }
+    }
+
+    pub(crate) fn start_row(&mut self) {
+        self.pending.clear();
+    }
+
+    pub(crate) fn record(&mut self, bytes: u64, list_len: usize) {
+        self.pending.push((bytes, list_len));
+    }
+
+    pub(crate) fn finish_row(&mut self) {
+        self.element.rows += 1;
+        for (stats, &(bytes, len)) in self.element.properties.iter_mut().zip(&self.pending) {
+            stats.bytes += bytes;
+            self.element.data_bytes += bytes;
+            if let Some(list) = &mut stats.list {
+                list.record(len);
+            }
+        }
+        self.pending.clear();
+    }
+}
+
+/// Reader adapter that counts the bytes read through it.
+pub(crate) struct CountingReader<'a, R> {
+    inner: &'a mut R,
+    pub(crate) count: usize,
+}
+
+impl<'a, R> CountingReader<'a, R> {
+    pub(crate) fn new(inner: &'a mut R) -> Self {
+        Self { inner, count: 0 }
+    }
+}
+
+impl<R: Read> Read for CountingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count += n;
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<'_, R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.count += amt;
+        self.inner.consume(amt);
+    }
+}
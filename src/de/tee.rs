@@ -0,0 +1,98 @@
+//! Copying the bytes a reader consumes to a writer.
+
+use std::io::{BufRead, Read, Write};
+
+use crate::DeserializeError;
+
+/// Reader that writes every byte consumed from it to a writer, see [`crate::PlyReader::tee`].
+///
+/// Bytes that are only looked at through [`BufRead::fill_buf`] aren't copied until they
+/// are consumed, so each byte reaches the writer exactly once and in order. Every
+/// consumed piece is a separate write, so wrap writers that don't buffer themselves in a
+/// [`std::io::BufWriter`].
+pub struct TeeReader<R, W> {
+    inner: R,
+    writer: W,
+    // First failed write from `consume`, which can't return it.
+    error: Option<std::io::Error>,
+    // Last byte written to `writer`.
+    last: Option<u8>,
+}
+
+impl<R, W: Write> TeeReader<R, W> {
+    pub(crate) fn new(inner: R, writer: W) -> Self {
+        Self {
+            inner,
+            writer,
+            error: None,
+            last: None,
+        }
+    }
+
+    /// The writer receiving the copy.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    fn check(&mut self) -> std::io::Result<()> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.writer.write_all(buf)?;
+        if let Some(&byte) = buf.last() {
+            self.last = Some(byte);
+        }
+        Ok(())
+    }
+}
+
+impl<R: BufRead, W: Write> TeeReader<R, W> {
+    /// Flush the writer and return it, along with any error from writing to it.
+    ///
+    /// ASCII values end at the first byte after them, so the last row of a file with
+    /// `\r\n` line breaks leaves the `\n` unread. It's copied here, so that the copy of
+    /// a complete file is exact.
+    pub fn into_writer(mut self) -> Result<W, DeserializeError> {
+        self.check()?;
+        if self.last == Some(b'\r') && self.inner.fill_buf()?.first() == Some(&b'\n') {
+            self.consume(1);
+            self.check()?;
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<R: Read, W: Write> Read for TeeReader<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.check()?;
+        let n = self.inner.read(buf)?;
+        self.write(&buf[..n])?;
+        Ok(n)
+    }
+}
+
+impl<R: BufRead, W: Write> BufRead for TeeReader<R, W> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.check()?;
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The buffer is already filled, so this doesn't read anything.
+        if let Ok(buf) = self.inner.fill_buf() {
+            let buf = &buf[..amt.min(buf.len())];
+            match self.writer.write_all(buf) {
+                Ok(()) => self.last = buf.last().copied().or(self.last),
+                Err(e) => {
+                    self.error.get_or_insert(e);
+                }
+            }
+        }
+        self.inner.consume(amt)
+    }
+}
@@ -4,6 +4,8 @@ use std::marker::PhantomData;
 use byteorder::ByteOrder;
 use byteorder::ReadBytesExt;
 
+use crate::{ElementDef, PropertyType, ScalarType};
+
 pub(crate) struct BinValReader<E: ByteOrder> {
     _endian: PhantomData<E>,
 }
@@ -11,6 +13,9 @@ pub(crate) struct BinValReader<E: ByteOrder> {
 pub(crate) struct AsciiValReader {}
 
 pub(crate) trait ScalarReader {
+    /// Whether values are whitespace separated text, as in ASCII files.
+    const TEXT: bool = false;
+
     fn read_i8(reader: impl Read) -> Result<i8, std::io::Error>;
     fn read_u8(reader: impl Read) -> Result<u8, std::io::Error>;
     fn read_i16(reader: impl Read) -> Result<i16, std::io::Error>;
@@ -19,6 +24,67 @@ pub(crate) trait ScalarReader {
     fn read_u32(reader: impl Read) -> Result<u32, std::io::Error>;
     fn read_f32(data: impl Read) -> Result<f32, std::io::Error>;
     fn read_f64(reader: impl Read) -> Result<f64, std::io::Error>;
+
+    /// Like [`Self::read_f32`], but also accept a decimal comma if `decimal_comma` is set.
+    /// Only text has a decimal separator, so by default the flag is ignored.
+    fn read_f32_decimal(reader: impl Read, decimal_comma: bool) -> Result<f32, std::io::Error> {
+        let _ = decimal_comma;
+        Self::read_f32(reader)
+    }
+
+    /// Like [`Self::read_f64`], but also accept a decimal comma if `decimal_comma` is set.
+    fn read_f64_decimal(reader: impl Read, decimal_comma: bool) -> Result<f64, std::io::Error> {
+        let _ = decimal_comma;
+        Self::read_f64(reader)
+    }
+
+    /// Skip `count` values of type `ty` without converting them.
+    fn skip(reader: impl Read, ty: ScalarType, count: usize) -> Result<(), std::io::Error>;
+
+    /// Skip a whole row of `elem_def` without converting any values.
+    fn skip_row(reader: impl Read, elem_def: &ElementDef) -> Result<(), std::io::Error> {
+        skip_properties::<Self>(reader, elem_def)
+    }
+}
+
+fn skip_properties<S: ScalarReader + ?Sized>(
+    mut reader: impl Read,
+    elem_def: &ElementDef,
+) -> Result<(), std::io::Error> {
+    for prop in &elem_def.properties {
+        match prop.property_type {
+            PropertyType::Scalar(ty) => S::skip(&mut reader, ty, 1)?,
+            PropertyType::List {
+                count_type,
+                data_type,
+            } => {
+                let count = read_count::<S>(&mut reader, count_type)?;
+                S::skip(&mut reader, data_type, count)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Read the length of a list stored as `ty`.
+pub(crate) fn read_count<S: ScalarReader + ?Sized>(
+    mut reader: impl Read,
+    ty: ScalarType,
+) -> Result<usize, std::io::Error> {
+    Ok(match ty {
+        ScalarType::I8 => S::read_i8(&mut reader)? as usize,
+        ScalarType::U8 => S::read_u8(&mut reader)? as usize,
+        ScalarType::I16 => S::read_i16(&mut reader)? as usize,
+        ScalarType::U16 => S::read_u16(&mut reader)? as usize,
+        ScalarType::I32 => S::read_i32(&mut reader)? as usize,
+        ScalarType::U32 => S::read_u32(&mut reader)? as usize,
+        ScalarType::F32 | ScalarType::F64 => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "List count cannot be a float",
+            ))
+        }
+    })
 }
 
 impl<E: ByteOrder> ScalarReader for BinValReader<E> {
@@ -53,9 +119,34 @@ impl<E: ByteOrder> ScalarReader for BinValReader<E> {
     fn read_f64(mut reader: impl Read) -> Result<f64, std::io::Error> {
         reader.read_f64::<E>()
     }
+
+    fn skip(reader: impl Read, ty: ScalarType, count: usize) -> Result<(), std::io::Error> {
+        skip_bytes(reader, ty.size_bytes() * count)
+    }
+
+    fn skip_row(reader: impl Read, elem_def: &ElementDef) -> Result<(), std::io::Error> {
+        // Rows without lists have a fixed size, so they can be skipped in one go.
+        match elem_def.row_byte_size() {
+            Some(size) => skip_bytes(reader, size),
+            None => skip_properties::<Self>(reader, elem_def),
+        }
+    }
+}
+
+fn skip_bytes(reader: impl Read, n: usize) -> Result<(), std::io::Error> {
+    let skipped = std::io::copy(&mut reader.take(n as u64), &mut std::io::sink())?;
+    if skipped < n as u64 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "Unexpected end of file",
+        ));
+    }
+    Ok(())
 }
 
 impl ScalarReader for AsciiValReader {
+    const TEXT: bool = true;
+
     fn read_i8(reader: impl Read) -> Result<i8, std::io::Error> {
         Self::read_ascii_token(reader)?.parse::<i8>().map_err(|_| {
             std::io::Error::new(
@@ -111,7 +202,49 @@ impl ScalarReader for AsciiValReader {
     }
 
     fn read_f32(reader: impl Read) -> Result<f32, std::io::Error> {
-        Self::read_ascii_token(reader)?.parse::<f32>().map_err(|_| {
+        Self::parse_f32(&Self::read_ascii_token(reader)?)
+    }
+
+    fn read_f64(reader: impl Read) -> Result<f64, std::io::Error> {
+        Self::parse_f64(&Self::read_ascii_token(reader)?)
+    }
+
+    fn read_f32_decimal(reader: impl Read, decimal_comma: bool) -> Result<f32, std::io::Error> {
+        let token = Self::read_ascii_token(reader)?;
+        Self::parse_f32(&token).or_else(|e| match decimal_comma_to_point(&token, decimal_comma) {
+            Some(token) => Self::parse_f32(&token),
+            None => Err(e),
+        })
+    }
+
+    fn read_f64_decimal(reader: impl Read, decimal_comma: bool) -> Result<f64, std::io::Error> {
+        let token = Self::read_ascii_token(reader)?;
+        Self::parse_f64(&token).or_else(|e| match decimal_comma_to_point(&token, decimal_comma) {
+            Some(token) => Self::parse_f64(&token),
+            None => Err(e),
+        })
+    }
+
+    fn skip(mut reader: impl Read, _ty: ScalarType, count: usize) -> Result<(), std::io::Error> {
+        for _ in 0..count {
+            Self::skip_ascii_token(&mut reader)?;
+        }
+        Ok(())
+    }
+}
+
+/// `token` with its decimal comma replaced by a point, if `decimal_comma` is set and the
+/// token has exactly one comma and no point.
+fn decimal_comma_to_point(token: &str, decimal_comma: bool) -> Option<String> {
+    if !decimal_comma || token.contains('.') || token.matches(',').count() != 1 {
+        return None;
+    }
+    Some(token.replacen(',', ".", 1))
+}
+
+impl AsciiValReader {
+    fn parse_f32(token: &str) -> Result<f32, std::io::Error> {
+        token.parse::<f32>().map_err(|_| {
             std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
                 "Failed to parse f32 from ASCII",
@@ -119,17 +252,31 @@ impl ScalarReader for AsciiValReader {
         })
     }
 
-    fn read_f64(reader: impl Read) -> Result<f64, std::io::Error> {
-        Self::read_ascii_token(reader)?.parse::<f64>().map_err(|_| {
+    fn parse_f64(token: &str) -> Result<f64, std::io::Error> {
+        token.parse::<f64>().map_err(|_| {
             std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
                 "Failed to parse f64 from ASCII",
             )
         })
     }
-}
 
-impl AsciiValReader {
+    /// Like [`Self::read_ascii_token`], but without collecting the token.
+    fn skip_ascii_token(mut reader: impl Read) -> Result<(), std::io::Error> {
+        let mut in_token = false;
+        loop {
+            let mut byte = [0u8; 1];
+            reader.read_exact(&mut byte)?;
+            if byte[0].is_ascii_whitespace() {
+                if in_token {
+                    return Ok(());
+                }
+            } else {
+                in_token = true;
+            }
+        }
+    }
+
     fn read_ascii_token(mut reader: impl Read) -> Result<String, std::io::Error> {
         let mut token = String::new();
 
@@ -2,6 +2,8 @@ use core::fmt;
 
 use thiserror::Error;
 
+use crate::ScalarType;
+
 /// Error that occurs during PLY deserialization.
 #[derive(Error, Debug)]
 #[error("Error while deserializing ply: {0}")]
@@ -12,6 +14,402 @@ pub struct DeserializeError(#[from] pub std::io::Error);
 #[error("Error while serializing ply: {0}")]
 pub struct SerializeError(#[from] pub std::io::Error);
 
+/// For tools that read and write in one go, eg. transcoders. The underlying
+/// [`std::io::Error`] is kept, including its kind and cause.
+///
+/// # Example
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use serde_ply::{SerializeError, SerializeOptions};
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Vertex { x: f32 }
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Mesh { vertex: Vec<Vertex> }
+///
+/// fn to_binary(ply: &str) -> Result<Vec<u8>, SerializeError> {
+///     let mesh: Mesh = serde_ply::from_str(ply)?;
+///     serde_ply::to_bytes(&mesh, SerializeOptions::binary_le())
+/// }
+///
+/// let err = to_binary("ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nend_header\n1\n").unwrap_err();
+/// assert_eq!(err.0.kind(), std::io::ErrorKind::UnexpectedEof);
+/// ```
+impl From<DeserializeError> for SerializeError {
+    fn from(err: DeserializeError) -> Self {
+        SerializeError(err.0)
+    }
+}
+
+/// Keeps the underlying [`std::io::Error`], like the conversion the other way.
+impl From<SerializeError> for DeserializeError {
+    fn from(err: SerializeError) -> Self {
+        DeserializeError(err.0)
+    }
+}
+
+/// Cause of the error returned when an element is requested after the last one.
+///
+/// Check for it with [`DeserializeError::is_elements_exhausted`].
+#[derive(Error, Debug)]
+pub struct ElementsExhausted {
+    expected: Option<String>,
+}
+
+impl fmt::Display for ElementsExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.expected {
+            Some(name) => write!(
+                f,
+                "expected element '{name}' but all elements have been read"
+            ),
+            None => f.write_str("all elements have been read"),
+        }
+    }
+}
+
+impl DeserializeError {
+    pub(crate) fn elements_exhausted(expected: Option<&str>) -> Self {
+        let cause = ElementsExhausted {
+            expected: expected.map(str::to_string),
+        };
+        DeserializeError(std::io::Error::new(std::io::ErrorKind::InvalidInput, cause))
+    }
+
+    /// Whether an element was requested after the last element of the file.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyReader;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\n1.0\n";
+    /// let mut reader = PlyReader::from_reader(ply_data.as_bytes())?;
+    /// let _vertices: Vec<Vertex> = reader.next_element()?;
+    /// let err = reader.next_element::<Vec<Vertex>>().err().unwrap();
+    /// assert!(err.is_elements_exhausted());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn is_elements_exhausted(&self) -> bool {
+        self.0
+            .get_ref()
+            .is_some_and(|cause| cause.is::<ElementsExhausted>())
+    }
+}
+
+/// Cause of the error returned when the input is empty, as left behind by a writer that
+/// crashed before writing anything.
+///
+/// Check for it with [`DeserializeError::is_empty_input`].
+#[derive(Error, Debug)]
+#[error("input is empty, expected a ply header")]
+pub struct EmptyInput;
+
+/// Cause of the error returned when the data ends before an element has all the rows
+/// the header declares, eg. for a file with a header but no data.
+///
+/// Get it with [`DeserializeError::as_missing_data`].
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("File ended after {rows_read} of {expected_rows} rows of element '{element}'")]
+pub struct MissingData {
+    /// Name of the element.
+    pub element: String,
+    /// Number of rows the header declares.
+    pub expected_rows: usize,
+    /// Number of rows the data holds.
+    pub rows_read: usize,
+}
+
+impl DeserializeError {
+    pub(crate) fn empty_input() -> Self {
+        DeserializeError(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            EmptyInput,
+        ))
+    }
+
+    pub(crate) fn missing_data(element: &str, expected_rows: usize, rows_read: usize) -> Self {
+        let cause = MissingData {
+            element: element.to_string(),
+            expected_rows,
+            rows_read,
+        };
+        DeserializeError(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            cause,
+        ))
+    }
+
+    /// Whether the input was empty.
+    ///
+    /// # Example
+    /// ```rust
+    /// let err = serde_ply::PlyReader::from_reader(&b""[..]).err().unwrap();
+    /// assert!(err.is_empty_input());
+    /// ```
+    pub fn is_empty_input(&self) -> bool {
+        self.0
+            .get_ref()
+            .is_some_and(|cause| cause.is::<EmptyInput>())
+    }
+
+    /// The element whose rows are missing, if the data ended before an element was
+    /// complete.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use serde_ply::PlyReader;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Vertex { x: f32 }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nend_header\n";
+    /// let mut reader = PlyReader::from_reader(ply_data.as_bytes())?;
+    /// let err = reader.next_element::<Vec<Vertex>>().err().unwrap();
+    /// let missing = err.as_missing_data().unwrap();
+    /// assert_eq!((missing.element.as_str(), missing.expected_rows), ("vertex", 3));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn as_missing_data(&self) -> Option<&MissingData> {
+        self.0
+            .get_ref()
+            .and_then(|cause| cause.downcast_ref::<MissingData>())
+    }
+}
+
+/// Cause of the error returned by [`crate::DeserializeOptions::diagnose_misalignment`] when
+/// a binary list looks like its count was written with a different type than the header
+/// declares.
+///
+/// Check for it with [`DeserializeError::is_misaligned_list`].
+#[derive(Error, Debug)]
+pub struct MisalignedList {
+    element: String,
+    property: String,
+    row: usize,
+    count_type: ScalarType,
+    problem: String,
+    suggestion: Option<ScalarType>,
+}
+
+impl fmt::Display for MisalignedList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (element, property) = (&self.element, &self.property);
+        write!(
+            f,
+            "list '{property}' of element '{element}' {} (row {}). The data is likely misaligned: \
+             the file may write the list counts with another type than the declared {}. ",
+            self.problem,
+            self.row,
+            self.count_type.header_name()
+        )?;
+        match self.suggestion {
+            Some(count_type) => write!(
+                f,
+                "Try DeserializeOptions::override_list_count_type(\"{element}\", \"{property}\", ScalarType::{count_type:?})"
+            ),
+            None => write!(
+                f,
+                "Set the type the file uses with DeserializeOptions::override_list_count_type"
+            ),
+        }
+    }
+}
+
+impl DeserializeError {
+    pub(crate) fn misaligned_list(
+        element: &str,
+        property: &str,
+        row: usize,
+        count_type: ScalarType,
+        problem: String,
+        suggestion: Option<ScalarType>,
+    ) -> Self {
+        let cause = MisalignedList {
+            element: element.to_string(),
+            property: property.to_string(),
+            row,
+            count_type,
+            problem,
+            suggestion,
+        };
+        DeserializeError(std::io::Error::new(std::io::ErrorKind::InvalidData, cause))
+    }
+
+    /// Whether [`crate::DeserializeOptions::diagnose_misalignment`] found a list whose
+    /// count type doesn't match the data.
+    pub fn is_misaligned_list(&self) -> bool {
+        self.0
+            .get_ref()
+            .is_some_and(|cause| cause.is::<MisalignedList>())
+    }
+}
+
+/// Cause of the error returned by [`crate::to_slice`] when the output doesn't fit.
+///
+/// Get it with [`SerializeError::as_buffer_too_small`].
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("the output needs {needed} bytes, but the buffer only has {available}")]
+pub struct BufferTooSmall {
+    /// Size of the output in bytes.
+    pub needed: usize,
+    /// Size of the buffer in bytes.
+    pub available: usize,
+}
+
+impl SerializeError {
+    pub(crate) fn buffer_too_small(needed: usize, available: usize) -> Self {
+        SerializeError(std::io::Error::new(
+            std::io::ErrorKind::WriteZero,
+            BufferTooSmall { needed, available },
+        ))
+    }
+
+    /// The sizes involved, if the output didn't fit the buffer given to [`crate::to_slice`].
+    pub fn as_buffer_too_small(&self) -> Option<&BufferTooSmall> {
+        self.0
+            .get_ref()
+            .and_then(|cause| cause.downcast_ref::<BufferTooSmall>())
+    }
+}
+
+/// Cause of the error returned when an element of the header declares the same property
+/// twice, as left behind by a header whose property line was duplicated.
+///
+/// Get it with [`DeserializeError::as_duplicate_property`].
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("element '{element}' declares property '{property}' twice")]
+pub struct DuplicateProperty {
+    /// Name of the element.
+    pub element: String,
+    /// Name of the duplicated property.
+    pub property: String,
+}
+
+impl DeserializeError {
+    pub(crate) fn duplicate_property(element: &str, property: &str) -> Self {
+        let cause = DuplicateProperty {
+            element: element.to_string(),
+            property: property.to_string(),
+        };
+        DeserializeError(std::io::Error::new(std::io::ErrorKind::InvalidData, cause))
+    }
+
+    /// The element and property, if the header declares a property twice.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::PlyReader;
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float x\nend_header\n";
+    /// let err = PlyReader::from_reader(ply_data.as_bytes()).err().unwrap();
+    /// let duplicate = err.as_duplicate_property().unwrap();
+    /// assert_eq!((duplicate.element.as_str(), duplicate.property.as_str()), ("vertex", "x"));
+    /// ```
+    pub fn as_duplicate_property(&self) -> Option<&DuplicateProperty> {
+        self.0
+            .get_ref()
+            .and_then(|cause| cause.downcast_ref::<DuplicateProperty>())
+    }
+}
+
+/// Cause of the error returned when the header ends without a `format` line.
+///
+/// Check for it with [`DeserializeError::is_missing_format`].
+#[derive(Error, Debug)]
+#[error("Missing format line, the header must declare a format before end_header")]
+pub struct MissingFormat;
+
+impl DeserializeError {
+    pub(crate) fn missing_format() -> Self {
+        DeserializeError(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            MissingFormat,
+        ))
+    }
+
+    /// Whether the header has no `format` line.
+    ///
+    /// # Example
+    /// ```rust
+    /// let ply_data = "ply\nelement vertex 1\nproperty float x\nend_header\n1.0\n";
+    /// let err = serde_ply::PlyReader::from_reader(ply_data.as_bytes()).err().unwrap();
+    /// assert!(err.is_missing_format());
+    /// ```
+    pub fn is_missing_format(&self) -> bool {
+        self.0
+            .get_ref()
+            .is_some_and(|cause| cause.is::<MissingFormat>())
+    }
+}
+
+/// Stage of reading a file in which a [`DeserializeError`] happened.
+///
+/// Get it with [`DeserializeError::phase`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorPhase {
+    /// The header is malformed or incomplete. Parsing stops before the element data, so
+    /// the data can still be read with a repaired header through
+    /// [`crate::PlyReader::from_parts`].
+    Header,
+    /// The element data doesn't match the header, or doesn't fit the requested types.
+    Data,
+    /// The underlying reader failed.
+    Io,
+}
+
+/// Cause of the errors for a malformed or incomplete header.
+#[derive(Error, Debug)]
+#[error("{message}")]
+struct HeaderError {
+    message: String,
+}
+
+impl DeserializeError {
+    pub(crate) fn header(kind: std::io::ErrorKind, message: impl Into<String>) -> Self {
+        let cause = HeaderError {
+            message: message.into(),
+        };
+        DeserializeError(std::io::Error::new(kind, cause))
+    }
+
+    /// The stage of reading the error happened in, eg. to decide whether repairing the
+    /// header is worth a try.
+    ///
+    /// Errors raised by the underlying reader are [`ErrorPhase::Io`], except for running
+    /// out of data in the middle of an element, which is [`ErrorPhase::Data`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::{ErrorPhase, PlyReader};
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex one\nend_header\n";
+    /// let err = PlyReader::from_reader(ply_data.as_bytes()).err().unwrap();
+    /// assert_eq!(err.phase(), ErrorPhase::Header);
+    /// ```
+    pub fn phase(&self) -> ErrorPhase {
+        if self.0.get_ref().is_some_and(|cause| {
+            cause.is::<HeaderError>()
+                || cause.is::<EmptyInput>()
+                || cause.is::<DuplicateProperty>()
+                || cause.is::<MissingFormat>()
+        }) {
+            return ErrorPhase::Header;
+        }
+        match self.0.kind() {
+            std::io::ErrorKind::InvalidData
+            | std::io::ErrorKind::InvalidInput
+            | std::io::ErrorKind::UnexpectedEof => ErrorPhase::Data,
+            _ => ErrorPhase::Io,
+        }
+    }
+}
+
 impl serde::de::Error for DeserializeError {
     fn custom<T: fmt::Display>(msg: T) -> Self {
         DeserializeError(std::io::Error::new(
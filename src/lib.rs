@@ -37,22 +37,105 @@
 //! let output = to_string(&mesh, SerializeOptions::ascii())?;
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
+//!
+//! # Nested structs
+//!
+//! PLY rows are a flat list of properties. To group properties into sub-structs, mark
+//! the field with `#[serde(flatten)]` so its fields become properties of the row. Without
+//! it, serializing fails with an error naming the field.
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use serde_ply::{from_str, to_string, SerializeOptions};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Position {
+//!     x: f32,
+//!     y: f32,
+//!     z: f32,
+//! }
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Vertex {
+//!     #[serde(flatten)]
+//!     pos: Position,
+//!     red: u8,
+//! }
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Mesh {
+//!     vertex: Vec<Vertex>,
+//! }
+//!
+//! let mesh = Mesh {
+//!     vertex: vec![Vertex { pos: Position { x: 1.0, y: 2.0, z: 3.0 }, red: 255 }],
+//! };
+//! let text = to_string(&mesh, SerializeOptions::ascii())?;
+//! assert!(text.contains("property float x\nproperty float y\nproperty float z\n"));
+//! let mesh: Mesh = from_str(&text)?;
+//! assert_eq!(mesh.vertex[0].pos.y, 2.0);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! # Skipped fields
+//!
+//! The header is collected from the first row of each element, so every row has to write
+//! the same properties in the same order. `#[serde(skip)]` always works, while
+//! `#[serde(skip_serializing_if)]` only works if it skips the field in every row or in
+//! none of them. Otherwise serializing fails with an error naming the property.
+//!
+//! # Extra properties
+//!
+//! When reading, properties without a field in the row type are skipped, whichever reader
+//! is used. A `#[serde(flatten)]` field or a map row collects them instead,
+//! `#[serde(deny_unknown_fields)]` errors on the first one, and
+//! [`DeserializeOptions::deny_extra_properties`] errors up front listing all of them.
 
 mod de;
 mod error;
+pub mod mesh;
 mod ser;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
+#[cfg(feature = "with")]
+pub mod with;
 
 pub use de::{
     chunked::{PlyChunkedReader, RowVisitor},
-    PlyReader,
+    coerce::{coercion, coercion_matrix, Coercion, FieldType},
+    ply_file::{ElementReader, Rows},
+    pod::PodLayout,
+    progress::{ElementState, ElementStatus},
+    raw::{PlyValue, RawRows},
+    read_buffer::ReadBuffer,
+    stats::{ElementStats, ListStats, LoadReport, PrecisionWarning, PropertyStats},
+    tee::TeeReader,
+    DeserializeOptions, PlyReader, PrecisionLoss,
+};
+pub use de::{
+    find_data_start, from_bytes, from_element_bytes, from_reader, from_reader_keep, from_str,
+    read_header_prefix, validate,
+};
+pub use error::{
+    BufferTooSmall, DeserializeError, DuplicateProperty, ElementsExhausted, EmptyInput, ErrorPhase,
+    MisalignedList, MissingData, MissingFormat, SerializeError,
+};
+#[cfg(feature = "ndarray")]
+pub use ser::array::to_writer_array2;
+#[cfg(feature = "parallel")]
+pub use ser::parallel::to_writer_parallel;
+pub use ser::{
+    columns::Columns,
+    map::{map_element, map_elements, MapElementStats},
+    to_bytes, to_slice, to_string, to_writer,
+    writer::PlyWriter,
+    ArrayMode, MetaOrder, SerializeOptions,
 };
-pub use de::{from_bytes, from_reader, from_str};
-pub use error::{DeserializeError, SerializeError};
-pub use ser::{to_bytes, to_string, to_writer, SerializeOptions};
 
 use std::io::BufRead;
 
 use std::fmt::{self, Display};
+use std::ops::Range;
 use std::str::FromStr;
 
 /// PLY file format encoding.
@@ -68,6 +151,31 @@ pub enum PlyFormat {
     BinaryBigEndian,
 }
 
+impl PlyFormat {
+    /// Whether the data is stored in binary, in either byte order.
+    pub fn is_binary(&self) -> bool {
+        self.byte_order().is_some()
+    }
+
+    /// Byte order of binary data, `None` for ASCII.
+    pub fn byte_order(&self) -> Option<ByteOrderKind> {
+        match self {
+            PlyFormat::Ascii => None,
+            PlyFormat::BinaryLittleEndian => Some(ByteOrderKind::Little),
+            PlyFormat::BinaryBigEndian => Some(ByteOrderKind::Big),
+        }
+    }
+}
+
+/// Byte order of a binary [`PlyFormat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrderKind {
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
 impl fmt::Display for PlyFormat {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -95,8 +203,10 @@ pub enum ScalarType {
 }
 
 impl ScalarType {
+    /// Parse a type token of a header. Some exporters write the types in upper case, eg.
+    /// `FLOAT`, so the case is ignored.
     pub(crate) fn parse(s: &str) -> Result<Self, DeserializeError> {
-        match s {
+        match s.to_ascii_lowercase().as_str() {
             "char" | "int8" => Ok(ScalarType::I8),
             "uchar" | "uint8" => Ok(ScalarType::U8),
             "short" | "int16" => Ok(ScalarType::I16),
@@ -105,12 +215,77 @@ impl ScalarType {
             "uint" | "uint32" => Ok(ScalarType::U32),
             "float" | "float32" => Ok(ScalarType::F32),
             "double" | "float64" => Ok(ScalarType::F64),
-            _ => Err(DeserializeError(std::io::Error::new(
+            _ => Err(DeserializeError::header(
                 std::io::ErrorKind::InvalidData,
                 format!("Unknown scalar type: {}", s),
-            ))),
+            )),
+        }
+    }
+
+    /// Name used when writing this type to a header.
+    pub(crate) fn header_name(&self) -> &'static str {
+        match self {
+            ScalarType::I8 => "char",
+            ScalarType::U8 => "uchar",
+            ScalarType::I16 => "short",
+            ScalarType::U16 => "ushort",
+            ScalarType::I32 => "int",
+            ScalarType::U32 => "uint",
+            ScalarType::F32 => "float",
+            ScalarType::F64 => "double",
+        }
+    }
+
+    /// Name of the matching Rust type, used for summaries.
+    pub(crate) fn rust_name(&self) -> &'static str {
+        match self {
+            ScalarType::I8 => "i8",
+            ScalarType::U8 => "u8",
+            ScalarType::I16 => "i16",
+            ScalarType::U16 => "u16",
+            ScalarType::I32 => "i32",
+            ScalarType::U32 => "u32",
+            ScalarType::F32 => "f32",
+            ScalarType::F64 => "f64",
+        }
+    }
+
+    /// Whether this is an integer type, as list counts must be.
+    pub(crate) fn is_integer(&self) -> bool {
+        !matches!(self, ScalarType::F32 | ScalarType::F64)
+    }
+
+    /// Size of a single binary value of this type in bytes.
+    pub fn size_bytes(&self) -> usize {
+        match self {
+            ScalarType::I8 | ScalarType::U8 => 1,
+            ScalarType::I16 | ScalarType::U16 => 2,
+            ScalarType::I32 | ScalarType::U32 | ScalarType::F32 => 4,
+            ScalarType::F64 => 8,
         }
     }
+
+    /// Check whether every value of this type can be represented exactly in `target`.
+    ///
+    /// Integers widen to larger integers of compatible signedness (`u8` -> `u16` -> `u32`,
+    /// `i8` -> `i16` -> `i32`), and unsigned integers also widen to any strictly larger
+    /// signed integer. Integers convert to floats only if the float's mantissa can hold
+    /// every value, so 32 bit integers only convert to `f64`. `f32` widens to `f64`.
+    ///
+    /// These are the pairs the [`coercion_matrix`] reads as [`Coercion::Lossless`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::ScalarType;
+    ///
+    /// assert!(ScalarType::U8.can_losslessly_convert_to(ScalarType::I16));
+    /// assert!(ScalarType::I32.can_losslessly_convert_to(ScalarType::F64));
+    /// assert!(!ScalarType::I32.can_losslessly_convert_to(ScalarType::F32));
+    /// assert!(!ScalarType::I8.can_losslessly_convert_to(ScalarType::U32));
+    /// ```
+    pub fn can_losslessly_convert_to(&self, target: ScalarType) -> bool {
+        coercion(*self, FieldType::native(target)) == Coercion::Lossless
+    }
 }
 
 impl FromStr for ScalarType {
@@ -140,7 +315,7 @@ impl Display for ScalarType {
 ///
 /// Properties can be either single scalar values or variable-length lists.
 /// Lists store a count followed by that many data elements.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PropertyType {
     /// Single scalar value
     Scalar(ScalarType),
@@ -153,18 +328,194 @@ pub enum PropertyType {
 
 /// Definition of a single property within a PLY element.
 ///
-/// Contains the property name and its type (scalar or list).
+/// Contains the property name and its type (scalar or list). The struct is
+/// `#[non_exhaustive]`, create one with [`PlyProperty::new`].
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct PlyProperty {
     pub name: String,
     pub property_type: PropertyType,
+    // The type as spelled in the source header, see `type_spelling`.
+    pub(crate) type_spelling: Option<String>,
+}
+
+/// Properties are equal if their names and types are, however the types were spelled.
+impl PartialEq for PlyProperty {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.property_type == other.property_type
+    }
+}
+
+impl PlyProperty {
+    /// A property named `name` of type `property_type`.
+    pub fn new(name: impl Into<String>, property_type: PropertyType) -> Self {
+        Self {
+            name: name.into(),
+            property_type,
+            type_spelling: None,
+        }
+    }
+
+    /// The type as spelled in the source header, eg. `int8` or `list uint8 int`. `None`
+    /// for properties that weren't parsed from a file.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::{PlyProperty, PlyReader, PropertyType, ScalarType};
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement point 0\nproperty int8 label\nend_header\n";
+    /// let reader = PlyReader::from_reader(ply_data.as_bytes())?;
+    /// let label = &reader.header().elem_defs[0].properties[0];
+    /// assert_eq!(label.type_spelling(), Some("int8"));
+    /// // The same type however it's spelled.
+    /// assert_eq!(*label, PlyProperty::new("label", PropertyType::Scalar(ScalarType::I8)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn type_spelling(&self) -> Option<&str> {
+        self.type_spelling.as_deref()
+    }
+
+    /// Whether this is a list property.
+    pub fn is_list(&self) -> bool {
+        matches!(self.property_type, PropertyType::List { .. })
+    }
+
+    /// The type of a scalar property, `None` for lists.
+    pub fn scalar_type(&self) -> Option<ScalarType> {
+        match self.property_type {
+            PropertyType::Scalar(t) => Some(t),
+            PropertyType::List { .. } => None,
+        }
+    }
+
+    /// The count and data type of a list property, `None` for scalars.
+    pub fn list_types(&self) -> Option<(ScalarType, ScalarType)> {
+        match self.property_type {
+            PropertyType::Scalar(_) => None,
+            PropertyType::List {
+                count_type,
+                data_type,
+            } => Some((count_type, data_type)),
+        }
+    }
+
+    /// The type of this property as written in a header line.
+    ///
+    /// Uses the source spelling if asked to and it still describes the property type.
+    pub(crate) fn header_type(&self, preserve_spelling: bool) -> String {
+        if preserve_spelling {
+            if let Some(spelling) = self.type_spelling.as_deref() {
+                if parse_property_type(spelling).ok() == Some(self.property_type) {
+                    return spelling.to_string();
+                }
+            }
+        }
+        match self.property_type {
+            PropertyType::Scalar(t) => t.header_name().to_string(),
+            PropertyType::List {
+                count_type,
+                data_type,
+            } => format!("list {count_type} {data_type}"),
+        }
+    }
+}
+
+/// Error for input that doesn't start with the `ply` magic, showing what it starts with.
+fn not_ply_error(start: &[u8]) -> DeserializeError {
+    let start = &start[..start.len().min(32)];
+    let hex: Vec<String> = start.iter().map(|b| format!("{b:02x}")).collect();
+    let text: String = start
+        .iter()
+        .map(|&b| if b.is_ascii_graphic() { b as char } else { '.' })
+        .collect();
+    DeserializeError::header(
+        std::io::ErrorKind::InvalidData,
+        format!(
+            "File must start with 'ply', but starts with {} |{text}|. If the PLY data is \
+             embedded at an offset, seek the reader there first or use PlyReader::from_reader_at",
+            hex.join(" ")
+        ),
+    )
+}
+
+/// Read a header line into `bytes`, including its line break.
+///
+/// Returns `true` when the line is `end_header` directly followed by data, leaving the
+/// reader just past `end_header`. The line is checked as it accumulates rather than in a
+/// single buffer, as `end_header` may be split between two reads of a small buffer.
+fn read_header_line(mut reader: impl BufRead, bytes: &mut Vec<u8>) -> std::io::Result<bool> {
+    const END_HEADER: &[u8] = b"end_header";
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(false);
+        }
+        if bytes == END_HEADER && !buf[0].is_ascii_whitespace() {
+            return Ok(true);
+        }
+        let mut take = memchr::memchr(b'\n', buf).map_or(buf.len(), |i| i + 1);
+        // Stop at the end of a possible `end_header`, so data glued to it stays unread.
+        if bytes.len() < END_HEADER.len() && END_HEADER.starts_with(bytes) {
+            take = take.min(END_HEADER.len() - bytes.len());
+        }
+        bytes.extend_from_slice(&buf[..take]);
+        reader.consume(take);
+        if bytes.ends_with(b"\n") {
+            return Ok(false);
+        }
+    }
+}
+
+/// Parse the type part of a property line, eg. `float` or `list uchar int`.
+fn parse_property_type(spelling: &str) -> Result<PropertyType, DeserializeError> {
+    let parts: Vec<&str> = spelling.split_whitespace().collect();
+    match parts.as_slice() {
+        [t] => Ok(PropertyType::Scalar(ScalarType::parse(t)?)),
+        [list, count_type, data_type] if list.eq_ignore_ascii_case("list") => {
+            Ok(PropertyType::List {
+                count_type: ScalarType::parse(count_type)?,
+                data_type: ScalarType::parse(data_type)?,
+            })
+        }
+        _ => Err(DeserializeError::header(
+            std::io::ErrorKind::InvalidData,
+            format!("Invalid property type: {spelling}"),
+        )),
+    }
+}
+
+/// Error for a property that `element` already declares. A duplicated column would shift
+/// every value after it.
+fn check_new_property(element: &ElementDef, name: &str) -> Result<(), DeserializeError> {
+    if element.has_property(name) {
+        return Err(DeserializeError::duplicate_property(&element.name, name));
+    }
+    Ok(())
+}
+
+impl Display for PlyProperty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.property_type {
+            PropertyType::Scalar(t) => write!(f, "{} {}", self.name, t.rust_name()),
+            PropertyType::List {
+                count_type,
+                data_type,
+            } => write!(
+                f,
+                "{} list<{},{}>",
+                self.name,
+                count_type.rust_name(),
+                data_type.rust_name()
+            ),
+        }
+    }
 }
 
 /// Definition of a PLY element type.
 ///
 /// Elements define the structure of data rows in a PLY file. Common examples
 /// are "vertex" and "face" elements. Each element has a count and list of properties.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ElementDef {
     pub name: String,
     pub count: usize,
@@ -181,51 +532,199 @@ impl ElementDef {
     pub fn has_property(&self, name: &str) -> bool {
         self.get_property(name).is_some()
     }
+
+    /// Size in bytes of a single binary row.
+    ///
+    /// Returns `None` if the element has list properties, as rows then vary in size.
+    pub fn row_byte_size(&self) -> Option<usize> {
+        self.properties
+            .iter()
+            .map(|p| match p.property_type {
+                PropertyType::Scalar(t) => Some(t.size_bytes()),
+                PropertyType::List { .. } => None,
+            })
+            .sum()
+    }
+}
+
+impl Display for ElementDef {
+    /// Formats as eg. `vertex (8 rows): x f32, y f32, z f32`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} rows):", self.name, self.count)?;
+        for (i, prop) in self.properties.iter().enumerate() {
+            let sep = if i == 0 { " " } else { ", " };
+            write!(f, "{sep}{prop}")?;
+        }
+        Ok(())
+    }
 }
 
 /// PLY file header containing format, elements, and metadata.
 ///
 /// The header defines the structure of the entire PLY file including
-/// data format, element definitions, and optional comments.
+/// data format, element definitions, and optional comments. The struct is
+/// `#[non_exhaustive]`, create one with [`PlyHeader::new`].
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct PlyHeader {
     pub format: PlyFormat,
     pub elem_defs: Vec<ElementDef>,
     pub comments: Vec<String>,
+    // Bytes of each comment line as found in the file, see `comments_raw`.
+    pub(crate) comments_raw: Vec<Vec<u8>>,
     pub obj_info: Vec<String>,
 }
 
+/// Headers are equal if their contents are, whether or not they were parsed from a file.
+/// The raw comment bytes are left out, like the type spellings of [`PlyProperty`].
+impl PartialEq for PlyHeader {
+    fn eq(&self, other: &Self) -> bool {
+        self.format == other.format
+            && self.elem_defs == other.elem_defs
+            && self.comments == other.comments
+            && self.obj_info == other.obj_info
+    }
+}
+
+impl Display for PlyHeader {
+    /// Multi-line summary with the format, one line per element, and the comment count.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.format)?;
+        for elem in &self.elem_defs {
+            writeln!(f, "{elem}")?;
+        }
+        write!(f, "{} comments", self.comments.len())
+    }
+}
+
 impl PlyHeader {
-    pub(crate) fn parse<R: BufRead>(mut reader: R) -> Result<Self, DeserializeError> {
-        let mut line = String::new();
-        reader.read_line(&mut line)?;
-        if line.trim() != "ply" {
-            return Err(DeserializeError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "File must start with 'ply'",
-            )));
+    /// A header with `elem_defs` in `format`, without comments or obj_info.
+    pub fn new(format: PlyFormat, elem_defs: Vec<ElementDef>) -> Self {
+        Self {
+            format,
+            elem_defs,
+            comments: Vec::new(),
+            comments_raw: Vec::new(),
+            obj_info: Vec::new(),
+        }
+    }
+
+    /// The bytes of each comment line after `comment`, as found in the file.
+    ///
+    /// Comments of legacy files may not be UTF-8 (eg. Windows-1252). Those are decoded
+    /// lossily in [`Self::comments`], and kept unchanged here. Empty for headers that
+    /// weren't parsed from a file. Writing the header keeps these bytes for comments that
+    /// still match [`Self::comments`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::PlyReader;
+    ///
+    /// let ply_data = b"ply\nformat ascii 1.0\ncomment Caf\xe9\nelement vertex 0\nproperty float x\nend_header\n";
+    /// let reader = PlyReader::from_reader(&ply_data[..])?;
+    /// assert_eq!(reader.header().comments, ["Caf\u{fffd}"]);
+    /// assert_eq!(reader.header().comments_raw(), [b"Caf\xe9"]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn comments_raw(&self) -> &[Vec<u8>] {
+        &self.comments_raw
+    }
+
+    /// Single line summary of the header, for log lines.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::PlyReader;
+    /// use std::io::Cursor;
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement vertex 0\nproperty float x\nend_header\n";
+    /// let reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    /// assert_eq!(reader.header().summary(), "ascii, vertex (0 rows), 0 comments");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn summary(&self) -> String {
+        let mut summary = self.format.to_string();
+        for elem in &self.elem_defs {
+            summary += &format!(", {} ({} rows)", elem.name, elem.count);
+        }
+        summary += &format!(", {} comments", self.comments.len());
+        summary
+    }
+
+    pub(crate) fn parse<R: BufRead>(reader: R) -> Result<Self, DeserializeError> {
+        Self::parse_with(reader, false)
+    }
+
+    /// Parse the header, where `whole_input` tells whether the reader holds the rest of
+    /// the file rather than just what arrived so far.
+    ///
+    /// Some writers omit the line break after `end_header`. With the whole input this is
+    /// accepted before binary data and at the end of the file, in both cases the data
+    /// starts right after `end_header`. A partial input could still receive the line
+    /// break, and ASCII data glued to `end_header` is more likely a corrupt file, so
+    /// otherwise it's an error.
+    pub(crate) fn parse_with<R: BufRead>(
+        mut reader: R,
+        whole_input: bool,
+    ) -> Result<Self, DeserializeError> {
+        // Check the magic before looking for a line break, which might be far into data
+        // that isn't PLY at all.
+        let start = reader.fill_buf()?;
+        if start.is_empty() {
+            return Err(DeserializeError::empty_input());
+        }
+        if !start.iter().zip(b"ply").all(|(a, b)| a == b) {
+            return Err(not_ply_error(start));
+        }
+        let mut line = Vec::new();
+        reader.read_until(b'\n', &mut line)?;
+        if !line.ends_with(b"\n") {
+            return Err(DeserializeError::header(
+                std::io::ErrorKind::UnexpectedEof,
+                "Unexpected end of file",
+            ));
+        }
+        if line.trim_ascii() != b"ply" {
+            return Err(not_ply_error(&line));
         }
 
         let mut format = None;
         let mut elements = Vec::new();
         let mut comments = Vec::new();
+        let mut comments_raw = Vec::new();
         let mut obj_info = Vec::new();
         let mut current_element: Option<ElementDef> = None;
 
         loop {
-            let mut line = String::new();
-            let bytes_read = reader.read_line(&mut line)?;
-            if bytes_read == 0 {
-                return Err(DeserializeError(std::io::Error::new(
+            // Data glued to `end_header` isn't consumed, so the reader is left at the
+            // start of the data.
+            let mut bytes = Vec::new();
+            if read_header_line(&mut reader, &mut bytes)? {
+                if whole_input && format.is_some_and(|f: PlyFormat| f.is_binary()) {
+                    break;
+                }
+                return Err(DeserializeError::header(
+                    std::io::ErrorKind::InvalidData,
+                    "end_header must be followed by a line break, but is followed by data",
+                ));
+            }
+            // Comments might not be UTF-8, only they keep their raw bytes.
+            let line = String::from_utf8_lossy(&bytes);
+            // A line without a newline ends at EOF. It might be cut off, so only
+            // interpret it once the rest of it is available.
+            if !line.ends_with('\n') {
+                if whole_input && line.trim() == "end_header" {
+                    break;
+                }
+                return Err(DeserializeError::header(
                     std::io::ErrorKind::UnexpectedEof,
                     "Unexpected end of file",
-                )));
+                ));
             }
 
             // We have reached the end of the header. Don't really care
-            // about whitespace here, BUT we do have to be sure that the newline IS present.
-            // If its not, the line ends at EOF, and the next chunk of data could then incorrectly contain this newline.
-            if line.trim() == "end_header" && line.ends_with("\n") {
+            // about whitespace here.
+            if line.trim() == "end_header" {
                 break;
             }
             let parts: Vec<&str> = line.split_whitespace().collect();
@@ -235,35 +734,37 @@ impl PlyHeader {
             match parts[0] {
                 "format" => {
                     if parts.len() < 3 {
-                        return Err(DeserializeError(std::io::Error::new(
+                        return Err(DeserializeError::header(
                             std::io::ErrorKind::InvalidData,
                             "Invalid format line",
-                        )));
+                        ));
                     }
                     format = Some(match parts[1] {
                         "ascii" => PlyFormat::Ascii,
                         "binary_little_endian" => PlyFormat::BinaryLittleEndian,
                         "binary_big_endian" => PlyFormat::BinaryBigEndian,
                         _ => {
-                            return Err(DeserializeError(std::io::Error::new(
+                            return Err(DeserializeError::header(
                                 std::io::ErrorKind::InvalidData,
                                 format!("Unknown format: {}", parts[1]),
-                            )))
+                            ))
                         }
                     });
                 }
                 "comment" => {
                     comments.push(parts[1..].join(" "));
+                    let raw = bytes.trim_ascii();
+                    comments_raw.push(raw[b"comment".len()..].trim_ascii_start().to_vec());
                 }
                 "obj_info" => {
                     obj_info.push(parts[1..].join(" "));
                 }
                 "element" => {
                     if parts.len() < 3 {
-                        return Err(DeserializeError(std::io::Error::new(
+                        return Err(DeserializeError::header(
                             std::io::ErrorKind::InvalidData,
                             "Invalid element line",
-                        )));
+                        ));
                     }
 
                     if let Some(element) = current_element.take() {
@@ -272,10 +773,10 @@ impl PlyHeader {
 
                     let name = parts[1].to_string();
                     let count = parts[2].parse::<usize>().map_err(|_| {
-                        DeserializeError(std::io::Error::new(
+                        DeserializeError::header(
                             std::io::ErrorKind::InvalidData,
                             format!("Invalid element count: {}", parts[2]),
-                        ))
+                        )
                     })?;
 
                     current_element = Some(ElementDef {
@@ -286,30 +787,55 @@ impl PlyHeader {
                 }
                 "property" => {
                     let element = current_element.as_mut().ok_or_else(|| {
-                        DeserializeError(std::io::Error::new(
+                        DeserializeError::header(
                             std::io::ErrorKind::InvalidData,
                             "Property without element",
-                        ))
+                        )
                     })?;
 
                     if parts.len() < 3 {
-                        return Err(DeserializeError(std::io::Error::new(
+                        return Err(DeserializeError::header(
                             std::io::ErrorKind::InvalidData,
                             "Invalid property line",
-                        )));
+                        ));
                     }
 
-                    if parts[1] == "list" {
+                    if parts[1].eq_ignore_ascii_case("list") {
                         // List property: property list <count_type> <data_type> <name>
+                        if parts.len() == 4 && ScalarType::parse(parts[3]).is_err() {
+                            // The name took the place of a missing type.
+                            return Err(DeserializeError::header(
+                                std::io::ErrorKind::InvalidData,
+                                format!(
+                                    "missing data type in list property declaration? '{}' has no type for the list entries, expected 'property list {} <data type> {}'",
+                                    line.trim(),
+                                    parts[2],
+                                    parts[3]
+                                ),
+                            ));
+                        }
                         if parts.len() < 5 {
-                            return Err(DeserializeError(std::io::Error::new(
+                            return Err(DeserializeError::header(
                                 std::io::ErrorKind::InvalidData,
                                 "Invalid list property line",
-                            )));
+                            ));
                         }
                         let count_type = ScalarType::parse(parts[2])?;
+                        if !count_type.is_integer() {
+                            return Err(DeserializeError::header(
+                                std::io::ErrorKind::InvalidData,
+                                format!(
+                                    "Invalid list count type in '{}', list counts must be integers. \
+                                     Files written with a float count type by earlier versions of \
+                                     serde-ply can be read by declaring 'uint' counts instead and \
+                                     using PlyReader::from_parts, if they're ASCII",
+                                    line.trim()
+                                ),
+                            ));
+                        }
                         let data_type = ScalarType::parse(parts[3])?;
                         let name = parts[4].to_string();
+                        check_new_property(element, &name)?;
 
                         element.properties.push(PlyProperty {
                             property_type: PropertyType::List {
@@ -317,14 +843,29 @@ impl PlyHeader {
                                 data_type,
                             },
                             name,
+                            type_spelling: Some(parts[1..4].join(" ")),
                         });
                     } else {
-                        let data_type = ScalarType::parse(parts[1])?;
+                        let data_type = ScalarType::parse(parts[1]).map_err(|e| {
+                            if ScalarType::parse(parts[2]).is_ok() {
+                                DeserializeError::header(
+                                    std::io::ErrorKind::InvalidData,
+                                    format!(
+                                        "Unknown scalar type: {} (property type and name may be swapped, expected 'property {} {}')",
+                                        parts[1], parts[2], parts[1]
+                                    ),
+                                )
+                            } else {
+                                e
+                            }
+                        })?;
                         let name = parts[2].to_string();
+                        check_new_property(element, &name)?;
 
                         element.properties.push(PlyProperty {
                             property_type: PropertyType::Scalar(data_type),
                             name,
+                            type_spelling: Some(parts[1].to_string()),
                         });
                     }
                 }
@@ -334,21 +875,20 @@ impl PlyHeader {
         if let Some(element) = current_element {
             elements.push(element);
         }
-        let format = format.ok_or_else(|| {
-            DeserializeError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Missing format specification",
-            ))
-        })?;
+        let format = format.ok_or_else(DeserializeError::missing_format)?;
         Ok(PlyHeader {
             format,
             elem_defs: elements,
             comments,
+            comments_raw,
             obj_info,
         })
     }
 
     /// Find an element definition by name.
+    ///
+    /// If several elements share the name this is the first one. Read the others by
+    /// position with [`PlyReader::nth_element`].
     pub fn get_element(&self, name: &str) -> Option<ElementDef> {
         self.elem_defs.iter().find(|e| e.name == name).cloned()
     }
@@ -357,8 +897,61 @@ impl PlyHeader {
     pub fn has_element(&self, name: &str) -> bool {
         self.elem_defs.iter().any(|e| e.name == name)
     }
+
+    /// Absolute byte ranges of each element's data in the file.
+    ///
+    /// `header_len` is the size of the header in bytes, as returned by [`read_header_prefix`].
+    /// Ranges can only be computed for binary files, and only up to the first element with
+    /// list properties, as the size of list data isn't known without reading it. All other
+    /// entries are `None`. Useful to fetch only the element you need with range requests.
+    ///
+    /// # Example
+    /// ```rust
+    /// let prefix = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nproperty float x\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n";
+    /// let (header, header_len) = serde_ply::read_header_prefix(prefix)?;
+    /// let ranges = header.element_byte_ranges(header_len);
+    /// assert_eq!(ranges[0], Some(header_len..header_len + 8));
+    /// assert_eq!(ranges[1], None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn element_byte_ranges(&self, header_len: u64) -> Vec<Option<Range<u64>>> {
+        let mut offset = self.format.is_binary().then_some(header_len);
+        self.elem_defs
+            .iter()
+            .map(|elem| {
+                let start = offset?;
+                let Some(row_size) = elem.row_byte_size() else {
+                    offset = None;
+                    return None;
+                };
+                let end = start + row_size as u64 * elem.count as u64;
+                offset = Some(end);
+                Some(start..end)
+            })
+            .collect()
+    }
 }
 
+/// Wrapper to serialize PLY lists with `u8` count type.
+///
+/// `u8` is already the default count type, but this wrapper keeps it even when
+/// [`SerializeOptions::default_list_count_type`] picks another one, and gives all three
+/// count widths a wrapper of the same shape.
+///
+/// # Example
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+/// use serde_ply::ListCountU8;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Face {
+///     // This list can have up to 255 vertices
+///     vertex_indices: ListCountU8<Vec<u32>>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ListCountU8<T>(pub T);
+
 /// Wrapper to serialize PLY lists with `u16` count type.
 ///
 /// By default, PLY lists use `u8` for the element count. Use this wrapper
@@ -443,5 +1036,83 @@ macro_rules! impl_list_count_traits {
     };
 }
 
+impl_list_count_traits!(ListCountU8);
 impl_list_count_traits!(ListCountU16);
 impl_list_count_traits!(ListCountU32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_TYPES: [ScalarType; 8] = [
+        ScalarType::I8,
+        ScalarType::U8,
+        ScalarType::I16,
+        ScalarType::U16,
+        ScalarType::I32,
+        ScalarType::U32,
+        ScalarType::F32,
+        ScalarType::F64,
+    ];
+
+    #[test]
+    fn test_promotion_matrix() {
+        // Rows are the source type, columns the target type, both in ALL_TYPES order.
+        #[rustfmt::skip]
+        let expected = [
+            //  i8     u8     i16    u16    i32    u32    f32    f64
+            [true,  false, true,  false, true,  false, true,  true ], // i8
+            [false, true,  true,  true,  true,  true,  true,  true ], // u8
+            [false, false, true,  false, true,  false, true,  true ], // i16
+            [false, false, false, true,  true,  true,  true,  true ], // u16
+            [false, false, false, false, true,  false, false, true ], // i32
+            [false, false, false, false, false, true,  false, true ], // u32
+            [false, false, false, false, false, false, true,  true ], // f32
+            [false, false, false, false, false, false, false, true ], // f64
+        ];
+
+        for (from, row) in ALL_TYPES.iter().zip(expected) {
+            for (to, lossless) in ALL_TYPES.iter().zip(row) {
+                assert_eq!(
+                    from.can_losslessly_convert_to(*to),
+                    lossless,
+                    "{from} -> {to}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_byte_order() {
+        assert!(!PlyFormat::Ascii.is_binary());
+        assert_eq!(PlyFormat::Ascii.byte_order(), None);
+        assert!(PlyFormat::BinaryLittleEndian.is_binary());
+        assert_eq!(
+            PlyFormat::BinaryLittleEndian.byte_order(),
+            Some(ByteOrderKind::Little)
+        );
+        assert!(PlyFormat::BinaryBigEndian.is_binary());
+        assert_eq!(
+            PlyFormat::BinaryBigEndian.byte_order(),
+            Some(ByteOrderKind::Big)
+        );
+    }
+
+    #[test]
+    fn test_property_type_accessors() {
+        let prop = |property_type| PlyProperty::new("p", property_type);
+
+        let scalar = prop(PropertyType::Scalar(ScalarType::F32));
+        assert!(!scalar.is_list());
+        assert_eq!(scalar.scalar_type(), Some(ScalarType::F32));
+        assert_eq!(scalar.list_types(), None);
+
+        let list = prop(PropertyType::List {
+            count_type: ScalarType::U8,
+            data_type: ScalarType::I32,
+        });
+        assert!(list.is_list());
+        assert_eq!(list.scalar_type(), None);
+        assert_eq!(list.list_types(), Some((ScalarType::U8, ScalarType::I32)));
+    }
+}
@@ -0,0 +1,150 @@
+//! Typed helpers for common mesh elements.
+//!
+//! The PLY spec describes an `edge` element with `int vertex1` and `int vertex2` indices
+//! into the `vertex` element, optionally followed by `uchar red`, `green` and `blue`.
+//! [`Edge`] reads and writes that layout, and [`read_edges`] also checks the indices.
+//!
+//! # Example
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use serde_ply::mesh::{edge_element_def, read_edges, Edge};
+//! use serde_ply::{PlyReader, SerializeOptions};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Vertex { x: f32 }
+//!
+//! #[derive(Serialize)]
+//! struct Wireframe { vertex: Vec<Vertex>, edge: Vec<Edge> }
+//!
+//! let wireframe = Wireframe {
+//!     vertex: vec![Vertex { x: 0.0 }, Vertex { x: 1.0 }],
+//!     edge: vec![Edge { v1: 0, v2: 1, color: None }],
+//! };
+//! let options = SerializeOptions::ascii().with_element_def(edge_element_def(1, false));
+//! let text = serde_ply::to_string(&wireframe, options)?;
+//! assert!(text.contains("property int vertex1\nproperty int vertex2\n"));
+//!
+//! let mut reader = PlyReader::from_reader(text.as_bytes())?;
+//! let _vertices: Vec<Vertex> = reader.next_element()?;
+//! let edges = read_edges(&mut reader)?;
+//! assert_eq!(edges[0].v2, 1);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::io::BufRead;
+
+use serde::{
+    de::Error as _,
+    ser::{Error as _, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{DeserializeError, ElementDef, PlyProperty, PlyReader, PropertyType, ScalarType};
+
+/// An edge between two vertices, with an optional color.
+///
+/// Reads from `vertex1` and `vertex2` properties of any integer type. The color is read
+/// if the element has `red`, `green` and `blue` properties. Writes the properties the spec
+/// uses, see [`edge_element_def`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    /// Index of the first vertex.
+    pub v1: u32,
+    /// Index of the second vertex.
+    pub v2: u32,
+    /// Color of the edge as red, green and blue.
+    pub color: Option<[u8; 3]>,
+}
+
+#[derive(Deserialize)]
+struct EdgeRow {
+    vertex1: u32,
+    vertex2: u32,
+    red: Option<u8>,
+    green: Option<u8>,
+    blue: Option<u8>,
+}
+
+impl<'de> Deserialize<'de> for Edge {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let row = EdgeRow::deserialize(deserializer)?;
+        let color = match (row.red, row.green, row.blue) {
+            (Some(r), Some(g), Some(b)) => Some([r, g, b]),
+            (None, None, None) => None,
+            _ => {
+                return Err(D::Error::custom(
+                    "edge color needs all of red, green and blue",
+                ))
+            }
+        };
+        Ok(Edge {
+            v1: row.vertex1,
+            v2: row.vertex2,
+            color,
+        })
+    }
+}
+
+impl Serialize for Edge {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // The spec stores indices as int.
+        let index = |v: u32| {
+            i32::try_from(v).map_err(|_| S::Error::custom(format!("edge index {v} is too large")))
+        };
+        let len = if self.color.is_some() { 5 } else { 2 };
+        let mut row = serializer.serialize_struct("Edge", len)?;
+        row.serialize_field("vertex1", &index(self.v1)?)?;
+        row.serialize_field("vertex2", &index(self.v2)?)?;
+        if let Some([red, green, blue]) = self.color {
+            row.serialize_field("red", &red)?;
+            row.serialize_field("green", &green)?;
+            row.serialize_field("blue", &blue)?;
+        }
+        row.end()
+    }
+}
+
+/// Definition of an `edge` element with `count` rows, as laid out in the spec.
+///
+/// Pass to [`crate::SerializeOptions::with_element_def`] to write [`Edge`]s. All edges
+/// must have a color if `with_color` is set, and none otherwise.
+pub fn edge_element_def(count: usize, with_color: bool) -> ElementDef {
+    let prop = |name: &str, ty| PlyProperty::new(name, PropertyType::Scalar(ty));
+    let mut properties = vec![
+        prop("vertex1", ScalarType::I32),
+        prop("vertex2", ScalarType::I32),
+    ];
+    if with_color {
+        properties.extend(["red", "green", "blue"].map(|name| prop(name, ScalarType::U8)));
+    }
+    ElementDef {
+        name: "edge".to_string(),
+        count,
+        properties,
+    }
+}
+
+/// Read the next element, which must be `edge`, and check that every index refers to a
+/// vertex.
+///
+/// The number of vertices is taken from the header, so the `vertex` element can come
+/// before or after the edges.
+pub fn read_edges<R: BufRead>(reader: &mut PlyReader<R>) -> Result<Vec<Edge>, DeserializeError> {
+    let vertex_count = reader
+        .header()
+        .elem_defs
+        .iter()
+        .find(|e| e.name == "vertex")
+        .map_or(0, |e| e.count);
+    let edges: Vec<Edge> = reader.next_element_named("edge")?;
+    for (i, edge) in edges.iter().enumerate() {
+        for v in [edge.v1, edge.v2] {
+            if v as usize >= vertex_count {
+                return Err(DeserializeError::custom(format!(
+                    "edge {i} references vertex {v}, but there are only {vertex_count} vertices"
+                )));
+            }
+        }
+    }
+    Ok(edges)
+}
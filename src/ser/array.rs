@@ -0,0 +1,93 @@
+//! Writing an `ndarray` matrix as a PLY element.
+
+use std::io::Write;
+
+use ndarray::{Array2, ArrayView1};
+use serde::{
+    ser::{Error as _, SerializeMap, SerializeSeq},
+    Serialize, Serializer,
+};
+
+use crate::{ser::to_writer, SerializeError, SerializeOptions};
+
+/// Write a `(rows, properties)` matrix as a PLY file with a single element.
+///
+/// Each column is written as a `float` property named by `property_names`, and each row
+/// of the matrix as a row of the element called `element_name`. This is the inverse of
+/// [`crate::PlyReader::element_array2`].
+///
+/// # Example
+/// ```rust
+/// use serde_ply::{to_writer_array2, SerializeOptions};
+///
+/// let positions = ndarray::array![[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]];
+/// let mut buffer = Vec::new();
+/// to_writer_array2(&mut buffer, SerializeOptions::ascii(), "vertex", &["x", "y", "z"], &positions)?;
+/// assert!(String::from_utf8(buffer)?.ends_with("property float z\nend_header\n0 1 2\n3 4 5\n"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn to_writer_array2(
+    writer: impl Write,
+    options: SerializeOptions,
+    element_name: &str,
+    property_names: &[&str],
+    array: &Array2<f32>,
+) -> Result<(), SerializeError> {
+    if property_names.len() != array.ncols() {
+        return Err(SerializeError::custom(format!(
+            "{} property names given for element '{element_name}', but the array has {} columns",
+            property_names.len(),
+            array.ncols()
+        )));
+    }
+    let element = ArrayElement {
+        name: element_name,
+        properties: property_names,
+        array,
+    };
+    to_writer(&element, options, writer)
+}
+
+struct ArrayElement<'a> {
+    name: &'a str,
+    properties: &'a [&'a str],
+    array: &'a Array2<f32>,
+}
+
+impl Serialize for ArrayElement<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(self.name, &ArrayRows(self))?;
+        map.end()
+    }
+}
+
+struct ArrayRows<'a>(&'a ArrayElement<'a>);
+
+impl Serialize for ArrayRows<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.array.nrows()))?;
+        for row in self.0.array.rows() {
+            seq.serialize_element(&ArrayRow {
+                properties: self.0.properties,
+                row,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+struct ArrayRow<'a> {
+    properties: &'a [&'a str],
+    row: ArrayView1<'a, f32>,
+}
+
+impl Serialize for ArrayRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.properties.len()))?;
+        for (name, value) in self.properties.iter().zip(self.row.iter()) {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
@@ -0,0 +1,683 @@
+//! Writing an element from a struct of columns.
+
+use serde::{
+    ser::{Error as _, Impossible, SerializeSeq, SerializeStruct},
+    Serialize, Serializer,
+};
+
+use crate::SerializeError;
+
+/// Serialize a struct of `Vec`s as the rows of an element.
+///
+/// Each field of `T` is a column, and becomes a scalar property with the field's name and
+/// element type. Row `i` of the element holds the `i`-th value of every column, so data
+/// kept as columns can be written without transposing it into a `Vec` of row structs
+/// first. All columns must have the same length.
+///
+/// # Example
+/// ```rust
+/// use serde::Serialize;
+/// use serde_ply::{to_string, Columns, SerializeOptions};
+///
+/// #[derive(Serialize)]
+/// struct Cols { x: Vec<f32>, red: Vec<u8> }
+///
+/// #[derive(Serialize)]
+/// struct Ply<'a> { vertex: Columns<&'a Cols> }
+///
+/// let cols = Cols { x: vec![0.5, 1.5], red: vec![255, 0] };
+/// let ply = to_string(&Ply { vertex: Columns(&cols) }, SerializeOptions::ascii())?;
+/// assert!(ply.ends_with("property float x\nproperty uchar red\nend_header\n0.5 255\n1.5 0\n"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct Columns<T>(pub T);
+
+impl<T: Serialize> Serialize for Columns<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let columns = collect_columns(&self.0).map_err(S::Error::custom)?;
+        let len = columns.first().map_or(0, |(_, column)| column_len(column));
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        for index in 0..len {
+            seq.serialize_element(&ColumnRow {
+                columns: &columns,
+                index,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+/// The values of a column. `None` for an empty column, which has no type to go by.
+type Column = Option<ColumnData>;
+
+enum ColumnData {
+    I8(Vec<i8>),
+    U8(Vec<u8>),
+    I16(Vec<i16>),
+    U16(Vec<u16>),
+    I32(Vec<i32>),
+    U32(Vec<u32>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+}
+
+fn column_len(column: &Column) -> usize {
+    match column {
+        None => 0,
+        Some(ColumnData::I8(v)) => v.len(),
+        Some(ColumnData::U8(v)) => v.len(),
+        Some(ColumnData::I16(v)) => v.len(),
+        Some(ColumnData::U16(v)) => v.len(),
+        Some(ColumnData::I32(v)) => v.len(),
+        Some(ColumnData::U32(v)) => v.len(),
+        Some(ColumnData::F32(v)) => v.len(),
+        Some(ColumnData::F64(v)) => v.len(),
+    }
+}
+
+/// Collect the columns of `value`, and check that they all have the same length.
+fn collect_columns<T: Serialize>(value: &T) -> Result<Vec<(&'static str, Column)>, SerializeError> {
+    let mut columns = Vec::new();
+    value.serialize(ColumnsCollector(&mut columns))?;
+    if let Some((first, first_column)) = columns.first() {
+        for (name, column) in &columns[1..] {
+            if column_len(column) != column_len(first_column) {
+                return Err(SerializeError::custom(format!(
+                    "column '{name}' has {} values, but column '{first}' has {}",
+                    column_len(column),
+                    column_len(first_column)
+                )));
+            }
+        }
+    }
+    Ok(columns)
+}
+
+struct ColumnRow<'a> {
+    columns: &'a [(&'static str, Column)],
+    index: usize,
+}
+
+impl Serialize for ColumnRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut row = serializer.serialize_struct("Columns", self.columns.len())?;
+        for (name, column) in self.columns {
+            row.serialize_field(name, &ColumnValue(column, self.index))?;
+        }
+        row.end()
+    }
+}
+
+struct ColumnValue<'a>(&'a Column, usize);
+
+impl Serialize for ColumnValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let i = self.1;
+        match self.0 {
+            Some(ColumnData::I8(v)) => serializer.serialize_i8(v[i]),
+            Some(ColumnData::U8(v)) => serializer.serialize_u8(v[i]),
+            Some(ColumnData::I16(v)) => serializer.serialize_i16(v[i]),
+            Some(ColumnData::U16(v)) => serializer.serialize_u16(v[i]),
+            Some(ColumnData::I32(v)) => serializer.serialize_i32(v[i]),
+            Some(ColumnData::U32(v)) => serializer.serialize_u32(v[i]),
+            Some(ColumnData::F32(v)) => serializer.serialize_f32(v[i]),
+            Some(ColumnData::F64(v)) => serializer.serialize_f64(v[i]),
+            // Columns all have the same length, so an empty column is never indexed.
+            None => unreachable!("row of an empty column"),
+        }
+    }
+}
+
+fn not_columns() -> SerializeError {
+    SerializeError::custom("Columns must wrap a struct whose fields are sequences of scalars")
+}
+
+/// Collects the fields of a struct as columns.
+struct ColumnsCollector<'a>(&'a mut Vec<(&'static str, Column)>);
+
+impl SerializeStruct for ColumnsCollector<'_> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        let mut column = None;
+        value.serialize(ColumnCollector {
+            name: key,
+            column: &mut column,
+        })?;
+        self.0.push((key, column));
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl Serializer for ColumnsCollector<'_> {
+    type Ok = ();
+    type Error = SerializeError;
+    type SerializeSeq = Impossible<(), SerializeError>;
+    type SerializeTuple = Impossible<(), SerializeError>;
+    type SerializeTupleStruct = Impossible<(), SerializeError>;
+    type SerializeTupleVariant = Impossible<(), SerializeError>;
+    type SerializeMap = Impossible<(), SerializeError>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), SerializeError>;
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_char(self, _v: char) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(not_columns())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(not_columns())
+    }
+}
+
+/// Collects the values of a single column.
+struct ColumnCollector<'a> {
+    name: &'static str,
+    column: &'a mut Column,
+}
+
+impl ColumnCollector<'_> {
+    fn not_a_column(&self) -> SerializeError {
+        SerializeError::custom(format!(
+            "column '{}' must be a sequence of scalars",
+            self.name
+        ))
+    }
+}
+
+impl SerializeSeq for ColumnCollector<'_> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(ValueCollector {
+            name: self.name,
+            column: self.column,
+        })
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl Serializer for ColumnCollector<'_> {
+    type Ok = ();
+    type Error = SerializeError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Impossible<(), SerializeError>;
+    type SerializeTupleStruct = Impossible<(), SerializeError>;
+    type SerializeTupleVariant = Impossible<(), SerializeError>;
+    type SerializeMap = Impossible<(), SerializeError>;
+    type SerializeStruct = Impossible<(), SerializeError>;
+    type SerializeStructVariant = Impossible<(), SerializeError>;
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_char(self, _v: char) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Self::Error> {
+        // Eg. a `serde_bytes` column of colors.
+        *self.column = Some(ColumnData::U8(v.to_vec()));
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(self.not_a_column())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(self.not_a_column())
+    }
+}
+
+/// Appends a single value to a column, the first value decides the column's type.
+struct ValueCollector<'a> {
+    name: &'static str,
+    column: &'a mut Column,
+}
+
+macro_rules! push_value {
+    ($method:ident, $ty:ty, $variant:ident) => {
+        fn $method(self, v: $ty) -> Result<(), Self::Error> {
+            match self.column.get_or_insert(ColumnData::$variant(Vec::new())) {
+                ColumnData::$variant(values) => {
+                    values.push(v);
+                    Ok(())
+                }
+                _ => Err(self.mixed_types()),
+            }
+        }
+    };
+}
+
+impl ValueCollector<'_> {
+    fn mixed_types(&self) -> SerializeError {
+        SerializeError::custom(format!(
+            "column '{}' holds values of different types",
+            self.name
+        ))
+    }
+
+    fn not_a_scalar(&self, found: &str) -> SerializeError {
+        SerializeError::custom(format!(
+            "column '{}' holds {found}, but columns must hold scalars",
+            self.name
+        ))
+    }
+}
+
+impl Serializer for ValueCollector<'_> {
+    type Ok = ();
+    type Error = SerializeError;
+    type SerializeSeq = Impossible<(), SerializeError>;
+    type SerializeTuple = Impossible<(), SerializeError>;
+    type SerializeTupleStruct = Impossible<(), SerializeError>;
+    type SerializeTupleVariant = Impossible<(), SerializeError>;
+    type SerializeMap = Impossible<(), SerializeError>;
+    type SerializeStruct = Impossible<(), SerializeError>;
+    type SerializeStructVariant = Impossible<(), SerializeError>;
+
+    push_value!(serialize_i8, i8, I8);
+    push_value!(serialize_u8, u8, U8);
+    push_value!(serialize_i16, i16, I16);
+    push_value!(serialize_u16, u16, U16);
+    push_value!(serialize_i32, i32, I32);
+    push_value!(serialize_u32, u32, U32);
+    push_value!(serialize_f32, f32, F32);
+    push_value!(serialize_f64, f64, F64);
+
+    fn serialize_bool(self, v: bool) -> Result<(), Self::Error> {
+        // Bools are written as uchar 0 or 1.
+        self.serialize_u8(v as u8)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<(), Self::Error> {
+        Err(SerializeError::custom("i64 properties are not supported"))
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<(), Self::Error> {
+        Err(SerializeError::custom("u64 properties are not supported"))
+    }
+
+    fn serialize_char(self, _v: char) -> Result<(), Self::Error> {
+        Err(self.not_a_scalar("chars"))
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<(), Self::Error> {
+        Err(self.not_a_scalar("strings"))
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), Self::Error> {
+        Err(self.not_a_scalar("bytes"))
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        Err(self.not_a_scalar("options"))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<(), Self::Error> {
+        Err(self.not_a_scalar("options"))
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        Err(self.not_a_scalar("units"))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Self::Error> {
+        Err(self.not_a_scalar("unit structs"))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Self::Error> {
+        Err(self.not_a_scalar("enum variants"))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Self::Error> {
+        Err(self.not_a_scalar("enum variants"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(self.not_a_scalar("sequences"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(self.not_a_scalar("tuples"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(self.not_a_scalar("tuple structs"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(self.not_a_scalar("enum variants"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(self.not_a_scalar("maps"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(self.not_a_scalar("structs"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(self.not_a_scalar("enum variants"))
+    }
+}
@@ -0,0 +1,221 @@
+//! Serializing a sequence of `(name, rows)` pairs as a map of elements.
+
+use serde::{
+    ser::{Impossible, SerializeMap, SerializeSeq, SerializeTuple},
+    Serialize, Serializer,
+};
+
+use crate::SerializeError;
+
+/// Sequence of `(key, value)` tuples, each written as an entry of the wrapped map.
+///
+/// Keeps the order of the sequence, eg. for `Vec<(String, Vec<Row>)>` elements.
+pub struct EntrySeq<M>(pub(crate) M);
+
+impl<M: SerializeMap<Ok = (), Error = SerializeError>> SerializeSeq for EntrySeq<M> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(EntrySerializer(&mut self.0))
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.0.end()
+    }
+}
+
+struct EntrySerializer<'a, M>(&'a mut M);
+
+fn not_an_entry() -> SerializeError {
+    serde::ser::Error::custom("elements in a sequence must be (name, rows) tuples")
+}
+
+struct EntryTuple<'a, M> {
+    map: &'a mut M,
+    index: usize,
+}
+
+impl<M: SerializeMap<Ok = (), Error = SerializeError>> SerializeTuple for EntryTuple<'_, M> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.index += 1;
+        match self.index {
+            1 => self.map.serialize_key(value),
+            2 => self.map.serialize_value(value),
+            _ => Err(not_an_entry()),
+        }
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, M: SerializeMap<Ok = (), Error = SerializeError>> Serializer for EntrySerializer<'a, M> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    type SerializeSeq = Impossible<(), SerializeError>;
+    type SerializeTuple = EntryTuple<'a, M>;
+    type SerializeTupleStruct = Impossible<(), SerializeError>;
+    type SerializeTupleVariant = Impossible<(), SerializeError>;
+    type SerializeMap = Impossible<(), SerializeError>;
+    type SerializeStruct = Impossible<(), SerializeError>;
+    type SerializeStructVariant = Impossible<(), SerializeError>;
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        if len != 2 {
+            return Err(not_an_entry());
+        }
+        Ok(EntryTuple {
+            map: self.0,
+            index: 0,
+        })
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_char(self, _v: char) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(not_an_entry())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(not_an_entry())
+    }
+}
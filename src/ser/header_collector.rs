@@ -1,9 +1,12 @@
-use crate::{ser::SerializeOptions, ScalarType, SerializeError};
+use crate::{
+    ser::{entries::EntrySeq, list_count_type, MetaOrder, SerializeOptions},
+    PlyProperty, PropertyType, ScalarType, SerializeError,
+};
 use serde::{
-    ser::{Error, Impossible, SerializeMap, SerializeSeq, SerializeStruct},
+    ser::{Error, Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple},
     Serialize, Serializer,
 };
-use std::io::Write;
+use std::{fmt::Display, io::Write};
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 enum Recursion {
@@ -200,10 +203,34 @@ pub(crate) fn extract_string_key<T: Serialize + ?Sized>(key: &T) -> Result<Strin
     Ok(extractor.0)
 }
 
+/// The properties the header declares for an element, in order.
+///
+/// Rows are written without any separators, so every row has to write exactly these
+/// properties, see [`HeaderCollector::into_parts`].
+#[derive(Clone)]
+pub(crate) struct RowLayout {
+    pub element: String,
+    pub properties: Vec<String>,
+    // Fields written as columns, with the names of their columns.
+    pub arrays: Vec<(String, Vec<String>)>,
+}
+
+impl RowLayout {
+    /// Column names of `field`, if it's an array written as columns.
+    pub fn array_columns(&self, field: &str) -> Option<&[String]> {
+        self.arrays
+            .iter()
+            .find(|(f, _)| f == field)
+            .map(|(_, columns)| columns.as_slice())
+    }
+}
+
 pub(crate) struct HeaderCollector<W: Write> {
     writer: W,
     options: SerializeOptions,
     recursion: Recursion,
+    cur_element: String,
+    layouts: Vec<RowLayout>,
 }
 
 impl<W: Write> HeaderCollector<W> {
@@ -212,18 +239,94 @@ impl<W: Write> HeaderCollector<W> {
             writer,
             options,
             recursion: Recursion::Header,
+            cur_element: String::new(),
+            layouts: Vec::new(),
+        }
+    }
+
+    /// The options, and the layout of the rows of each element in the order they were
+    /// collected.
+    pub(crate) fn into_parts(self) -> (SerializeOptions, Vec<RowLayout>) {
+        (self.options, self.layouts)
+    }
+
+    /// Start collecting the rows of element `name`, erroring if an element of that name
+    /// was already collected. Names are case sensitive, so eg. `Vertex` and `vertex` are
+    /// different elements.
+    fn start_element(&mut self, name: &str) -> Result<(), SerializeError> {
+        if self.layouts.iter().any(|layout| layout.element == name) {
+            return Err(SerializeError::custom(format!(
+                "element '{name}' is written twice, element names must be unique. \
+                 Check the #[serde(rename)] attributes or keys that give elements this name"
+            )));
+        }
+        // The data pass takes a layout for every element, even one left out.
+        self.layouts.push(RowLayout {
+            element: name.to_string(),
+            properties: Vec::new(),
+            arrays: Vec::new(),
+        });
+        self.cur_element = name.to_string();
+        Ok(())
+    }
+
+    /// Record that the current element has `property` as its next property.
+    fn push_property(&mut self, property: &str) {
+        if let Some(layout) = self.layouts.last_mut() {
+            layout.properties.push(property.to_string());
         }
     }
+
+    /// Get the property type forced by an element definition in the options, if any.
+    fn property_override(&self, property: &str) -> Option<PropertyType> {
+        self.property_override_def(property)
+            .map(|p| p.property_type)
+    }
+
+    fn property_override_def(&self, property: &str) -> Option<&PlyProperty> {
+        let def = self.options.element_def(&self.cur_element)?;
+        def.get_property(property)
+    }
+
+    /// Declare the properties of the element definition for the current element, if any.
+    fn write_def_properties(&mut self) -> Result<(), SerializeError> {
+        let Some(def) = self.options.element_def(&self.cur_element) else {
+            return Ok(());
+        };
+        let preserve = self.options.preserve_type_spelling;
+        for prop in &def.properties {
+            writeln!(
+                self.writer,
+                "property {} {}",
+                prop.header_type(preserve),
+                prop.name
+            )?;
+        }
+        Ok(())
+    }
+
+    fn override_mismatch(&self, property: &str, declared: &str) -> SerializeError {
+        SerializeError::custom(format!(
+            "property '{property}' of element '{}' is {declared} in the element definition, but the field is not",
+            self.cur_element
+        ))
+    }
 }
 
 impl<W: Write> HeaderCollector<W> {
     fn write_options(&mut self) -> Result<(), SerializeError> {
         writeln!(self.writer, "ply\nformat {} 1.0", self.options.format)?;
-        for comment in &self.options.comments {
-            writeln!(self.writer, "comment {}", comment)?;
+        let mut metadata = [
+            ("comment", &self.options.comments),
+            ("obj_info", &self.options.obj_info),
+        ];
+        if self.options.metadata_order == MetaOrder::ObjInfoFirst {
+            metadata.reverse();
         }
-        for obj in &self.options.obj_info {
-            writeln!(self.writer, "obj_info {}", obj)?;
+        for (keyword, lines) in metadata {
+            for line in lines {
+                writeln!(self.writer, "{keyword} {line}")?;
+            }
         }
         Ok(())
     }
@@ -240,7 +343,7 @@ impl<'a, W: Write> Serializer for &'a mut HeaderCollector<W> {
     type SerializeTupleStruct = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeTupleVariant = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeStructVariant = serde::ser::Impossible<Self::Ok, Self::Error>;
-    type SerializeSeq = serde::ser::Impossible<Self::Ok, Self::Error>;
+    type SerializeSeq = EntrySeq<HeaderMapCollector<'a, W>>;
 
     fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
         Err(SerializeError::custom("Invalid ply structure"))
@@ -341,8 +444,12 @@ impl<'a, W: Write> Serializer for &'a mut HeaderCollector<W> {
         Err(SerializeError::custom("Invalid ply structure"))
     }
 
-    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        Err(SerializeError::custom("Invalid ply structure"))
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        // A sequence of (name, rows) elements, in order.
+        if self.recursion != Recursion::Header {
+            return Err(SerializeError::custom("Invalid ply structure"));
+        }
+        Ok(EntrySeq(self.serialize_map(len)?))
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
@@ -420,7 +527,18 @@ impl<W: Write> SerializeMap for HeaderMapCollector<'_, W> {
     where
         T: Serialize + ?Sized,
     {
-        self.cur_key = extract_string_key(key)?;
+        let key = extract_string_key(key)?;
+        // Map keys are often built at runtime, so catch names that would break the header
+        // before anything is written for them.
+        if self.recursion == Recursion::Header {
+            check_name("element", &key)?;
+        } else {
+            check_name(
+                "property",
+                &self.parent.options.transform_property_name(&key),
+            )?;
+        }
+        self.cur_key = key;
         Ok(())
     }
 
@@ -428,11 +546,13 @@ impl<W: Write> SerializeMap for HeaderMapCollector<'_, W> {
     where
         T: Serialize + ?Sized,
     {
+        let count_type = self.parent.options.default_list_count_type;
         value.serialize(PropertyCollector {
             parent: self.parent,
             property_name: &self.cur_key,
             recursion: self.recursion.next()?,
-            count_type: ScalarType::U8,
+            count_type,
+            wrapper: None,
         })
     }
 
@@ -457,11 +577,13 @@ impl<W: Write> SerializeStruct for HeaderStructCollector<'_, W> {
     where
         T: Serialize + ?Sized,
     {
+        let count_type = self.parent.options.default_list_count_type;
         value.serialize(PropertyCollector {
             parent: self.parent,
             property_name: key,
             recursion: self.recursion.next()?,
-            count_type: ScalarType::U8,
+            count_type,
+            wrapper: None,
         })
     }
 
@@ -478,13 +600,61 @@ struct PropertyCollector<'a, W: Write> {
     property_name: &'a str,
     recursion: Recursion,
     count_type: ScalarType,
+    // Name of the ListCount wrapper this value is in, if any.
+    wrapper: Option<&'static str>,
+}
+
+impl<W: Write> PropertyCollector<'_, W> {
+    /// Error if a ListCount wrapper is used around something other than a sequence.
+    fn check_unwrapped(&self, found: impl Display) -> Result<(), SerializeError> {
+        match self.wrapper {
+            Some(wrapper) => Err(SerializeError::custom(format!(
+                "{wrapper} on property '{}' must wrap a sequence, found {found}",
+                self.property_name
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Error if a row property is itself a struct or map. PLY rows are flat, so nested
+    /// fields have to be flattened into the row.
+    fn check_flat(&self, found: &str) -> Result<(), SerializeError> {
+        if self.recursion == Recursion::Row {
+            return Err(SerializeError::custom(format!(
+                "property '{}' of element '{}' is {found}, but PLY rows are flat. \
+                 Add #[serde(flatten)] to the field to write its fields as properties of the row",
+                self.property_name, self.parent.cur_element
+            )));
+        }
+        Ok(())
+    }
+
+    fn write_property(self, data_type: ScalarType) -> Result<(), SerializeError> {
+        self.check_unwrapped(format_args!("a scalar ({data_type})"))?;
+        let preserve = self.parent.options.preserve_type_spelling;
+        let header_type = match self.parent.property_override_def(self.property_name) {
+            Some(prop) if prop.is_list() => {
+                return Err(self.parent.override_mismatch(self.property_name, "a list"))
+            }
+            Some(prop) => prop.header_type(preserve),
+            None => data_type.header_name().to_string(),
+        };
+        let name = self
+            .parent
+            .options
+            .transform_property_name(self.property_name);
+        check_property_name(&name)?;
+        writeln!(self.parent.writer, "property {header_type} {name}")?;
+        self.parent.push_property(self.property_name);
+        Ok(())
+    }
 }
 
 impl<'a, W: Write> Serializer for PropertyCollector<'a, W> {
     type Ok = ();
     type Error = SerializeError;
     type SerializeSeq = ListPropertyCollector<'a, W>;
-    type SerializeTuple = serde::ser::Impossible<Self::Ok, Self::Error>;
+    type SerializeTuple = TupleCollector<'a, W>;
     type SerializeTupleStruct = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeTupleVariant = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeMap = HeaderMapCollector<'a, W>;
@@ -492,22 +662,19 @@ impl<'a, W: Write> Serializer for PropertyCollector<'a, W> {
     type SerializeStructVariant = serde::ser::Impossible<Self::Ok, Self::Error>;
 
     fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
-        Err(SerializeError::custom("Bool properties are not supported"))
+        self.write_property(ScalarType::U8)
     }
 
     fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
-        writeln!(self.parent.writer, "property char {}", self.property_name)?;
-        Ok(())
+        self.write_property(ScalarType::I8)
     }
 
     fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
-        writeln!(self.parent.writer, "property short {}", self.property_name)?;
-        Ok(())
+        self.write_property(ScalarType::I16)
     }
 
     fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
-        writeln!(self.parent.writer, "property int {}", self.property_name)?;
-        Ok(())
+        self.write_property(ScalarType::I32)
     }
 
     fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
@@ -515,18 +682,15 @@ impl<'a, W: Write> Serializer for PropertyCollector<'a, W> {
     }
 
     fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
-        writeln!(self.parent.writer, "property uchar {}", self.property_name)?;
-        Ok(())
+        self.write_property(ScalarType::U8)
     }
 
     fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
-        writeln!(self.parent.writer, "property ushort {}", self.property_name)?;
-        Ok(())
+        self.write_property(ScalarType::U16)
     }
 
     fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
-        writeln!(self.parent.writer, "property uint {}", self.property_name)?;
-        Ok(())
+        self.write_property(ScalarType::U32)
     }
 
     fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
@@ -534,13 +698,11 @@ impl<'a, W: Write> Serializer for PropertyCollector<'a, W> {
     }
 
     fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
-        writeln!(self.parent.writer, "property float {}", self.property_name)?;
-        Ok(())
+        self.write_property(ScalarType::F32)
     }
 
     fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
-        writeln!(self.parent.writer, "property double {}", self.property_name)?;
-        Ok(())
+        self.write_property(ScalarType::F64)
     }
 
     fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
@@ -594,12 +756,16 @@ impl<'a, W: Write> Serializer for PropertyCollector<'a, W> {
         T: Serialize + ?Sized,
     {
         // Check if this is a ListCount wrapper type
-        self.count_type = match name {
-            "ListCountU8" => ScalarType::U8,
-            "ListCountU16" => ScalarType::U16,
-            "ListCountU32" => ScalarType::U32,
-            _ => ScalarType::U8,
-        };
+        if let Some(count_type) = list_count_type(name) {
+            if let Some(outer) = self.wrapper {
+                return Err(SerializeError::custom(format!(
+                    "property '{}' nests {name} inside {outer}, use a single ListCount wrapper",
+                    self.property_name
+                )));
+            }
+            self.count_type = count_type;
+            self.wrapper = Some(name);
+        }
         value.serialize(self)
     }
 
@@ -622,13 +788,27 @@ impl<'a, W: Write> Serializer for PropertyCollector<'a, W> {
             let Some(len) = len else {
                 return Err(SerializeError::custom("Lists must have known length"));
             };
+            self.parent.start_element(self.property_name)?;
+            if len == 0 && self.parent.options.skip_empty_elements {
+                return Ok(ListPropertyCollector {
+                    parent: self.parent,
+                    recursion: self.recursion,
+                    prop_name: self.property_name,
+                    active: false,
+                    count_type: self.count_type,
+                });
+            }
             writeln!(self.parent.writer, "element {} {}", self.property_name, len)?;
+            if len == 0 {
+                // Without a row to go by, only an element definition knows the properties.
+                self.parent.write_def_properties()?;
+            }
         }
 
         // Now visit this list. This is needed to write the properties of the struct.
         // We really only want to visit the first one though.
         Ok(ListPropertyCollector {
-            writer: &mut self.parent.writer,
+            parent: self.parent,
             recursion: self.recursion,
             prop_name: self.property_name,
             active: true,
@@ -636,8 +816,38 @@ impl<'a, W: Write> Serializer for PropertyCollector<'a, W> {
         })
     }
 
-    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        Err(serde::ser::Error::custom("tuples not supported"))
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        // A ListCount wrapper asks for a list.
+        let columns = match (self.recursion, self.wrapper) {
+            (Recursion::Row, None) => {
+                self.parent
+                    .options
+                    .array_columns(&self.parent.cur_element, self.property_name, len)
+            }
+            _ => None,
+        };
+        let Some(columns) = columns else {
+            // Without columns, arrays are lists like any other sequence.
+            return self.serialize_seq(Some(len)).map(TupleCollector::List);
+        };
+        if columns.len() != len {
+            return Err(SerializeError::custom(format!(
+                "property '{}' of element '{}' has {len} entries, but is written as {} columns",
+                self.property_name,
+                self.parent.cur_element,
+                columns.len()
+            )));
+        }
+        if let Some(layout) = self.parent.layouts.last_mut() {
+            layout
+                .arrays
+                .push((self.property_name.to_string(), columns.clone()));
+        }
+        Ok(TupleCollector::Columns {
+            parent: self.parent,
+            columns,
+            index: 0,
+        })
     }
 
     fn serialize_tuple_struct(
@@ -659,6 +869,8 @@ impl<'a, W: Write> Serializer for PropertyCollector<'a, W> {
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        self.check_unwrapped("a map")?;
+        self.check_flat("a map")?;
         Ok(HeaderMapCollector {
             parent: self.parent,
             cur_key: "".to_string(),
@@ -671,6 +883,8 @@ impl<'a, W: Write> Serializer for PropertyCollector<'a, W> {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.check_unwrapped("a struct")?;
+        self.check_flat("a struct")?;
         Ok(HeaderStructCollector {
             parent: self.parent,
             recursion: self.recursion,
@@ -689,7 +903,7 @@ impl<'a, W: Write> Serializer for PropertyCollector<'a, W> {
 }
 
 struct ListPropertyCollector<'a, W: Write> {
-    writer: &'a mut W,
+    parent: &'a mut HeaderCollector<W>,
     recursion: Recursion,
     prop_name: &'a str,
     active: bool,
@@ -708,11 +922,11 @@ impl<W: Write> SerializeSeq for ListPropertyCollector<'_, W> {
             self.active = false;
 
             if self.recursion == Recursion::Element {
-                value.serialize(&mut HeaderCollector {
-                    writer: &mut self.writer,
-                    options: SerializeOptions::ascii(), // unused
-                    recursion: self.recursion,
-                })?
+                // Collect the row's properties with the same collector, one level deeper.
+                let recursion = std::mem::replace(&mut self.parent.recursion, self.recursion);
+                let res = value.serialize(&mut *self.parent);
+                self.parent.recursion = recursion;
+                res?
             } else if self.recursion == Recursion::Row {
                 value.serialize(self)?
             }
@@ -720,18 +934,122 @@ impl<W: Write> SerializeSeq for ListPropertyCollector<'_, W> {
         Ok(())
     }
 
-    fn end(self) -> Result<Self::Ok, Self::Error> {
+    fn end(mut self) -> Result<Self::Ok, Self::Error> {
+        // An empty list gives no sample to infer the data type from, but an element
+        // definition can still declare it.
+        if self.active && self.recursion == Recursion::Row {
+            let Some(PropertyType::List { data_type, .. }) =
+                self.parent.property_override(self.prop_name)
+            else {
+                return Err(SerializeError::custom(format!(
+                    "Cannot infer the data type of list property '{}' of element '{}', the list in the first row is empty",
+                    self.prop_name, self.parent.cur_element
+                )));
+            };
+            self.write_list_prop(data_type)?;
+        }
         Ok(())
     }
 }
 
+/// A tuple or fixed size array, written as a list or as one property per entry, see
+/// [`SerializeOptions::default_array_mode`].
+enum TupleCollector<'a, W: Write> {
+    List(ListPropertyCollector<'a, W>),
+    Columns {
+        parent: &'a mut HeaderCollector<W>,
+        columns: Vec<String>,
+        index: usize,
+    },
+}
+
+impl<W: Write> SerializeTuple for TupleCollector<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        match self {
+            TupleCollector::List(list) => SerializeSeq::serialize_element(list, value),
+            TupleCollector::Columns {
+                parent,
+                columns,
+                index,
+            } => {
+                *index += 1;
+                let count_type = parent.options.default_list_count_type;
+                value.serialize(PropertyCollector {
+                    parent,
+                    property_name: &columns[*index - 1],
+                    recursion: Recursion::Row,
+                    count_type,
+                    wrapper: None,
+                })
+            }
+        }
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        match self {
+            TupleCollector::List(list) => SerializeSeq::end(list),
+            TupleCollector::Columns { .. } => Ok(()),
+        }
+    }
+}
+
+/// Reject element and property names that don't make a single header token.
+fn check_name(kind: &str, name: &str) -> Result<(), SerializeError> {
+    let problem = if name.is_empty() {
+        "is empty"
+    } else if name.contains(char::is_whitespace) {
+        "contains whitespace"
+    } else if name.contains(char::is_control) {
+        "contains a control character"
+    } else {
+        return Ok(());
+    };
+    Err(SerializeError::custom(format!(
+        "{kind} name {name:?} {problem}, PLY names must be a single word"
+    )))
+}
+
+/// Reject property names that can't be what the user meant.
+fn check_property_name(name: &str) -> Result<(), SerializeError> {
+    if let Some(bare) = name.strip_prefix("r#") {
+        return Err(SerializeError::custom(format!(
+            "property name '{name}' starts with 'r#'. Raw identifiers are Rust syntax only, \
+             serde already names a field `r#{bare}` '{bare}'. \
+             Remove the 'r#' from #[serde(rename = \"{name}\")]"
+        )));
+    }
+    Ok(())
+}
+
 impl<W: Write> ListPropertyCollector<'_, W> {
+    /// Error for a list entry that isn't a scalar. PLY lists only hold scalars.
+    fn nested(&self, found: &str) -> SerializeError {
+        SerializeError::custom(format!(
+            "property '{}' of element '{}' is a list of {found}, but PLY lists can only hold scalars",
+            self.prop_name, self.parent.cur_element
+        ))
+    }
+
     fn write_list_prop(&mut self, t: ScalarType) -> Result<(), SerializeError> {
-        Ok(writeln!(
-            self.writer,
-            "property list {} {} {}",
-            self.count_type, t, self.prop_name
-        )?)
+        let preserve = self.parent.options.preserve_type_spelling;
+        let header_type = match self.parent.property_override_def(self.prop_name) {
+            Some(prop) if !prop.is_list() => {
+                return Err(self.parent.override_mismatch(self.prop_name, "a scalar"))
+            }
+            Some(prop) => prop.header_type(preserve),
+            None => format!("list {} {t}", self.count_type),
+        };
+        let name = self.parent.options.transform_property_name(self.prop_name);
+        check_property_name(&name)?;
+        writeln!(self.parent.writer, "property {header_type} {name}")?;
+        self.parent.push_property(self.prop_name);
+        Ok(())
     }
 }
 
@@ -751,7 +1069,7 @@ impl<W: Write> Serializer for &mut ListPropertyCollector<'_, W> {
     type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
 
     fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
-        Err(SerializeError::custom("Bool properties are not supported"))
+        self.write_list_prop(ScalarType::U8)
     }
 
     fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
@@ -812,21 +1130,20 @@ impl<W: Write> Serializer for &mut ListPropertyCollector<'_, W> {
         ))
     }
 
-    fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        Err(SerializeError::custom(
-            "Option properties are not supported",
-        ))
+        // Like for scalar properties, `Some` values are written as the value.
+        value.serialize(self)
     }
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
-        todo!()
+        Err(SerializeError::custom("Unit values not supported"))
     }
 
     fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
-        todo!()
+        Err(SerializeError::custom("Unit structs are not supported"))
     }
 
     fn serialize_unit_variant(
@@ -835,18 +1152,19 @@ impl<W: Write> Serializer for &mut ListPropertyCollector<'_, W> {
         _variant_index: u32,
         _variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
-        todo!()
+        Err(SerializeError::custom("Unit variant values not supported"))
     }
 
     fn serialize_newtype_struct<T>(
         self,
         _name: &'static str,
-        _value: &T,
+        value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        todo!()
+        // Eg. a #[serde(transparent)] index type, the list holds the wrapped values.
+        value.serialize(self)
     }
 
     fn serialize_newtype_variant<T>(
@@ -859,15 +1177,17 @@ impl<W: Write> Serializer for &mut ListPropertyCollector<'_, W> {
     where
         T: ?Sized + Serialize,
     {
-        todo!()
+        Err(SerializeError::custom(
+            "Newtype variant values not supported",
+        ))
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        todo!()
+        Err(self.nested("lists"))
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        todo!()
+        Err(self.nested("tuples"))
     }
 
     fn serialize_tuple_struct(
@@ -875,7 +1195,7 @@ impl<W: Write> Serializer for &mut ListPropertyCollector<'_, W> {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        todo!()
+        Err(self.nested("tuple structs"))
     }
 
     fn serialize_tuple_variant(
@@ -885,11 +1205,11 @@ impl<W: Write> Serializer for &mut ListPropertyCollector<'_, W> {
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        todo!()
+        Err(self.nested("tuple variants"))
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        todo!()
+        Err(self.nested("maps"))
     }
 
     fn serialize_struct(
@@ -897,7 +1217,7 @@ impl<W: Write> Serializer for &mut ListPropertyCollector<'_, W> {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        todo!()
+        Err(self.nested("structs"))
     }
 
     fn serialize_struct_variant(
@@ -907,7 +1227,7 @@ impl<W: Write> Serializer for &mut ListPropertyCollector<'_, W> {
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        todo!()
+        Err(self.nested("struct variants"))
     }
 }
 
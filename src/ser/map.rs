@@ -0,0 +1,468 @@
+//! Copying a PLY file while transforming the rows of one element.
+
+use std::{
+    cell::RefCell,
+    io::{BufRead, Read, Write},
+    rc::Rc,
+};
+
+use serde::{
+    de::{DeserializeOwned, IgnoredAny, Visitor},
+    ser::{Error as _, SerializeMap, SerializeSeq},
+    Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    de::raw::{NamedRow, RawRow},
+    ser::{
+        header_collector::HeaderCollector,
+        ply_file::{write_row, PlyReaderSerializer},
+        MetaOrder,
+    },
+    DeserializeError, ElementDef, PlyHeader, PlyReader, SerializeError, SerializeOptions,
+};
+
+/// Summary of a [`map_element`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MapElementStats {
+    /// Number of rows passed through the transform.
+    pub rows_mapped: usize,
+    /// Number of other elements copied byte for byte.
+    pub elements_copied: usize,
+    /// Number of other elements converted to the output format.
+    pub elements_transcoded: usize,
+}
+
+/// Copy a PLY file, passing each row of `element` through `f`.
+///
+/// Rows are read as `T` one at a time, transformed, and written back right away with the
+/// property types of the source file, so no element is held in memory. `T` must be a struct with a field for every property of the element,
+/// anything it doesn't model would be dropped, so that is an error. All other elements
+/// are copied byte for byte when `options` uses the format of the source file, and
+/// converted otherwise. Comments of the source file are kept byte for byte, followed by
+/// those in `options`. Like [`crate::to_writer`], the writer is flushed before returning.
+///
+/// # Example
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use serde_ply::{map_element, SerializeOptions};
+/// use std::io::Cursor;
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Vertex { x: f32, y: f32 }
+///
+/// let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\nend_header\n1 2\n3 4\n";
+/// let mut output = Vec::new();
+/// let stats = map_element(
+///     Cursor::new(ply_data),
+///     &mut output,
+///     "vertex",
+///     |v: Vertex| Vertex { x: v.x - 2.0, y: v.y - 3.0 },
+///     SerializeOptions::ascii(),
+/// )?;
+/// assert_eq!(stats.rows_mapped, 2);
+/// assert!(String::from_utf8(output)?.ends_with("-1 -1\n1 1\n"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn map_element<T, F>(
+    reader: impl BufRead,
+    mut writer: impl Write,
+    element: &str,
+    mut f: F,
+    options: SerializeOptions,
+) -> Result<MapElementStats, SerializeError>
+where
+    T: DeserializeOwned + Serialize,
+    F: FnMut(T) -> T,
+{
+    options.validate()?;
+    let capture = Rc::new(RefCell::new(None));
+    let mut reader = PlyReader::from_reader(Tee {
+        inner: reader,
+        capture: capture.clone(),
+    })
+    .map_err(de_error)?;
+    let header = reader.header().clone();
+
+    let source = header
+        .elem_defs
+        .iter()
+        .find(|e| e.name == element)
+        .ok_or_else(|| SerializeError::custom(format!("header has no element '{element}'")))?;
+    let mapped_def = mapped_element_def::<T>(source)?;
+
+    let copy_bytes = options.format == header.format;
+    let mut options = options;
+    for elem_def in &header.elem_defs {
+        let def = if elem_def.name == element {
+            mapped_def.clone()
+        } else {
+            elem_def.clone()
+        };
+        options = options.with_element_def(def);
+    }
+    write_header(&mut writer, &header, &options, 0)?;
+
+    // Every element is streamed a row at a time, so only one row is in memory at once.
+    let mut stats = MapElementStats::default();
+    for elem_def in &header.elem_defs {
+        let out_def = options.element_def(&elem_def.name);
+        if elem_def.name == element {
+            for row in reader.rows::<T>().map_err(de_error)? {
+                let row = f(row.map_err(de_error)?);
+                write_row(&mut writer, &options, out_def, None, &row)?;
+                stats.rows_mapped += 1;
+            }
+        } else if copy_bytes {
+            // Rows are only checked, the bytes read for each are passed on as they are.
+            *capture.borrow_mut() = Some(Vec::new());
+            for row in reader.rows::<IgnoredAny>().map_err(de_error)? {
+                row.map_err(de_error)?;
+                if let Some(bytes) = capture.borrow_mut().as_mut() {
+                    writer.write_all(bytes)?;
+                    bytes.clear();
+                }
+            }
+            // Trailing bytes of the element, eg. the line break of an empty ASCII element.
+            if let Some(bytes) = capture.borrow_mut().take() {
+                writer.write_all(&bytes)?;
+            }
+            stats.elements_copied += 1;
+        } else {
+            for row in reader.rows::<RawRow>().map_err(de_error)? {
+                let row = NamedRow(elem_def, &row.map_err(de_error)?.0);
+                write_row(&mut writer, &options, out_def, None, &row)?;
+            }
+            stats.elements_transcoded += 1;
+        }
+    }
+    writer.flush()?;
+    Ok(stats)
+}
+
+/// Stream the rows of `element` through `f` into a new file with only that element.
+///
+/// Rows are read as `A` one at a time, converted to `B` and written right away, so the
+/// element is never held in memory. Unlike [`map_element`], the row type may change: the
+/// header is built from the first converted row as [`crate::to_writer`] would, so the
+/// properties follow `B` and `options`. Elements before `element` are skipped, elements
+/// after it aren't read. Returns the number of rows written. The writer is flushed before
+/// returning.
+///
+/// # Example
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use serde_ply::{map_elements, SerializeOptions};
+/// use std::io::Cursor;
+///
+/// #[derive(Deserialize)]
+/// struct Vertex { x: f32, y: f32 }
+///
+/// #[derive(Serialize)]
+/// struct Point { x: f64, y: f64, w: u8 }
+///
+/// let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\nend_header\n1 2\n3 4\n";
+/// let mut output = Vec::new();
+/// let rows = map_elements(
+///     Cursor::new(ply_data),
+///     &mut output,
+///     "vertex",
+///     |v: Vertex| Point { x: v.x as f64, y: v.y as f64, w: 1 },
+///     SerializeOptions::ascii(),
+/// )?;
+/// assert_eq!(rows, 2);
+/// let output = String::from_utf8(output)?;
+/// assert!(output.contains("property double x\nproperty double y\nproperty uchar w\n"));
+/// assert!(output.ends_with("1 2 1\n3 4 1\n"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn map_elements<A, B, F>(
+    reader: impl BufRead,
+    mut writer: impl Write,
+    element: &str,
+    mut f: F,
+    options: SerializeOptions,
+) -> Result<usize, SerializeError>
+where
+    A: DeserializeOwned,
+    B: Serialize,
+    F: FnMut(A) -> B,
+{
+    options.validate()?;
+    let mut reader = PlyReader::from_reader(reader).map_err(de_error)?;
+    if !reader.header().has_element(element) {
+        return Err(SerializeError::custom(format!(
+            "header has no element '{element}'"
+        )));
+    }
+    while reader.peek_element_name() != Some(element) {
+        reader.element().and_then(|e| e.skip()).map_err(de_error)?;
+    }
+
+    let mut rows = reader.rows::<A>().map_err(de_error)?;
+    let len = rows.len();
+    let first = rows.next().transpose().map_err(de_error)?.map(&mut f);
+
+    let mut header = HeaderCollector::new(options, &mut writer);
+    OneElement(
+        element,
+        RowSample {
+            len,
+            first: first.as_ref(),
+        },
+    )
+    .serialize(&mut header)?;
+    let (options, layouts) = header.into_parts();
+
+    let error = RefCell::new(None);
+    let stream = RowStream {
+        len,
+        rows: RefCell::new(
+            first
+                .map(Ok)
+                .into_iter()
+                .chain(rows.map(|row| row.map(&mut f))),
+        ),
+        error: &error,
+    };
+    let written = OneElement(element, stream)
+        .serialize(&mut PlyReaderSerializer::new(options, &mut writer).with_layouts(layouts));
+    // A read error is passed through serde as a message, return the original.
+    if let Some(e) = error.take() {
+        return Err(de_error(e));
+    }
+    written?;
+    writer.flush()?;
+    Ok(len)
+}
+
+/// Serializes as a map with a single element with the rows `.1`.
+struct OneElement<'a, S>(&'a str, S);
+
+impl<S: Serialize> Serialize for OneElement<'_, S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(self.0, &self.1)?;
+        map.end()
+    }
+}
+
+/// Rows for the header pass, which only looks at the first row.
+struct RowSample<'a, B> {
+    len: usize,
+    first: Option<&'a B>,
+}
+
+impl<B: Serialize> Serialize for RowSample<'_, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len))?;
+        if let Some(first) = self.first {
+            seq.serialize_element(first)?;
+        }
+        seq.end()
+    }
+}
+
+/// Rows pulled from a reader as they are serialized. Can only be serialized once.
+struct RowStream<'a, I> {
+    len: usize,
+    rows: RefCell<I>,
+    error: &'a RefCell<Option<DeserializeError>>,
+}
+
+impl<B, I> Serialize for RowStream<'_, I>
+where
+    B: Serialize,
+    I: Iterator<Item = Result<B, DeserializeError>>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len))?;
+        for row in &mut *self.rows.borrow_mut() {
+            match row {
+                Ok(row) => seq.serialize_element(&row)?,
+                Err(e) => {
+                    let msg = e.to_string();
+                    *self.error.borrow_mut() = Some(e);
+                    return Err(S::Error::custom(msg));
+                }
+            }
+        }
+        seq.end()
+    }
+}
+
+fn de_error(e: DeserializeError) -> SerializeError {
+    SerializeError(e.0)
+}
+
+/// Definition of the mapped element with the properties in the order `T` writes them.
+fn mapped_element_def<T: DeserializeOwned>(
+    source: &ElementDef,
+) -> Result<ElementDef, SerializeError> {
+    let mut fields = None;
+    let _ = T::deserialize(FieldProbe(&mut fields));
+    let fields = fields.ok_or_else(|| {
+        SerializeError::custom(format!(
+            "rows of element '{}' must be mapped with a struct with named fields",
+            source.name
+        ))
+    })?;
+
+    let mut properties = Vec::with_capacity(fields.len());
+    for field in fields {
+        let prop = source.get_property(field).ok_or_else(|| {
+            SerializeError::custom(format!(
+                "field '{field}' is not a property of element '{}'",
+                source.name
+            ))
+        })?;
+        properties.push(prop.clone());
+    }
+    if let Some(missing) = source
+        .properties
+        .iter()
+        .find(|p| !fields.contains(&p.name.as_str()))
+    {
+        return Err(SerializeError::custom(format!(
+            "property '{}' of element '{}' has no field in the row type, add a field for it \
+             or read the file with PlyReader to handle it yourself",
+            missing.name, source.name
+        )));
+    }
+
+    Ok(ElementDef {
+        name: source.name.clone(),
+        count: source.count,
+        properties,
+    })
+}
+
+/// Write the elements and metadata of `header`, in the format and with the extra
+/// metadata of `options`. Element counts are padded with leading zeros to `count_width`.
+///
+/// Returns the offset of each element count from the start of the header.
+pub(crate) fn write_header<W: Write>(
+    writer: &mut W,
+    header: &PlyHeader,
+    options: &SerializeOptions,
+    count_width: usize,
+) -> Result<Vec<usize>, SerializeError> {
+    // Rendered in memory first, to know where the counts end up.
+    let mut buf = Vec::new();
+    writeln!(buf, "ply\nformat {} 1.0", options.format)?;
+    let write_comments = |writer: &mut Vec<u8>| -> Result<(), SerializeError> {
+        // Source comments are copied as bytes, they aren't necessarily UTF-8. Comments
+        // that were changed since the header was parsed are written as they are now.
+        for (i, comment) in header.comments.iter().enumerate() {
+            match header.comments_raw.get(i) {
+                Some(raw) if decodes_to(raw, comment) => {
+                    writer.write_all(b"comment ")?;
+                    writer.write_all(raw)?;
+                    writer.write_all(b"\n")?;
+                }
+                _ => writeln!(writer, "comment {comment}")?,
+            }
+        }
+        for comment in &options.comments {
+            writeln!(writer, "comment {comment}")?;
+        }
+        Ok(())
+    };
+    let write_obj_info = |writer: &mut Vec<u8>| -> Result<(), SerializeError> {
+        for obj in header.obj_info.iter().chain(&options.obj_info) {
+            writeln!(writer, "obj_info {obj}")?;
+        }
+        Ok(())
+    };
+    match options.metadata_order {
+        MetaOrder::CommentsFirst => {
+            write_comments(&mut buf)?;
+            write_obj_info(&mut buf)?;
+        }
+        MetaOrder::ObjInfoFirst => {
+            write_obj_info(&mut buf)?;
+            write_comments(&mut buf)?;
+        }
+    }
+    let mut offsets = Vec::with_capacity(header.elem_defs.len());
+    for elem_def in &header.elem_defs {
+        let elem_def = options.element_def(&elem_def.name).unwrap_or(elem_def);
+        write!(buf, "element {} ", elem_def.name)?;
+        offsets.push(buf.len());
+        writeln!(buf, "{:0>count_width$}", elem_def.count)?;
+        for prop in &elem_def.properties {
+            let header_type = prop.header_type(options.preserve_type_spelling);
+            writeln!(buf, "property {header_type} {}", prop.name)?;
+        }
+    }
+    writeln!(buf, "end_header")?;
+    writer.write_all(&buf)?;
+    Ok(offsets)
+}
+
+/// Whether `raw` is the comment that was parsed as `comment`, which has its whitespace
+/// collapsed and invalid UTF-8 replaced.
+fn decodes_to(raw: &[u8], comment: &str) -> bool {
+    let decoded = String::from_utf8_lossy(raw);
+    decoded.split_whitespace().collect::<Vec<_>>().join(" ") == comment
+}
+
+/// Reader that can record the bytes read through it. The capture is shared, so it can be
+/// drained while the reader is borrowed.
+struct Tee<R> {
+    inner: R,
+    capture: Rc<RefCell<Option<Vec<u8>>>>,
+}
+
+impl<R: BufRead> Read for Tee<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if let Some(capture) = self.capture.borrow_mut().as_mut() {
+            capture.extend_from_slice(&buf[..n]);
+        }
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Tee<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Some(capture) = self.capture.borrow_mut().as_mut() {
+            // The buffer is already filled, so this doesn't read anything.
+            if let Ok(buf) = self.inner.fill_buf() {
+                capture.extend_from_slice(&buf[..amt.min(buf.len())]);
+            }
+        }
+        self.inner.consume(amt)
+    }
+}
+
+/// Captures the field names of a struct, without deserializing anything.
+struct FieldProbe<'a>(&'a mut Option<&'static [&'static str]>);
+
+impl<'de> Deserializer<'de> for FieldProbe<'_> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(serde::de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = Some(fields);
+        Err(serde::de::Error::custom("fields captured"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 u8 i16 u16 i32 u32 i64 u64 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
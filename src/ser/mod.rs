@@ -1,25 +1,67 @@
 //! PLY file serialization.
 
-use std::io::Write;
+use std::{
+    borrow::Cow,
+    fmt,
+    io::{BufWriter, Write},
+    sync::Arc,
+};
 
-use serde::{ser::Error, Serialize};
+use serde::{
+    ser::{Error, SerializeMap},
+    Serialize, Serializer,
+};
 
 use crate::{
     ser::{header_collector::HeaderCollector, ply_file::PlyReaderSerializer},
-    PlyFormat, SerializeError,
+    ElementDef, PlyFormat, PlyHeader, PropertyType, ScalarType, SerializeError,
 };
 
+#[cfg(feature = "ndarray")]
+pub(crate) mod array;
+pub(crate) mod columns;
+mod entries;
 mod header_collector;
+pub(crate) mod map;
+#[cfg(feature = "parallel")]
+pub(crate) mod parallel;
 mod ply_file;
 mod row;
+pub(crate) mod writer;
 
 pub mod val_writer;
 
+/// Count type declared by a ListCount wrapper, given the wrapper's newtype name.
+/// Error unless `count_type` can hold list counts, which must be integers.
+pub(crate) fn check_count_type(count_type: ScalarType) -> Result<(), SerializeError> {
+    if count_type.is_integer() {
+        return Ok(());
+    }
+    Err(SerializeError::custom(format!(
+        "List count type {count_type:?} is not an integer type. Earlier versions of serde-ply \
+         wrote such counts as floats, which readers reject; use ScalarType::U8, U16 or U32"
+    )))
+}
+
+pub(crate) fn list_count_type(newtype_name: &str) -> Option<ScalarType> {
+    match newtype_name {
+        "ListCountU8" => Some(ScalarType::U8),
+        "ListCountU16" => Some(ScalarType::U16),
+        "ListCountU32" => Some(ScalarType::U32),
+        _ => None,
+    }
+}
+
 /// Serialize PLY data to a writer.
 ///
 /// Writes the complete PLY file including header and data in the specified format.
 /// The writer receives the raw PLY bytes.
 ///
+/// The writer is flushed before returning, so that an error flushing eg. a `BufWriter`
+/// is returned rather than lost when the writer is dropped. Values are written with many
+/// small writes, so pass a buffered writer or set [`SerializeOptions::buffer_hint`] when
+/// writing to eg. a `File` or socket.
+///
 /// # Example
 /// ```rust
 /// use serde::Serialize;
@@ -41,6 +83,21 @@ pub mod val_writer;
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 pub fn to_writer<T>(
+    val: &T,
+    options: SerializeOptions,
+    writer: impl Write,
+) -> Result<(), SerializeError>
+where
+    T: Serialize,
+{
+    match options.buffer_capacity {
+        0 => write_ply(val, options, writer),
+        capacity => write_ply(val, options, BufWriter::with_capacity(capacity, writer)),
+    }
+}
+
+/// Write the header and data passes of [`to_writer`] to `writer` and flush it.
+fn write_ply<T>(
     val: &T,
     options: SerializeOptions,
     mut writer: impl Write,
@@ -48,9 +105,12 @@ pub fn to_writer<T>(
 where
     T: Serialize,
 {
-    let format = options.format;
-    val.serialize(&mut HeaderCollector::new(options, &mut writer))?;
-    val.serialize(&mut PlyReaderSerializer::new(format, &mut writer))?;
+    options.validate()?;
+    let mut header = HeaderCollector::new(options, &mut writer);
+    val.serialize(&mut header)?;
+    let (options, layouts) = header.into_parts();
+    val.serialize(&mut PlyReaderSerializer::new(options, &mut writer).with_layouts(layouts))?;
+    writer.flush()?;
     Ok(())
 }
 
@@ -86,6 +146,85 @@ where
     Ok(buf)
 }
 
+/// Serialize PLY data into a fixed size buffer, returning the number of bytes written.
+///
+/// Useful to write into shared or memory mapped memory. The size of the output is
+/// measured first, and if it doesn't fit, nothing is written and the error has a
+/// [`crate::BufferTooSmall`] cause with the size needed, see
+/// [`SerializeError::as_buffer_too_small`]. Bytes of `out` after the written ones are left
+/// as they were. Like [`to_writer`], the value is traversed once more to measure it.
+///
+/// # Example
+/// ```rust
+/// use serde::Serialize;
+/// use serde_ply::{to_slice, SerializeOptions};
+///
+/// #[derive(Serialize)]
+/// struct Point { x: f32, y: f32 }
+///
+/// #[derive(Serialize)]
+/// struct Points { vertex: Vec<Point> }
+///
+/// let points = Points { vertex: vec![Point { x: 1.0, y: 2.0 }] };
+///
+/// let mut out = [0u8; 256];
+/// let len = to_slice(&points, SerializeOptions::binary_le(), &mut out)?;
+/// assert_eq!(&out[len - 8..len], [1.0f32.to_le_bytes(), 2.0f32.to_le_bytes()].concat());
+///
+/// let err = to_slice(&points, SerializeOptions::binary_le(), &mut out[..len - 1]).unwrap_err();
+/// assert_eq!(err.as_buffer_too_small().unwrap().needed, len);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn to_slice<T>(
+    val: &T,
+    options: SerializeOptions,
+    out: &mut [u8],
+) -> Result<usize, SerializeError>
+where
+    T: Serialize,
+{
+    let mut counter = ByteCounter(0);
+    to_writer(val, options.clone(), &mut counter)?;
+    let needed = counter.0;
+    if needed > out.len() {
+        return Err(SerializeError::buffer_too_small(needed, out.len()));
+    }
+    let mut writer = &mut out[..needed];
+    to_writer(val, options, &mut writer)?;
+    // A value that serializes differently the second time, eg. through interior mutability.
+    if !writer.is_empty() {
+        return Err(SerializeError::custom(
+            "Value serialized to a different size than measured",
+        ));
+    }
+    Ok(needed)
+}
+
+/// Writer that only counts the bytes written to it.
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Serializes as a map with a single element.
+pub(crate) struct SingleElement<'a, T>(pub &'a str, pub &'a [T]);
+
+impl<T: Serialize> Serialize for SingleElement<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(self.0, self.1)?;
+        map.end()
+    }
+}
+
 /// Serialize PLY data to a string.
 ///
 /// This only works with ASCII format since binary data cannot be represented as valid UTF-8.
@@ -114,7 +253,7 @@ pub fn to_string<T>(val: &T, options: SerializeOptions) -> Result<String, Serial
 where
     T: Serialize,
 {
-    if options.format != PlyFormat::Ascii {
+    if options.format.is_binary() {
         return Err(SerializeError::custom(
             "Cannot serialize binary PLY to string",
         ));
@@ -122,14 +261,65 @@ where
     String::from_utf8(to_bytes(val, options)?).map_err(|e| SerializeError::custom(e.to_string()))
 }
 
+/// A property name transform, shared between clones of the options.
+#[derive(Clone)]
+struct NameTransform(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl fmt::Debug for NameTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("NameTransform")
+    }
+}
+
+impl PartialEq for NameTransform {
+    /// Closures can't be compared, so transforms are only equal if they are the same one.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Which of the `comment` and `obj_info` lines come first in a written header, see
+/// [`SerializeOptions::metadata_order`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MetaOrder {
+    /// All `comment` lines, then all `obj_info` lines.
+    #[default]
+    CommentsFirst,
+    /// All `obj_info` lines, then all `comment` lines.
+    ObjInfoFirst,
+}
+
+/// How tuples and fixed size arrays like `[u8; 4]` are written, see
+/// [`SerializeOptions::default_array_mode`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrayMode {
+    /// A list property, with a count in every row.
+    #[default]
+    List,
+    /// One scalar property per entry, named `{property}_{index}`.
+    Columns,
+}
+
 /// Options for PLY file serialization.
 ///
 /// Builder struct for configuring PLY output format and metadata like comments.
 /// Use the convenience methods like [`Self::ascii()`] for common configurations.
+#[derive(Debug, Clone, PartialEq)]
 pub struct SerializeOptions {
     format: PlyFormat,
     comments: Vec<String>,
     obj_info: Vec<String>,
+    property_name_transform: Option<NameTransform>,
+    pub(crate) element_defs: Vec<ElementDef>,
+    pub(crate) default_list_count_type: ScalarType,
+    pub(crate) preserve_type_spelling: bool,
+    pub(crate) ascii_separator: char,
+    pub(crate) skip_empty_elements: bool,
+    pub(crate) metadata_order: MetaOrder,
+    pub(crate) buffer_capacity: usize,
+    pub(crate) default_array_mode: ArrayMode,
+    // (element, property, column names) of arrays with their own mode, None for a list.
+    pub(crate) array_overrides: Vec<(String, String, Option<Vec<String>>)>,
 }
 
 impl SerializeOptions {
@@ -139,6 +329,16 @@ impl SerializeOptions {
             format,
             comments: Vec::new(),
             obj_info: Vec::new(),
+            property_name_transform: None,
+            element_defs: Vec::new(),
+            default_list_count_type: ScalarType::U8,
+            preserve_type_spelling: false,
+            ascii_separator: ' ',
+            skip_empty_elements: false,
+            metadata_order: MetaOrder::CommentsFirst,
+            buffer_capacity: 0,
+            default_array_mode: ArrayMode::List,
+            array_overrides: Vec::new(),
         }
     }
 
@@ -157,6 +357,52 @@ impl SerializeOptions {
         Self::new(PlyFormat::BinaryBigEndian)
     }
 
+    /// Write the data in `format`, keeping all other options.
+    pub fn with_format(mut self, format: PlyFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// The format the data is written in.
+    pub fn format(&self) -> PlyFormat {
+        self.format
+    }
+
+    /// The comments written to the header.
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    /// The obj_info lines written to the header.
+    pub fn obj_info(&self) -> &[String] {
+        &self.obj_info
+    }
+
+    /// Options that write a file with the format, comments and obj_info of `header`.
+    ///
+    /// Use this to re-export a parsed file without losing its metadata. The property types
+    /// of the source elements aren't kept, pass their definitions to
+    /// [`Self::with_element_def`] for that.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde_ply::{PlyFormat, PlyReader, SerializeOptions};
+    /// use std::io::Cursor;
+    ///
+    /// let ply_data = "ply\nformat binary_big_endian 1.0\ncomment scanned by X\nobj_info units mm\nend_header\n";
+    /// let reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    /// let options = SerializeOptions::from_header(reader.header());
+    /// assert_eq!(options.format(), PlyFormat::BinaryBigEndian);
+    /// assert_eq!(options.comments(), ["scanned by X"]);
+    /// assert_eq!(options.obj_info(), ["units mm"]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_header(header: &PlyHeader) -> Self {
+        Self::new(header.format)
+            .with_comments(header.comments.clone())
+            .with_obj_info(header.obj_info.clone())
+    }
+
     /// Add comments to the PLY header.
     ///
     /// Comments appear in the header section and are often used for metadata.
@@ -173,4 +419,402 @@ impl SerializeOptions {
         self.obj_info.extend(obj_info);
         self
     }
+
+    /// Transform property names before they are written to the header.
+    ///
+    /// Useful for remapping names dynamically where a static `#[serde(rename)]` won't do,
+    /// eg. mapping `nx` to `normal_x`. Element names are left untouched.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_ply::{to_string, SerializeOptions};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Vertex { nx: f32 }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Mesh { vertex: Vec<Vertex> }
+    ///
+    /// let mesh = Mesh { vertex: vec![Vertex { nx: 1.0 }] };
+    /// let options =
+    ///     SerializeOptions::ascii().property_name_transform(|name| name.replacen('n', "normal_", 1));
+    /// let ply = to_string(&mesh, options)?;
+    /// assert!(ply.contains("property float normal_x\n"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn property_name_transform(
+        mut self,
+        transform: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.property_name_transform = Some(NameTransform(Arc::new(transform)));
+        self
+    }
+
+    /// Set the count type of all list properties, `uchar` by default.
+    ///
+    /// A `ListCount` wrapper on a field or a definition passed to [`Self::with_element_def`]
+    /// still takes precedence for that property.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_ply::{to_string, ScalarType, SerializeOptions};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Face { vertex_indices: Vec<u32> }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Mesh { face: Vec<Face> }
+    ///
+    /// let mesh = Mesh { face: vec![Face { vertex_indices: vec![0, 1, 2] }] };
+    /// let options = SerializeOptions::ascii().default_list_count_type(ScalarType::U32);
+    /// let ply = to_string(&mesh, options)?;
+    /// assert!(ply.contains("property list uint32 uint32 vertex_indices\n"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn default_list_count_type(mut self, count_type: ScalarType) -> Self {
+        self.default_list_count_type = count_type;
+        self
+    }
+
+    /// Write an element with the exact property types of the given definition.
+    ///
+    /// Rows of the element with the same name are written with the property types of
+    /// `def` instead of the types of the Rust fields. Values are converted with checked
+    /// narrowing, so writing a `u32` field as a `ushort` property errors if the value doesn't
+    /// fit. Combine with [`crate::PlyReader::next_element_preserving`] to keep the schema of a
+    /// file stable in read-modify-write pipelines. Properties not in `def` keep their
+    /// regular type. An element without rows is declared with the properties of `def`, as
+    /// it has no row to take them from.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::{Deserialize, Serialize};
+    /// use serde_ply::{PlyReader, SerializeOptions};
+    /// use std::io::Cursor;
+    ///
+    /// #[derive(Deserialize, Serialize)]
+    /// struct Point { intensity: u32 }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Cloud { point: Vec<Point> }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement point 1\nproperty ushort intensity\nend_header\n300\n";
+    /// let mut reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    /// let (point, def) = reader.next_element_preserving::<Point>()?;
+    ///
+    /// let options = SerializeOptions::ascii().with_element_def(def);
+    /// let output = serde_ply::to_string(&Cloud { point }, options)?;
+    /// assert!(output.contains("property ushort intensity"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_element_def(mut self, def: ElementDef) -> Self {
+        self.element_defs.retain(|d| d.name != def.name);
+        self.element_defs.push(def);
+        self
+    }
+
+    /// Write property types of element definitions as they were spelled in the source file.
+    ///
+    /// By default types are written with their canonical names (`char`, `uchar`, ...), so
+    /// a source file using `int8` or `uint8` comes back with different type names. With
+    /// this enabled, properties of definitions passed to [`Self::with_element_def`] keep
+    /// their original spelling, unless their type was changed. Disabled by default.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::{Deserialize, Serialize};
+    /// use serde_ply::{PlyReader, SerializeOptions};
+    /// use std::io::Cursor;
+    ///
+    /// #[derive(Deserialize, Serialize)]
+    /// struct Point { label: i8 }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Cloud { point: Vec<Point> }
+    ///
+    /// let ply_data = "ply\nformat ascii 1.0\nelement point 1\nproperty int8 label\nend_header\n-3\n";
+    /// let mut reader = PlyReader::from_reader(Cursor::new(ply_data))?;
+    /// let (point, def) = reader.next_element_preserving::<Point>()?;
+    ///
+    /// let options = SerializeOptions::ascii()
+    ///     .with_element_def(def)
+    ///     .preserve_type_spelling(true);
+    /// let output = serde_ply::to_string(&Cloud { point }, options)?;
+    /// assert!(output.contains("property int8 label\n"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn preserve_type_spelling(mut self, preserve: bool) -> Self {
+        self.preserve_type_spelling = preserve;
+        self
+    }
+
+    /// Separator written between the values of a row in ASCII files. Defaults to a space.
+    ///
+    /// Readers split ASCII rows on any whitespace, so eg. a tab keeps the file valid while
+    /// making it easy to load as tab-separated values. The separator must be a space, tab
+    /// or form feed; line breaks would split the rows, so writing with any other
+    /// separator fails. Binary files are unaffected.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_ply::{to_string, SerializeOptions};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Point { x: f32, y: f32 }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Cloud { vertex: Vec<Point> }
+    ///
+    /// let cloud = Cloud { vertex: vec![Point { x: 1.0, y: 2.5 }] };
+    /// let ply = to_string(&cloud, SerializeOptions::ascii().ascii_separator('\t'))?;
+    /// assert!(ply.ends_with("end_header\n1\t2.5\n"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn ascii_separator(mut self, separator: char) -> Self {
+        self.ascii_separator = separator;
+        self
+    }
+
+    /// Leave elements without rows out of the file.
+    ///
+    /// An element with no rows has no row to take its properties from, so it is written as
+    /// `element name 0` without properties, unless [`Self::with_element_def`] gives its
+    /// definition. With this enabled, such elements are left out of the header instead.
+    /// Disabled by default.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_ply::SerializeOptions;
+    /// use std::collections::BTreeMap;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Point { x: f32 }
+    ///
+    /// let elements = BTreeMap::from([("edge", vec![]), ("point", vec![Point { x: 1.0 }])]);
+    /// let output = serde_ply::to_string(&elements, SerializeOptions::ascii())?;
+    /// assert!(output.contains("element edge 0\nelement point 1\n"));
+    ///
+    /// let output = serde_ply::to_string(&elements, SerializeOptions::ascii().skip_empty_elements(true))?;
+    /// assert!(!output.contains("edge"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn skip_empty_elements(mut self, skip: bool) -> Self {
+        self.skip_empty_elements = skip;
+        self
+    }
+
+    /// Order of the `comment` and `obj_info` lines in the header. Comments come first by
+    /// default.
+    ///
+    /// The PLY format doesn't fix the order, but some parsers only accept one of them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_ply::{to_string, MetaOrder, SerializeOptions};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Point { x: f32 }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Cloud { vertex: Vec<Point> }
+    ///
+    /// let cloud = Cloud { vertex: vec![Point { x: 1.0 }] };
+    /// let options = SerializeOptions::ascii()
+    ///     .with_comments(vec!["made by hand".to_string()])
+    ///     .with_obj_info(vec!["units mm".to_string()])
+    ///     .metadata_order(MetaOrder::ObjInfoFirst);
+    /// let ply = to_string(&cloud, options)?;
+    /// assert!(ply.contains("obj_info units mm\ncomment made by hand\n"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn metadata_order(mut self, order: MetaOrder) -> Self {
+        self.metadata_order = order;
+        self
+    }
+
+    /// Buffer the output of [`to_writer`] in a buffer of `capacity` bytes.
+    ///
+    /// Rows are written value by value, which is slow for writers where every write is a
+    /// system call, like an unbuffered `File`. With this set the output goes through a
+    /// `BufWriter`, which is flushed before [`to_writer`] returns. A capacity of 0, the
+    /// default, writes directly to the writer, which is best for writers that are already
+    /// buffered or write to memory.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_ply::{to_writer, SerializeOptions};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Point { x: f32 }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Cloud { vertex: Vec<Point> }
+    ///
+    /// let cloud = Cloud { vertex: vec![Point { x: 1.0 }] };
+    /// let path = std::env::temp_dir().join("serde_ply_buffer_hint.ply");
+    /// let file = std::fs::File::create(&path)?;
+    /// to_writer(&cloud, SerializeOptions::ascii().buffer_hint(64 * 1024), file)?;
+    /// assert!(std::fs::read_to_string(&path)?.ends_with("end_header\n1\n"));
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn buffer_hint(mut self, capacity: usize) -> Self {
+        self.buffer_capacity = capacity;
+        self
+    }
+
+    /// Write tuple and fixed size array properties as lists or as columns. Lists by default.
+    ///
+    /// With [`ArrayMode::Columns`], a field `rgba: [u8; 4]` is written as the scalar
+    /// properties `rgba_0` to `rgba_3`. [`Self::array_as_columns`] and
+    /// [`Self::array_as_list`] override the mode for a single property, as does a
+    /// `ListCount` wrapper, which always writes a list.
+    ///
+    /// Reading columns back into an array isn't supported, deserialize them into a struct
+    /// with a field for each of the N columns instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_ply::{to_string, ArrayMode, SerializeOptions};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Vertex { position: [f32; 3], rgb: [u8; 3] }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Cloud { vertex: Vec<Vertex> }
+    ///
+    /// let cloud = Cloud { vertex: vec![Vertex { position: [1.0, 2.0, 3.0], rgb: [255, 0, 0] }] };
+    /// let options = SerializeOptions::ascii()
+    ///     .default_array_mode(ArrayMode::Columns)
+    ///     .array_as_columns("vertex", "position", ["x", "y", "z"]);
+    /// let ply = to_string(&cloud, options)?;
+    /// assert!(ply.contains("property float x\nproperty float y\nproperty float z\n"));
+    /// assert!(ply.contains("property uchar rgb_0\nproperty uchar rgb_1\nproperty uchar rgb_2\n"));
+    /// assert!(ply.ends_with("end_header\n1 2 3 255 0 0\n"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn default_array_mode(mut self, mode: ArrayMode) -> Self {
+        self.default_array_mode = mode;
+        self
+    }
+
+    /// Write a fixed size array property as one scalar property per entry.
+    ///
+    /// Tuples and arrays like `[u8; 4]` are written as list properties by default. With
+    /// this set, `property` of `element` is written as the scalar properties `columns`
+    /// instead, eg. an `rgba` field as `uchar` properties `red`, `green`, `blue` and
+    /// `alpha`. The array must have as many entries as there are columns. To read the
+    /// columns back, deserialize into a struct with a field for each of them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_ply::{to_string, SerializeOptions};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Vertex { x: f32, rgba: [u8; 4] }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Cloud { vertex: Vec<Vertex> }
+    ///
+    /// let cloud = Cloud { vertex: vec![Vertex { x: 1.0, rgba: [255, 128, 0, 255] }] };
+    /// let ply = to_string(&cloud, SerializeOptions::ascii())?;
+    /// assert!(ply.contains("property list uint8 uint8 rgba\n"));
+    ///
+    /// let options =
+    ///     SerializeOptions::ascii().array_as_columns("vertex", "rgba", ["red", "green", "blue", "alpha"]);
+    /// let ply = to_string(&cloud, options)?;
+    /// assert!(ply.contains("property uchar red\nproperty uchar green\n"));
+    /// assert!(ply.ends_with("end_header\n1 255 128 0 255\n"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn array_as_columns(
+        self,
+        element: impl Into<String>,
+        property: impl Into<String>,
+        columns: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        let columns = columns.into_iter().map(Into::into).collect();
+        self.with_array_override(element.into(), property.into(), Some(columns))
+    }
+
+    /// Write a fixed size array property as a list, whatever the
+    /// [`Self::default_array_mode`].
+    pub fn array_as_list(self, element: impl Into<String>, property: impl Into<String>) -> Self {
+        self.with_array_override(element.into(), property.into(), None)
+    }
+
+    fn with_array_override(
+        mut self,
+        element: String,
+        property: String,
+        columns: Option<Vec<String>>,
+    ) -> Self {
+        self.array_overrides
+            .retain(|(e, p, _)| *e != element || *p != property);
+        self.array_overrides.push((element, property, columns));
+        self
+    }
+
+    /// Check option combinations that can't be rejected while building.
+    pub(crate) fn validate(&self) -> Result<(), SerializeError> {
+        if !matches!(self.ascii_separator, ' ' | '\t' | '\x0C') {
+            return Err(SerializeError::custom(format!(
+                "ASCII separator {:?} is not a space, tab or form feed",
+                self.ascii_separator
+            )));
+        }
+        check_count_type(self.default_list_count_type)?;
+        for def in &self.element_defs {
+            for prop in &def.properties {
+                if let PropertyType::List { count_type, .. } = prop.property_type {
+                    check_count_type(count_type)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Column names of array `property` of `element` with `len` entries, if it's written
+    /// as columns.
+    pub(crate) fn array_columns(
+        &self,
+        element: &str,
+        property: &str,
+        len: usize,
+    ) -> Option<Vec<String>> {
+        let columns = self
+            .array_overrides
+            .iter()
+            .find(|(e, p, _)| e == element && p == property);
+        match (columns, self.default_array_mode) {
+            (Some((_, _, columns)), _) => columns.clone(),
+            (None, ArrayMode::List) => None,
+            (None, ArrayMode::Columns) => {
+                Some((0..len).map(|i| format!("{property}_{i}")).collect())
+            }
+        }
+    }
+
+    pub(crate) fn has_element_defs(&self) -> bool {
+        !self.element_defs.is_empty()
+    }
+
+    pub(crate) fn element_def(&self, name: &str) -> Option<&ElementDef> {
+        self.element_defs.iter().find(|d| d.name == name)
+    }
+
+    pub(crate) fn transform_property_name<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        match &self.property_name_transform {
+            Some(transform) => Cow::Owned((transform.0)(name)),
+            None => Cow::Borrowed(name),
+        }
+    }
 }
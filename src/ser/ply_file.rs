@@ -1,11 +1,14 @@
 use crate::{
     ser::{
+        entries::EntrySeq,
+        header_collector::{extract_string_key, RowLayout},
         row::RowSerializer,
         val_writer::{AsciiValWriter, BinValWriter},
+        SerializeOptions,
     },
-    PlyFormat, SerializeError,
+    ElementDef, PlyFormat, SerializeError,
 };
-use std::{io::Write, marker::PhantomData};
+use std::io::Write;
 
 use byteorder::{BigEndian, LittleEndian};
 use serde::{
@@ -14,13 +17,24 @@ use serde::{
 };
 
 pub struct PlyReaderSerializer<W: Write> {
-    format: PlyFormat,
+    options: SerializeOptions,
     writer: W,
+    layouts: Vec<RowLayout>,
 }
 
 impl<W: Write> PlyReaderSerializer<W> {
-    pub fn new(format: PlyFormat, writer: W) -> Self {
-        Self { format, writer }
+    pub fn new(options: SerializeOptions, writer: W) -> Self {
+        Self {
+            options,
+            writer,
+            layouts: Vec::new(),
+        }
+    }
+
+    /// Check that the rows of each element write the properties of its layout.
+    pub(crate) fn with_layouts(mut self, layouts: Vec<RowLayout>) -> Self {
+        self.layouts = layouts;
+        self
     }
 }
 
@@ -28,10 +42,10 @@ impl<'a, W: Write> Serializer for &'a mut PlyReaderSerializer<W> {
     type Ok = ();
     type Error = SerializeError;
 
-    type SerializeMap = PlyMapSerializer<&'a mut W>;
-    type SerializeStruct = PlyMapSerializer<&'a mut W>;
+    type SerializeMap = PlyMapSerializer<'a, W>;
+    type SerializeStruct = PlyMapSerializer<'a, W>;
 
-    type SerializeSeq = serde::ser::Impossible<Self::Ok, Self::Error>;
+    type SerializeSeq = EntrySeq<PlyMapSerializer<'a, W>>;
     type SerializeTuple = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeTupleStruct = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeTupleVariant = serde::ser::Impossible<Self::Ok, Self::Error>;
@@ -145,8 +159,9 @@ impl<'a, W: Write> Serializer for &'a mut PlyReaderSerializer<W> {
         Err(SerializeError::custom("Invalid ply structure"))
     }
 
-    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        Err(SerializeError::custom("Invalid ply structure"))
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        // A sequence of (name, rows) elements, in order.
+        Ok(EntrySeq(self.serialize_map(len)?))
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
@@ -173,8 +188,10 @@ impl<'a, W: Write> Serializer for &'a mut PlyReaderSerializer<W> {
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
         Ok(PlyMapSerializer {
-            format: self.format,
+            options: &self.options,
             writer: &mut self.writer,
+            elem_def: None,
+            layouts: self.layouts.iter(),
         })
     }
 
@@ -184,8 +201,10 @@ impl<'a, W: Write> Serializer for &'a mut PlyReaderSerializer<W> {
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
         Ok(PlyMapSerializer {
-            format: self.format,
+            options: &self.options,
             writer: &mut self.writer,
+            elem_def: None,
+            layouts: self.layouts.iter(),
         })
     }
 
@@ -200,26 +219,33 @@ impl<'a, W: Write> Serializer for &'a mut PlyReaderSerializer<W> {
     }
 }
 
-pub struct PlyMapSerializer<W: Write> {
-    format: PlyFormat,
-    writer: W,
+pub struct PlyMapSerializer<'a, W: Write> {
+    options: &'a SerializeOptions,
+    writer: &'a mut W,
+    elem_def: Option<&'a ElementDef>,
+    // Layouts of the elements still to be written.
+    layouts: std::slice::Iter<'a, RowLayout>,
 }
 
-impl<W: Write> SerializeMap for PlyMapSerializer<W> {
+impl<W: Write> SerializeMap for PlyMapSerializer<'_, W> {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_key<T: Serialize + ?Sized>(&mut self, _key: &T) -> Result<(), Self::Error> {
-        // Capture the element name
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        // Only need the element name when it might have a fixed definition.
+        if self.options.has_element_defs() {
+            self.elem_def = self.options.element_def(&extract_string_key(key)?);
+        }
         Ok(())
     }
 
     fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
         // Each value should be a Vec<Row> representing an element
         value.serialize(ElementSerializer {
-            format: self.format,
-            writer: &mut self.writer,
-            _ph: PhantomData,
+            options: self.options,
+            writer: self.writer,
+            elem_def: self.elem_def,
+            layout: self.layouts.next(),
         })
     }
 
@@ -228,20 +254,21 @@ impl<W: Write> SerializeMap for PlyMapSerializer<W> {
     }
 }
 
-impl<W: Write> SerializeStruct for PlyMapSerializer<W> {
+impl<W: Write> SerializeStruct for PlyMapSerializer<'_, W> {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
     where
         T: Serialize + ?Sized,
     {
         // Each field represents an element (e.g., "vertex", "face")
         // The value should be a Vec<Row>
         value.serialize(ElementSerializer {
-            format: self.format,
-            writer: &mut self.writer,
-            _ph: PhantomData,
+            options: self.options,
+            writer: self.writer,
+            elem_def: self.options.element_def(key),
+            layout: self.layouts.next(),
         })
     }
 
@@ -251,9 +278,10 @@ impl<W: Write> SerializeStruct for PlyMapSerializer<W> {
 }
 
 struct ElementSerializer<'a, W: Write> {
-    format: PlyFormat,
+    options: &'a SerializeOptions,
     writer: &'a mut W,
-    _ph: PhantomData<&'a W>,
+    elem_def: Option<&'a ElementDef>,
+    layout: Option<&'a RowLayout>,
 }
 
 impl<'a, W: Write> Serializer for ElementSerializer<'a, W> {
@@ -379,10 +407,12 @@ impl<'a, W: Write> Serializer for ElementSerializer<'a, W> {
         let count = len.unwrap_or(0);
 
         Ok(ElementSeqSerializer {
-            format: self.format,
+            options: self.options,
             count,
             current: 0,
             writer: self.writer,
+            elem_def: self.elem_def,
+            layout: self.layout,
         })
     }
 
@@ -432,10 +462,12 @@ impl<'a, W: Write> Serializer for ElementSerializer<'a, W> {
 }
 
 pub struct ElementSeqSerializer<'a, W: Write> {
-    format: PlyFormat,
+    options: &'a SerializeOptions,
     count: usize,
     current: usize,
     writer: &'a mut W,
+    elem_def: Option<&'a ElementDef>,
+    layout: Option<&'a RowLayout>,
 }
 
 impl<W: Write> SerializeSeq for ElementSeqSerializer<'_, W> {
@@ -450,23 +482,13 @@ impl<W: Write> SerializeSeq for ElementSeqSerializer<'_, W> {
             return Err(serde::ser::Error::custom("too many elements"));
         }
 
-        match self.format {
-            PlyFormat::Ascii => {
-                value.serialize(&mut RowSerializer::new(AsciiValWriter::new(
-                    &mut self.writer,
-                )))?;
-            }
-            PlyFormat::BinaryBigEndian => {
-                value.serialize(&mut RowSerializer::new(BinValWriter::<_, BigEndian>::new(
-                    &mut self.writer,
-                )))?;
-            }
-            PlyFormat::BinaryLittleEndian => {
-                value.serialize(&mut RowSerializer::new(
-                    BinValWriter::<_, LittleEndian>::new(&mut self.writer),
-                ))?;
-            }
-        }
+        write_row(
+            &mut *self.writer,
+            self.options,
+            self.elem_def,
+            self.layout,
+            value,
+        )?;
         self.current += 1;
         Ok(())
     }
@@ -478,3 +500,40 @@ impl<W: Write> SerializeSeq for ElementSeqSerializer<'_, W> {
         Ok(())
     }
 }
+
+/// Write `row` as a row of an element, in the format of `options`.
+pub(crate) fn write_row<W: Write, T: Serialize + ?Sized>(
+    writer: &mut W,
+    options: &SerializeOptions,
+    elem_def: Option<&ElementDef>,
+    layout: Option<&RowLayout>,
+    row: &T,
+) -> Result<(), SerializeError> {
+    let count_type = options.default_list_count_type;
+    match options.format {
+        PlyFormat::Ascii => row.serialize(
+            &mut RowSerializer::new(
+                AsciiValWriter::new(writer, options.ascii_separator),
+                elem_def,
+                count_type,
+            )
+            .with_layout(layout),
+        ),
+        PlyFormat::BinaryBigEndian => row.serialize(
+            &mut RowSerializer::new(
+                BinValWriter::<_, BigEndian>::new(writer),
+                elem_def,
+                count_type,
+            )
+            .with_layout(layout),
+        ),
+        PlyFormat::BinaryLittleEndian => row.serialize(
+            &mut RowSerializer::new(
+                BinValWriter::<_, LittleEndian>::new(writer),
+                elem_def,
+                count_type,
+            )
+            .with_layout(layout),
+        ),
+    }
+}
@@ -1,23 +1,43 @@
-use crate::{ser::val_writer::ScalarWriter, ScalarType, SerializeError};
-
-use std::marker::PhantomData;
+use crate::{
+    ser::{
+        check_count_type,
+        header_collector::{extract_string_key, RowLayout},
+        list_count_type,
+        val_writer::ScalarWriter,
+    },
+    ElementDef, PropertyType, ScalarType, SerializeError,
+};
 
 use serde::{
-    ser::{Error, SerializeMap, SerializeSeq, SerializeStruct},
+    ser::{Error, SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple},
     Serialize, Serializer,
 };
 
-pub(crate) struct RowSerializer<W: ScalarWriter> {
+pub(crate) struct RowSerializer<'e, W: ScalarWriter> {
     pub val_writer: W,
+    elem_def: Option<&'e ElementDef>,
+    count_type: ScalarType,
+    layout: Option<&'e RowLayout>,
 }
 
-impl<W: ScalarWriter> RowSerializer<W> {
-    pub fn new(val_writer: W) -> Self {
-        Self { val_writer }
+impl<'e, W: ScalarWriter> RowSerializer<'e, W> {
+    pub fn new(val_writer: W, elem_def: Option<&'e ElementDef>, count_type: ScalarType) -> Self {
+        Self {
+            val_writer,
+            elem_def,
+            count_type,
+            layout: None,
+        }
+    }
+
+    /// Error if the row doesn't write the properties of `layout`, in order.
+    pub fn with_layout(mut self, layout: Option<&'e RowLayout>) -> Self {
+        self.layout = layout;
+        self
     }
 }
 
-impl<'a, W: ScalarWriter> Serializer for &'a mut RowSerializer<W> {
+impl<'a, W: ScalarWriter> Serializer for &'a mut RowSerializer<'_, W> {
     type Ok = ();
     type Error = SerializeError;
 
@@ -166,8 +186,13 @@ impl<'a, W: ScalarWriter> Serializer for &'a mut RowSerializer<W> {
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
         Ok(RowMapSerializer {
-            parent: self,
-            _marker: PhantomData,
+            val_writer: &mut self.val_writer,
+            elem_def: self.elem_def,
+            count_type: self.count_type,
+            target: None,
+            columns: None,
+            layout: self.layout,
+            written: 0,
         })
     }
 
@@ -177,8 +202,13 @@ impl<'a, W: ScalarWriter> Serializer for &'a mut RowSerializer<W> {
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
         Ok(RowMapSerializer {
-            parent: self,
-            _marker: PhantomData,
+            val_writer: &mut self.val_writer,
+            elem_def: self.elem_def,
+            count_type: self.count_type,
+            target: None,
+            columns: None,
+            layout: self.layout,
+            written: 0,
         })
     }
 
@@ -196,18 +226,92 @@ impl<'a, W: ScalarWriter> Serializer for &'a mut RowSerializer<W> {
 }
 
 pub(crate) struct RowMapSerializer<'a, W: ScalarWriter> {
-    parent: &'a mut RowSerializer<W>,
-    _marker: PhantomData<W>,
+    val_writer: &'a mut W,
+    elem_def: Option<&'a ElementDef>,
+    count_type: ScalarType,
+    target: Option<PropertyType>,
+    columns: Option<ArrayColumns<'a>>,
+    layout: Option<&'a RowLayout>,
+    // Number of properties written so far.
+    written: usize,
+}
+
+impl<'a, W: ScalarWriter> RowMapSerializer<'a, W> {
+    fn property_target(&self, key: &str) -> Option<PropertyType> {
+        self.elem_def
+            .and_then(|def| def.get_property(key))
+            .map(|p| p.property_type)
+    }
+
+    /// Check that the property or, for an array written as columns, the properties of
+    /// `key` are next in the header. Returns the columns of an array.
+    fn check_field(&mut self, key: &str) -> Result<Option<ArrayColumns<'a>>, SerializeError> {
+        let Some(names) = self.layout.and_then(|layout| layout.array_columns(key)) else {
+            self.check_next(key)?;
+            return Ok(None);
+        };
+        for name in names {
+            self.check_next(name)?;
+        }
+        Ok(Some(ArrayColumns {
+            names,
+            elem_def: self.elem_def,
+        }))
+    }
+
+    /// Check that `key` is the next property in the header. The header is collected from
+    /// the first row, so eg. `#[serde(skip_serializing_if)]` could otherwise shift the
+    /// values of later rows into the wrong properties.
+    fn check_next(&mut self, key: &str) -> Result<(), SerializeError> {
+        let Some(layout) = self.layout else {
+            return Ok(());
+        };
+        let expected = layout.properties.get(self.written);
+        self.written += 1;
+        match expected {
+            Some(name) if name == key => Ok(()),
+            Some(name) => Err(SerializeError::custom(format!(
+                "a row of element '{}' writes property '{key}' where the header has '{name}'. \
+                 All rows must write the same properties in the same order as the first row",
+                layout.element
+            ))),
+            None => Err(SerializeError::custom(format!(
+                "a row of element '{}' writes property '{key}', which the first row doesn't have. \
+                 All rows must write the same properties in the same order as the first row",
+                layout.element
+            ))),
+        }
+    }
+
+    /// Check that all properties in the header were written.
+    fn check_complete(&self) -> Result<(), SerializeError> {
+        let Some(layout) = self.layout else {
+            return Ok(());
+        };
+        match layout.properties.get(self.written) {
+            Some(name) => Err(SerializeError::custom(format!(
+                "a row of element '{}' doesn't write property '{name}', which the first row has. \
+                 All rows must write the same properties in the same order as the first row",
+                layout.element
+            ))),
+            None => Ok(()),
+        }
+    }
 }
 
 impl<W: ScalarWriter> SerializeMap for RowMapSerializer<'_, W> {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_key<T>(&mut self, _key: &T) -> Result<(), Self::Error>
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
     where
         T: Serialize + ?Sized,
     {
+        if self.elem_def.is_some() || self.layout.is_some() {
+            let key = extract_string_key(key)?;
+            self.columns = self.check_field(&key)?;
+            self.target = self.property_target(&key);
+        }
         Ok(())
     }
 
@@ -216,14 +320,17 @@ impl<W: ScalarWriter> SerializeMap for RowMapSerializer<'_, W> {
         T: Serialize + ?Sized,
     {
         value.serialize(PropertySerializer {
-            val_writer: &mut self.parent.val_writer,
-            count_type: ScalarType::U8,
+            val_writer: self.val_writer,
+            count_type: self.count_type,
+            target: self.target,
+            columns: self.columns,
         })?;
         Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        self.parent.val_writer.write_row_end()?;
+        self.check_complete()?;
+        self.val_writer.write_row_end()?;
         Ok(())
     }
 }
@@ -232,51 +339,156 @@ impl<W: ScalarWriter> SerializeStruct for RowMapSerializer<'_, W> {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
     where
         T: Serialize + ?Sized,
     {
+        let columns = self.check_field(key)?;
+        let target = self.property_target(key);
         value.serialize(PropertySerializer {
-            val_writer: &mut self.parent.val_writer,
-            count_type: ScalarType::U8,
+            val_writer: self.val_writer,
+            count_type: self.count_type,
+            target,
+            columns,
         })
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        self.parent.val_writer.write_row_end()?;
+        self.check_complete()?;
+        self.val_writer.write_row_end()?;
         Ok(())
     }
 }
 
+/// A scalar value on its way to being written as a possibly different type.
+#[derive(Clone, Copy)]
+enum Scalar {
+    Int(i64),
+    Float(f64),
+}
+
+/// Write a value as `target`, erroring if it doesn't fit.
+fn write_converted<W: ScalarWriter>(
+    val_writer: &mut W,
+    target: ScalarType,
+    value: Scalar,
+) -> Result<(), SerializeError> {
+    fn int<T: TryFrom<i64>>(value: Scalar, target: ScalarType) -> Result<T, SerializeError> {
+        let v = match value {
+            Scalar::Int(v) => v,
+            Scalar::Float(f)
+                if f.fract() == 0.0 && f >= i64::MIN as f64 && f <= i64::MAX as f64 =>
+            {
+                f as i64
+            }
+            Scalar::Float(f) => {
+                return Err(SerializeError::custom(format!(
+                    "Value {f} cannot be written as {target} without losing precision"
+                )))
+            }
+        };
+        T::try_from(v)
+            .map_err(|_| SerializeError::custom(format!("Value {v} is out of range for {target}")))
+    }
+
+    fn float(value: Scalar) -> f64 {
+        match value {
+            Scalar::Int(v) => v as f64,
+            Scalar::Float(f) => f,
+        }
+    }
+
+    match target {
+        ScalarType::I8 => val_writer.write_i8(int(value, target)?),
+        ScalarType::U8 => val_writer.write_u8(int(value, target)?),
+        ScalarType::I16 => val_writer.write_i16(int(value, target)?),
+        ScalarType::U16 => val_writer.write_u16(int(value, target)?),
+        ScalarType::I32 => val_writer.write_i32(int(value, target)?),
+        ScalarType::U32 => val_writer.write_u32(int(value, target)?),
+        ScalarType::F32 => {
+            let f = float(value);
+            if f.is_finite() && f.abs() > f32::MAX as f64 {
+                return Err(SerializeError::custom(format!(
+                    "Value {f} is out of range for {target}"
+                )));
+            }
+            val_writer.write_f32(f as f32)
+        }
+        ScalarType::F64 => val_writer.write_f64(float(value)),
+    }
+}
+
+/// The columns an array property is written as, see
+/// [`crate::SerializeOptions::array_as_columns`].
+#[derive(Clone, Copy)]
+struct ArrayColumns<'a> {
+    names: &'a [String],
+    elem_def: Option<&'a ElementDef>,
+}
+
+impl ArrayColumns<'_> {
+    /// Type forced by the element definition for the column of entry `index`.
+    fn target(&self, index: usize) -> Option<PropertyType> {
+        let name = self.names.get(index)?;
+        self.elem_def
+            .and_then(|def| def.get_property(name))
+            .map(|p| p.property_type)
+    }
+}
+
 struct PropertySerializer<'a, W: ScalarWriter> {
     val_writer: &'a mut W,
     count_type: ScalarType,
+    target: Option<PropertyType>,
+    columns: Option<ArrayColumns<'a>>,
+}
+
+impl<W: ScalarWriter> PropertySerializer<'_, W> {
+    /// Write a scalar as the type forced by an element definition.
+    fn write_target(self, value: Scalar) -> Result<(), SerializeError> {
+        match self.target {
+            Some(PropertyType::Scalar(t)) => write_converted(self.val_writer, t, value),
+            _ => Err(SerializeError::custom(
+                "Property is a list in the element definition, but the field is a scalar",
+            )),
+        }
+    }
 }
 
 impl<'a, W: ScalarWriter> Serializer for PropertySerializer<'a, W> {
     type Ok = ();
     type Error = SerializeError;
     type SerializeSeq = ListValuesSerializer<'a, W>;
-    type SerializeTuple = serde::ser::Impossible<Self::Ok, Self::Error>;
+    type SerializeTuple = ListValuesSerializer<'a, W>;
     type SerializeTupleStruct = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeTupleVariant = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeMap = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeStruct = serde::ser::Impossible<Self::Ok, Self::Error>;
     type SerializeStructVariant = serde::ser::Impossible<Self::Ok, Self::Error>;
 
-    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
-        Err(SerializeError::custom("Unsupported type: bool"))
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        // Bools are written as uchar 0 or 1.
+        self.serialize_u8(v as u8)
     }
 
     fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        if self.target.is_some() {
+            return self.write_target(Scalar::Int(v.into()));
+        }
         self.val_writer.write_i8(v)
     }
 
     fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        if self.target.is_some() {
+            return self.write_target(Scalar::Int(v.into()));
+        }
         self.val_writer.write_i16(v)
     }
 
     fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        if self.target.is_some() {
+            return self.write_target(Scalar::Int(v.into()));
+        }
         self.val_writer.write_i32(v)
     }
 
@@ -285,14 +497,23 @@ impl<'a, W: ScalarWriter> Serializer for PropertySerializer<'a, W> {
     }
 
     fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        if self.target.is_some() {
+            return self.write_target(Scalar::Int(v.into()));
+        }
         self.val_writer.write_u8(v)
     }
 
     fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        if self.target.is_some() {
+            return self.write_target(Scalar::Int(v.into()));
+        }
         self.val_writer.write_u16(v)
     }
 
     fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        if self.target.is_some() {
+            return self.write_target(Scalar::Int(v.into()));
+        }
         self.val_writer.write_u32(v)
     }
 
@@ -301,10 +522,16 @@ impl<'a, W: ScalarWriter> Serializer for PropertySerializer<'a, W> {
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        if self.target.is_some() {
+            return self.write_target(Scalar::Float(v.into()));
+        }
         self.val_writer.write_f32(v)
     }
 
     fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        if self.target.is_some() {
+            return self.write_target(Scalar::Float(v));
+        }
         self.val_writer.write_f64(v)
     }
 
@@ -357,12 +584,9 @@ impl<'a, W: ScalarWriter> Serializer for PropertySerializer<'a, W> {
         T: Serialize + ?Sized,
     {
         // Check if this is a ListCount wrapper type
-        self.count_type = match name {
-            "ListCountU8" => ScalarType::U8,
-            "ListCountU16" => ScalarType::U16,
-            "ListCountU32" => ScalarType::U32,
-            _ => ScalarType::U8,
-        };
+        if let Some(count_type) = list_count_type(name) {
+            self.count_type = count_type;
+        }
         value.serialize(self)
     }
 
@@ -379,11 +603,28 @@ impl<'a, W: ScalarWriter> Serializer for PropertySerializer<'a, W> {
         Err(SerializeError::custom("Unsupported type: newtype_variant"))
     }
 
-    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+    fn serialize_seq(mut self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
         let count = len.ok_or_else(|| {
             SerializeError::custom("Unsupported type: sequence without known length")
         })?;
 
+        let item_target = match self.target {
+            Some(PropertyType::List {
+                count_type,
+                data_type,
+            }) => {
+                self.count_type = count_type;
+                Some(PropertyType::Scalar(data_type))
+            }
+            Some(PropertyType::Scalar(_)) => {
+                return Err(SerializeError::custom(
+                    "Property is a scalar in the element definition, but the field is a list",
+                ))
+            }
+            None => None,
+        };
+
+        check_count_type(self.count_type)?;
         // Check if count fits in the specified count type
         let max_count = match self.count_type {
             ScalarType::I8 => i8::MAX as usize,
@@ -392,8 +633,7 @@ impl<'a, W: ScalarWriter> Serializer for PropertySerializer<'a, W> {
             ScalarType::U16 => u16::MAX as usize,
             ScalarType::I32 => i32::MAX as usize,
             ScalarType::U32 => u32::MAX as usize,
-            ScalarType::F32 => u32::MAX as usize,
-            ScalarType::F64 => u32::MAX as usize,
+            ScalarType::F32 | ScalarType::F64 => unreachable!("checked above"),
         };
 
         if count > max_count {
@@ -411,17 +651,34 @@ impl<'a, W: ScalarWriter> Serializer for PropertySerializer<'a, W> {
             ScalarType::U16 => self.val_writer.write_u16(count as u16)?,
             ScalarType::I32 => self.val_writer.write_i32(count as i32)?,
             ScalarType::U32 => self.val_writer.write_u32(count as u32)?,
-            ScalarType::F32 => self.val_writer.write_f32(count as f32)?,
-            ScalarType::F64 => self.val_writer.write_f64(count as f64)?,
+            ScalarType::F32 | ScalarType::F64 => unreachable!("checked above"),
         }
 
         Ok(ListValuesSerializer {
             val_writer: self.val_writer,
+            target: item_target,
+            columns: None,
+            index: 0,
         })
     }
 
-    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        Err(serde::ser::Error::custom("tuples not supported"))
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        let Some(columns) = self.columns else {
+            return self.serialize_seq(Some(len));
+        };
+        if columns.names.len() != len {
+            return Err(SerializeError::custom(format!(
+                "Array of {len} entries is written as {} columns",
+                columns.names.len()
+            )));
+        }
+        // Columns are separate properties, without a count.
+        Ok(ListValuesSerializer {
+            val_writer: self.val_writer,
+            target: None,
+            columns: Some(columns),
+            index: 0,
+        })
     }
 
     fn serialize_tuple_struct(
@@ -467,6 +724,10 @@ impl<'a, W: ScalarWriter> Serializer for PropertySerializer<'a, W> {
 
 pub(crate) struct ListValuesSerializer<'a, W: ScalarWriter> {
     val_writer: &'a mut W,
+    target: Option<PropertyType>,
+    // Set for an array written as columns, which each have their own target.
+    columns: Option<ArrayColumns<'a>>,
+    index: usize,
 }
 
 impl<W: ScalarWriter> SerializeSeq for ListValuesSerializer<'_, W> {
@@ -477,9 +738,16 @@ impl<W: ScalarWriter> SerializeSeq for ListValuesSerializer<'_, W> {
     where
         T: Serialize + ?Sized,
     {
+        let target = match self.columns {
+            Some(columns) => columns.target(self.index),
+            None => self.target,
+        };
+        self.index += 1;
         value.serialize(PropertySerializer {
             val_writer: self.val_writer,
             count_type: ScalarType::U8,
+            target,
+            columns: None,
         })
     }
 
@@ -487,3 +755,19 @@ impl<W: ScalarWriter> SerializeSeq for ListValuesSerializer<'_, W> {
         Ok(())
     }
 }
+
+impl<W: ScalarWriter> SerializeTuple for ListValuesSerializer<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        SerializeSeq::end(self)
+    }
+}
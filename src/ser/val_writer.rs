@@ -23,13 +23,15 @@ impl<W: Write, E: ByteOrder> BinValWriter<W, E> {
 
 pub(crate) struct AsciiValWriter<W: Write> {
     writer: W,
+    separator: char,
     first_in_row: bool,
 }
 
 impl<W: Write> AsciiValWriter<W> {
-    pub(crate) fn new(writer: W) -> Self {
+    pub(crate) fn new(writer: W, separator: char) -> Self {
         Self {
             writer,
+            separator,
             first_in_row: true,
         }
     }
@@ -89,7 +91,7 @@ impl<W: Write, E: ByteOrder> ScalarWriter for BinValWriter<W, E> {
 impl<W: Write> AsciiValWriter<W> {
     fn write_field(&mut self, val: impl Display) -> Result<(), SerializeError> {
         if !self.first_in_row {
-            write!(self.writer, " ")?;
+            write!(self.writer, "{}", self.separator)?;
         }
         self.first_in_row = false;
         write!(self.writer, "{val}")?;
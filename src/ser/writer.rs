@@ -0,0 +1,464 @@
+//! Writing a file one row at a time.
+
+use std::io::{Seek, SeekFrom, Write};
+
+use serde::{ser::Error as _, Serialize};
+
+use crate::{
+    ser::{header_collector::RowLayout, map::write_header, ply_file::write_row},
+    ElementDef, PlyHeader, SerializeError, SerializeOptions,
+};
+
+/// Writer for files whose rows are produced one at a time, eg. streamed from another
+/// source, rather than collected into a value for [`crate::to_writer`].
+///
+/// The header is written up front from the element definitions. Rows are then written to
+/// the elements in header order, calling [`Self::next_element`] to move on to the next
+/// element and [`Self::finish`] once all rows are written. Each element must get exactly
+/// the number of rows its definition declares.
+///
+/// Rows are serialized like rows passed to [`crate::to_writer`], so the output is the same
+/// byte for byte. Every row has to write the properties of its element in header order,
+/// and values are converted to the declared property types, including the count types of
+/// lists. Values are written with many small writes, so pass a buffered writer when
+/// writing to eg. a `File`.
+///
+/// Elements whose number of rows isn't known up front can be written with
+/// [`Self::start_element_unbounded`], on a writer created with [`Self::unbounded`] or
+/// [`Self::unbounded_seekable`].
+///
+/// A row is the unit of flushing: the writer is only flushed between rows, by
+/// [`Self::flush`] or by the policies of [`Self::flush_every_rows`] and
+/// [`Self::flush_every_bytes`], so a consumer on the other end of eg. a socket doesn't
+/// wait on rows sitting in a `BufWriter`, and never gets part of a row at a flush.
+///
+/// # Example
+/// ```rust
+/// use serde::Serialize;
+/// use serde_ply::{PlyReader, PlyWriter};
+///
+/// #[derive(Serialize)]
+/// struct Vertex { x: f32, y: f32 }
+///
+/// #[derive(Serialize)]
+/// struct Face { vertex_indices: Vec<u32> }
+///
+/// let header = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\n\
+///               element face 1\nproperty list uchar int vertex_indices\nend_header\n";
+/// let reader = PlyReader::from_reader(header.as_bytes())?;
+///
+/// let mut writer = PlyWriter::new(Vec::new(), reader.header())?;
+/// for i in 0..3 {
+///     writer.write_row(&Vertex { x: i as f32, y: 1.0 })?;
+/// }
+/// writer.next_element()?;
+/// writer.write_row(&Face { vertex_indices: vec![0, 1, 2] })?;
+/// let bytes = writer.finish()?;
+///
+/// let text = String::from_utf8(bytes)?;
+/// assert!(text.ends_with("end_header\n0 1\n1 1\n2 1\n3 0 1 2\n"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct PlyWriter<W: Write> {
+    writer: W,
+    options: SerializeOptions,
+    elements: Vec<ElementDef>,
+    layouts: Vec<RowLayout>,
+    // Index of the element rows are written to, and the number of rows written to it.
+    current: usize,
+    rows: usize,
+    // Whether each element was started with `start_element_unbounded`.
+    unbounded: Vec<bool>,
+    counts: Counts<W>,
+    // Flush once this many rows or bytes were written since the last flush.
+    flush_rows: Option<usize>,
+    flush_bytes: Option<usize>,
+    unflushed_rows: usize,
+    unflushed_bytes: usize,
+}
+
+/// How the element counts get into the header.
+enum Counts<W> {
+    /// The declared counts are written up front.
+    Declared,
+    /// The header and rows are kept in memory until `finish` writes them.
+    Buffered { header: PlyHeader, body: Vec<u8> },
+    /// The counts are zero padded placeholders, overwritten by `patch`. The header starts at
+    /// `start`, and each count at its offset from there.
+    Placeholders {
+        start: u64,
+        offsets: Vec<usize>,
+        patch: fn(&mut W, u64, &[u8]) -> std::io::Result<()>,
+    },
+}
+
+/// Width of a count placeholder, enough for any `usize`.
+const COUNT_WIDTH: usize = 20;
+
+impl<W: Write> PlyWriter<W> {
+    /// Write the header of `header`, in its format and with its comments and obj_info.
+    pub fn new(writer: W, header: &PlyHeader) -> Result<Self, SerializeError> {
+        let options = SerializeOptions::new(header.format);
+        Self::start(writer, header, options, Counts::Declared)
+    }
+
+    /// Write a header with `elements`, in the format and with the metadata of `options`.
+    ///
+    /// Definitions passed to [`SerializeOptions::with_element_def`] replace the element
+    /// of the same name, and arrays of [`SerializeOptions::array_as_columns`] are written
+    /// to the columns of their element.
+    pub fn with_options(
+        writer: W,
+        elements: Vec<ElementDef>,
+        options: SerializeOptions,
+    ) -> Result<Self, SerializeError> {
+        let header = options_header(elements, &options);
+        Self::start(writer, &header, options, Counts::Declared)
+    }
+
+    /// Like [`Self::with_options`], but elements can be written with
+    /// [`Self::start_element_unbounded`].
+    ///
+    /// Nothing is written until [`Self::finish`], which writes the header with the number
+    /// of rows each element got, and then the rows. All rows are kept in memory until then,
+    /// so prefer [`Self::unbounded_seekable`] for large files. The output is the same as
+    /// [`Self::with_options`] with the final counts.
+    pub fn unbounded(
+        writer: W,
+        elements: Vec<ElementDef>,
+        options: SerializeOptions,
+    ) -> Result<Self, SerializeError> {
+        let header = options_header(elements, &options);
+        let counts = Counts::Buffered {
+            header: header.clone(),
+            body: Vec::new(),
+        };
+        Self::start(writer, &header, options, counts)
+    }
+
+    fn start(
+        mut writer: W,
+        header: &PlyHeader,
+        mut options: SerializeOptions,
+        mut counts: Counts<W>,
+    ) -> Result<Self, SerializeError> {
+        let mut elements: Vec<ElementDef> = Vec::with_capacity(header.elem_defs.len());
+        for def in &header.elem_defs {
+            if elements.iter().any(|e| e.name == def.name) {
+                return Err(SerializeError::custom(format!(
+                    "element '{}' is declared twice, element names must be unique",
+                    def.name
+                )));
+            }
+            elements.push(options.element_def(&def.name).unwrap_or(def).clone());
+        }
+        // Validated along with the options, so list count types are checked.
+        for def in &elements {
+            options = options.with_element_def(def.clone());
+        }
+        options.validate()?;
+        match &mut counts {
+            Counts::Declared => {
+                write_header(&mut writer, header, &options, 0)?;
+            }
+            // Written by `finish`, once the counts are known.
+            Counts::Buffered { .. } => {}
+            Counts::Placeholders { offsets, .. } => {
+                *offsets = write_header(&mut writer, header, &options, COUNT_WIDTH)?;
+            }
+        }
+
+        let layouts = elements
+            .iter()
+            .map(|def| RowLayout {
+                element: def.name.clone(),
+                properties: def.properties.iter().map(|p| p.name.clone()).collect(),
+                arrays: options
+                    .array_overrides
+                    .iter()
+                    .filter(|(element, _, _)| *element == def.name)
+                    .filter_map(|(_, property, columns)| Some((property.clone(), columns.clone()?)))
+                    .collect(),
+            })
+            .collect();
+        Ok(Self {
+            writer,
+            options,
+            elements,
+            layouts,
+            current: 0,
+            rows: 0,
+            unbounded: vec![false; header.elem_defs.len()],
+            counts,
+            flush_rows: None,
+            flush_bytes: None,
+            unflushed_rows: 0,
+            unflushed_bytes: 0,
+        })
+    }
+
+    /// Flush the writer once `rows` rows were written since the last flush, counting the
+    /// rows of all elements. A count of 0 flushes after every row.
+    pub fn flush_every_rows(mut self, rows: usize) -> Self {
+        self.flush_rows = Some(rows);
+        self
+    }
+
+    /// Flush the writer after the row that brings the bytes of the rows written since the
+    /// last flush to `bytes` or more. Rows aren't split, so a flush can hold more than
+    /// `bytes`.
+    pub fn flush_every_bytes(mut self, bytes: usize) -> Self {
+        self.flush_bytes = Some(bytes);
+        self
+    }
+
+    /// The element rows are written to, `None` if the header has no elements.
+    pub fn element(&self) -> Option<&ElementDef> {
+        self.elements.get(self.current)
+    }
+
+    /// Number of rows written to the current element.
+    pub fn rows_written(&self) -> usize {
+        self.rows
+    }
+
+    /// Write a row of the current element.
+    ///
+    /// Errors if the element already has all its rows, or if the row doesn't write the
+    /// properties of the element in order. A row that fails may be partially written, so
+    /// the output is unusable after an error.
+    pub fn write_row<T: Serialize + ?Sized>(&mut self, row: &T) -> Result<(), SerializeError> {
+        let Some(elem_def) = self.elements.get(self.current) else {
+            return Err(SerializeError::custom(
+                "the header has no elements to write",
+            ));
+        };
+        if self.rows == elem_def.count && !self.unbounded[self.current] {
+            return Err(SerializeError::custom(format!(
+                "element '{}' already has all its {} rows, call next_element to write the \
+                 next element",
+                elem_def.name, elem_def.count
+            )));
+        }
+        let layout = self.layouts.get(self.current);
+        let written = match &mut self.counts {
+            Counts::Buffered { body, .. } => {
+                let len = body.len();
+                write_row(body, &self.options, Some(elem_def), layout, row)?;
+                body.len() - len
+            }
+            _ => {
+                let mut counted = Counted(&mut self.writer, 0);
+                write_row(&mut counted, &self.options, Some(elem_def), layout, row)?;
+                counted.1
+            }
+        };
+        self.rows += 1;
+        self.unflushed_rows += 1;
+        self.unflushed_bytes += written;
+        if self
+            .flush_rows
+            .is_some_and(|rows| self.unflushed_rows >= rows)
+            || self
+                .flush_bytes
+                .is_some_and(|bytes| self.unflushed_bytes >= bytes)
+        {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write each row of `rows` to the current element, see [`Self::write_row`].
+    pub fn write_rows<T: Serialize>(
+        &mut self,
+        rows: impl IntoIterator<Item = T>,
+    ) -> Result<(), SerializeError> {
+        for row in rows {
+            self.write_row(&row)?;
+        }
+        Ok(())
+    }
+
+    /// Flush the writer, so the header and the rows written so far reach their destination.
+    ///
+    /// Rows are written whole, so only complete rows are flushed. The rows of
+    /// [`Self::unbounded`] writers are kept in memory until [`Self::finish`] writes them
+    /// after the header, this only flushes the writer.
+    pub fn flush(&mut self) -> Result<(), SerializeError> {
+        self.writer.flush()?;
+        self.unflushed_rows = 0;
+        self.unflushed_bytes = 0;
+        Ok(())
+    }
+
+    /// Move on to the next element in the header.
+    ///
+    /// Errors if the current element doesn't have all its rows, or if it's the last one.
+    pub fn next_element(&mut self) -> Result<(), SerializeError> {
+        self.check_complete()?;
+        if self.current + 1 >= self.elements.len() {
+            return Err(SerializeError::custom(match self.element() {
+                Some(def) => format!("element '{}' is the last element of the header", def.name),
+                None => "the header has no elements to write".to_string(),
+            }));
+        }
+        self.end_element();
+        self.current += 1;
+        self.rows = 0;
+        Ok(())
+    }
+
+    /// Move on to element `name`, which is written with any number of rows rather than
+    /// the number it declares.
+    ///
+    /// `name` must be the next element, or the current one if nothing was written to it
+    /// yet. Only writers created with [`Self::unbounded`] or [`Self::unbounded_seekable`]
+    /// can do this, as the count in the header has to be written after the rows.
+    pub fn start_element_unbounded(&mut self, name: &str) -> Result<(), SerializeError> {
+        if matches!(self.counts, Counts::Declared) {
+            return Err(SerializeError::custom(format!(
+                "element '{name}' can't be written without a count, create the writer with \
+                 PlyWriter::unbounded or PlyWriter::unbounded_seekable"
+            )));
+        }
+        let unstarted = self.rows == 0 && self.unbounded.get(self.current) == Some(&false);
+        let target = match self.element() {
+            Some(def) if def.name == name && unstarted => self.current,
+            _ => self.current + 1,
+        };
+        match self.elements.get(target) {
+            Some(def) if def.name == name => {}
+            next => {
+                return Err(SerializeError::custom(match next {
+                    Some(def) => format!(
+                        "element '{name}' isn't the next element to write, '{}' is",
+                        def.name
+                    ),
+                    None => format!("the header has no element '{name}' left to write"),
+                }))
+            }
+        }
+        if target != self.current {
+            self.next_element()?;
+        }
+        self.unbounded[self.current] = true;
+        Ok(())
+    }
+
+    /// Flush the writer and return it.
+    ///
+    /// Errors if an element doesn't have all its rows. Elements after the current one
+    /// must not declare any rows.
+    pub fn finish(mut self) -> Result<W, SerializeError> {
+        self.check_complete()?;
+        let mut rest = self.elements.iter().skip(self.current + 1);
+        if let Some(def) = rest.find(|def| def.count > 0) {
+            return Err(SerializeError::custom(format!(
+                "element '{}' has 0 of its {} rows",
+                def.name, def.count
+            )));
+        }
+        if !self.elements.is_empty() {
+            self.end_element();
+        }
+        match &mut self.counts {
+            Counts::Declared => {}
+            Counts::Buffered { header, body } => {
+                header.elem_defs.clone_from(&self.elements);
+                let mut options = self.options.clone();
+                for def in &self.elements {
+                    options = options.with_element_def(def.clone());
+                }
+                write_header(&mut self.writer, header, &options, 0)?;
+                self.writer.write_all(body)?;
+            }
+            Counts::Placeholders {
+                start,
+                offsets,
+                patch,
+            } => {
+                for (def, &offset) in self.elements.iter().zip(offsets.iter()) {
+                    let count = format!("{:0>COUNT_WIDTH$}", def.count);
+                    patch(&mut self.writer, *start + offset as u64, count.as_bytes())?;
+                }
+            }
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Record the rows written to the current element as its count, if it's unbounded.
+    fn end_element(&mut self) {
+        if self.unbounded[self.current] {
+            self.elements[self.current].count = self.rows;
+        }
+    }
+
+    /// Error unless the current element has all its rows.
+    fn check_complete(&self) -> Result<(), SerializeError> {
+        match self.element() {
+            Some(def) if self.rows < def.count && !self.unbounded[self.current] => {
+                Err(SerializeError::custom(format!(
+                    "element '{}' has {} of its {} rows",
+                    def.name, self.rows, def.count
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<W: Write + Seek> PlyWriter<W> {
+    /// Like [`Self::with_options`], but elements can be written with
+    /// [`Self::start_element_unbounded`].
+    ///
+    /// The header is written up front with every element count padded with leading zeros
+    /// to a fixed width, eg. `element vertex 00000000000000000003`, and [`Self::finish`]
+    /// seeks back to overwrite them with the number of rows each element got. Rows go
+    /// straight to the writer, but the header is larger than that of
+    /// [`Self::with_options`]. Leading zeros are accepted by any integer parser, so the
+    /// counts read the same. A writer that isn't finished leaves the declared counts in
+    /// the header.
+    pub fn unbounded_seekable(
+        mut writer: W,
+        elements: Vec<ElementDef>,
+        options: SerializeOptions,
+    ) -> Result<Self, SerializeError> {
+        let counts = Counts::Placeholders {
+            start: writer.stream_position()?,
+            offsets: Vec::new(),
+            patch: patch_at::<W>,
+        };
+        let header = options_header(elements, &options);
+        Self::start(writer, &header, options, counts)
+    }
+}
+
+/// A header with `elements`, whose format and metadata come from `options`.
+fn options_header(elements: Vec<ElementDef>, options: &SerializeOptions) -> PlyHeader {
+    PlyHeader::new(options.format(), elements)
+}
+
+/// Writer that counts the bytes written to the writer it wraps.
+struct Counted<'a, W>(&'a mut W, usize);
+
+impl<W: Write> Write for Counted<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.0.write(buf)?;
+        self.1 += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Overwrite the bytes at `position` and return to where the writer was.
+fn patch_at<W: Write + Seek>(writer: &mut W, position: u64, bytes: &[u8]) -> std::io::Result<()> {
+    let end = writer.stream_position()?;
+    writer.seek(SeekFrom::Start(position))?;
+    writer.write_all(bytes)?;
+    writer.seek(SeekFrom::Start(end))?;
+    Ok(())
+}
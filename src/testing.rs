@@ -0,0 +1,243 @@
+//! Deterministic PLY fixtures for tests and benchmarks.
+//!
+//! Everything here is generated from a seed with a small PRNG that is part of the crate,
+//! so a given seed produces the same rows, and the same file bytes, on every platform and
+//! for every user. Enabled with the `test-util` feature.
+//!
+//! # Example
+//! ```rust
+//! use serde_ply::testing::{fixture_bytes, gen_mesh, Mesh};
+//! use serde_ply::PlyFormat;
+//!
+//! let bytes = fixture_bytes(PlyFormat::BinaryLittleEndian, 7, (100, 50))?;
+//! let mesh: Mesh = serde_ply::from_bytes(&bytes)?;
+//! assert_eq!(mesh, gen_mesh(7, 100, 50));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::{fs::File, io::BufWriter, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ElementDef, PlyFormat, PlyProperty, PropertyType, ScalarType, SerializeError, SerializeOptions,
+};
+
+/// Small xorshift PRNG. Its output for a seed is part of the fixture format and won't
+/// change.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// Create a generator. Any seed works, including 0.
+    pub fn new(seed: u64) -> Self {
+        // Scramble the seed so nearby seeds give unrelated streams.
+        Self((seed ^ 0x9E37_79B9_7F4A_7C15).wrapping_mul(0xBF58_476D_1CE4_E5B9) | 1)
+    }
+
+    /// Next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// Uniform value in `0..n`. `n` must be non-zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    /// Uniform value in `[0, 1)`, with 24 bits of precision so it is exact in an `f32`.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u32 << 24) as f32
+    }
+}
+
+/// A vertex with a position and a color.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vertex {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+/// A polygon referencing vertices by index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Face {
+    pub vertex_indices: Vec<u32>,
+}
+
+/// A mesh with `vertex` and `face` elements.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mesh {
+    pub vertex: Vec<Vertex>,
+    pub face: Vec<Face>,
+}
+
+/// Generate `n` vertices with positions in `[-1, 1)`.
+pub fn gen_vertices(seed: u64, n: usize) -> Vec<Vertex> {
+    let mut rng = Rng::new(seed);
+    (0..n)
+        .map(|_| Vertex {
+            x: rng.next_f32() * 2.0 - 1.0,
+            y: rng.next_f32() * 2.0 - 1.0,
+            z: rng.next_f32() * 2.0 - 1.0,
+            red: rng.next_u64() as u8,
+            green: rng.next_u64() as u8,
+            blue: rng.next_u64() as u8,
+        })
+        .collect()
+}
+
+/// Generate a mesh with `n_verts` vertices and `n_faces` faces of 3 to 5 vertices each.
+///
+/// Faces only reference existing vertices, so they are empty if there are no vertices.
+pub fn gen_mesh(seed: u64, n_verts: usize, n_faces: usize) -> Mesh {
+    let vertex = gen_vertices(seed, n_verts);
+    // Faces use their own stream so the vertices don't depend on the face count.
+    let mut rng = Rng::new(seed.wrapping_add(1));
+    let face = (0..n_faces)
+        .map(|_| {
+            let len = if n_verts == 0 { 0 } else { 3 + rng.below(3) };
+            Face {
+                vertex_indices: (0..len).map(|_| rng.below(n_verts as u64) as u32).collect(),
+            }
+        })
+        .collect();
+    Mesh { vertex, face }
+}
+
+/// Options with the schema of the fixture files, so it doesn't depend on the rows.
+fn fixture_options(format: PlyFormat, sizes: (usize, usize)) -> SerializeOptions {
+    let prop = |name: &str, property_type| PlyProperty::new(name, property_type);
+    let vertex = ElementDef {
+        name: "vertex".to_string(),
+        count: sizes.0,
+        properties: ["x", "y", "z"]
+            .map(|name| prop(name, PropertyType::Scalar(ScalarType::F32)))
+            .into_iter()
+            .chain(
+                ["red", "green", "blue"]
+                    .map(|name| prop(name, PropertyType::Scalar(ScalarType::U8))),
+            )
+            .collect(),
+    };
+    let face = ElementDef {
+        name: "face".to_string(),
+        count: sizes.1,
+        properties: vec![prop(
+            "vertex_indices",
+            PropertyType::List {
+                count_type: ScalarType::U8,
+                data_type: ScalarType::U32,
+            },
+        )],
+    };
+    SerializeOptions::new(format)
+        .with_element_def(vertex)
+        .with_element_def(face)
+}
+
+/// Bytes of the PLY file for `gen_mesh(seed, sizes.0, sizes.1)`.
+pub fn fixture_bytes(
+    format: PlyFormat,
+    seed: u64,
+    sizes: (usize, usize),
+) -> Result<Vec<u8>, SerializeError> {
+    let mesh = gen_mesh(seed, sizes.0, sizes.1);
+    crate::to_bytes(&mesh, fixture_options(format, sizes))
+}
+
+/// Write the PLY file for `gen_mesh(seed, sizes.0, sizes.1)` to `path`.
+pub fn write_fixture(
+    path: impl AsRef<Path>,
+    format: PlyFormat,
+    seed: u64,
+    sizes: (usize, usize),
+) -> Result<(), SerializeError> {
+    let mesh = gen_mesh(seed, sizes.0, sizes.1);
+    let writer = BufWriter::new(File::create(path)?);
+    crate::to_writer(&mesh, fixture_options(format, sizes), writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// FNV-1a, to pin fixture bytes without committing them.
+    fn fnv1a(bytes: &[u8]) -> u64 {
+        bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+            (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
+
+    #[test]
+    fn test_fixture_golden_hashes() {
+        let cases = [
+            (PlyFormat::Ascii, 0, (10, 5), 0x5f8b_ff99_1474_1ea6),
+            (PlyFormat::Ascii, 42, (1000, 500), 0x048a_a367_fd6d_ca6b),
+            (
+                PlyFormat::BinaryLittleEndian,
+                1,
+                (100, 50),
+                0x86ee_5691_c1d4_794a,
+            ),
+            (
+                PlyFormat::BinaryLittleEndian,
+                42,
+                (1000, 500),
+                0xb53f_e2fb_7efa_cc88,
+            ),
+            (
+                PlyFormat::BinaryBigEndian,
+                42,
+                (1000, 500),
+                0x0759_614e_87e3_1700,
+            ),
+            (
+                PlyFormat::BinaryLittleEndian,
+                3,
+                (0, 4),
+                0x5f7a_eef9_4f32_1b90,
+            ),
+        ];
+        for (format, seed, sizes, expected) in cases {
+            let bytes = fixture_bytes(format, seed, sizes).unwrap();
+            assert_eq!(
+                fnv1a(&bytes),
+                expected,
+                "{format} seed {seed} sizes {sizes:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fixture_roundtrip() {
+        for format in [
+            PlyFormat::Ascii,
+            PlyFormat::BinaryLittleEndian,
+            PlyFormat::BinaryBigEndian,
+        ] {
+            let bytes = fixture_bytes(format, 9, (64, 32)).unwrap();
+            let mesh: Mesh = crate::from_bytes(&bytes).unwrap();
+            assert_eq!(mesh, gen_mesh(9, 64, 32));
+        }
+        let path = std::env::temp_dir().join("serde_ply_fixture_roundtrip.ply");
+        write_fixture(&path, PlyFormat::BinaryBigEndian, 9, (64, 32)).unwrap();
+        let written = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            written,
+            fixture_bytes(PlyFormat::BinaryBigEndian, 9, (64, 32)).unwrap()
+        );
+
+        assert_ne!(gen_vertices(1, 8), gen_vertices(2, 8));
+        assert_eq!(gen_mesh(5, 10, 3).vertex, gen_vertices(5, 10));
+    }
+}
@@ -0,0 +1,107 @@
+//! Field transforms for `#[serde(with = "...")]`.
+//!
+//! Each module converts between the type stored in the file and a more convenient type
+//! in the struct, in both directions, so rows read with a transform are written back
+//! with the same property types. Enabled with the `with` feature.
+//!
+//! # Example
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Vertex {
+//!     #[serde(with = "serde_ply::with::u8_normalized")]
+//!     red: f32,
+//!     #[serde(with = "serde_ply::with::log_scale")]
+//!     scale_0: f32,
+//! }
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Splat { vertex: Vec<Vertex> }
+//!
+//! let ply = "ply\nformat ascii 1.0\nelement vertex 1\nproperty uchar red\nproperty float scale_0\nend_header\n255 0\n";
+//! let splat: Splat = serde_ply::from_str(ply)?;
+//! assert_eq!((splat.vertex[0].red, splat.vertex[0].scale_0), (1.0, 1.0));
+//!
+//! let text = serde_ply::to_string(&splat, serde_ply::SerializeOptions::ascii())?;
+//! assert!(text.contains("property uchar red\nproperty float scale_0\n"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+/// A `uchar` property as an `f32` in `0.0..=1.0`.
+///
+/// Reading divides by 255. Writing clamps to `0.0..=1.0` and rounds to the nearest
+/// step, so every `uchar` value round trips exactly, and other values move by at most
+/// half a step (1/510).
+pub mod u8_normalized {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Write `value` as a `uchar`.
+    pub fn serialize<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8((value.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    /// Read a `uchar` as a value in `0.0..=1.0`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+        Ok(u8::deserialize(deserializer)? as f32 / 255.0)
+    }
+}
+
+/// A `ushort` property as an `f32` in `0.0..=1.0`.
+///
+/// Like [`u8_normalized`] with 65535 steps. Every `ushort` value round trips exactly,
+/// other values move by at most 1/131070.
+pub mod u16_normalized {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Write `value` as a `ushort`.
+    pub fn serialize<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16((value.clamp(0.0, 1.0) * 65535.0).round() as u16)
+    }
+
+    /// Read a `ushort` as a value in `0.0..=1.0`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+        Ok(u16::deserialize(deserializer)? as f32 / 65535.0)
+    }
+}
+
+/// A `uint` property holding a packed `0xRRGGBBAA` color as `[f32; 4]` channels in
+/// `0.0..=1.0`.
+///
+/// Red is in the most significant byte. Each channel is converted like
+/// [`u8_normalized`], so every packed value round trips exactly.
+pub mod rgba_u32 {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Write the channels as a packed `uint`.
+    pub fn serialize<S: Serializer>(value: &[f32; 4], serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = value.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+        serializer.serialize_u32(u32::from_be_bytes(bytes))
+    }
+
+    /// Read a packed `uint` as red, green, blue and alpha channels.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[f32; 4], D::Error> {
+        let packed = u32::deserialize(deserializer)?;
+        Ok(packed.to_be_bytes().map(|c| c as f32 / 255.0))
+    }
+}
+
+/// A `float` property holding the natural logarithm of the value, as Gaussian splat
+/// files store their scales.
+///
+/// Reading applies `exp`, writing applies `ln`. The round trip isn't exact: the stored
+/// value comes back within a few ulps, and values that aren't positive are written as
+/// `-inf` or NaN.
+pub mod log_scale {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Write the logarithm of `value`.
+    pub fn serialize<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f32(value.ln())
+    }
+
+    /// Read a logarithm and return the value.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+        Ok(f32::deserialize(deserializer)?.exp())
+    }
+}
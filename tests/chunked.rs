@@ -416,3 +416,384 @@ fn test_binary_lists() {
     assert_eq!(faces[0].vertex_indices, vec![0, 1, 2]);
     assert_eq!(faces[1].vertex_indices, vec![0, 1, 2, 3]);
 }
+
+/// A malformed header is an error, not a wait for more data
+#[test]
+fn test_malformed_header_errors() {
+    let mut file = PlyChunkedReader::new();
+    file.buffer_mut()
+        .extend_from_slice(b"ply\nformat text 1.0\nelement vertex 1\nproperty float x\n");
+    assert!(file.header().is_none());
+    assert!(file.next_chunk::<Vec<Vertex>>().is_err());
+
+    // A header line cut off mid-way is still just incomplete.
+    let mut file = PlyChunkedReader::new();
+    file.buffer_mut().extend_from_slice(b"ply\nformat asc");
+    assert!(file.next_chunk::<Vec<Vertex>>().unwrap().is_empty());
+    file.buffer_mut()
+        .extend_from_slice(b"ii 1.0\nelement vertex 0\nproperty float x\nend_header\n");
+    assert!(file.current_element().is_some());
+}
+
+/// Feed a body-only binary stream to a reader constructed from a pre-parsed header
+#[test]
+fn test_with_header_body_only() {
+    use serde_ply::{ElementDef, PlyFormat, PlyHeader, PlyProperty, PropertyType, ScalarType};
+
+    let scalar = |name: &str| PlyProperty::new(name, PropertyType::Scalar(ScalarType::F32));
+    let header = PlyHeader::new(
+        PlyFormat::BinaryLittleEndian,
+        vec![
+            ElementDef {
+                name: "vertex".to_string(),
+                count: 3,
+                properties: vec![scalar("x"), scalar("y"), scalar("z")],
+            },
+            ElementDef {
+                name: "face".to_string(),
+                count: 1,
+                properties: vec![PlyProperty::new(
+                    "vertex_indices",
+                    PropertyType::List {
+                        count_type: ScalarType::U8,
+                        data_type: ScalarType::U32,
+                    },
+                )],
+            },
+        ],
+    );
+
+    let mut body = Vec::new();
+    for v in 0..9 {
+        body.extend_from_slice(&(v as f32).to_le_bytes());
+    }
+    body.push(3);
+    for i in [0u32, 1, 2] {
+        body.extend_from_slice(&i.to_le_bytes());
+    }
+
+    let parse = |mut file: PlyChunkedReader, data: &[u8]| {
+        let mut vertices = Vec::new();
+        let mut faces = Vec::new();
+        for chunk in data.chunks(5) {
+            file.buffer_mut().extend_from_slice(chunk);
+            while let Some(name) = file.current_element().map(|e| e.name.clone()) {
+                let before = (vertices.len(), faces.len());
+                if name == "vertex" {
+                    vertices.extend(file.next_chunk::<Vec<Vertex>>().unwrap());
+                } else {
+                    faces.extend(file.next_chunk::<Vec<Face>>().unwrap());
+                }
+                if before == (vertices.len(), faces.len()) {
+                    break;
+                }
+            }
+        }
+        (vertices, faces)
+    };
+
+    let mut combined = b"ply\nformat binary_little_endian 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n".to_vec();
+    combined.extend_from_slice(&body);
+
+    let expected = parse(PlyChunkedReader::new(), &combined);
+    assert_eq!(expected.0.len(), 3);
+    assert_eq!(expected.1.len(), 1);
+    assert_eq!(
+        parse(PlyChunkedReader::with_header(header.clone()), &body),
+        expected
+    );
+    assert_eq!(
+        parse(
+            PlyChunkedReader::from_parts(header.clone(), body[..6].to_vec()),
+            &body[6..]
+        ),
+        expected
+    );
+
+    // Handing in the header and then feeding it again is a mistake.
+    let mut file = PlyChunkedReader::with_header(header);
+    file.buffer_mut().extend_from_slice(&combined);
+    assert!(file.next_chunk::<Vec<Vertex>>().is_err());
+}
+
+/// A declared property missing from an ASCII row never falls back to a serde default
+#[test]
+fn test_short_row_is_incomplete() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Face {
+        #[serde(default)]
+        vertex_indices: Vec<u32>,
+        #[serde(default)]
+        flags: Vec<u8>,
+    }
+
+    let mut file = PlyChunkedReader::new();
+    file.buffer_mut().extend_from_slice(
+        b"ply\nformat ascii 1.0\nelement face 2\nproperty list uchar uint vertex_indices\nproperty list uchar uchar flags\nend_header\n3 0 1 2 1 7\n3 0 1 2\n",
+    );
+    let faces: Vec<Face> = file.next_chunk().unwrap();
+    assert_eq!(faces.len(), 1);
+    assert_eq!(faces[0].flags, vec![7]);
+
+    // The second row waits for its flags instead of using the default.
+    let faces: Vec<Face> = file.next_chunk().unwrap();
+    assert!(faces.is_empty());
+    assert_eq!(file.rows_done(), 1);
+
+    file.buffer_mut().extend_from_slice(b"0\n");
+    let faces: Vec<Face> = file.next_chunk().unwrap();
+    assert_eq!(faces[0].flags, Vec::<u8>::new());
+    assert!(file.current_element().is_none());
+}
+
+/// Finishing a truncated file reports the element that is short
+#[test]
+fn test_finish_truncated() {
+    let data = b"ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\nelement face 0\nproperty list uchar uint vertex_indices\nend_header\n1 2 3\n4 5 6\n7 8";
+
+    let mut file = PlyChunkedReader::new();
+    assert!(file.finish().is_err());
+
+    file.buffer_mut().extend_from_slice(data);
+    let vertices: Vec<Vertex> = file.next_chunk().unwrap();
+    assert_eq!(vertices.len(), 2);
+    let err = file.finish().unwrap_err();
+    assert_eq!(err.0.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert!(err
+        .to_string()
+        .contains("File ended after 2 of 3 rows of element 'vertex'"));
+
+    // The rest of the row arrives, the empty face element needs no data.
+    file.buffer_mut().extend_from_slice(b" 9\n");
+    let vertices: Vec<Vertex> = file.next_chunk().unwrap();
+    assert_eq!(vertices.len(), 1);
+    file.finish().unwrap();
+}
+
+/// A row filter keeps every other row, also when rows are split across chunks
+#[test]
+fn test_row_visitor_filter() {
+    use serde::de::DeserializeSeed;
+    use serde_ply::RowVisitor;
+
+    let count = 101;
+    let mut data = format!(
+        "ply\nformat binary_little_endian 1.0\nelement vertex {count}\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n"
+    )
+    .into_bytes();
+    for i in 0..count {
+        for v in [i as f32, 0.0, -(i as f32)] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+    data.push(3);
+    for i in [0u32, 1, 2] {
+        data.extend_from_slice(&i.to_le_bytes());
+    }
+
+    for chunk_size in [1, 5, 12, 13, 4096] {
+        let mut file = PlyChunkedReader::new();
+        let mut vertices = Vec::new();
+        let mut faces = Vec::new();
+        let mut vertex_visitor =
+            RowVisitor::new(|v: Vertex| vertices.push(v)).with_row_filter(|i| i % 2 == 0);
+        for chunk in data.chunks(chunk_size) {
+            file.buffer_mut().extend_from_slice(chunk);
+            while let Some(element) = file.current_element() {
+                let (name, before) = (element.name.clone(), file.buffered_len());
+                if name == "vertex" {
+                    vertex_visitor.deserialize(&mut file).unwrap();
+                } else {
+                    RowVisitor::new(|f: Face| faces.push(f))
+                        .deserialize(&mut file)
+                        .unwrap();
+                }
+                if file.buffered_len() == before {
+                    break;
+                }
+            }
+        }
+        file.finish().unwrap();
+
+        assert_eq!(vertices.len(), 51, "chunk size {chunk_size}");
+        for (i, v) in vertices.iter().enumerate() {
+            assert_eq!(v.x, (2 * i) as f32);
+            assert_eq!(v.z, -((2 * i) as f32));
+        }
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].vertex_indices, vec![0, 1, 2]);
+    }
+}
+
+#[test]
+fn test_into_chunked() {
+    use serde_ply::PlyReader;
+    use std::io::BufReader;
+
+    let mut data = b"ply\nformat binary_little_endian 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\nelement face 2\nproperty list uchar uint vertex_indices\nend_header\n".to_vec();
+    for v in 0..9 {
+        data.extend_from_slice(&(v as f32).to_le_bytes());
+    }
+    for face in [[0u32, 1, 2], [2, 1, 0]] {
+        data.push(3);
+        for i in face {
+            data.extend_from_slice(&i.to_le_bytes());
+        }
+    }
+
+    // Stop the blocking reader partway into the faces, with some of them buffered.
+    let split = data.len() - 10;
+    let mut source = &data[..split];
+    let mut reader = PlyReader::from_reader(BufReader::with_capacity(16, &mut source)).unwrap();
+    let vertices: Vec<Vertex> = reader.next_element().unwrap();
+    assert_eq!(vertices[2].z, 8.0);
+
+    let mut file = reader.into_chunked().unwrap();
+    assert_eq!(file.current_element().unwrap().name, "face");
+    // Whatever the blocking reader didn't take is still in the source.
+    file.buffer_mut().extend_from_slice(source);
+
+    let mut faces = Vec::new();
+    for chunk in data[split..].chunks(3) {
+        file.buffer_mut().extend_from_slice(chunk);
+        faces.extend(file.next_chunk::<Vec<Face>>().unwrap());
+    }
+    assert_eq!(faces[0].vertex_indices, vec![0, 1, 2]);
+    assert_eq!(faces[1].vertex_indices, vec![2, 1, 0]);
+    file.finish().unwrap();
+
+    // Switching right after the header starts at the first element.
+    let reader = PlyReader::from_reader(&data[..]).unwrap();
+    let mut file = reader.into_chunked().unwrap();
+    let vertices: Vec<Vertex> = file.next_chunk().unwrap();
+    assert_eq!(vertices.len(), 3);
+    let faces: Vec<Face> = file.next_chunk().unwrap();
+    assert_eq!(faces.len(), 2);
+}
+
+#[test]
+fn test_chunked_precision_warnings() {
+    #[derive(Deserialize)]
+    #[allow(unused)]
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+
+    let mut data = b"ply\nformat binary_little_endian 1.0\nelement vertex 4\nproperty double x\nproperty double y\nend_header\n".to_vec();
+    for v in [
+        500000.123f64,
+        0.5,
+        500001.123,
+        0.5,
+        0.5,
+        0.5,
+        500002.123,
+        0.5,
+    ] {
+        data.extend_from_slice(&v.to_le_bytes());
+    }
+
+    // Split the last row after its lossy value, so it is read again once the row is complete.
+    let (first, rest) = data.split_at(data.len() - 4);
+    let mut reader = PlyChunkedReader::new()
+        .with_options(serde_ply::DeserializeOptions::new().with_f32_relative_tolerance(1e-9));
+    let mut points = 0;
+    for chunk in [first, rest] {
+        reader.buffer_mut().extend_from_slice(chunk);
+        points += reader.next_chunk::<Vec<Point>>().unwrap().len();
+    }
+    assert_eq!(points, 4);
+    let warnings = reader.precision_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!((warnings[0].property.as_str(), warnings[0].count), ("x", 3));
+    assert_eq!(warnings[0].example, 500000.123);
+}
+
+#[test]
+fn test_chunked_elements_exhausted() {
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n1 2 3\n3 0 1 2\n";
+
+    let mut ply_file = PlyChunkedReader::new();
+    ply_file.buffer_mut().extend_from_slice(ply_data.as_bytes());
+    let vertices: Vec<Vertex> = ply_file.next_chunk().unwrap();
+    assert_eq!(vertices.len(), 1);
+    let faces: Vec<Face> = ply_file.next_chunk().unwrap();
+    assert_eq!(faces.len(), 1);
+    assert!(ply_file.current_element().is_none());
+
+    let err = ply_file.next_chunk::<Vec<Face>>().unwrap_err();
+    assert!(err.is_elements_exhausted());
+    assert!(err.to_string().contains("all elements have been read"));
+
+    // A file without elements is exhausted right away.
+    let mut ply_file = PlyChunkedReader::new();
+    ply_file
+        .buffer_mut()
+        .extend_from_slice(b"ply\nformat ascii 1.0\nend_header\n");
+    assert!(ply_file.current_element().is_none());
+    let err = ply_file.next_chunk::<Vec<Vertex>>().unwrap_err();
+    assert!(err.is_elements_exhausted());
+}
+
+#[test]
+fn test_header_only_missing_data() {
+    for format in ["ascii", "binary_little_endian"] {
+        let mut file = PlyChunkedReader::new();
+        file.buffer_mut().extend_from_slice(
+            format!("ply\nformat {format} 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nend_header\n").as_bytes(),
+        );
+        // Without data every chunk is empty, the input only ends with finish.
+        for _ in 0..3 {
+            let vertices: Vec<Vertex> = file.next_chunk().unwrap();
+            assert!(vertices.is_empty());
+        }
+        let err = file.finish().unwrap_err();
+        let missing = err.as_missing_data().unwrap();
+        assert_eq!(
+            (
+                missing.element.as_str(),
+                missing.expected_rows,
+                missing.rows_read
+            ),
+            ("vertex", 2, 0)
+        );
+    }
+
+    let mut file = PlyChunkedReader::new();
+    assert!(file.next_chunk::<Vec<Vertex>>().unwrap().is_empty());
+    assert!(file.finish().unwrap_err().is_empty_input());
+}
+
+#[test]
+fn test_next_rows_batches() {
+    let mut data = b"ply\nformat binary_little_endian 1.0\nelement vertex 500\nproperty float x\nproperty float y\nproperty float z\nend_header\n".to_vec();
+    for i in 0..500 {
+        for v in [i as f32, 0.0, -(i as f32)] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+
+    for chunk_size in [1, 7, 100, 8192] {
+        let mut file = PlyChunkedReader::new();
+        let mut vertices = Vec::new();
+        for chunk in data.chunks(chunk_size) {
+            file.buffer_mut().extend_from_slice(chunk);
+            // Read fewer rows than arrive, so the backlog grows until the input ends.
+            let rows: Vec<Vertex> = file.next_rows(3).unwrap();
+            assert!(rows.len() <= 3);
+            vertices.extend(rows);
+        }
+        while file.buffered_len() > 0 {
+            let rows: Vec<Vertex> = file.next_rows(16).unwrap();
+            assert!(!rows.is_empty());
+            vertices.extend(rows);
+        }
+        file.finish().unwrap();
+
+        assert_eq!(vertices.len(), 500, "chunk size {chunk_size}");
+        for (i, v) in vertices.iter().enumerate() {
+            assert_eq!((v.x, v.z), (i as f32, -(i as f32)));
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Allocations of the chunked reader. Kept in its own test binary, as it installs a
+//! global allocator that tracks the largest allocation.
+
+use serde::Deserialize;
+use serde_ply::PlyChunkedReader;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+struct LargestAlloc;
+
+static LARGEST: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for LargestAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LARGEST.fetch_max(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        LARGEST.fetch_max(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: LargestAlloc = LargestAlloc;
+
+#[derive(Deserialize)]
+struct Vertex {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+#[test]
+fn test_huge_count_allocates_for_buffered_rows() {
+    let count = 100_000_000;
+    for format in ["ascii", "binary_little_endian"] {
+        let header = format!(
+            "ply\nformat {format} 1.0\nelement vertex {count}\nproperty float x\nproperty float y\nproperty float z\nend_header\n"
+        );
+        let mut file = PlyChunkedReader::new();
+        file.buffer_mut().extend_from_slice(header.as_bytes());
+
+        LARGEST.store(0, Ordering::Relaxed);
+        let mut rows = 0;
+        for i in 0..50 {
+            let row = match format {
+                "ascii" => format!("{i} 1 2\n").into_bytes(),
+                _ => [i as f32, 1.0, 2.0]
+                    .iter()
+                    .flat_map(|v| v.to_le_bytes())
+                    .collect(),
+            };
+            // Feed each row in tiny pieces.
+            for piece in row.chunks(5) {
+                file.buffer_mut().extend_from_slice(piece);
+                let vertices: Vec<Vertex> = file.next_chunk().unwrap();
+                rows += vertices.len();
+                for v in vertices {
+                    assert_eq!((v.x, v.y, v.z), (i as f32, 1.0, 2.0));
+                }
+            }
+        }
+        assert_eq!(rows, 50);
+        // Nowhere near the 1.2 GB the declared count would take.
+        let largest = LARGEST.load(Ordering::Relaxed);
+        assert!(largest < 64 * 1024, "{format}: allocated {largest} bytes");
+    }
+}
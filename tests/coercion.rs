@@ -0,0 +1,261 @@
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_ply::{
+    coercion, coercion_matrix, Coercion, DeserializeOptions, FieldType, PlyReader, PrecisionLoss,
+    ScalarType,
+};
+use std::fmt::Debug;
+
+/// A property value of a given type, along with its text and little endian bytes.
+#[derive(Clone, Copy, Debug)]
+enum Value {
+    Int(ScalarType, i64),
+    Float(ScalarType, f64),
+}
+
+impl Value {
+    fn ascii(self) -> String {
+        match self {
+            Value::Int(_, v) => v.to_string(),
+            Value::Float(ScalarType::F32, v) => (v as f32).to_string(),
+            Value::Float(_, v) => v.to_string(),
+        }
+    }
+
+    fn binary(self) -> Vec<u8> {
+        match self {
+            Value::Int(ScalarType::I8, v) => (v as i8).to_le_bytes().to_vec(),
+            Value::Int(ScalarType::U8, v) => (v as u8).to_le_bytes().to_vec(),
+            Value::Int(ScalarType::I16, v) => (v as i16).to_le_bytes().to_vec(),
+            Value::Int(ScalarType::U16, v) => (v as u16).to_le_bytes().to_vec(),
+            Value::Int(ScalarType::I32, v) => (v as i32).to_le_bytes().to_vec(),
+            Value::Int(_, v) => (v as u32).to_le_bytes().to_vec(),
+            Value::Float(ScalarType::F32, v) => (v as f32).to_le_bytes().to_vec(),
+            Value::Float(_, v) => v.to_le_bytes().to_vec(),
+        }
+    }
+}
+
+/// Small xorshift generator, so the sampled values are the same on every run.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Boundary values of `ty` and a few random ones.
+fn samples(ty: ScalarType, rng: &mut Rng) -> Vec<Value> {
+    let (min, max) = match ty {
+        ScalarType::I8 => (i8::MIN as i64, i8::MAX as i64),
+        ScalarType::U8 => (0, u8::MAX as i64),
+        ScalarType::I16 => (i16::MIN as i64, i16::MAX as i64),
+        ScalarType::U16 => (0, u16::MAX as i64),
+        ScalarType::I32 => (i32::MIN as i64, i32::MAX as i64),
+        ScalarType::U32 => (0, u32::MAX as i64),
+        ScalarType::F32 | ScalarType::F64 => {
+            let mut values = vec![0.0, 1.0, -1.5, 0.1, 1e-30, 16_777_217.0, 500_000.123, 3e38];
+            if ty == ScalarType::F64 {
+                values.extend([1e300, -1e300]);
+            }
+            for _ in 0..8 {
+                values.push((rng.next() as i64) as f64 / 1e12);
+            }
+            return values.into_iter().map(|v| Value::Float(ty, v)).collect();
+        }
+    };
+    let mut values: Vec<i64> = [
+        min, max, 0, 1, -1, 127, 128, 255, 256, 32_767, 32_768, 65_535, 65_536, 16_777_217,
+    ]
+    .into_iter()
+    .filter(|v| (min..=max).contains(v))
+    .collect();
+    for _ in 0..8 {
+        values.push(min + (rng.next() % (max - min + 1) as u64) as i64);
+    }
+    values.into_iter().map(|v| Value::Int(ty, v)).collect()
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Row<T> {
+    v: T,
+    l: Vec<T>,
+}
+
+fn header(format: &str, ty: ScalarType) -> String {
+    format!(
+        "ply\nformat {format} 1.0\nelement point 1\nproperty {ty} v\nproperty list uchar {ty} l\nend_header\n"
+    )
+}
+
+/// Read `value` as a scalar and as a list entry from an ASCII and a binary file.
+fn read<T: DeserializeOwned + Debug + PartialEq>(value: Value) -> [Result<String, String>; 2] {
+    let ty = match value {
+        Value::Int(ty, _) | Value::Float(ty, _) => ty,
+    };
+    let ascii = format!(
+        "{}{} 1 {}\n",
+        header("ascii", ty),
+        value.ascii(),
+        value.ascii()
+    );
+    let mut binary = header("binary_little_endian", ty).into_bytes();
+    binary.extend(value.binary());
+    binary.push(1);
+    binary.extend(value.binary());
+
+    [ascii.into_bytes(), binary].map(|data| {
+        let mut reader = PlyReader::from_reader(data.as_slice()).map_err(|e| e.to_string())?;
+        let rows: Vec<Row<T>> = reader.next_element().map_err(|e| e.to_string())?;
+        let row = &rows[0];
+        assert_eq!(row.l.len(), 1);
+        assert_eq!(row.l[0], row.v);
+        Ok(format!("{:?}", row.v))
+    })
+}
+
+/// Read `value` like [`read`] from a binary file, directly and through
+/// [`serde_ply::RawRows`].
+fn read_raw<T: DeserializeOwned + Debug>(
+    value: Value,
+    options: DeserializeOptions,
+) -> [Result<String, String>; 2] {
+    let ty = match value {
+        Value::Int(ty, _) | Value::Float(ty, _) => ty,
+    };
+    let mut data = header("binary_little_endian", ty).into_bytes();
+    data.extend(value.binary());
+    data.push(1);
+    data.extend(value.binary());
+
+    let reader =
+        || PlyReader::from_reader(data.as_slice()).map(|r| r.with_options(options.clone()));
+    let direct = reader().and_then(|mut r| r.next_element::<Vec<Row<T>>>());
+    let raw = reader().and_then(|mut r| r.next_element_raw()?.deserialize_into::<Row<T>>());
+    [direct, raw].map(|rows| {
+        rows.map(|rows| format!("{rows:?}"))
+            .map_err(|e| e.to_string())
+    })
+}
+
+/// Whether `value` fits an integer field of type `to`.
+fn fits(value: i64, to: FieldType) -> bool {
+    let (min, max) = match to {
+        FieldType::I8 => (i8::MIN as i128, i8::MAX as i128),
+        FieldType::U8 => (0, u8::MAX as i128),
+        FieldType::I16 => (i16::MIN as i128, i16::MAX as i128),
+        FieldType::U16 => (0, u16::MAX as i128),
+        FieldType::I32 => (i32::MIN as i128, i32::MAX as i128),
+        FieldType::U32 => (0, u32::MAX as i128),
+        FieldType::I64 | FieldType::I128 => (i64::MIN as i128, i128::MAX),
+        FieldType::U64 | FieldType::U128 => (0, i128::MAX),
+        _ => unreachable!(),
+    };
+    (min..=max).contains(&(value as i128))
+}
+
+#[test]
+fn test_ascii_and_binary_coerce_alike() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for (from, to, rule) in coercion_matrix() {
+        for value in samples(from, &mut rng) {
+            let [ascii, binary] = match to {
+                FieldType::Bool => read::<bool>(value),
+                FieldType::I8 => read::<i8>(value),
+                FieldType::U8 => read::<u8>(value),
+                FieldType::I16 => read::<i16>(value),
+                FieldType::U16 => read::<u16>(value),
+                FieldType::I32 => read::<i32>(value),
+                FieldType::U32 => read::<u32>(value),
+                FieldType::I64 => read::<i64>(value),
+                FieldType::U64 => read::<u64>(value),
+                FieldType::I128 => read::<i128>(value),
+                FieldType::U128 => read::<u128>(value),
+                FieldType::F32 => read::<f32>(value),
+                FieldType::F64 => read::<f64>(value),
+            };
+            let context = format!("{value:?} into {to} ({rule:?})");
+            assert_eq!(
+                ascii.is_ok(),
+                binary.is_ok(),
+                "{context}: {ascii:?} {binary:?}"
+            );
+            if let (Ok(a), Ok(b)) = (&ascii, &binary) {
+                assert_eq!(a, b, "{context}");
+            }
+
+            // The outcome is the one the matrix promises.
+            let expect_ok = match (rule, value) {
+                (Coercion::Rejected, _) => false,
+                (Coercion::Checked, Value::Int(_, v)) => fits(v, to),
+                // Narrowing only errors under PrecisionLoss::Error, the default warns.
+                _ => true,
+            };
+            assert_eq!(binary.is_ok(), expect_ok, "{context}: {binary:?}");
+        }
+    }
+}
+
+#[test]
+fn test_raw_rows_coerce_alike() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    let error = DeserializeOptions::new().with_precision_loss(PrecisionLoss::Error);
+    for (from, to, rule) in coercion_matrix() {
+        for value in samples(from, &mut rng) {
+            for options in [DeserializeOptions::new(), error.clone()] {
+                let [direct, raw] = match to {
+                    FieldType::Bool => read_raw::<bool>(value, options),
+                    FieldType::I8 => read_raw::<i8>(value, options),
+                    FieldType::U8 => read_raw::<u8>(value, options),
+                    FieldType::I16 => read_raw::<i16>(value, options),
+                    FieldType::U16 => read_raw::<u16>(value, options),
+                    FieldType::I32 => read_raw::<i32>(value, options),
+                    FieldType::U32 => read_raw::<u32>(value, options),
+                    FieldType::I64 => read_raw::<i64>(value, options),
+                    FieldType::U64 => read_raw::<u64>(value, options),
+                    FieldType::I128 => read_raw::<i128>(value, options),
+                    FieldType::U128 => read_raw::<u128>(value, options),
+                    FieldType::F32 => read_raw::<f32>(value, options),
+                    FieldType::F64 => read_raw::<f64>(value, options),
+                };
+                // The same rows, or the same error.
+                assert_eq!(direct, raw, "{value:?} into {to} ({rule:?})");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_coercion_matrix() {
+    assert_eq!(coercion_matrix().count(), 8 * FieldType::ALL.len());
+    // Reading a property as its own type never converts anything.
+    for (from, to) in [
+        (ScalarType::I8, FieldType::I8),
+        (ScalarType::U8, FieldType::U8),
+        (ScalarType::I16, FieldType::I16),
+        (ScalarType::U16, FieldType::U16),
+        (ScalarType::I32, FieldType::I32),
+        (ScalarType::U32, FieldType::U32),
+        (ScalarType::F32, FieldType::F32),
+        (ScalarType::F64, FieldType::F64),
+    ] {
+        assert_eq!(coercion(from, to), Coercion::Lossless);
+    }
+    for (from, to, rule) in coercion_matrix() {
+        let float = matches!(from, ScalarType::F32 | ScalarType::F64);
+        match to {
+            FieldType::Bool => assert_eq!(rule, Coercion::NonZero),
+            FieldType::F32 | FieldType::F64 => assert_ne!(rule, Coercion::Rejected),
+            _ if float => assert_eq!(rule, Coercion::Rejected),
+            _ => assert!(matches!(rule, Coercion::Lossless | Coercion::Checked)),
+        }
+    }
+    assert_eq!(coercion(ScalarType::U32, FieldType::F32), Coercion::Rounded);
+    assert_eq!(
+        coercion(ScalarType::F64, FieldType::F32),
+        Coercion::Narrowed
+    );
+}
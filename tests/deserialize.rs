@@ -1,5 +1,5 @@
 use serde::Deserialize;
-use serde_ply::{PlyFormat, PlyReader};
+use serde_ply::{DeserializeOptions, PlyFormat, PlyReader};
 use std::{
     collections::HashMap,
     io::{BufReader, Cursor},
@@ -80,6 +80,37 @@ end_header
     );
 }
 
+#[test]
+fn test_vertex_normals() {
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nproperty float nx\nproperty float ny\nproperty float nz\nend_header\n1 2 3 0 0 1\n4 5 6 1 0 0\n";
+
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let vertices: Vec<VertexWithNormal> = reader.next_element().unwrap();
+    assert_eq!(
+        vertices[1],
+        VertexWithNormal {
+            x: 4.0,
+            y: 5.0,
+            z: 6.0,
+            nx: 1.0,
+            ny: 0.0,
+            nz: 0.0
+        }
+    );
+
+    // The normals are skipped for rows without them.
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let vertices: Vec<Vertex> = reader.next_element().unwrap();
+    assert_eq!(
+        vertices[0],
+        Vertex {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0
+        }
+    );
+}
+
 #[test]
 fn test_parse_rn() {
     let ply_data = "ply\r\nformat ascii 1.0\r\nelement vertex 1\r\nproperty float x\r\nproperty float y\r\nproperty float z\r\nend_header\r\n0 0 1\r\n";
@@ -119,6 +150,71 @@ end_header
     assert_eq!(reader.header().elem_defs.len(), 1);
     let result = reader.next_element::<Vec<Vertex>>();
     assert!(result.is_err());
+
+    // Rows may share lines, so the short row takes values of the next one and the data
+    // runs out in the last row.
+    let err = result.unwrap_err();
+    assert_eq!(err.0.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert!(err.to_string().contains(
+        "row 1 of element 'vertex' ends its line after 2 values, but the element has 3 properties, so the rows after it are misaligned and the data runs out in row 2"
+    ));
+
+    // Skipped rows are counted the same way.
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let err = reader
+        .next_element::<Vec<serde::de::IgnoredAny>>()
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("row 1 of element 'vertex' ends its line after 2 values"));
+
+    // A row that is short at the end of the data is the one reported.
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nend_header\n0.0 0.0 0.0\n1.0 0.0\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let err = reader.next_element::<Vec<Vertex>>().unwrap_err();
+    assert!(err.to_string().contains(
+        "row 1 of element 'vertex' ends after 2 values, but the element has 3 properties"
+    ));
+}
+
+#[test]
+fn test_ascii_first_short_row() {
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 5\nproperty float x\nproperty float y\nproperty float z\nend_header\n0 0 0\n1 1\n2 2 2\n3 3 3\n4 4 4\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let err = reader.next_element::<Vec<Vertex>>().unwrap_err();
+    assert_eq!(err.0.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert!(err.to_string().contains(
+        "row 1 of element 'vertex' ends its line after 2 values, but the element has 3 properties, so the rows after it are misaligned and the data runs out in row 4"
+    ));
+
+    // A value that fails to parse points to the short row as well.
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 5\nproperty float x\nproperty float y\nproperty uchar flag\nend_header\n0.5 0.5 1\n1.5 1.5\n2.5 2.5 1\n3.5 3.5 1\n4.5 4.5 1\n";
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Flagged {
+        x: f32,
+        y: f32,
+        flag: u8,
+    }
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let err = reader.next_element::<Vec<Flagged>>().unwrap_err();
+    assert_eq!(err.0.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains(
+        "(row 1 of element 'vertex' ends its line after 2 values, but the element has 3 properties)"
+    ));
+
+    // Rows sharing lines are still read.
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nend_header\n0 0\n0 1 1\n1\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let vertices: Vec<Vertex> = reader.next_element().unwrap();
+    assert_eq!(
+        vertices[1],
+        Vertex {
+            x: 1.0,
+            y: 1.0,
+            z: 1.0
+        }
+    );
 }
 
 #[test]
@@ -216,6 +312,78 @@ end_header
     assert_eq!(point.o, 8.0);
 }
 
+#[test]
+fn test_ascii_plus_sign_and_leading_zeros() {
+    let ply_data = r#"ply
+format ascii 1.0
+element point 2
+property char a
+property int8 b
+property uchar c
+property uint8 d
+property short e
+property int16 f
+property uint16 g
+property ushort h
+property int32 i
+property int j
+property uint32 k
+property uint l
+property float32 m
+property float n
+property float64 o
+property double p
+end_header
++1 -01 +2 002 +3 -003 +4 0004 +5 -05 +6 006 +7.5 007.25 +8 -08
+007 +0 +000 0 +0 -0 00 +00 +007 -007 +007 0 +0.5 -0.5 +1e2 001E+1
+"#;
+
+    let mut file = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let points: Vec<AllTypes> = file.next_element().unwrap();
+    assert_eq!(
+        points[0],
+        AllTypes {
+            a: 1,
+            b: -1,
+            c: 2,
+            d: 2,
+            e: 3,
+            f: -3,
+            g: 4,
+            h: 4,
+            i: 5,
+            j: -5,
+            k: 6,
+            l: 6,
+            m: 7.5,
+            n: 7.25,
+            o: 8.0,
+            p: -8.0,
+        }
+    );
+    assert_eq!(
+        points[1],
+        AllTypes {
+            a: 7,
+            b: 0,
+            c: 0,
+            d: 0,
+            e: 0,
+            f: 0,
+            g: 0,
+            h: 0,
+            i: 7,
+            j: -7,
+            k: 7,
+            l: 0,
+            m: 0.5,
+            n: -0.5,
+            o: 100.0,
+            p: 10.0,
+        }
+    );
+}
+
 #[test]
 fn test_empty_elements() {
     let ply_data = r#"ply
@@ -332,6 +500,100 @@ end_header
     assert_eq!(vertices[0].indices, vec![10, 20]);
 }
 
+#[test]
+fn test_ascii_list_shapes() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Row {
+        a: Vec<u8>,
+        b: Vec<f64>,
+        tail: i16,
+    }
+
+    // Empty, single and long lists, two lists in a row, and a scalar after a list.
+    let ply_data = "ply\nformat ascii 1.0\nelement row 4\nproperty list uchar uchar a\nproperty list int double b\nproperty short tail\nend_header\n0 0 -1\n1 7 1 0.5 2\n3 1 2 3\t2 -1e3 4\t-3\n10 0 1 2 3 4 5 6 7 8 9 0 0\n";
+
+    let mut file = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let rows: Vec<Row> = file.next_element().unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            Row {
+                a: vec![],
+                b: vec![],
+                tail: -1,
+            },
+            Row {
+                a: vec![7],
+                b: vec![0.5],
+                tail: 2,
+            },
+            Row {
+                a: vec![1, 2, 3],
+                b: vec![-1000.0, 4.0],
+                tail: -3,
+            },
+            Row {
+                a: (0..10).collect(),
+                b: vec![],
+                tail: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_fixed_array_from_list() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Block {
+        data: [u8; 16],
+        id: u8,
+    }
+
+    let header = |format: &str| {
+        format!("ply\nformat {format} 1.0\nelement block 2\nproperty list uchar uchar data\nproperty uchar id\nend_header\n")
+    };
+    let ascii = |count: u8| {
+        let mut ply = header("ascii");
+        for id in 0..2 {
+            let entries: Vec<String> = (0..count).map(|i| i.to_string()).collect();
+            ply += &format!("{count} {} {id}\n", entries.join(" "));
+        }
+        ply.into_bytes()
+    };
+    let binary = |count: u8| {
+        let mut ply = header("binary_little_endian").into_bytes();
+        for id in 0..2 {
+            ply.push(count);
+            ply.extend(0..count);
+            ply.push(id);
+        }
+        ply
+    };
+
+    let expected: Vec<Block> = (0..2)
+        .map(|id| Block {
+            data: std::array::from_fn(|i| i as u8),
+            id,
+        })
+        .collect();
+    for build in [&ascii as &dyn Fn(u8) -> Vec<u8>, &binary] {
+        let mut reader = PlyReader::from_reader(Cursor::new(build(16))).unwrap();
+        let blocks: Vec<Block> = reader.next_element().unwrap();
+        assert_eq!(blocks, expected);
+
+        for count in [15, 17] {
+            let mut reader = PlyReader::from_reader(Cursor::new(build(count))).unwrap();
+            let err = reader.next_element::<Vec<Block>>().unwrap_err().to_string();
+            assert!(
+                err.contains(&format!(
+                    "property 'data' of element 'block' is a list with {count} entries but field is an array of 16 (row 0)"
+                )),
+                "{err}"
+            );
+        }
+    }
+}
+
 #[test]
 fn test_leading_whitespace() {
     let ply_data = r#"ply
@@ -766,3 +1028,2779 @@ end_header
     assert_eq!(colors[0]["red"], 255.0);
     assert_eq!(colors[1]["red"], 32.0);
 }
+
+#[test]
+fn test_scalar_property_into_list_field() {
+    #[derive(Deserialize, Debug)]
+    #[allow(unused)]
+    struct Weighted {
+        weights: Vec<f32>,
+    }
+
+    let ply_data = r#"ply
+format ascii 1.0
+element vertex 2
+property float weights
+end_header
+0.5
+0.25
+"#;
+
+    let mut file = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let err = file.next_element::<Vec<Weighted>>().unwrap_err();
+    assert!(err.to_string().contains(
+        "property 'weights' of element 'vertex' is a scalar but field expects a list (Vec) (row 0)"
+    ));
+}
+
+#[test]
+fn test_scalar_property_into_list_field_binary() {
+    #[derive(Deserialize, Debug)]
+    #[allow(unused)]
+    struct Weighted {
+        weights: Vec<f32>,
+    }
+
+    for (format, bytes) in [
+        ("binary_little_endian", 0.5f32.to_le_bytes()),
+        ("binary_big_endian", 0.5f32.to_be_bytes()),
+    ] {
+        let mut ply_data = format!(
+            "ply\nformat {format} 1.0\nelement vertex 2\nproperty float weights\nend_header\n"
+        )
+        .into_bytes();
+        ply_data.extend_from_slice(&bytes);
+        ply_data.extend_from_slice(&bytes);
+
+        // The float bytes must not be taken as a list count.
+        let mut file = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+        let err = file.next_element::<Vec<Weighted>>().unwrap_err();
+        assert!(err.to_string().contains(
+            "property 'weights' of element 'vertex' is a scalar but field expects a list (Vec) (row 0)"
+        ));
+    }
+}
+
+#[test]
+fn test_list_property_into_scalar_field() {
+    #[derive(Deserialize, Debug)]
+    #[allow(unused)]
+    struct Weighted {
+        weights: f32,
+    }
+
+    let mut binary_data = b"ply\nformat binary_little_endian 1.0\nelement vertex 1\nproperty list uchar float weights\nend_header\n".to_vec();
+    binary_data.push(1u8);
+    binary_data.extend_from_slice(&0.5f32.to_le_bytes());
+
+    let mut file = PlyReader::from_reader(Cursor::new(binary_data)).unwrap();
+    let err = file.next_element::<Vec<Weighted>>().unwrap_err();
+    assert!(err.to_string().contains(
+        "property 'weights' of element 'vertex' is a list but field expects a scalar (row 0)"
+    ));
+}
+
+#[test]
+fn test_single_element_list_as_scalar() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Weighted {
+        weights: f32,
+    }
+
+    let ply_data = r#"ply
+format ascii 1.0
+element vertex 3
+property list uchar float weights
+end_header
+1 0.5
+1 0.25
+2 1.0 2.0
+"#;
+
+    let options = DeserializeOptions::new().with_single_element_list_as_scalar(true);
+    let mut file = PlyReader::from_reader(Cursor::new(ply_data))
+        .unwrap()
+        .with_options(options.clone());
+    let err = file.next_element::<Vec<Weighted>>().unwrap_err();
+    assert!(err.to_string().contains(
+        "property 'weights' of element 'vertex' is a list with 2 entries but field expects a scalar (row 2)"
+    ));
+
+    let mut file = PlyReader::from_reader(Cursor::new(ply_data.replace("2 1.0 2.0", "1 1.0")))
+        .unwrap()
+        .with_options(options);
+    let vertices: Vec<Weighted> = file.next_element().unwrap();
+    assert_eq!(
+        vertices,
+        vec![
+            Weighted { weights: 0.5 },
+            Weighted { weights: 0.25 },
+            Weighted { weights: 1.0 },
+        ]
+    );
+}
+
+#[test]
+fn test_element_columns_f64() {
+    let mut binary_data = b"ply\nformat binary_big_endian 1.0\nelement vertex 2\nproperty double x\nproperty double y\nproperty double z\nelement face 0\nproperty list uchar uint vertex_indices\nend_header\n".to_vec();
+    for val in [4.0e6f64, 1.25e-7, -3.5, 0.1, 0.2, 0.3] {
+        binary_data.extend_from_slice(&val.to_be_bytes());
+    }
+
+    let mut file = PlyReader::from_reader(Cursor::new(binary_data)).unwrap();
+    let err = file.element_columns_f64::<3>("face").unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("expected element 'face' but the next element is 'vertex'"));
+
+    let positions = file.element_columns_f64::<3>("vertex").unwrap();
+    assert_eq!(positions, vec![[4.0e6, 1.25e-7, -3.5], [0.1, 0.2, 0.3]]);
+    assert_eq!(file.current_element().unwrap().name, "face");
+}
+
+#[test]
+fn test_element_columns_f64_rejects_float() {
+    let ply_data = r#"ply
+format ascii 1.0
+element vertex 1
+property double x
+property float y
+end_header
+1.0 2.0
+"#;
+
+    let mut file = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let err = file.element_columns_f64::<2>("vertex").unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("property 'y' of element 'vertex' is not a double"));
+
+    // Nothing was consumed, so the element can still be read normally.
+    let vertices: Vec<HashMap<String, f64>> = file.next_element().unwrap();
+    assert_eq!(vertices[0]["y"], 2.0);
+}
+
+#[test]
+fn test_element_byte_ranges() {
+    use serde::Serialize;
+    use serde_ply::{from_element_bytes, read_header_prefix, to_bytes, SerializeOptions};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Point {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Color {
+        red: u8,
+        green: u8,
+        blue: u8,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct FaceRow {
+        vertex_indices: Vec<u32>,
+    }
+
+    #[derive(Serialize)]
+    struct Fixture {
+        vertex: Vec<Point>,
+        color: Vec<Color>,
+        face: Vec<FaceRow>,
+        after_face: Vec<Color>,
+    }
+
+    let fixture = Fixture {
+        vertex: (0..3)
+            .map(|i| Point {
+                x: i as f32,
+                y: 1.0,
+                z: 2.0,
+            })
+            .collect(),
+        color: (0..2)
+            .map(|i| Color {
+                red: i,
+                green: 2 * i,
+                blue: 3 * i,
+            })
+            .collect(),
+        face: vec![FaceRow {
+            vertex_indices: vec![0, 1, 2],
+        }],
+        after_face: vec![Color {
+            red: 1,
+            green: 2,
+            blue: 3,
+        }],
+    };
+
+    for options in [SerializeOptions::binary_le(), SerializeOptions::binary_be()] {
+        let file = to_bytes(&fixture, options).unwrap();
+
+        // Only fetch a prefix, the header doesn't need the rest of the file.
+        let (header, header_len) = read_header_prefix(&file[..file.len() - 20]).unwrap();
+        let ranges = header.element_byte_ranges(header_len);
+        assert_eq!(ranges.len(), 4);
+        assert_eq!(ranges[0], Some(header_len..header_len + 36));
+        assert_eq!(ranges[1], Some(header_len + 36..header_len + 42));
+        assert_eq!(ranges[2], None);
+        assert_eq!(ranges[3], None);
+
+        let slice = |range: &Option<std::ops::Range<u64>>| {
+            let range = range.clone().unwrap();
+            &file[range.start as usize..range.end as usize]
+        };
+        let vertices: Vec<Point> =
+            from_element_bytes(&header, "vertex", slice(&ranges[0])).unwrap();
+        assert_eq!(vertices, fixture.vertex);
+        let colors: Vec<Color> = from_element_bytes(&header, "color", slice(&ranges[1])).unwrap();
+        assert_eq!(colors, fixture.color);
+    }
+
+    // ASCII files have no fixed layout.
+    let file = to_bytes(&fixture, SerializeOptions::ascii()).unwrap();
+    let (header, header_len) = read_header_prefix(&file).unwrap();
+    assert!(header
+        .element_byte_ranges(header_len)
+        .iter()
+        .all(Option::is_none));
+}
+
+#[test]
+fn test_read_header_prefix_incomplete() {
+    let prefix = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nprop";
+    let err = serde_ply::read_header_prefix(prefix).unwrap_err();
+    assert_eq!(err.0.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_last_element_bytes() {
+    use std::io::Cursor;
+
+    #[derive(Deserialize)]
+    struct Face {
+        #[allow(dead_code)]
+        vertex_indices: Vec<u32>,
+    }
+
+    let mut data = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n".to_vec();
+    for v in [0.0f32, 1.0, 2.0, 3.0, 4.0, 5.0] {
+        data.extend_from_slice(&v.to_le_bytes());
+    }
+    data.push(3);
+    for i in [0u32, 1, 2] {
+        data.extend_from_slice(&i.to_le_bytes());
+    }
+
+    let mut reader = PlyReader::from_reader(Cursor::new(data)).unwrap();
+    assert_eq!(reader.last_element_bytes(), 0);
+
+    let _: Vec<Vertex> = reader.next_element().unwrap();
+    assert_eq!(reader.last_element_bytes(), 2 * 3 * 4);
+
+    let _: Vec<Face> = reader.next_element().unwrap();
+    assert_eq!(reader.last_element_bytes(), 1 + 3 * 4);
+}
+
+#[test]
+fn test_tuple_rows() {
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n1 2 3\n4 5 6\n3 0 1 2\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+
+    let vertices: Vec<(f32, f32, f32)> = reader.next_element().unwrap();
+    assert_eq!(vertices, vec![(1.0, 2.0, 3.0), (4.0, 5.0, 6.0)]);
+
+    let faces: Vec<(Vec<u32>,)> = reader.next_element().unwrap();
+    assert_eq!(faces, vec![(vec![0, 1, 2],)]);
+
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let err = reader.next_element::<Vec<(f32, f32)>>().unwrap_err();
+    assert!(err.to_string().contains("has 3 properties"));
+}
+
+#[test]
+fn test_missing_format_line() {
+    let ply_data = "ply\nelement vertex 1\nproperty float x\nend_header\n1.0\n";
+    let err = PlyReader::from_reader(Cursor::new(ply_data)).err().unwrap();
+    assert!(err.is_missing_format());
+    assert_eq!(err.phase(), serde_ply::ErrorPhase::Header);
+    assert_eq!(err.0.kind(), std::io::ErrorKind::InvalidData);
+
+    // Other header errors aren't mistaken for it.
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex one\nend_header\n";
+    let err = PlyReader::from_reader(Cursor::new(ply_data)).err().unwrap();
+    assert!(!err.is_missing_format());
+}
+
+#[test]
+fn test_header_display() {
+    let ply_data = "ply\nformat ascii 1.0\ncomment made by Greg Turk\ncomment this file is a cube\nelement vertex 8\nproperty float x\nproperty float y\nproperty float z\nelement face 6\nproperty list uchar int vertex_index\nend_header\n";
+    let file = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let header = file.header();
+
+    assert_eq!(
+        header.to_string(),
+        "ascii\n\
+         vertex (8 rows): x f32, y f32, z f32\n\
+         face (6 rows): vertex_index list<u8,i32>\n\
+         2 comments"
+    );
+    assert_eq!(
+        header.summary(),
+        "ascii, vertex (8 rows), face (6 rows), 2 comments"
+    );
+    assert_eq!(
+        header.elem_defs[1].properties[0].to_string(),
+        "vertex_index list<u8,i32>"
+    );
+
+    // Property names are written as is, whatever characters they contain.
+    let ply_data =
+        "ply\nformat binary_little_endian 1.0\nelement pünkt 1\nproperty double 距離\nend_header\n";
+    let file = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    assert_eq!(
+        file.header().elem_defs[0].to_string(),
+        "pünkt (1 rows): 距離 f64"
+    );
+}
+
+#[test]
+fn test_next_element_named() {
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n1 2 3\n3 0 1 2\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    assert_eq!(reader.peek_element_name(), Some("vertex"));
+
+    let err = reader.next_element_named::<Vec<Face>>("face").unwrap_err();
+    assert!(err.to_string().contains(
+        "expected element 'face' but the next element is 'vertex' (did you forget to read or skip 'vertex'?)"
+    ));
+
+    // Nothing was consumed by the failed call.
+    let vertices: Vec<Vertex> = reader.next_element_named("vertex").unwrap();
+    assert_eq!(vertices.len(), 1);
+    assert_eq!(reader.peek_element_name(), Some("face"));
+
+    let faces: Vec<Face> = reader.next_element_named("face").unwrap();
+    assert_eq!(faces[0].vertex_indices, vec![0, 1, 2]);
+    assert_eq!(reader.peek_element_name(), None);
+    assert!(reader
+        .next_element_named::<Vec<Face>>("face")
+        .unwrap_err()
+        .to_string()
+        .contains("all elements have been read"));
+}
+
+#[test]
+fn test_elements_exhausted() {
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n1 2 3\n3 0 1 2\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let mut names = vec![];
+    while reader.has_next_element() {
+        names.push(reader.current_element().unwrap().name.clone());
+        reader.next_element::<serde::de::IgnoredAny>().unwrap();
+    }
+    assert_eq!(names, ["vertex", "face"]);
+
+    // Every way of reading past the end gives the same error.
+    assert!(reader
+        .next_element::<Vec<Vertex>>()
+        .unwrap_err()
+        .is_elements_exhausted());
+    assert!(reader.element().err().unwrap().is_elements_exhausted());
+    assert!(reader
+        .next_element_named::<Vec<Face>>("face")
+        .unwrap_err()
+        .is_elements_exhausted());
+    assert!(reader
+        .next_element_preserving::<Vertex>()
+        .unwrap_err()
+        .is_elements_exhausted());
+
+    // Other errors don't count as running out of elements.
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let err = reader.next_element::<Vec<Face>>().unwrap_err();
+    assert!(!err.is_elements_exhausted());
+}
+
+#[test]
+fn test_no_elements() {
+    let ply_data = "ply\nformat ascii 1.0\ncomment nothing here\nend_header\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    assert!(!reader.has_next_element());
+    let err = reader.next_element::<Vec<Vertex>>().unwrap_err();
+    assert!(err.is_elements_exhausted());
+    assert_eq!(err.0.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_swapped_property_type_and_name() {
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty x float\nend_header\n1.0\n";
+    let err = PlyReader::from_reader(Cursor::new(ply_data)).err().unwrap();
+    assert!(err.to_string().contains(
+        "Unknown scalar type: x (property type and name may be swapped, expected 'property float x')"
+    ));
+
+    // Without a valid type on either side there's no hint.
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty x y\nend_header\n1.0\n";
+    let err = PlyReader::from_reader(Cursor::new(ply_data)).err().unwrap();
+    assert!(!err.to_string().contains("swapped"));
+}
+
+#[test]
+fn test_uppercase_type_tokens() {
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty FLOAT x\nproperty Float y\nproperty float32 z\nelement face 1\nproperty LIST UCHAR Int vertex_indices\nend_header\n1 2 3\n4 5 6\n3 0 1 1\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let vertex = &reader.header().elem_defs[0];
+    assert!(vertex
+        .properties
+        .iter()
+        .all(|p| p.property_type == serde_ply::PropertyType::Scalar(serde_ply::ScalarType::F32)));
+    let vertices: Vec<Vertex> = reader.next_element().unwrap();
+    assert_eq!(
+        vertices[1],
+        Vertex {
+            x: 4.0,
+            y: 5.0,
+            z: 6.0
+        }
+    );
+    let faces: Vec<Face> = reader.next_element().unwrap();
+    assert_eq!(faces[0].vertex_indices, [0, 1, 1]);
+
+    // Unknown types are still rejected, whatever their case.
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty REAL x\nend_header\n1\n";
+    let err = PlyReader::from_reader(Cursor::new(ply_data)).err().unwrap();
+    assert!(
+        err.to_string().contains("Unknown scalar type: REAL"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_malformed_property_declarations() {
+    use serde_ply::ErrorPhase;
+
+    // A property line duplicated verbatim, as scalar and as list.
+    for (line, property) in [
+        ("property float y", "y"),
+        ("property list uchar int vertex_indices", "vertex_indices"),
+    ] {
+        let ply_data = "ply\nformat binary_little_endian 1.0\nelement vertex 1\nproperty float x\nproperty float y\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n";
+        let ply_data = ply_data.replacen(line, &format!("{line}\n{line}"), 1);
+        let err = PlyReader::from_reader(Cursor::new(ply_data)).err().unwrap();
+        let duplicate = err.as_duplicate_property().unwrap();
+        let element = if property == "y" { "vertex" } else { "face" };
+        assert_eq!(duplicate.element, element);
+        assert_eq!(duplicate.property, property);
+        assert_eq!(err.phase(), ErrorPhase::Header);
+        assert!(err.to_string().contains(&format!(
+            "element '{element}' declares property '{property}' twice"
+        )));
+    }
+
+    // The same name in different elements is fine.
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nelement normal 1\nproperty float x\nend_header\n1\n2\n";
+    assert!(PlyReader::from_reader(Cursor::new(ply_data)).is_ok());
+
+    // A list without its data type, so the name takes the place of the type.
+    let ply_data = "ply\nformat ascii 1.0\nelement face 1\nproperty list uchar vertex_indices\nend_header\n3 0 1 2\n";
+    let err = PlyReader::from_reader(Cursor::new(ply_data)).err().unwrap();
+    assert_eq!(err.phase(), ErrorPhase::Header);
+    let err = err.to_string();
+    assert!(
+        err.contains("missing data type in list property declaration? 'property list uchar vertex_indices' has no type for the list entries, expected 'property list uchar <data type> vertex_indices'"),
+        "{err}"
+    );
+
+    // A list without its name is still just an invalid line.
+    let ply_data =
+        "ply\nformat ascii 1.0\nelement face 1\nproperty list uchar int\nend_header\n3 0 1 2\n";
+    let err = PlyReader::from_reader(Cursor::new(ply_data)).err().unwrap();
+    assert!(
+        err.to_string().contains("Invalid list property line"),
+        "{err}"
+    );
+
+    // List counts must be integers.
+    for count_type in ["float", "DOUBLE"] {
+        let ply_data = format!("ply\nformat ascii 1.0\nelement face 1\nproperty list {count_type} int vertex_indices\nend_header\n3 0 1 2\n");
+        let err = PlyReader::from_reader(Cursor::new(ply_data)).err().unwrap();
+        assert_eq!(err.phase(), ErrorPhase::Header);
+        assert!(
+            err.to_string().contains(&format!(
+                "Invalid list count type in 'property list {count_type} int vertex_indices', list counts must be integers"
+            )),
+            "{err}"
+        );
+    }
+}
+
+#[test]
+fn test_next_element_pod_bytes() {
+    use serde_ply::{PodLayout, ScalarType};
+
+    #[derive(Deserialize)]
+    struct ColorVertex {
+        x: f32,
+        y: f32,
+        z: f32,
+        red: u8,
+        green: u8,
+        blue: u8,
+    }
+
+    let header = |format: &str| {
+        format!("ply\nformat {format} 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nproperty uchar red\nproperty uchar green\nproperty uchar blue\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n")
+    };
+    let vertices = [
+        ([1.0f32, 2.0, 3.0], [10u8, 20, 30]),
+        ([-4.0, 5.5, 6.0], [40, 50, 60]),
+    ];
+
+    let mut files = vec![];
+    let mut ascii = header("ascii");
+    for (pos, col) in vertices {
+        ascii += &format!(
+            "{} {} {} {} {} {}\n",
+            pos[0], pos[1], pos[2], col[0], col[1], col[2]
+        );
+    }
+    ascii += "3 0 1 2\n";
+    files.push(ascii.into_bytes());
+    for (format, le) in [("binary_little_endian", true), ("binary_big_endian", false)] {
+        let mut file = header(format).into_bytes();
+        for (pos, col) in vertices {
+            for v in pos {
+                file.extend_from_slice(&if le { v.to_le_bytes() } else { v.to_be_bytes() });
+            }
+            file.extend_from_slice(&col);
+        }
+        file.push(3);
+        for i in [0u32, 1, 2] {
+            file.extend_from_slice(&if le { i.to_le_bytes() } else { i.to_be_bytes() });
+        }
+        files.push(file);
+    }
+
+    for file in files {
+        let expected: Vec<ColorVertex> = PlyReader::from_reader(Cursor::new(&file))
+            .unwrap()
+            .next_element()
+            .unwrap();
+
+        // #[repr(C)] struct GpuVertex { pos: [f32; 3], col: [u8; 4] }
+        let layout = PodLayout::new(16)
+            .field("x", 0, ScalarType::F32)
+            .field("y", 4, ScalarType::F32)
+            .field("z", 8, ScalarType::F32)
+            .field("red", 12, ScalarType::U8)
+            .field("green", 13, ScalarType::U8)
+            .field("blue", 14, ScalarType::U8)
+            .field_with_default("alpha", 15, ScalarType::U8, 255.0);
+        let mut reader = PlyReader::from_reader(Cursor::new(&file)).unwrap();
+        let bytes = reader.next_element_pod_bytes(&layout).unwrap();
+        let mut manual = vec![];
+        for v in &expected {
+            for f in [v.x, v.y, v.z] {
+                manual.extend_from_slice(&f.to_ne_bytes());
+            }
+            manual.extend_from_slice(&[v.red, v.green, v.blue, 255]);
+        }
+        assert_eq!(bytes, manual);
+
+        // The reader continues with the next element as normal.
+        let faces: Vec<Face> = reader.next_element().unwrap();
+        assert_eq!(faces[0].vertex_indices, vec![0, 1, 2]);
+
+        // A layout derived from the element matches the file exactly (bulk path for binary).
+        let mut reader = PlyReader::from_reader(Cursor::new(&file)).unwrap();
+        let layout = PodLayout::from_element(reader.current_element().unwrap()).unwrap();
+        assert_eq!(layout.stride(), 16);
+        let bytes = reader.next_element_pod_bytes(&layout).unwrap();
+        for (row, v) in bytes.chunks(16).zip(&expected) {
+            assert_eq!(row[0..4], v.x.to_ne_bytes());
+            assert_eq!(row[8..12], v.z.to_ne_bytes());
+            assert_eq!(row[12..15], [v.red, v.green, v.blue]);
+        }
+
+        // Converting layout: positions as doubles, only one color channel.
+        let layout =
+            PodLayout::new(16)
+                .field("y", 0, ScalarType::F64)
+                .field("green", 8, ScalarType::U16);
+        let mut reader = PlyReader::from_reader(Cursor::new(&file)).unwrap();
+        let bytes = reader.next_element_pod_bytes(&layout).unwrap();
+        for (row, v) in bytes.chunks(16).zip(&expected) {
+            assert_eq!(row[0..8], (v.y as f64).to_ne_bytes());
+            assert_eq!(row[8..10], (v.green as u16).to_ne_bytes());
+        }
+
+        // Missing property without a default.
+        let layout = PodLayout::new(4).field("alpha", 0, ScalarType::F32);
+        let mut reader = PlyReader::from_reader(Cursor::new(&file)).unwrap();
+        assert!(reader.next_element_pod_bytes(&layout).is_err());
+    }
+}
+
+#[test]
+fn test_pod_bytes_big_endian_swap() {
+    use serde_ply::{PodLayout, ScalarType};
+
+    const TYPES: [(&str, ScalarType); 8] = [
+        ("char", ScalarType::I8),
+        ("uchar", ScalarType::U8),
+        ("short", ScalarType::I16),
+        ("ushort", ScalarType::U16),
+        ("int", ScalarType::I32),
+        ("uint", ScalarType::U32),
+        ("float", ScalarType::F32),
+        ("double", ScalarType::F64),
+    ];
+
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    let mut next = move |n: u64| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % n
+    };
+
+    for _ in 0..100 {
+        let types: Vec<_> = (0..1 + next(10)).map(|_| TYPES[next(8) as usize]).collect();
+        let count = next(20) as usize;
+
+        let mut file = format!("ply\nformat binary_big_endian 1.0\nelement vertex {count}\n");
+        for (i, (name, _)) in types.iter().enumerate() {
+            file += &format!("property {name} p{i}\n");
+        }
+        file += "end_header\n";
+        let mut file = file.into_bytes();
+
+        let mut expected = vec![];
+        for _ in 0..count {
+            for (_, ty) in &types {
+                let bits = next(u64::MAX);
+                macro_rules! bytes {
+                    ($v:expr) => {{
+                        let v = $v;
+                        (v.to_be_bytes().to_vec(), v.to_ne_bytes().to_vec())
+                    }};
+                }
+                let (be, ne) = match ty {
+                    ScalarType::I8 => bytes!(bits as i8),
+                    ScalarType::U8 => bytes!(bits as u8),
+                    ScalarType::I16 => bytes!(bits as i16),
+                    ScalarType::U16 => bytes!(bits as u16),
+                    ScalarType::I32 => bytes!(bits as i32),
+                    ScalarType::U32 => bytes!(bits as u32),
+                    // Values that are exact in the type, so the converting path keeps them.
+                    ScalarType::F32 => bytes!((bits as i32 >> 8) as f32 / 64.0),
+                    ScalarType::F64 => bytes!((bits as i64 >> 11) as f64 / 64.0),
+                };
+                file.extend_from_slice(&be);
+                expected.extend_from_slice(&ne);
+            }
+        }
+
+        // Fields packed like the file rows take the bulk path, one spare byte per row
+        // makes every value go through the converting path.
+        let row_size: usize = types.iter().map(|(_, ty)| ty.size_bytes()).sum();
+        let layout = |stride: usize| {
+            let mut layout = PodLayout::new(stride);
+            let mut offset = 0;
+            for (i, (_, ty)) in types.iter().enumerate() {
+                layout = layout.field(&format!("p{i}"), offset, *ty);
+                offset += ty.size_bytes();
+            }
+            layout
+        };
+
+        let mut reader = PlyReader::from_reader(Cursor::new(&file)).unwrap();
+        let packed = reader.next_element_pod_bytes(&layout(row_size)).unwrap();
+        assert_eq!(packed, expected, "types {types:?}");
+
+        let mut reader = PlyReader::from_reader(Cursor::new(&file)).unwrap();
+        let converted = reader
+            .next_element_pod_bytes(&layout(row_size + 1))
+            .unwrap();
+        let converted: Vec<u8> = converted
+            .chunks(row_size + 1)
+            .flat_map(|row| &row[..row_size])
+            .copied()
+            .collect();
+        assert_eq!(converted, expected, "types {types:?}");
+    }
+}
+
+#[test]
+fn test_pod_bytes_untrusted_count() {
+    use serde_ply::{PodLayout, ScalarType};
+
+    // The row count doesn't fit in memory, or claims far more rows than there is data.
+    for count in ["1152921504606846976", "1000000"] {
+        let mut file = format!("ply\nformat binary_little_endian 1.0\nelement vertex {count}\nproperty float x\nproperty float y\nproperty float z\nproperty float w\nend_header\n").into_bytes();
+        file.extend_from_slice(&1.0f32.to_le_bytes());
+
+        let packed = PodLayout::new(16)
+            .field("x", 0, ScalarType::F32)
+            .field("y", 4, ScalarType::F32)
+            .field("z", 8, ScalarType::F32)
+            .field("w", 12, ScalarType::F32);
+        let converted = PodLayout::new(4).field("x", 0, ScalarType::F32);
+        for layout in [packed, converted] {
+            let mut reader = PlyReader::from_reader(Cursor::new(&file)).unwrap();
+            assert!(
+                reader.next_element_pod_bytes(&layout).is_err(),
+                "count {count}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_pod_bytes_coercion() {
+    use serde_ply::{PodLayout, PrecisionLoss, ScalarType};
+
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty int i\nproperty double d\nend_header\n1.5 300 0.5\n2.5 7 500000.123\n";
+    let read = |layout: PodLayout, options: DeserializeOptions| {
+        let mut reader = PlyReader::from_reader(Cursor::new(ply_data))
+            .unwrap()
+            .with_options(options);
+        let bytes = reader.next_element_pod_bytes(&layout);
+        (bytes, reader.precision_warnings().to_vec())
+    };
+
+    // A float property can't go in an integer field, like with serde rows.
+    let (bytes, _) = read(
+        PodLayout::new(4).field("x", 0, ScalarType::I32),
+        DeserializeOptions::new(),
+    );
+    assert!(bytes.unwrap_err().to_string().contains("can't be stored"));
+
+    // Integers are range checked instead of saturating.
+    let (bytes, _) = read(
+        PodLayout::new(1).field("i", 0, ScalarType::U8),
+        DeserializeOptions::new(),
+    );
+    assert!(bytes
+        .unwrap_err()
+        .to_string()
+        .contains("300 is out of range for u8"));
+    let (bytes, _) = read(
+        PodLayout::new(2).field("i", 0, ScalarType::U16),
+        DeserializeOptions::new(),
+    );
+    let values: Vec<u16> = bytes
+        .unwrap()
+        .chunks(2)
+        .map(|b| u16::from_ne_bytes(b.try_into().unwrap()))
+        .collect();
+    assert_eq!(values, [300, 7]);
+
+    // Doubles narrowed to f32 follow the precision loss policy.
+    let layout = || PodLayout::new(4).field("d", 0, ScalarType::F32);
+    let tight = || DeserializeOptions::new().with_f32_relative_tolerance(1e-9);
+    let (bytes, warnings) = read(layout(), tight());
+    assert_eq!(bytes.unwrap()[4..], (500000.123f64 as f32).to_ne_bytes());
+    assert_eq!((warnings[0].property.as_str(), warnings[0].count), ("d", 1));
+    let (bytes, _) = read(layout(), tight().with_precision_loss(PrecisionLoss::Error));
+    assert!(bytes.is_err());
+
+    // Defaults must fit integer fields exactly.
+    let (bytes, _) = read(
+        PodLayout::new(1).field_with_default("alpha", 0, ScalarType::U8, 255.5),
+        DeserializeOptions::new(),
+    );
+    assert!(bytes.is_err());
+}
+
+#[test]
+fn test_from_reader_keep() {
+    use std::io::Read;
+
+    #[derive(Deserialize)]
+    struct Mesh {
+        vertex: Vec<Vertex>,
+    }
+
+    let mut data = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nend_header\n".to_vec();
+    for v in [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0] {
+        data.extend_from_slice(&v.to_le_bytes());
+    }
+    data.extend_from_slice(b"appended");
+
+    let (mesh, mut reader): (Mesh, _) = serde_ply::from_reader_keep(Cursor::new(data)).unwrap();
+    assert_eq!(mesh.vertex.len(), 2);
+    assert_eq!(mesh.vertex[1].z, 6.0);
+
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"appended");
+}
+
+#[test]
+fn test_serde_default_only_for_absent_properties() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Face {
+        #[serde(default)]
+        vertex_indices: Vec<u32>,
+        #[serde(default)]
+        flags: Vec<u8>,
+    }
+
+    let options = [
+        DeserializeOptions::new(),
+        DeserializeOptions::new().with_single_element_list_as_scalar(true),
+    ];
+
+    for options in options {
+        // The property is absent from the header, so the default applies.
+        let ply_data = "ply\nformat ascii 1.0\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n3 0 1 2\n";
+        let faces: Vec<Face> = PlyReader::from_reader(Cursor::new(ply_data))
+            .unwrap()
+            .with_options(options.clone())
+            .next_element()
+            .unwrap();
+        assert_eq!(faces[0].flags, Vec::<u8>::new());
+
+        // The property is declared but the row omits it, which is an error.
+        let ply_data = "ply\nformat ascii 1.0\nelement face 1\nproperty list uchar uint vertex_indices\nproperty list uchar uchar flags\nend_header\n3 0 1 2\n";
+        let result = PlyReader::from_reader(Cursor::new(ply_data))
+            .unwrap()
+            .with_options(options.clone())
+            .next_element::<Vec<Face>>();
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn test_rows_iterator() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Vertex {
+        x: f32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Face {
+        vertex_indices: Vec<u32>,
+    }
+
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n1\n2\n3\n3 0 1 2\n";
+
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let mut rows = reader.rows::<Vertex>().unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows.next().unwrap().unwrap(), Vertex { x: 1.0 });
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows.remaining(), 2);
+    let rest: Vec<Vertex> = rows.by_ref().collect::<Result<_, _>>().unwrap();
+    assert_eq!(rest.len(), 2);
+    assert_eq!(rows.len(), 0);
+    assert!(rows.next().is_none());
+    drop(rows);
+
+    // Dropping the iterator halfway skips the rest of the element.
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let mut rows = reader.rows::<Vertex>().unwrap();
+    rows.next().unwrap().unwrap();
+    drop(rows);
+    let faces: Vec<Face> = reader.next_element_named("face").unwrap();
+    assert_eq!(faces[0].vertex_indices, vec![0, 1, 2]);
+    assert!(reader.rows::<Face>().is_err());
+}
+
+#[test]
+fn test_rows_iterator_fused_after_error() {
+    #[derive(Deserialize, Debug)]
+    struct Vertex {
+        #[allow(dead_code)]
+        x: f32,
+    }
+
+    let ply_data =
+        "ply\nformat ascii 1.0\nelement vertex 4\nproperty float x\nend_header\n1\nnope\n3\n4\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let mut rows = reader.rows::<Vertex>().unwrap();
+    assert!(rows.next().unwrap().is_ok());
+    assert!(rows.next().unwrap().is_err());
+    assert_eq!(rows.len(), 0);
+    assert!(rows.next().is_none());
+    assert!(rows.next().is_none());
+}
+
+#[test]
+fn test_element_reader() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Vertex {
+        x: f32,
+        y: i32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Face {
+        vertex_indices: Vec<u32>,
+    }
+
+    let ascii = "ply\nformat ascii 1.0\nelement vertex 4\nproperty float x\nproperty int y\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n0.5 0\n1.5 -1\n2.5 -2\n3.5 -3\n3 0 1 2\n";
+    let mut binary = b"ply\nformat binary_big_endian 1.0\nelement vertex 4\nproperty float x\nproperty int y\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n".to_vec();
+    for i in 0..4i32 {
+        binary.extend_from_slice(&(i as f32 + 0.5).to_be_bytes());
+        binary.extend_from_slice(&(-i).to_be_bytes());
+    }
+    binary.push(3);
+    for i in 0..3u32 {
+        binary.extend_from_slice(&i.to_be_bytes());
+    }
+
+    let vertex = |i: i32| Vertex {
+        x: i as f32 + 0.5,
+        y: -i,
+    };
+    let read_faces = |reader: &mut PlyReader<Cursor<&[u8]>>| {
+        let faces: Vec<Face> = reader.next_element_named("face").unwrap();
+        assert_eq!(faces[0].vertex_indices, [0, 1, 2]);
+    };
+
+    for data in [ascii.as_bytes(), &binary] {
+        let mut reader = PlyReader::from_reader(Cursor::new(data))
+            .unwrap()
+            .with_options(DeserializeOptions::new().collect_stats(true));
+        let mut vertices = reader.element().unwrap();
+        assert_eq!(vertices.def().name, "vertex");
+        assert_eq!(vertices.remaining(), 4);
+        // Peeking doesn't consume the row or count it.
+        assert_eq!(vertices.peek_row::<Vertex>().unwrap(), vertex(0));
+        assert_eq!(vertices.peek_row::<Vertex>().unwrap(), vertex(0));
+        assert_eq!(vertices.stats().unwrap().rows, 0);
+        assert_eq!(vertices.take::<Vertex>(1).unwrap(), [vertex(0)]);
+        assert_eq!(vertices.peek_row::<Vertex>().unwrap(), vertex(1));
+        assert_eq!(vertices.stats().unwrap().rows, 1);
+        assert_eq!(
+            vertices.collect::<Vertex>().unwrap(),
+            [vertex(1), vertex(2), vertex(3)]
+        );
+        read_faces(&mut reader);
+        assert!(reader.element().is_err());
+
+        // Taking more rows than are left stops at the end of the element.
+        let mut reader = PlyReader::from_reader(Cursor::new(data)).unwrap();
+        let mut vertices = reader.element().unwrap();
+        assert_eq!(vertices.take::<Vertex>(10).unwrap().len(), 4);
+        assert!(vertices.peek_row::<Vertex>().is_err());
+        drop(vertices);
+        read_faces(&mut reader);
+
+        // Dropping the element reader halfway skips the rest of the element.
+        let mut reader = PlyReader::from_reader(Cursor::new(data)).unwrap();
+        let mut vertices = reader.element().unwrap();
+        vertices.take::<Vertex>(2).unwrap();
+        drop(vertices);
+        read_faces(&mut reader);
+
+        // As does dropping it untouched, or the rows iterator made from it.
+        let mut reader = PlyReader::from_reader(Cursor::new(data)).unwrap();
+        drop(reader.element().unwrap());
+        read_faces(&mut reader);
+
+        let mut reader = PlyReader::from_reader(Cursor::new(data)).unwrap();
+        let mut rows = reader.element().unwrap().rows::<Vertex>();
+        assert_eq!(rows.next().unwrap().unwrap(), vertex(0));
+        drop(rows);
+        read_faces(&mut reader);
+
+        let mut reader = PlyReader::from_reader(Cursor::new(data)).unwrap();
+        let mut vertices = reader.element().unwrap();
+        vertices.take::<Vertex>(3).unwrap();
+        vertices.skip().unwrap();
+        read_faces(&mut reader);
+    }
+
+    // A failed row stops the element reader and skipping.
+    let broken = ascii.replace("1.5 -1", "nope -1");
+    let mut reader = PlyReader::from_reader(Cursor::new(broken.as_bytes())).unwrap();
+    let mut vertices = reader.element().unwrap();
+    assert!(vertices.take::<Vertex>(3).is_err());
+    assert_eq!(vertices.remaining(), 0);
+    assert!(vertices.skip().is_ok());
+
+    // Skipping reports errors that dropping can't.
+    let truncated = &binary[..binary.len() - 20];
+    let mut reader = PlyReader::from_reader(Cursor::new(truncated)).unwrap();
+    assert!(reader.element().unwrap().skip().is_err());
+}
+
+#[test]
+fn test_rows_iterator_par_bridge() {
+    use rayon::iter::{ParallelBridge, ParallelIterator};
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Vertex {
+        x: f32,
+        y: i32,
+    }
+
+    let count = 1000i32;
+    let mut ply_data = format!(
+        "ply\nformat binary_little_endian 1.0\nelement vertex {count}\nproperty float x\nproperty int y\nend_header\n"
+    )
+    .into_bytes();
+    for i in 0..count {
+        ply_data.extend_from_slice(&(i as f32 * 0.5).to_le_bytes());
+        ply_data.extend_from_slice(&(-i).to_le_bytes());
+    }
+
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply_data)).unwrap();
+    let sequential: Vec<Vertex> = reader
+        .rows::<Vertex>()
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply_data)).unwrap();
+    let mut parallel: Vec<Vertex> = reader
+        .rows::<Vertex>()
+        .unwrap()
+        .par_bridge()
+        .map(|row| row.unwrap())
+        .collect();
+    parallel.sort_by_key(|v| -v.y);
+    assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn test_find_data_start() {
+    use serde_ply::find_data_start;
+
+    let lf = b"ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\n1.0\n";
+    assert_eq!(&lf[find_data_start(lf).unwrap()..], b"1.0\n");
+
+    let crlf =
+        b"ply\r\nformat ascii 1.0\r\nelement vertex 1\r\nproperty float x\r\nend_header\r\n1.0\r\n";
+    assert_eq!(&crlf[find_data_start(crlf).unwrap()..], b"1.0\r\n");
+
+    // Mentions of end_header that aren't the header's last line.
+    let adversarial = b"ply\nformat ascii 1.0\ncomment end_header\nend_headers\nx end_header\nend_header\n\x01\x02";
+    assert_eq!(
+        &adversarial[find_data_start(adversarial).unwrap()..],
+        b"\x01\x02"
+    );
+    let suffix = b"ply\ncomment not the end_header\nend_header\nDATA";
+    assert_eq!(&suffix[find_data_start(suffix).unwrap()..], b"DATA");
+
+    // The end_header line isn't complete yet.
+    assert_eq!(find_data_start(b"ply\nformat ascii 1.0\nend_header"), None);
+    assert_eq!(
+        find_data_start(b"ply\nformat ascii 1.0\nend_header\r"),
+        None
+    );
+    assert_eq!(find_data_start(b"ply\nformat ascii 1.0\nend_hea"), None);
+    assert_eq!(find_data_start(b""), None);
+
+    // Agrees with the header parser.
+    let indented: &[u8] = b"ply\nformat ascii 1.0\n  end_header \r\n1.0\n";
+    for file in [&lf[..], &crlf[..], indented] {
+        let (_, header_len) = serde_ply::read_header_prefix(file).unwrap();
+        assert_eq!(find_data_start(file), Some(header_len as usize));
+    }
+}
+
+#[test]
+fn test_non_utf8_comment() {
+    #[derive(Deserialize)]
+    struct Vertex {
+        x: f32,
+    }
+
+    // "Café" in Windows-1252 / Latin-1.
+    let mut ply_data = b"ply\nformat ascii 1.0\ncomment made in Caf\xe9  2.0\r\ncomment plain\nelement vertex 1\nproperty float x\nend_header\n1.5\n".to_vec();
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply_data)).unwrap();
+    let header = reader.header();
+    assert_eq!(header.comments, vec!["made in Caf\u{fffd} 2.0", "plain"]);
+    assert_eq!(
+        header.comments_raw(),
+        [b"made in Caf\xe9  2.0".to_vec(), b"plain".to_vec()]
+    );
+    let vertices: Vec<Vertex> = reader.next_element().unwrap();
+    assert_eq!(vertices[0].x, 1.5);
+
+    // The raw bytes don't take part in equality, a header built by hand is the same.
+    let plain = b"ply\nformat ascii 1.0\ncomment hello\nelement vertex 1\nproperty float x\nend_header\n1.5\n";
+    let parsed = PlyReader::from_reader(Cursor::new(plain))
+        .unwrap()
+        .header()
+        .clone();
+    let mut built = serde_ply::PlyHeader::new(PlyFormat::Ascii, parsed.elem_defs.clone());
+    built.comments = vec!["hello".to_string()];
+    assert!(built.comments_raw().is_empty());
+    assert_eq!(parsed, built);
+
+    // Copying the file keeps the comment bytes.
+    #[derive(Deserialize, serde::Serialize)]
+    struct Row {
+        x: f32,
+    }
+    let mut output = Vec::new();
+    serde_ply::map_element(
+        Cursor::new(&ply_data),
+        &mut output,
+        "vertex",
+        |r: Row| r,
+        serde_ply::SerializeOptions::ascii(),
+    )
+    .unwrap();
+    ply_data.retain(|&b| b != b'\r');
+    assert_eq!(output, ply_data);
+
+    // A comment changed after parsing is written as it is now, the others keep their bytes.
+    let mut header = PlyReader::from_reader(Cursor::new(&ply_data))
+        .unwrap()
+        .header()
+        .clone();
+    header.comments[1] = "edited".to_string();
+    let mut writer = serde_ply::PlyWriter::new(Vec::new(), &header).unwrap();
+    writer.write_row(&Row { x: 1.5 }).unwrap();
+    let output = writer.finish().unwrap();
+    let expected = b"comment made in Caf\xe9  2.0\ncomment edited\n";
+    assert!(output.windows(expected.len()).any(|w| w == expected));
+}
+
+#[test]
+fn test_ascii_float_parsing() {
+    #[derive(Deserialize)]
+    struct Row {
+        a: f32,
+        b: f64,
+    }
+
+    let tokens = [
+        "0",
+        "-0.0",
+        "0.1",
+        "3.14159",
+        "1.5e3",
+        "-2.5E-7",
+        "6.02214076e23",
+        "1e-45",
+        "1e39",
+        "123456789012345678901",
+        "inf",
+        "-inf",
+        "infinity",
+        "NaN",
+        "0.30000000000000004",
+    ];
+    let mut ply_data = format!(
+        "ply\nformat ascii 1.0\nelement row {}\nproperty float a\nproperty double b\nend_header\n",
+        tokens.len()
+    );
+    for t in tokens {
+        ply_data.push_str(&format!("{t} {t}\n"));
+    }
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let rows: Vec<Row> = reader.next_element().unwrap();
+    for (t, row) in tokens.iter().zip(rows) {
+        let (a, b) = (t.parse::<f32>().unwrap(), t.parse::<f64>().unwrap());
+        assert!(
+            row.a.to_bits() == a.to_bits() || (a.is_nan() && row.a.is_nan()),
+            "{t}"
+        );
+        assert!(
+            row.b.to_bits() == b.to_bits() || (b.is_nan() && row.b.is_nan()),
+            "{t}"
+        );
+    }
+}
+
+#[test]
+fn test_skip_rows_into_empty_types() {
+    use serde::de::IgnoredAny;
+
+    #[derive(Deserialize)]
+    struct Unit;
+
+    #[derive(Deserialize)]
+    struct Empty {}
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Face {
+        vertex_indices: Vec<u32>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Point {
+        x: f32,
+    }
+
+    // Several rows on one line, and lists, must still be skipped row by row.
+    let ascii = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty list uchar int n\nelement face 2\nproperty list uchar uint vertex_indices\nelement point 1\nproperty float x\nend_header\n1 2 5 6\n2 0 3 3 7 8 9\n3 0 1 2\n4 1 2 3 4\n2.5\n";
+
+    let mut binary = b"ply\nformat binary_little_endian 1.0\nelement vertex 3\nproperty float x\nproperty list uchar int n\nelement face 2\nproperty list uchar uint vertex_indices\nelement point 1\nproperty float x\nend_header\n".to_vec();
+    for (x, n) in [(1.0f32, &[5i32, 6][..]), (2.0, &[]), (3.0, &[7, 8, 9])] {
+        binary.extend_from_slice(&x.to_le_bytes());
+        binary.push(n.len() as u8);
+        for v in n {
+            binary.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+    for face in [&[0u32, 1, 2][..], &[1, 2, 3, 4]] {
+        binary.push(face.len() as u8);
+        for v in face {
+            binary.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+    binary.extend_from_slice(&2.5f32.to_le_bytes());
+
+    for data in [ascii.as_bytes(), &binary] {
+        let check_rest = |reader: &mut PlyReader<Cursor<&[u8]>>| {
+            let faces: Vec<Face> = reader.next_element().unwrap();
+            assert_eq!(faces[1].vertex_indices, vec![1, 2, 3, 4]);
+            let points: Vec<Point> = reader.next_element().unwrap();
+            assert_eq!(points, vec![Point { x: 2.5 }]);
+        };
+
+        let mut reader = PlyReader::from_reader(Cursor::new(data)).unwrap();
+        let rows: Vec<()> = reader.next_element().unwrap();
+        assert_eq!(rows.len(), 3);
+        check_rest(&mut reader);
+
+        let mut reader = PlyReader::from_reader(Cursor::new(data)).unwrap();
+        assert_eq!(reader.next_element::<Vec<Unit>>().unwrap().len(), 3);
+        check_rest(&mut reader);
+
+        let mut reader = PlyReader::from_reader(Cursor::new(data)).unwrap();
+        assert_eq!(reader.next_element::<Vec<Empty>>().unwrap().len(), 3);
+        check_rest(&mut reader);
+
+        let mut reader = PlyReader::from_reader(Cursor::new(data)).unwrap();
+        assert_eq!(reader.next_element::<Vec<IgnoredAny>>().unwrap().len(), 3);
+        check_rest(&mut reader);
+    }
+
+    // Skipping still notices a truncated element.
+    let truncated = &binary[..binary.len() - 30];
+    let mut reader = PlyReader::from_reader(Cursor::new(truncated)).unwrap();
+    reader.next_element::<Vec<()>>().unwrap();
+    assert!(reader.next_element::<Vec<()>>().is_err());
+}
+
+#[test]
+fn test_from_seekable_truncated() {
+    #[derive(Deserialize)]
+    #[allow(unused)]
+    struct Vertex {
+        x: f32,
+        y: f32,
+        z: f32,
+        red: u8,
+        green: u8,
+        blue: u8,
+    }
+
+    let mut bytes = b"ply\nformat binary_little_endian 1.0\nelement vertex 1000\nproperty float x\nproperty float y\nproperty float z\nproperty uchar red\nproperty uchar green\nproperty uchar blue\nend_header\n".to_vec();
+    let data_start = bytes.len();
+    // 1000 rows of 3 floats and 3 bytes.
+    bytes.resize(data_start + 15_000, 1);
+
+    // Complete files read as before, starting at the data.
+    let mut reader = PlyReader::from_seekable(Cursor::new(&bytes)).unwrap();
+    let vertices: Vec<Vertex> = reader.next_element().unwrap();
+    assert_eq!(vertices.len(), 1000);
+
+    let truncated = &bytes[..bytes.len() - 100];
+    let err = PlyReader::from_seekable(Cursor::new(truncated))
+        .err()
+        .unwrap();
+    assert_eq!(err.0.kind(), std::io::ErrorKind::UnexpectedEof);
+    let msg = err.to_string();
+    assert!(msg.contains("15000"), "{msg}");
+    assert!(msg.contains("14900"), "{msg}");
+
+    // Non-seekable readers only notice once they get there.
+    let mut reader = PlyReader::from_reader(truncated).unwrap();
+    assert!(reader.next_element::<Vec<Vertex>>().is_err());
+
+    // With a list first the size isn't known, so the check is skipped.
+    let mut lists = b"ply\nformat binary_little_endian 1.0\nelement face 2\nproperty list uchar int vertex_indices\nelement vertex 10\nproperty float x\nend_header\n".to_vec();
+    lists.extend_from_slice(&[1, 0, 0, 0, 0]);
+    let mut reader = PlyReader::from_seekable(Cursor::new(lists)).unwrap();
+    assert!(reader.next_element::<Vec<serde::de::IgnoredAny>>().is_err());
+
+    // ASCII files aren't checked either.
+    let ascii = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nend_header\n1\n";
+    assert!(PlyReader::from_seekable(Cursor::new(ascii)).is_ok());
+}
+
+#[test]
+fn test_load_report() {
+    use serde::de::IgnoredAny;
+    use serde_ply::ListStats;
+
+    #[derive(Deserialize)]
+    #[allow(unused)]
+    struct Vertex {
+        x: f32,
+        y: f32,
+        red: u8,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(unused)]
+    struct Face {
+        vertex_indices: Vec<u32>,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(unused)]
+    struct Mesh {
+        vertex: Vec<Vertex>,
+        face: Vec<Face>,
+    }
+
+    let faces: [&[u32]; 4] = [&[0, 1, 2], &[0, 1, 2, 3], &[], &[4, 3, 2, 1, 0]];
+    let mut data = b"ply\nformat binary_big_endian 1.0\nelement vertex 5\nproperty float x\nproperty float y\nproperty uchar red\nelement face 4\nproperty list ushort uint vertex_indices\nend_header\n".to_vec();
+    let data_start = data.len();
+    for i in 0..5 {
+        data.extend_from_slice(&(i as f32).to_be_bytes());
+        data.extend_from_slice(&(i as f32).to_be_bytes());
+        data.push(i);
+    }
+    for face in faces {
+        data.extend_from_slice(&(face.len() as u16).to_be_bytes());
+        for i in face {
+            data.extend_from_slice(&i.to_be_bytes());
+        }
+    }
+
+    let options = DeserializeOptions::new().collect_stats(true);
+    let mut reader = PlyReader::from_reader(Cursor::new(&data))
+        .unwrap()
+        .with_options(options.clone());
+    assert_eq!(reader.report().unwrap().data_bytes(), 0);
+    let _mesh: Mesh = Mesh::deserialize(&mut reader).unwrap();
+    let report = reader.report().unwrap().clone();
+
+    assert_eq!(report.data_bytes(), (data.len() - data_start) as u64);
+    let vertex = report.element("vertex").unwrap();
+    assert_eq!((vertex.rows, vertex.data_bytes), (5, 45));
+    assert_eq!(vertex.property("x").unwrap().bytes, 20);
+    assert_eq!(vertex.property("red").unwrap().bytes, 5);
+    assert_eq!(vertex.property("red").unwrap().list, None);
+
+    // 4 counts of 2 bytes and 12 indices of 4 bytes.
+    let face = report.element("face").unwrap();
+    assert_eq!((face.rows, face.data_bytes), (4, 56));
+    let indices = face.property("vertex_indices").unwrap();
+    assert_eq!(indices.bytes, 56);
+    let lists = indices.list.as_ref().unwrap();
+    assert_eq!(
+        *lists,
+        ListStats {
+            lists: 4,
+            min_len: 0,
+            max_len: 5,
+            total_len: 12,
+        }
+    );
+    assert_eq!(lists.mean_len(), 3.0);
+
+    // Skipped rows are counted the same, element by element.
+    let mut reader = PlyReader::from_reader(Cursor::new(&data))
+        .unwrap()
+        .with_options(options.clone());
+    reader.next_element::<Vec<()>>().unwrap();
+    assert_eq!(reader.report().unwrap().elements[0], report.elements[0]);
+    assert_eq!(reader.report().unwrap().elements[1].rows, 0);
+    let rows: Vec<_> = reader.rows::<IgnoredAny>().unwrap().collect();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(reader.report().unwrap(), &report);
+
+    // Switching to the chunked reader keeps the report, and rows cut off by the end of a
+    // chunk are only counted once.
+    let reader = PlyReader::from_reader(Cursor::new(&data[..data_start]))
+        .unwrap()
+        .with_options(options.clone());
+    let mut file = reader.into_chunked().unwrap();
+    for chunk in data[data_start..].chunks(7) {
+        file.buffer_mut().extend_from_slice(chunk);
+        // Parse until the data runs out partway through an element.
+        while let Some(element) = file.current_element().map(|e| e.name.clone()) {
+            if element == "vertex" {
+                file.next_chunk::<Vec<Vertex>>().unwrap();
+            } else {
+                file.next_chunk::<Vec<Face>>().unwrap();
+            }
+            if file.current_element().map(|e| &e.name) == Some(&element) {
+                break;
+            }
+        }
+    }
+    assert_eq!(file.report().unwrap(), &report);
+
+    // Disabled by default.
+    let reader = PlyReader::from_reader(Cursor::new(&data)).unwrap();
+    assert!(reader.report().is_none());
+}
+
+#[test]
+fn test_load_report_ascii() {
+    use serde::de::IgnoredAny;
+
+    let ply = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty list uchar int n\nend_header\n1.5 2 10 20\n-3 0\n";
+    let data_start = ply.find("end_header\n").unwrap() + "end_header\n".len();
+    let mut reader = PlyReader::from_reader(Cursor::new(ply))
+        .unwrap()
+        .with_options(DeserializeOptions::new().collect_stats(true));
+    reader.next_element::<Vec<IgnoredAny>>().unwrap();
+
+    let report = reader.report().unwrap();
+    // Whitespace counts towards the value it ends.
+    assert_eq!(report.data_bytes(), (ply.len() - data_start) as u64);
+    let vertex = &report.elements[0];
+    assert_eq!(
+        vertex.properties[0].bytes,
+        "1.5 ".len() as u64 + "-3 ".len() as u64
+    );
+    let lists = vertex.properties[1].list.as_ref().unwrap();
+    assert_eq!((lists.min_len, lists.max_len, lists.total_len), (0, 2, 2));
+}
+
+#[test]
+fn test_double_into_f32_precision() {
+    use serde_ply::{PrecisionLoss, PrecisionWarning};
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Point {
+        x: f32,
+        y: Option<f32>,
+        z: f64,
+        w: Vec<f32>,
+    }
+
+    // UTM scale coordinates lose their millimeters in an f32, small values held exactly
+    // don't, under a relative tolerance tight enough to notice rounding.
+    let values: [(f64, f64); 3] = [(0.25, 0.125), (500000.123, 1.5), (4649776.224, 0.375)];
+    let tight = || DeserializeOptions::new().with_f32_relative_tolerance(1e-9);
+    let header = |format: &str| {
+        format!("ply\nformat {format} 1.0\nelement vertex 3\nproperty double x\nproperty double y\nproperty double z\nproperty list uchar double w\nend_header\n")
+    };
+    let mut ascii = header("ascii");
+    let mut binary = header("binary_little_endian").into_bytes();
+    for (big, small) in values {
+        ascii += &format!("{big} {small} {big} 2 {small} {big}\n");
+        for v in [big, small, big] {
+            binary.extend_from_slice(&v.to_le_bytes());
+        }
+        binary.push(2);
+        binary.extend_from_slice(&small.to_le_bytes());
+        binary.extend_from_slice(&big.to_le_bytes());
+    }
+
+    for data in [ascii.into_bytes(), binary] {
+        let mut reader = PlyReader::from_reader(Cursor::new(&data))
+            .unwrap()
+            .with_options(tight());
+        let points: Vec<Point> = reader.next_element().unwrap();
+        assert_eq!(points[1].x, 500000.123f64 as f32);
+        assert_eq!(points[1].z, 500000.123);
+        assert_eq!(
+            reader.precision_warnings(),
+            [
+                PrecisionWarning {
+                    element: "vertex".to_string(),
+                    property: "x".to_string(),
+                    count: 2,
+                    example: 500000.123,
+                },
+                PrecisionWarning {
+                    element: "vertex".to_string(),
+                    property: "w".to_string(),
+                    count: 2,
+                    example: 500000.123,
+                },
+            ]
+        );
+
+        // The default tolerance is above the rounding error of an f32, so it accepts them.
+        let mut reader = PlyReader::from_reader(Cursor::new(&data)).unwrap();
+        reader.next_element::<Vec<Point>>().unwrap();
+        assert!(reader.precision_warnings().is_empty());
+
+        let mut reader = PlyReader::from_reader(Cursor::new(&data))
+            .unwrap()
+            .with_options(tight().with_precision_loss(PrecisionLoss::Allow));
+        let allowed: Vec<Point> = reader.next_element().unwrap();
+        assert_eq!(allowed, points);
+        assert!(reader.precision_warnings().is_empty());
+
+        let mut reader = PlyReader::from_reader(Cursor::new(&data))
+            .unwrap()
+            .with_options(tight().with_precision_loss(PrecisionLoss::Error));
+        let err = reader.next_element::<Vec<Point>>().unwrap_err().to_string();
+        assert!(
+            err.contains("property 'x' of element 'vertex' is a double, and 500000.123 loses precision in an f32 field (row 1)"),
+            "{err}"
+        );
+    }
+
+    #[derive(Deserialize)]
+    #[allow(unused)]
+    struct X {
+        x: f32,
+    }
+
+    // Ordinary coordinates only change by the rounding error of an f32, relative to their
+    // size, so they load by default even under the strict policy.
+    let ply = "ply\nformat ascii 1.0\nelement vertex 3\nproperty double x\nend_header\n4096.1\n0.1\n-123.456\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply))
+        .unwrap()
+        .with_options(DeserializeOptions::new().with_precision_loss(PrecisionLoss::Error));
+    reader.next_element::<Vec<X>>().unwrap();
+
+    // Values that collapse to zero or a subnormal lose most of their digits.
+    let ply =
+        "ply\nformat ascii 1.0\nelement vertex 2\nproperty double x\nend_header\n1e-50\n1.5\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply)).unwrap();
+    reader.next_element::<Vec<X>>().unwrap();
+    assert_eq!(reader.precision_warnings()[0].count, 1);
+    assert_eq!(reader.precision_warnings()[0].example, 1e-50);
+
+    // Doubles beyond the f32 range always count.
+    let ply = "ply\nformat ascii 1.0\nelement vertex 1\nproperty double x\nend_header\n1e300\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply))
+        .unwrap()
+        .with_options(DeserializeOptions::new().with_f32_relative_tolerance(f64::INFINITY));
+    reader.next_element::<Vec<X>>().unwrap();
+    assert_eq!(reader.precision_warnings()[0].example, 1e300);
+}
+
+#[test]
+fn test_repeated_element_names() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct X {
+        x: f32,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Model {
+        vertex: Vec<X>,
+        face: Vec<Face>,
+    }
+
+    let ply = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nelement vertex 1\nproperty float x\nelement face 1\nproperty list uchar uint vertex_indices\nelement vertex 1\nproperty float x\nend_header\n1\n2\n3\n3 0 1 2\n4\n";
+
+    // A struct field gets the first element of that name, the later ones are skipped.
+    let model: Model = serde_ply::from_str(ply).unwrap();
+    assert_eq!(model.vertex, [X { x: 1.0 }, X { x: 2.0 }]);
+    assert_eq!(model.face[0].vertex_indices, [0, 1, 2]);
+
+    let mut reader = PlyReader::from_reader(Cursor::new(ply)).unwrap();
+    let map: HashMap<String, Vec<serde::de::IgnoredAny>> =
+        Deserialize::deserialize(&mut reader).unwrap();
+    assert_eq!(map["vertex"].len(), 2);
+    assert_eq!(map.len(), 2);
+
+    // Sequences and nth_element read by position.
+    let elements: Vec<(String, Vec<serde::de::IgnoredAny>)> = serde_ply::from_str(ply).unwrap();
+    let shape: Vec<_> = elements
+        .iter()
+        .map(|(n, rows)| (n.as_str(), rows.len()))
+        .collect();
+    assert_eq!(
+        shape,
+        [("vertex", 2), ("vertex", 1), ("face", 1), ("vertex", 1)]
+    );
+
+    let mut reader = PlyReader::from_reader(Cursor::new(ply)).unwrap();
+    let second: Vec<X> = reader.nth_element(1).unwrap();
+    assert_eq!(second, [X { x: 3.0 }]);
+    let third: Vec<X> = reader.nth_element(1).unwrap();
+    assert_eq!(third, [X { x: 4.0 }]);
+    assert!(reader.nth_element::<Vec<X>>(0).is_err());
+
+    let mut reader = PlyReader::from_reader(Cursor::new(ply)).unwrap();
+    assert!(reader.nth_element::<Vec<X>>(4).is_err());
+    assert_eq!(reader.peek_element_name(), Some("vertex"));
+}
+
+#[test]
+fn test_decimal_comma() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Vertex {
+        x: f32,
+        y: f64,
+        n: u8,
+    }
+
+    let ply = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty double y\nproperty uchar n\nend_header\n1,5 -0,25 3\n2.5 7 4\n";
+
+    assert!(serde_ply::from_str::<HashMap<String, Vec<Vertex>>>(ply).is_err());
+
+    let options = DeserializeOptions::new().decimal_comma(true);
+    let mut reader = PlyReader::from_reader(Cursor::new(ply))
+        .unwrap()
+        .with_options(options.clone());
+    let vertices: Vec<Vertex> = reader.next_element().unwrap();
+    assert_eq!(
+        vertices,
+        [
+            Vertex {
+                x: 1.5,
+                y: -0.25,
+                n: 3
+            },
+            Vertex {
+                x: 2.5,
+                y: 7.0,
+                n: 4
+            }
+        ]
+    );
+
+    // Mixed separators are ambiguous, and integers never take a comma.
+    for row in ["1,234.5 0 0", "1,2,3 0 0", "0 0 3,0"] {
+        let ply = format!("ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty double y\nproperty uchar n\nend_header\n{row}\n");
+        let mut reader = PlyReader::from_reader(Cursor::new(ply))
+            .unwrap()
+            .with_options(options.clone());
+        assert!(reader.next_element::<Vec<Vertex>>().is_err(), "{row}");
+    }
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn test_element_array2() {
+    let mut ply = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nproperty float x\nproperty double y\nproperty int z\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n".to_vec();
+    for (x, y, z) in [(1.5f32, 2.5f64, 3i32), (-4.0, 5.25, -6)] {
+        ply.extend_from_slice(&x.to_le_bytes());
+        ply.extend_from_slice(&y.to_le_bytes());
+        ply.extend_from_slice(&z.to_le_bytes());
+    }
+    ply.push(3);
+    for i in [0u32, 1, 0] {
+        ply.extend_from_slice(&i.to_le_bytes());
+    }
+
+    let mut reader = PlyReader::from_reader(Cursor::new(ply)).unwrap();
+    // The next element is 'vertex'.
+    assert!(reader.element_array2("face").is_err());
+    let vertices = reader.element_array2("vertex").unwrap();
+    assert_eq!(vertices.dim(), (2, 3));
+    assert_eq!(
+        vertices,
+        ndarray::array![[1.5, 2.5, 3.0], [-4.0, 5.25, -6.0]]
+    );
+
+    // List properties can't be a matrix column.
+    assert!(reader.element_array2("face").is_err());
+    let faces: Vec<Face> = reader.next_element().unwrap();
+    assert_eq!(faces[0].vertex_indices, [0, 1, 0]);
+}
+
+#[test]
+fn test_extra_whitespace_in_binary() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Face {
+        vertex_indices: Vec<i32>,
+    }
+
+    let mut ply = b"ply\nformat binary_little_endian 1.0\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n\n".to_vec();
+    ply.push(3);
+    for i in [0i32, 1, 2] {
+        ply.extend_from_slice(&i.to_le_bytes());
+    }
+
+    // Strictly, the stray newline is the list length, so the row runs past the data.
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply)).unwrap();
+    assert!(reader.next_element::<Vec<Face>>().is_err());
+
+    let options = DeserializeOptions::new().allow_extra_whitespace_in_binary(true);
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply))
+        .unwrap()
+        .with_options(options);
+    let faces: Vec<Face> = reader.next_element().unwrap();
+    assert_eq!(
+        faces,
+        [Face {
+            vertex_indices: vec![0, 1, 2]
+        }]
+    );
+}
+
+#[test]
+fn test_not_ply_error_shows_start() {
+    // A reader that was already read past the magic.
+    let data = b"ply\nformat binary_little_endian 1.0\nelement vertex 1\nproperty float x\nend_header\n\x00\x00\x80\x3f";
+    let err = PlyReader::from_reader(Cursor::new(&data[4..]))
+        .err()
+        .unwrap();
+    assert_eq!(err.0.kind(), std::io::ErrorKind::InvalidData);
+    let msg = err.to_string();
+    assert!(msg.contains("File must start with 'ply', but starts with 66 6f 72 6d 61 74 20"));
+    assert!(msg.contains("|format.binary_little_endian.1.0.|"));
+    assert!(msg.contains("PlyReader::from_reader_at"));
+
+    // Binary data without any line break fails right away, with at most 32 bytes shown.
+    let err = PlyReader::from_reader(Cursor::new(vec![0xffu8; 100]))
+        .err()
+        .unwrap();
+    assert!(err
+        .to_string()
+        .contains(&format!("{} |{}|", ["ff"; 32].join(" "), ".".repeat(32))));
+
+    // A first line that only starts like the magic.
+    let err = PlyReader::from_reader(Cursor::new("plyfile\nformat ascii 1.0\nend_header\n"))
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("|plyfile.|"));
+}
+
+#[test]
+fn test_from_reader_at() {
+    let mut data = b"CONTAINER".to_vec();
+    data.extend_from_slice(b"ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float y\nproperty float z\nend_header\n1 2 3\n");
+
+    let mut reader = PlyReader::from_reader_at(Cursor::new(&data), 9).unwrap();
+    let vertices: Vec<Vertex> = reader.next_element().unwrap();
+    assert_eq!(
+        vertices,
+        [Vertex {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0
+        }]
+    );
+
+    assert!(PlyReader::from_reader_at(Cursor::new(&data), 0).is_err());
+}
+
+#[test]
+fn test_owned_header() {
+    let ply_data = "ply\nformat ascii 1.0\ncomment kept\nelement vertex 1\nproperty float x\nproperty float y\nproperty float z\nend_header\n1 2 3\n";
+
+    let header = {
+        let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+        let header = reader.clone_header();
+        // The copy stays usable while the reader is read from.
+        let _: Vec<Vertex> = reader.next_element().unwrap();
+        assert_eq!(&header, reader.header());
+        header
+    };
+    assert_eq!(header.comments, ["kept"]);
+    assert_eq!(header.elem_defs[0].name, "vertex");
+
+    let reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    assert_eq!(reader.into_header(), header);
+}
+
+/// A mesh whose header declares `uchar` face counts while the data has `ushort` counts.
+fn ushort_counts_as_uchar() -> Vec<u8> {
+    let mut ply_data = b"ply\nformat binary_little_endian 1.0\nelement vertex 4\nproperty float x\nproperty float y\nproperty float z\nelement face 2\nproperty list uchar int vertex_indices\nend_header\n".to_vec();
+    for i in 0..12 {
+        ply_data.extend_from_slice(&(i as f32).to_le_bytes());
+    }
+    for face in [[0i32, 1, 2], [1, 3, 2]] {
+        ply_data.extend_from_slice(&3u16.to_le_bytes());
+        for i in face {
+            ply_data.extend_from_slice(&i.to_le_bytes());
+        }
+    }
+    ply_data
+}
+
+#[test]
+fn test_diagnose_misaligned_list() {
+    let ply_data = ushort_counts_as_uchar();
+    let options = DeserializeOptions::new().diagnose_misalignment(true);
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply_data))
+        .unwrap()
+        .with_options(options.clone());
+    let _vertices: Vec<Vertex> = reader.next_element().unwrap();
+    let err = reader.next_element::<Vec<Face>>().unwrap_err();
+    assert!(err.is_misaligned_list());
+    let msg = err.to_string();
+    assert!(msg.contains("list 'vertex_indices' of element 'face' has entry 256, but there are only 4 vertices (row 0)"));
+    assert!(msg.contains(
+        "DeserializeOptions::override_list_count_type(\"face\", \"vertex_indices\", ScalarType::U16)"
+    ));
+
+    // Skipping the vertices doesn't change the diagnosis.
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply_data))
+        .unwrap()
+        .with_options(options);
+    reader.next_element::<serde::de::IgnoredAny>().unwrap();
+    assert!(reader
+        .next_element::<Vec<Face>>()
+        .unwrap_err()
+        .is_misaligned_list());
+}
+
+#[test]
+fn test_override_list_count_type() {
+    let ply_data = ushort_counts_as_uchar();
+    let options = DeserializeOptions::new()
+        .diagnose_misalignment(true)
+        .override_list_count_type("face", "vertex_indices", serde_ply::ScalarType::U16);
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply_data))
+        .unwrap()
+        .with_options(options.clone());
+    assert_eq!(
+        reader.header().get_element("face").unwrap().properties[0].property_type,
+        serde_ply::PropertyType::List {
+            count_type: serde_ply::ScalarType::U16,
+            data_type: serde_ply::ScalarType::I32,
+        }
+    );
+    let _vertices: Vec<Vertex> = reader.next_element().unwrap();
+    let faces: Vec<Face> = reader.next_element().unwrap();
+    assert_eq!(faces[1].vertex_indices, [1, 3, 2]);
+
+    let mut chunked = serde_ply::PlyChunkedReader::new().with_options(options);
+    chunked.buffer_mut().extend_from_slice(&ply_data);
+    let _vertices: Vec<Vertex> = chunked.next_chunk().unwrap();
+    let faces: Vec<Face> = chunked.next_chunk().unwrap();
+    assert_eq!(faces[1].vertex_indices, [1, 3, 2]);
+}
+
+#[test]
+fn test_diagnose_implausible_list_len() {
+    // Declared as int counts, written as uchar.
+    let mut ply_data =
+        b"ply\nformat binary_little_endian 1.0\nelement face 1\nproperty list int int vertex_indices\nend_header\n"
+            .to_vec();
+    ply_data.push(3);
+    for i in [1000i32, 2000, 3000] {
+        ply_data.extend_from_slice(&i.to_le_bytes());
+    }
+    let options = DeserializeOptions::new().diagnose_misalignment(true);
+    let err = PlyReader::from_reader(Cursor::new(&ply_data))
+        .unwrap()
+        .with_options(options.clone())
+        .next_element::<Vec<Face>>()
+        .unwrap_err();
+    assert!(err.is_misaligned_list());
+    assert!(err
+        .to_string()
+        .contains("has 256003 entries, more than the plausible 1024"));
+    assert!(err.to_string().contains("ScalarType::U8)"));
+
+    let faces: Vec<Face> = PlyReader::from_reader(Cursor::new(&ply_data))
+        .unwrap()
+        .with_options(options.clone().override_list_count_type(
+            "face",
+            "vertex_indices",
+            serde_ply::ScalarType::U8,
+        ))
+        .next_element()
+        .unwrap();
+    assert_eq!(faces[0].vertex_indices, [1000, 2000, 3000]);
+
+    // The threshold is configurable, and ASCII lists aren't checked.
+    let ply_data = "ply\nformat ascii 1.0\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n3 0 1 2\n";
+    let options = options.with_max_plausible_list_len(2);
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data))
+        .unwrap()
+        .with_options(options.clone());
+    assert!(reader.next_element::<Vec<Face>>().is_ok());
+    let bytes = serde_ply::to_bytes(
+        &HashMap::from([(
+            "face",
+            vec![HashMap::from([("vertex_indices", vec![0u32, 1, 2])])],
+        )]),
+        serde_ply::SerializeOptions::binary_le(),
+    )
+    .unwrap();
+    let err = PlyReader::from_reader(Cursor::new(bytes))
+        .unwrap()
+        .with_options(options)
+        .next_element::<Vec<Face>>()
+        .unwrap_err();
+    assert!(err.is_misaligned_list());
+}
+
+#[test]
+fn test_pad_short_rows_with_default() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Point {
+        x: f32,
+        #[serde(default)]
+        intensity: u8,
+        confidence: Option<f32>,
+    }
+
+    let ply_data = "ply\nformat ascii 1.0\nelement point 5\nproperty float x\nproperty uchar intensity\nproperty float confidence\nelement edge 1\nproperty int vertex1\nend_header\n0 1 0.5\n1 2 \n2\r\n3 4 0.25\r\n4\n7\n";
+    let options = DeserializeOptions::new().pad_short_rows_with_default(true);
+    let expected = [
+        (0.0, 1, Some(0.5)),
+        (1.0, 2, None),
+        (2.0, 0, None),
+        (3.0, 4, Some(0.25)),
+        (4.0, 0, None),
+    ]
+    .map(|(x, intensity, confidence)| Point {
+        x,
+        intensity,
+        confidence,
+    });
+
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data))
+        .unwrap()
+        .with_options(options.clone());
+    let points: Vec<Point> = reader.next_element().unwrap();
+    assert_eq!(points, expected);
+    let edges: Vec<HashMap<String, i32>> = reader.next_element().unwrap();
+    assert_eq!(edges[0]["vertex1"], 7);
+
+    // Skipped rows end at their line too.
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data))
+        .unwrap()
+        .with_options(options.clone());
+    reader.next_element::<serde::de::IgnoredAny>().unwrap();
+    let edges: Vec<HashMap<String, i32>> = reader.next_element().unwrap();
+    assert_eq!(edges[0]["vertex1"], 7);
+
+    let mut chunked = serde_ply::PlyChunkedReader::new().with_options(options.clone());
+    chunked.buffer_mut().extend_from_slice(ply_data.as_bytes());
+    let points: Vec<Point> = chunked.next_chunk().unwrap();
+    assert_eq!(points, expected);
+
+    // Fields without a default still need their value.
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Required {
+        x: f32,
+        intensity: u8,
+    }
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data))
+        .unwrap()
+        .with_options(options);
+    let err = reader.next_element::<Vec<Required>>().unwrap_err();
+    assert!(err.to_string().contains("missing field `intensity`"));
+
+    // Without the option the short rows take values of the next ones.
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    assert!(reader.next_element::<Vec<Point>>().is_err());
+}
+
+#[test]
+fn test_empty_input() {
+    for err in [
+        PlyReader::from_reader(Cursor::new(b"")).err().unwrap(),
+        PlyReader::from_seekable(Cursor::new(b"")).err().unwrap(),
+        serde_ply::from_bytes::<serde::de::IgnoredAny>(b"").unwrap_err(),
+    ] {
+        assert!(err.is_empty_input());
+        assert_eq!(err.0.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+    // A cut off header isn't an empty input.
+    let err = PlyReader::from_reader(Cursor::new(b"ply\nformat"))
+        .err()
+        .unwrap();
+    assert!(!err.is_empty_input());
+}
+
+#[test]
+fn test_header_only() {
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Mesh {
+        vertex: Vec<Vertex>,
+        face: Vec<Face>,
+    }
+
+    for format in ["ascii", "binary_little_endian", "binary_big_endian"] {
+        let ply_data = format!("ply\nformat {format} 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n");
+        let expected = serde_ply::MissingData {
+            element: "vertex".to_string(),
+            expected_rows: 3,
+            rows_read: 0,
+        };
+
+        let mut reader = PlyReader::from_reader(Cursor::new(&ply_data)).unwrap();
+        let err = reader.validate_complete().unwrap_err();
+        assert_eq!(err.as_missing_data(), Some(&expected));
+        let err = reader.next_element::<Vec<Vertex>>().unwrap_err();
+        assert_eq!(err.as_missing_data(), Some(&expected));
+        assert_eq!(err.0.kind(), std::io::ErrorKind::UnexpectedEof);
+
+        let err = serde_ply::from_str::<Mesh>(&ply_data).unwrap_err();
+        assert_eq!(err.as_missing_data(), Some(&expected));
+        let mut reader = PlyReader::from_reader(Cursor::new(&ply_data)).unwrap();
+        assert!(reader.element().err().unwrap().as_missing_data().is_some());
+    }
+
+    // Data that stops at the end of an element names the next one.
+    let mut ply_data = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n".to_vec();
+    ply_data.extend_from_slice(&[0; 24]);
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply_data)).unwrap();
+    reader.validate_complete().unwrap();
+    let _vertices: Vec<Vertex> = reader.next_element().unwrap();
+    let err = reader.next_element::<Vec<Face>>().unwrap_err();
+    assert_eq!(err.as_missing_data().unwrap().element, "face");
+
+    // A binary element cut short reports the rows it has.
+    ply_data.truncate(ply_data.len() - 4);
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply_data)).unwrap();
+    let err = reader.validate_complete().unwrap_err();
+    assert_eq!(
+        err.as_missing_data(),
+        Some(&serde_ply::MissingData {
+            element: "vertex".to_string(),
+            expected_rows: 2,
+            rows_read: 1,
+        })
+    );
+
+    // Elements without rows don't need data.
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 0\nproperty float x\nproperty float y\nproperty float z\nend_header\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    reader.validate_complete().unwrap();
+    assert!(reader.next_element::<Vec<Vertex>>().unwrap().is_empty());
+}
+
+#[test]
+fn test_repair_missing_end_header_newline() {
+    // ASCII data glued to `end_header` is rejected, but can be read with a repaired header.
+    let mut ply_data = b"ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nend_header".to_vec();
+    let data_start = ply_data.len();
+    ply_data.extend_from_slice(b"1 2 3\n4 5 10\n");
+
+    let mut cursor = Cursor::new(&ply_data);
+    let err = PlyReader::from_reader(&mut cursor).err().unwrap();
+    assert_eq!(err.phase(), serde_ply::ErrorPhase::Header);
+    assert!(err.to_string().contains("end_header"));
+    // The data after `end_header` wasn't consumed, the reader stops at its start.
+    assert_eq!(cursor.position(), data_start as u64);
+
+    let mut fixed = ply_data[..data_start].to_vec();
+    fixed.push(b'\n');
+    let (header, _) = serde_ply::read_header_prefix(&fixed).unwrap();
+    let mut reader = PlyReader::from_parts(header, &mut cursor);
+    let vertices: Vec<Vertex> = reader.next_element().unwrap();
+    assert_eq!(
+        vertices,
+        [
+            Vertex {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0
+            },
+            Vertex {
+                x: 4.0,
+                y: 5.0,
+                z: 10.0
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_binary_without_end_header_newline() {
+    let header = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nend_header";
+    let mut ply_data = header.to_vec();
+    for v in [1.0f32, 2.0, 3.0, 4.0, 5.0, 10.0] {
+        ply_data.extend_from_slice(&v.to_le_bytes());
+    }
+
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply_data)).unwrap();
+    let vertices: Vec<Vertex> = reader.next_element().unwrap();
+    assert_eq!(vertices[1].z, 10.0);
+    let all: HashMap<String, Vec<Vertex>> = serde_ply::from_bytes(&ply_data).unwrap();
+    assert_eq!(all["vertex"], vertices);
+
+    // A file that ends at `end_header` has a header but no data.
+    let empty =
+        b"ply\nformat binary_little_endian 1.0\nelement vertex 0\nproperty float x\nend_header";
+    let mut reader = PlyReader::from_reader(&empty[..]).unwrap();
+    assert!(reader.next_element::<Vec<Vertex>>().unwrap().is_empty());
+
+    // Chunked reads keep waiting for the line break, and reject data in its place.
+    let mut file = serde_ply::PlyChunkedReader::new();
+    file.buffer_mut().extend_from_slice(header);
+    assert!(file.header().is_none());
+    file.buffer_mut()
+        .extend_from_slice(&ply_data[header.len()..]);
+    assert!(file.next_chunk::<Vec<Vertex>>().is_err());
+}
+
+#[test]
+fn test_end_header_split_between_reads() {
+    use std::io::Read;
+
+    // Small buffers split `end_header` and the data glued to it over several reads.
+    let header = b"ply\nformat binary_little_endian 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nend_header";
+    let mut ply_data = header.to_vec();
+    for v in [1.0f32, 2.0, 3.0, 4.0, 5.0, 10.0] {
+        ply_data.extend_from_slice(&v.to_le_bytes());
+    }
+    let ascii = b"ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header1\n";
+
+    for capacity in 1..=16 {
+        let reader = BufReader::with_capacity(capacity, &ply_data[..]);
+        let mut reader = PlyReader::from_reader(reader).unwrap();
+        let vertices: Vec<Vertex> = reader.next_element().unwrap();
+        assert_eq!(vertices[1].z, 10.0, "capacity {capacity}");
+
+        let mut reader = BufReader::with_capacity(capacity, &ascii[..]);
+        let err = PlyReader::from_reader(&mut reader).err().unwrap();
+        assert!(
+            err.to_string().contains("end_header"),
+            "capacity {capacity}"
+        );
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"1\n", "capacity {capacity}");
+    }
+}
+
+#[test]
+fn test_error_phase() {
+    use serde_ply::ErrorPhase;
+    use std::io::Read;
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Mesh {
+        vertex: Vec<Vertex>,
+    }
+
+    /// Fails once `fail_at` bytes have been read.
+    struct FailingReader {
+        data: Cursor<Vec<u8>>,
+        fail_at: u64,
+    }
+
+    impl Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let left = self.fail_at.saturating_sub(self.data.position()) as usize;
+            if left == 0 {
+                return Err(std::io::Error::other("disk unplugged"));
+            }
+            let len = buf.len().min(left);
+            self.data.read(&mut buf[..len])
+        }
+    }
+
+    let header = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nend_header\n";
+    let header_cases = [
+        "",
+        "hello\n",
+        "ply\nformat ascii 1.0\nelement vertex 2\n",
+        "ply\nformat text 1.0\nend_header\n",
+        "ply\nformat ascii\nend_header\n",
+        "ply\nformat ascii 1.0\nelement vertex -1\nend_header\n",
+        "ply\nformat ascii 1.0\nelement vertex\nend_header\n",
+        "ply\nformat ascii 1.0\nproperty float x\nend_header\n",
+        "ply\nformat ascii 1.0\nelement vertex 1\nproperty half x\nend_header\n",
+        "ply\nformat ascii 1.0\nelement vertex 1\nproperty x float\nend_header\n",
+        "ply\nformat ascii 1.0\nelement vertex 1\nproperty list uchar x\nend_header\n",
+        "ply\nelement vertex 1\nproperty float x\nend_header\n",
+        "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header1.0\n",
+    ];
+    for ply_data in header_cases {
+        let err = PlyReader::from_reader(Cursor::new(ply_data)).err().unwrap();
+        assert_eq!(err.phase(), ErrorPhase::Header, "{ply_data:?}: {err}");
+        let err = serde_ply::from_str::<Mesh>(ply_data).unwrap_err();
+        assert_eq!(err.phase(), ErrorPhase::Header, "{ply_data:?}: {err}");
+    }
+
+    let mut chunked = serde_ply::PlyChunkedReader::new();
+    chunked
+        .buffer_mut()
+        .extend_from_slice(b"ply\nformat ascii 1.0\nelement vertex one\nend_header\n");
+    let err = chunked.next_chunk::<Vec<Vertex>>().unwrap_err();
+    assert_eq!(err.phase(), ErrorPhase::Header);
+    let mut chunked = serde_ply::PlyChunkedReader::new();
+    chunked.buffer_mut().extend_from_slice(b"ply\nformat");
+    assert_eq!(chunked.finish().unwrap_err().phase(), ErrorPhase::Header);
+
+    let data_cases = [
+        format!("{header}1 2 3\n"),
+        format!("{header}1 2 3\n4 5\n"),
+        format!("{header}1 2 3\n4 five 6\n"),
+        format!("{header}1 2 3\n4 5 -\n"),
+    ];
+    for ply_data in &data_cases {
+        let err = serde_ply::from_str::<Mesh>(ply_data).unwrap_err();
+        assert_eq!(err.phase(), ErrorPhase::Data, "{ply_data:?}: {err}");
+    }
+    let ply_data = format!("{header}1 2 3\n4 5 6\n");
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply_data)).unwrap();
+    let err = reader.next_element::<Vec<Face>>().unwrap_err();
+    assert_eq!(err.phase(), ErrorPhase::Data, "{err}");
+    let mut reader = PlyReader::from_reader(Cursor::new(&ply_data)).unwrap();
+    let _vertices: Vec<Vertex> = reader.next_element().unwrap();
+    let err = reader.next_element::<Vec<Vertex>>().unwrap_err();
+    assert!(err.is_elements_exhausted());
+    assert_eq!(err.phase(), ErrorPhase::Data);
+
+    for fail_at in [0, 10, header.len() as u64 + 2] {
+        let reader = FailingReader {
+            data: Cursor::new(ply_data.clone().into_bytes()),
+            fail_at,
+        };
+        let err = serde_ply::from_reader::<Mesh>(BufReader::with_capacity(4, reader)).unwrap_err();
+        assert_eq!(err.phase(), ErrorPhase::Io, "{fail_at}: {err}");
+    }
+}
+
+#[test]
+fn test_lidar_integer_fields_as_f32() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Point {
+        x: f32,
+        intensity: f32,
+        scalar: f32,
+        y: f32,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Cloud {
+        vertex: Vec<Point>,
+    }
+
+    let rows = [(1.5f32, 65535u16, 255u8, -2.0f32), (0.25, 300, 7, 8.0)];
+    for format in [PlyFormat::BinaryLittleEndian, PlyFormat::BinaryBigEndian] {
+        let mut ply_data = format!("ply\nformat {format} 1.0\nelement vertex 2\nproperty float x\nproperty ushort intensity\nproperty uchar scalar\nproperty float y\nend_header\n").into_bytes();
+        for (x, intensity, scalar, y) in rows {
+            if format == PlyFormat::BinaryLittleEndian {
+                ply_data.extend_from_slice(&x.to_le_bytes());
+                ply_data.extend_from_slice(&intensity.to_le_bytes());
+                ply_data.push(scalar);
+                ply_data.extend_from_slice(&y.to_le_bytes());
+            } else {
+                ply_data.extend_from_slice(&x.to_be_bytes());
+                ply_data.extend_from_slice(&intensity.to_be_bytes());
+                ply_data.push(scalar);
+                ply_data.extend_from_slice(&y.to_be_bytes());
+            }
+        }
+
+        // The following float only lines up if each integer is read with its own width.
+        let cloud: Cloud = serde_ply::from_bytes(&ply_data).unwrap();
+        let expected: Vec<Point> = rows
+            .iter()
+            .map(|&(x, intensity, scalar, y)| Point {
+                x,
+                intensity: intensity as f32,
+                scalar: scalar as f32,
+                y,
+            })
+            .collect();
+        assert_eq!(cloud.vertex, expected);
+    }
+}
+
+#[test]
+fn test_validate() {
+    let header = |format: &str| {
+        format!("ply\nformat {format} 1.0\nelement vertex 2\nproperty float x\nproperty uchar flag\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n")
+    };
+    let ascii = format!("{}1.5 1\n2.5 2\n3 0 1 1\n\n", header("ascii"));
+    let mut binary = header("binary_little_endian").into_bytes();
+    for (x, flag) in [(1.5f32, 1u8), (2.5, 2)] {
+        binary.extend_from_slice(&x.to_le_bytes());
+        binary.push(flag);
+    }
+    binary.push(3);
+    for i in [0i32, 1, 1] {
+        binary.extend_from_slice(&i.to_le_bytes());
+    }
+
+    let parsed = serde_ply::validate(ascii.as_bytes()).unwrap();
+    assert_eq!(parsed.elem_defs.len(), 2);
+    let parsed = serde_ply::validate(Cursor::new(&binary)).unwrap();
+    assert_eq!(parsed.format, PlyFormat::BinaryLittleEndian);
+
+    let ascii_corruptions = [
+        ("2.5 2", "2.5 x", "row 1 of element 'vertex'"),
+        ("2.5 2", "2.5 300", "row 1 of element 'vertex'"),
+        ("3 0 1 1", "3 0 1 1.5", "row 0 of element 'face'"),
+        ("3 0 1 1", "3 0 1", "row 0 of element 'face'"),
+        // Rows may share lines, so a short row shows up as leftover data.
+        ("2.5 2", "2.5", "after the last element"),
+        (
+            "3 0 1 1\n",
+            "3 0 1 1\n4 0 1 2 3\n",
+            "after the last element",
+        ),
+    ];
+    for (from, to, location) in ascii_corruptions {
+        let corrupt = ascii.replace(from, to);
+        let err = serde_ply::validate(corrupt.as_bytes()).unwrap_err();
+        assert_eq!(err.phase(), serde_ply::ErrorPhase::Data, "{to:?}: {err}");
+        assert!(err.to_string().contains(location), "{to:?}: {err}");
+    }
+
+    let data_start = header("binary_little_endian").len();
+    let mut long_list = binary.clone();
+    long_list[data_start + 10] = 4;
+    let mut trailing = binary.clone();
+    trailing.push(0);
+    for (corrupt, missing) in [
+        (binary[..binary.len() - 1].to_vec(), Some(("face", 0))),
+        (binary[..data_start + 3].to_vec(), Some(("vertex", 0))),
+        (long_list, Some(("face", 0))),
+        (trailing, None),
+    ] {
+        let err = serde_ply::validate(Cursor::new(&corrupt)).unwrap_err();
+        assert_eq!(err.phase(), serde_ply::ErrorPhase::Data, "{err}");
+        let found = err
+            .as_missing_data()
+            .map(|m| (m.element.as_str(), m.rows_read));
+        assert_eq!(found, missing, "{err}");
+    }
+}
+
+#[test]
+fn test_element_states() {
+    use serde_ply::ElementStatus::{Done, Pending, Reading, Skipped};
+
+    fn statuses<'a>(
+        states: impl Iterator<Item = serde_ply::ElementState> + 'a,
+    ) -> Vec<(String, serde_ply::ElementStatus, usize)> {
+        states.map(|s| (s.name, s.status, s.rows_done)).collect()
+    }
+    let s = |name: &str, status, rows| (name.to_string(), status, rows);
+
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 4\nproperty float x\nproperty float y\nproperty float z\nelement face 2\nproperty list uchar int vertex_indices\nelement edge 1\nproperty int vertex1\nend_header\n0 0 0\n1 1 1\n2 2 2\n3 3 3\n3 0 1 2\n3 1 2 3\n0\n";
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    assert_eq!(
+        statuses(reader.element_states()),
+        [
+            s("vertex", Pending, 0),
+            s("face", Pending, 0),
+            s("edge", Pending, 0)
+        ]
+    );
+
+    let mut rows = reader.rows::<Vertex>().unwrap();
+    rows.next().unwrap().unwrap();
+    rows.next().unwrap().unwrap();
+    let states: Vec<_> = rows.element_states().collect();
+    assert_eq!(states[0].fraction_done(), 0.5);
+    assert_eq!(
+        statuses(states.into_iter()),
+        [
+            s("vertex", Reading, 2),
+            s("face", Pending, 0),
+            s("edge", Pending, 0)
+        ]
+    );
+    assert_eq!(rows.by_ref().count(), 2);
+    assert_eq!(statuses(rows.element_states())[0], s("vertex", Done, 4));
+    drop(rows);
+
+    let mut faces = reader.element().unwrap();
+    let _first: Vec<Face> = faces.take(1).unwrap();
+    assert_eq!(statuses(faces.element_states())[1], s("face", Reading, 1));
+    drop(faces);
+    assert_eq!(
+        statuses(reader.element_states()),
+        [
+            s("vertex", Done, 4),
+            s("face", Skipped, 2),
+            s("edge", Pending, 0)
+        ]
+    );
+
+    // Elements read in one go, and those passed over to reach a later one.
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let _edges: Vec<serde::de::IgnoredAny> = reader.nth_element(2).unwrap();
+    assert_eq!(
+        statuses(reader.element_states()),
+        [
+            s("vertex", Skipped, 4),
+            s("face", Skipped, 2),
+            s("edge", Done, 1)
+        ]
+    );
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    let _vertices: Vec<Vertex> = reader.next_element().unwrap();
+    reader.element().unwrap().skip().unwrap();
+    assert_eq!(
+        statuses(reader.element_states()),
+        [
+            s("vertex", Done, 4),
+            s("face", Skipped, 2),
+            s("edge", Pending, 0)
+        ]
+    );
+
+    // The chunked reader picks up where the reader stopped.
+    let split = ply_data.find("3 0 1 2").unwrap() + 4;
+    let mut reader = PlyReader::from_reader(&ply_data.as_bytes()[..split]).unwrap();
+    let _vertices: Vec<Vertex> = reader.next_element().unwrap();
+    let mut chunked = reader.into_chunked().unwrap();
+    let faces: Vec<Face> = chunked.next_chunk().unwrap();
+    assert!(faces.is_empty());
+    chunked
+        .buffer_mut()
+        .extend_from_slice(&ply_data.as_bytes()[split..ply_data.len() - 4]);
+    let faces: Vec<Face> = chunked.next_chunk().unwrap();
+    assert_eq!(faces.len(), 1);
+    assert_eq!(
+        statuses(chunked.element_states()),
+        [
+            s("vertex", Done, 4),
+            s("face", Reading, 1),
+            s("edge", Pending, 0)
+        ]
+    );
+    chunked
+        .buffer_mut()
+        .extend_from_slice(&ply_data.as_bytes()[ply_data.len() - 4..]);
+    let _faces: Vec<Face> = chunked.next_chunk().unwrap();
+    let _edges: Vec<serde::de::IgnoredAny> = chunked.next_chunk().unwrap();
+    assert_eq!(
+        statuses(chunked.element_states()),
+        [s("vertex", Done, 4), s("face", Done, 2), s("edge", Done, 1)]
+    );
+}
+
+#[test]
+fn test_next_element_lines() {
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n0 0 0\n1.5  0 -2\r\n0 1 0 \n3 0 1 2\n";
+
+    let mut reader = PlyReader::from_reader(Cursor::new(ply_data)).unwrap();
+    // The wrong element consumes nothing.
+    assert!(reader.next_element_lines("face").is_err());
+    let lines = reader.next_element_lines("vertex").unwrap();
+    assert_eq!(lines, ["0 0 0", "1.5  0 -2", "0 1 0 "]);
+    // The reader continues with the next element.
+    let faces: Vec<Face> = reader.next_element().unwrap();
+    assert_eq!(faces[0].vertex_indices, [0, 1, 2]);
+
+    // Rows missing from the data.
+    let truncated = &ply_data[..ply_data.find("0 1 0").unwrap()];
+    let mut reader = PlyReader::from_reader(Cursor::new(truncated)).unwrap();
+    let err = reader.next_element_lines("vertex").unwrap_err();
+    let missing = err.as_missing_data().unwrap();
+    assert_eq!((missing.expected_rows, missing.rows_read), (3, 2));
+
+    // Binary rows have no lines.
+    let mut binary =
+        b"ply\nformat binary_little_endian 1.0\nelement vertex 1\nproperty float x\nend_header\n"
+            .to_vec();
+    binary.extend_from_slice(&1.0f32.to_le_bytes());
+    let mut reader = PlyReader::from_reader(Cursor::new(binary)).unwrap();
+    let err = reader.next_element_lines("vertex").unwrap_err();
+    assert!(err.to_string().contains("only ASCII rows"), "{err}");
+    assert_eq!(reader.peek_element_name(), Some("vertex"));
+}
+
+#[test]
+fn test_raw_rows() {
+    use serde_ply::{PlyValue, PlyWriter, RawRows, SerializeOptions};
+    use PlyValue::{Float, Int, List};
+
+    #[derive(serde::Serialize, Deserialize, Debug, PartialEq)]
+    struct MixedVertex {
+        x: f64,
+        y: f32,
+        red: u32,
+        t: i32,
+    }
+
+    #[derive(serde::Serialize, Deserialize, Debug, PartialEq)]
+    struct MixedFace {
+        vertex_indices: Vec<u8>,
+        uv: Vec<f64>,
+    }
+
+    let ascii = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty double y\n\
+                 property uchar red\nproperty short t\nelement face 2\n\
+                 property list uchar int vertex_indices\nproperty list ushort float uv\n\
+                 end_header\n0.5 1.25 255 -3\n1 2.5 0 7\n-1.5 1e-3 17 0\n3 0 1 2 2 0.5 0.25\n\
+                 4 0 1 2 3 0\n";
+    let mut reader = PlyReader::from_reader(ascii.as_bytes()).unwrap();
+    let header = reader.header().clone();
+    let vertices: Vec<MixedVertex> = reader.next_element().unwrap();
+    let faces: Vec<MixedFace> = reader.next_element().unwrap();
+    let mut writer =
+        PlyWriter::with_options(Vec::new(), header.elem_defs, SerializeOptions::binary_le())
+            .unwrap();
+    writer.write_rows(&vertices).unwrap();
+    writer.next_element().unwrap();
+    writer.write_rows(&faces).unwrap();
+    let binary = writer.finish().unwrap();
+
+    // Read directly and through the raw rows, the rows are the same.
+    for data in [ascii.as_bytes(), binary.as_slice()] {
+        let mut reader = PlyReader::from_reader(data).unwrap();
+        let raw_vertices = reader.next_element_raw().unwrap();
+        let raw_faces = reader.next_element_raw().unwrap();
+        assert_eq!(raw_vertices.elem_def.name, "vertex");
+        assert_eq!(
+            raw_vertices.rows[0],
+            [Float(0.5), Float(1.25), Int(255), Int(-3)]
+        );
+        assert_eq!(
+            raw_faces.rows[1][0],
+            List(vec![Int(0), Int(1), Int(2), Int(3)])
+        );
+        assert_eq!(raw_faces.rows[1][1], List(vec![]));
+
+        let mut reader = PlyReader::from_reader(data).unwrap();
+        let direct: Vec<MixedVertex> = reader.next_element().unwrap();
+        assert_eq!(direct, vertices);
+        assert_eq!(
+            raw_vertices.deserialize_into::<MixedVertex>().unwrap(),
+            direct
+        );
+        let direct: Vec<MixedFace> = reader.next_element().unwrap();
+        assert_eq!(raw_faces.deserialize_into::<MixedFace>().unwrap(), direct);
+
+        // Rows read as tuples, with the property types.
+        let mut reader = PlyReader::from_reader(data).unwrap();
+        let direct: Vec<(f32, f64, u8, i16)> = reader.next_element().unwrap();
+        assert_eq!(
+            raw_vertices
+                .deserialize_into::<(f32, f64, u8, i16)>()
+                .unwrap(),
+            direct
+        );
+    }
+
+    // From typed rows and back.
+    let raw = RawRows::from_typed("vertex", &vertices).unwrap();
+    assert_eq!(raw.elem_def.properties.len(), 4);
+    assert_eq!(
+        raw.rows[2],
+        [Float(-1.5), Float(1e-3f32 as f64), Int(17), Int(0)]
+    );
+    assert_eq!(raw.deserialize_into::<MixedVertex>().unwrap(), vertices);
+    let raw = RawRows::from_typed("face", &faces).unwrap();
+    assert_eq!(raw.deserialize_into::<MixedFace>().unwrap(), faces);
+
+    // Values must have the type of their property.
+    let mut raw = RawRows::from_typed("vertex", &vertices).unwrap();
+    raw.rows[1][2] = Int(-1);
+    let err = raw.deserialize_into::<MixedVertex>().unwrap_err();
+    assert!(err.to_string().contains("isn't a uint value"), "{err}");
+    raw.rows[1][2] = Float(1.0);
+    assert!(raw.deserialize_into::<MixedVertex>().is_err());
+    raw.rows[1].pop();
+    let err = raw.deserialize_into::<MixedVertex>().unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("row 1 of element 'vertex' has 3 values"),
+        "{err}"
+    );
+
+    // Rows convert with the options of the reader.
+    let ply_data =
+        "ply\nformat ascii 1.0\nelement vertex 1\nproperty list uchar float x\nend_header\n1 0.5\n";
+    let options = DeserializeOptions::new().with_single_element_list_as_scalar(true);
+    let mut reader = PlyReader::from_reader(ply_data.as_bytes())
+        .unwrap()
+        .with_options(options);
+    let raw = reader.next_element_raw().unwrap();
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct X {
+        x: f32,
+    }
+    assert_eq!(raw.deserialize_into::<X>().unwrap(), [X { x: 0.5 }]);
+    let raw = RawRows::new(raw.elem_def, raw.rows);
+    let err = raw.deserialize_into::<X>().unwrap_err();
+    assert!(err.to_string().contains("field expects a scalar"), "{err}");
+}
+
+/// A source that only implements `Read`, and returns at most `step` bytes per read.
+struct RawRead<'a> {
+    data: &'a [u8],
+    step: usize,
+}
+
+impl std::io::Read for RawRead<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(self.step).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn test_from_read() {
+    #[derive(serde::Serialize, Deserialize, Debug, PartialEq)]
+    struct Point {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    #[derive(serde::Serialize, Deserialize, Debug, PartialEq)]
+    struct Face {
+        vertex_indices: Vec<u32>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Mesh {
+        vertex: Vec<Point>,
+        face: Vec<Face>,
+        edge: Vec<Face>,
+    }
+
+    let mesh = Mesh {
+        vertex: (0..40)
+            .map(|i| Point {
+                x: i as f32,
+                y: 0.5,
+                z: -(i as f32),
+            })
+            .collect(),
+        face: (0..30)
+            .map(|i| Face {
+                vertex_indices: (0..1 + i % 7).collect(),
+            })
+            .collect(),
+        edge: vec![Face {
+            vertex_indices: vec![1, 2],
+        }],
+    };
+
+    for format in [
+        PlyFormat::Ascii,
+        PlyFormat::BinaryLittleEndian,
+        PlyFormat::BinaryBigEndian,
+    ] {
+        let data = serde_ply::to_bytes(&mesh, serde_ply::SerializeOptions::new(format)).unwrap();
+        // Small steps put the chunk boundaries in the middle of lists and values.
+        for step in [1, 3, 5, 4096] {
+            let source = RawRead { data: &data, step };
+            let mut reader = PlyReader::from_read(source).unwrap();
+            assert_eq!(reader.header().elem_defs.len(), 3);
+
+            let vertices: Vec<Point> = reader.next_element().unwrap();
+            assert_eq!(vertices, mesh.vertex, "{format:?} step {step}");
+            let faces = reader
+                .element()
+                .unwrap()
+                .rows::<Face>()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(faces, mesh.face, "{format:?} step {step}");
+            let edges: Vec<Face> = reader.next_element().unwrap();
+            assert_eq!(edges, mesh.edge);
+
+            // Skipping reads past lists without converting them.
+            let source = RawRead { data: &data, step };
+            let mut reader = PlyReader::from_read(source).unwrap();
+            reader.element().unwrap().skip().unwrap();
+            reader.element().unwrap().skip().unwrap();
+            let edges: Vec<Face> = reader.next_element().unwrap();
+            assert_eq!(edges, mesh.edge, "{format:?} step {step}");
+            assert!(reader.into_inner().buffer().is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_from_read_capacity() {
+    let header = "ply\nformat binary_little_endian 1.0\nelement vertex 100000\nproperty float x\nend_header\n";
+    let mut data = header.as_bytes().to_vec();
+    data.resize(header.len() + 400_000, 0);
+    let reader = PlyReader::from_read(RawRead {
+        data: &data,
+        step: usize::MAX,
+    })
+    .unwrap();
+    // Binary data of a known size is buffered whole.
+    assert_eq!(reader.into_inner().capacity(), 400_000);
+
+    let ascii = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\n1\n";
+    let reader = PlyReader::from_read(RawRead {
+        data: ascii.as_bytes(),
+        step: usize::MAX,
+    })
+    .unwrap();
+    assert_eq!(reader.into_inner().capacity(), 64 * 1024);
+}
+
+#[test]
+fn test_tee() {
+    #[derive(serde::Serialize, Deserialize, Debug, PartialEq)]
+    struct Point {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    #[derive(serde::Serialize, Deserialize, Debug, PartialEq)]
+    struct Face {
+        vertex_indices: Vec<u32>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Mesh {
+        vertex: Vec<Point>,
+        face: Vec<Face>,
+        edge: Vec<Face>,
+    }
+
+    let mesh = Mesh {
+        vertex: (0..50)
+            .map(|i| Point {
+                x: i as f32,
+                y: 0.25,
+                z: -(i as f32),
+            })
+            .collect(),
+        face: (0..20)
+            .map(|i| Face {
+                vertex_indices: (0..1 + i % 5).collect(),
+            })
+            .collect(),
+        edge: vec![Face {
+            vertex_indices: vec![3, 4],
+        }],
+    };
+
+    let mut files: Vec<Vec<u8>> = [
+        PlyFormat::Ascii,
+        PlyFormat::BinaryLittleEndian,
+        PlyFormat::BinaryBigEndian,
+    ]
+    .into_iter()
+    .map(|format| serde_ply::to_bytes(&mesh, serde_ply::SerializeOptions::new(format)).unwrap())
+    .collect();
+    // Irregular whitespace and upper case types only survive as an exact copy.
+    files.push(b"ply\r\nformat ascii 1.0\r\ncomment  two  spaces\r\nelement vertex 2\r\nproperty FLOAT x\r\nproperty float y\r\nproperty float z\r\nelement face 1\r\nproperty list uchar uint vertex_indices\r\nelement edge 1\r\nproperty list uchar uint vertex_indices\r\nend_header\r\n1  2 3\r\n4 5   6\r\n3 0 1 1\r\n2 0 1\r\n".to_vec());
+
+    for data in &files {
+        // Deserialize, skip and read part of an element before dropping it.
+        let mut reader = PlyReader::from_reader(Cursor::new(data))
+            .unwrap()
+            .tee(Vec::new())
+            .unwrap();
+        let vertices: Vec<Point> = reader.next_element().unwrap();
+        assert!(!vertices.is_empty());
+        reader.element().unwrap().skip().unwrap();
+        let mut edges = reader.element().unwrap();
+        assert_eq!(edges.take::<Face>(0).unwrap(), []);
+        drop(edges);
+        assert_eq!(&reader.into_inner().into_writer().unwrap(), data);
+
+        // Through the reader's own buffering, with reads that split values.
+        for step in [1, 7, 4096] {
+            let mut reader = PlyReader::from_read(RawRead { data, step })
+                .unwrap()
+                .tee(Vec::new())
+                .unwrap();
+            reader.element().unwrap().skip().unwrap();
+            let faces: Vec<Face> = reader.next_element().unwrap();
+            assert!(!faces.is_empty());
+            let _: Vec<Face> = reader.next_element().unwrap();
+            assert_eq!(&reader.into_inner().into_writer().unwrap(), data);
+        }
+    }
+
+    // Bytes read before the tee can't be copied.
+    let mut reader = PlyReader::from_reader(Cursor::new(&files[0])).unwrap();
+    let _: Vec<Point> = reader.next_element().unwrap();
+    assert!(reader.tee(Vec::new()).is_err());
+}
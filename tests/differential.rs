@@ -0,0 +1,273 @@
+//! Differential tests between the blocking [`PlyReader`] and the [`PlyChunkedReader`].
+//!
+//! Randomly generated files are parsed by both readers, with the chunked reader
+//! fed in chunks of varying sizes. Both must agree on the parsed rows, or both
+//! must fail.
+
+use serde::Deserialize;
+use serde_ply::{PlyChunkedReader, PlyReader, ScalarType};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Cursor;
+
+const CHUNK_SIZES: [usize; 5] = [1, 3, 7, 64, 4096];
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum Value {
+    Scalar(f64),
+    List(Vec<f64>),
+}
+
+type Row = BTreeMap<String, Value>;
+type Elements = Vec<(String, Vec<Row>)>;
+
+/// Small xorshift generator so failures reproduce without extra dependencies.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn scalar_type(&mut self) -> ScalarType {
+        [
+            ScalarType::I8,
+            ScalarType::U8,
+            ScalarType::I16,
+            ScalarType::U16,
+            ScalarType::I32,
+            ScalarType::U32,
+            ScalarType::F32,
+            ScalarType::F64,
+        ][self.below(8)]
+    }
+
+    fn value(&mut self, ty: ScalarType) -> f64 {
+        let bits = self.next();
+        match ty {
+            ScalarType::I8 => bits as i8 as f64,
+            ScalarType::U8 => bits as u8 as f64,
+            ScalarType::I16 => bits as i16 as f64,
+            ScalarType::U16 => bits as u16 as f64,
+            ScalarType::I32 => bits as i32 as f64,
+            ScalarType::U32 => bits as u32 as f64,
+            // Keep floats exactly representable so ASCII round trips are exact.
+            ScalarType::F32 | ScalarType::F64 => (bits as i16 as f64) / 8.0,
+        }
+    }
+}
+
+fn type_name(ty: ScalarType) -> &'static str {
+    match ty {
+        ScalarType::I8 => "char",
+        ScalarType::U8 => "uchar",
+        ScalarType::I16 => "short",
+        ScalarType::U16 => "ushort",
+        ScalarType::I32 => "int",
+        ScalarType::U32 => "uint",
+        ScalarType::F32 => "float",
+        ScalarType::F64 => "double",
+    }
+}
+
+fn write_binary(out: &mut Vec<u8>, ty: ScalarType, v: f64, big_endian: bool) {
+    macro_rules! put {
+        ($t:ty) => {{
+            let v = v as $t;
+            out.extend_from_slice(&if big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            });
+        }};
+    }
+    match ty {
+        ScalarType::I8 => put!(i8),
+        ScalarType::U8 => put!(u8),
+        ScalarType::I16 => put!(i16),
+        ScalarType::U16 => put!(u16),
+        ScalarType::I32 => put!(i32),
+        ScalarType::U32 => put!(u32),
+        ScalarType::F32 => put!(f32),
+        ScalarType::F64 => put!(f64),
+    }
+}
+
+enum Prop {
+    Scalar(ScalarType),
+    List(ScalarType, ScalarType),
+}
+
+/// Generate a random PLY file.
+fn generate(rng: &mut Rng) -> Vec<u8> {
+    let format = rng.below(3);
+    let mut header = String::from("ply\n");
+    header += match format {
+        0 => "format ascii 1.0\n",
+        1 => "format binary_little_endian 1.0\n",
+        _ => "format binary_big_endian 1.0\n",
+    };
+
+    let mut body = Vec::new();
+    for e in 0..1 + rng.below(3) {
+        let count = rng.below(20);
+        let props: Vec<Prop> = (0..1 + rng.below(5))
+            .map(|_| {
+                if rng.below(3) == 0 {
+                    let count_type =
+                        [ScalarType::U8, ScalarType::U16, ScalarType::I32][rng.below(3)];
+                    Prop::List(count_type, rng.scalar_type())
+                } else {
+                    Prop::Scalar(rng.scalar_type())
+                }
+            })
+            .collect();
+
+        writeln!(header, "element elem{e} {count}").unwrap();
+        for (i, prop) in props.iter().enumerate() {
+            match prop {
+                Prop::Scalar(ty) => writeln!(header, "property {} p{i}", type_name(*ty)),
+                Prop::List(c, ty) => {
+                    writeln!(
+                        header,
+                        "property list {} {} p{i}",
+                        type_name(*c),
+                        type_name(*ty)
+                    )
+                }
+            }
+            .unwrap();
+        }
+
+        for _ in 0..count {
+            let mut values = Vec::new();
+            for prop in &props {
+                match prop {
+                    Prop::Scalar(ty) => values.push((*ty, rng.value(*ty))),
+                    Prop::List(c, ty) => {
+                        let len = rng.below(6);
+                        values.push((*c, len as f64));
+                        values.extend((0..len).map(|_| (*ty, rng.value(*ty))));
+                    }
+                }
+            }
+            if format == 0 {
+                let line: Vec<String> = values.iter().map(|(_, v)| v.to_string()).collect();
+                body.extend_from_slice(line.join(" ").as_bytes());
+                body.push(b'\n');
+            } else {
+                for (ty, v) in values {
+                    write_binary(&mut body, ty, v, format == 2);
+                }
+            }
+        }
+    }
+    header += "end_header\n";
+
+    let mut file = header.into_bytes();
+    file.extend(body);
+    file
+}
+
+fn parse_blocking(data: &[u8]) -> Result<Elements, String> {
+    let mut reader = PlyReader::from_reader(Cursor::new(data)).map_err(|e| e.to_string())?;
+    let mut elements = Vec::new();
+    while let Some(name) = reader.current_element().map(|e| e.name.clone()) {
+        let rows = reader
+            .next_element::<Vec<Row>>()
+            .map_err(|e| e.to_string())?;
+        elements.push((name, rows));
+    }
+    Ok(elements)
+}
+
+fn parse_chunked(data: &[u8], mut chunk_size: impl FnMut() -> usize) -> Result<Elements, String> {
+    let mut reader = PlyChunkedReader::new();
+    let mut elements: Elements = Vec::new();
+    let mut data = data;
+
+    loop {
+        let n = chunk_size().min(data.len());
+        reader.buffer_mut().extend_from_slice(&data[..n]);
+        data = &data[n..];
+
+        while let Some((name, count)) = reader.current_element().map(|e| (e.name.clone(), e.count))
+        {
+            let rows = reader.next_chunk::<Vec<Row>>().map_err(|e| e.to_string())?;
+            let made_progress = !rows.is_empty() || count == 0;
+            match elements.last_mut() {
+                Some((last, existing)) if *last == name => existing.extend(rows),
+                _ => elements.push((name, rows)),
+            }
+            if !made_progress {
+                break;
+            }
+        }
+
+        if data.is_empty() {
+            break;
+        }
+    }
+
+    reader.finish().map_err(|e| e.to_string())?;
+    Ok(elements)
+}
+
+fn check_file(data: &[u8], rng: &mut Rng) {
+    let blocking = parse_blocking(data);
+    let mut outcomes: Vec<_> = CHUNK_SIZES
+        .iter()
+        .map(|&size| (size.to_string(), parse_chunked(data, || size)))
+        .collect();
+    outcomes.push((
+        "random".to_string(),
+        parse_chunked(data, || CHUNK_SIZES[rng.below(CHUNK_SIZES.len())]),
+    ));
+
+    for (size, chunked) in outcomes {
+        match (&blocking, &chunked) {
+            (Ok(a), Ok(b)) => assert_eq!(a, b, "chunk size {size} diverges"),
+            (Err(_), Err(_)) => {}
+            _ => panic!(
+                "chunk size {size} diverges:\nblocking: {blocking:?}\nchunked: {chunked:?}\nfile: {:?}",
+                String::from_utf8_lossy(data)
+            ),
+        }
+    }
+}
+
+fn run(seed: u64, iterations: usize) {
+    let mut rng = Rng(seed);
+    for _ in 0..iterations {
+        let file = generate(&mut rng);
+        check_file(&file, &mut rng);
+
+        // Truncated and corrupted copies have to fail (or succeed) the same way.
+        let cut = rng.below(file.len());
+        check_file(&file[..cut], &mut rng);
+
+        let mut corrupted = file.clone();
+        let at = rng.below(corrupted.len());
+        corrupted[at] = rng.next() as u8;
+        check_file(&corrupted, &mut rng);
+    }
+}
+
+#[test]
+fn test_chunked_matches_blocking() {
+    run(0x5eed_1234_abcd_0001, 200);
+}
+
+#[test]
+#[ignore = "long running, run with --ignored"]
+fn test_chunked_matches_blocking_long() {
+    run(0x5eed_1234_abcd_0002, 20_000);
+}
@@ -0,0 +1,191 @@
+use serde::{de::IgnoredAny, Serialize};
+use serde_ply::mesh::{edge_element_def, read_edges, Edge};
+use serde_ply::{PlyReader, SerializeOptions};
+
+// The cube with edges from the PLY spec.
+const SPEC_CUBE: &str = "ply
+format ascii 1.0
+comment author: Greg Turk
+comment object: another cube
+element vertex 8
+property float x
+property float y
+property float z
+property uchar red
+property uchar green
+property uchar blue
+element face 7
+property list uchar int vertex_index
+element edge 5
+property int vertex1
+property int vertex2
+property uchar red
+property uchar green
+property uchar blue
+end_header
+0 0 0 255 0 0
+0 0 1 255 0 0
+0 1 1 255 0 0
+0 1 0 255 0 0
+1 0 0 0 0 255
+1 0 1 0 0 255
+1 1 1 0 0 255
+1 1 0 0 0 255
+3 0 1 2
+3 0 2 3
+4 7 6 5 4
+4 0 4 5 1
+4 1 5 6 2
+4 2 6 7 3
+4 3 7 4 0
+0 1 255 255 255
+1 2 255 255 255
+2 3 255 255 255
+3 0 255 255 255
+2 0 0 0 0
+";
+
+fn spec_edges() -> Vec<Edge> {
+    let white = Some([255, 255, 255]);
+    vec![
+        Edge {
+            v1: 0,
+            v2: 1,
+            color: white,
+        },
+        Edge {
+            v1: 1,
+            v2: 2,
+            color: white,
+        },
+        Edge {
+            v1: 2,
+            v2: 3,
+            color: white,
+        },
+        Edge {
+            v1: 3,
+            v2: 0,
+            color: white,
+        },
+        Edge {
+            v1: 2,
+            v2: 0,
+            color: Some([0, 0, 0]),
+        },
+    ]
+}
+
+#[test]
+fn test_read_spec_edges() {
+    let mut reader = PlyReader::from_reader(SPEC_CUBE.as_bytes()).unwrap();
+    reader.next_element::<Vec<IgnoredAny>>().unwrap();
+    reader.next_element::<Vec<IgnoredAny>>().unwrap();
+    assert_eq!(read_edges(&mut reader).unwrap(), spec_edges());
+
+    // Only edges can be read as edges.
+    let mut reader = PlyReader::from_reader(SPEC_CUBE.as_bytes()).unwrap();
+    assert!(read_edges(&mut reader).is_err());
+}
+
+#[test]
+fn test_edges_before_vertices() {
+    let ply = "ply\nformat ascii 1.0\nelement edge 2\nproperty uint vertex1\nproperty uint vertex2\nelement vertex 3\nproperty float x\nend_header\n0 2\n1 2\n0\n1\n2\n";
+    let mut reader = PlyReader::from_reader(ply.as_bytes()).unwrap();
+    let edges = read_edges(&mut reader).unwrap();
+    assert_eq!(
+        edges,
+        vec![
+            Edge {
+                v1: 0,
+                v2: 2,
+                color: None,
+            },
+            Edge {
+                v1: 1,
+                v2: 2,
+                color: None,
+            },
+        ]
+    );
+
+    let out_of_range = ply.replace("1 2\n0", "1 3\n0");
+    let mut reader = PlyReader::from_reader(out_of_range.as_bytes()).unwrap();
+    let err = read_edges(&mut reader).unwrap_err().to_string();
+    assert!(
+        err.contains("edge 1 references vertex 3, but there are only 3 vertices"),
+        "{err}"
+    );
+
+    let negative = ply.replace("property uint vertex2", "property int vertex2");
+    let negative = negative.replace("1 2\n0", "1 -1\n0");
+    let mut reader = PlyReader::from_reader(negative.as_bytes()).unwrap();
+    assert!(read_edges(&mut reader).is_err());
+
+    let partial_color = "ply\nformat ascii 1.0\nelement edge 1\nproperty int vertex1\nproperty int vertex2\nproperty uchar red\nend_header\n0 0 255\n";
+    let mut reader = PlyReader::from_reader(partial_color.as_bytes()).unwrap();
+    let err = read_edges(&mut reader).unwrap_err().to_string();
+    assert!(err.contains("red, green and blue"), "{err}");
+}
+
+#[test]
+fn test_write_edges() {
+    #[derive(Serialize)]
+    struct Vertex {
+        x: f32,
+    }
+
+    #[derive(Serialize)]
+    struct Wireframe {
+        vertex: Vec<Vertex>,
+        edge: Vec<Edge>,
+    }
+
+    let wireframe = Wireframe {
+        vertex: (0..4).map(|i| Vertex { x: i as f32 }).collect(),
+        edge: spec_edges(),
+    };
+    for options in [
+        SerializeOptions::ascii,
+        SerializeOptions::binary_le,
+        SerializeOptions::binary_be,
+    ] {
+        let options = options().with_element_def(edge_element_def(5, true));
+        let bytes = serde_ply::to_bytes(&wireframe, options).unwrap();
+        let header = String::from_utf8_lossy(&bytes);
+        assert!(header.contains(
+            "element edge 5\nproperty int vertex1\nproperty int vertex2\n\
+             property uchar red\nproperty uchar green\nproperty uchar blue\n"
+        ));
+
+        let mut reader = PlyReader::from_reader(&bytes[..]).unwrap();
+        reader.next_element::<Vec<IgnoredAny>>().unwrap();
+        assert_eq!(read_edges(&mut reader).unwrap(), spec_edges());
+    }
+
+    // Without color only the indices are written.
+    let wireframe = Wireframe {
+        vertex: vec![Vertex { x: 0.0 }, Vertex { x: 1.0 }],
+        edge: vec![Edge {
+            v1: 1,
+            v2: 0,
+            color: None,
+        }],
+    };
+    let text = serde_ply::to_string(
+        &wireframe,
+        SerializeOptions::ascii().with_element_def(edge_element_def(1, false)),
+    )
+    .unwrap();
+    assert!(text.ends_with("property int vertex1\nproperty int vertex2\nend_header\n0\n1\n1 0\n"));
+
+    let too_large = Wireframe {
+        vertex: vec![],
+        edge: vec![Edge {
+            v1: u32::MAX,
+            v2: 0,
+            color: None,
+        }],
+    };
+    assert!(serde_ply::to_string(&too_large, SerializeOptions::ascii()).is_err());
+}
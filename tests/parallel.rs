@@ -0,0 +1,82 @@
+#![cfg(feature = "parallel")]
+
+use serde::Serialize;
+use serde_ply::{to_bytes, to_writer_parallel, SerializeOptions};
+
+const FORMATS: [fn() -> SerializeOptions; 3] = [
+    SerializeOptions::ascii,
+    SerializeOptions::binary_le,
+    SerializeOptions::binary_be,
+];
+
+#[derive(Serialize)]
+struct Splat {
+    x: f32,
+    y: f64,
+    opacity: u8,
+    neighbours: Vec<u32>,
+}
+
+#[derive(Serialize)]
+struct Ply<'a> {
+    vertex: &'a [Splat],
+}
+
+fn splats(count: usize) -> Vec<Splat> {
+    (0..count)
+        .map(|i| Splat {
+            x: i as f32 * 0.1,
+            y: -(i as f64) / 3.0,
+            opacity: (i % 256) as u8,
+            neighbours: (0..=(i % 4) as u32).collect(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_parallel_matches_sequential() {
+    let rows = splats(1000);
+    for format in FORMATS {
+        let expected = to_bytes(&Ply { vertex: &rows }, format()).unwrap();
+        for chunk_rows in [1, 7, 128, 1000, 5000] {
+            let mut out = Vec::new();
+            to_writer_parallel(&mut out, format(), "vertex", &rows, chunk_rows).unwrap();
+            assert!(out == expected, "chunks of {chunk_rows} rows differ");
+        }
+    }
+}
+
+#[test]
+fn test_parallel_options() {
+    let rows = splats(100);
+    let options = || {
+        SerializeOptions::ascii()
+            .with_comments(vec!["made in parallel".to_string()])
+            .ascii_separator('\t')
+            .default_list_count_type(serde_ply::ScalarType::U16)
+    };
+    let expected = to_bytes(&Ply { vertex: &rows }, options()).unwrap();
+    let mut out = Vec::new();
+    to_writer_parallel(&mut out, options(), "vertex", &rows, 16).unwrap();
+    assert_eq!(String::from_utf8(out), String::from_utf8(expected));
+}
+
+#[test]
+fn test_parallel_empty_and_invalid() {
+    for format in FORMATS {
+        let expected = to_bytes(&Ply { vertex: &[] }, format()).unwrap();
+        let mut out = Vec::new();
+        to_writer_parallel::<Splat>(&mut out, format(), "vertex", &[], 16).unwrap();
+        assert_eq!(out, expected);
+    }
+
+    let err = to_writer_parallel(
+        Vec::new(),
+        SerializeOptions::ascii(),
+        "vertex",
+        &splats(2),
+        0,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("chunk_rows"));
+}
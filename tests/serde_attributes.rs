@@ -0,0 +1,632 @@
+//! How serde attributes behave when writing and reading rows, in every format.
+//!
+//! Each attribute either works, or fails with an error. None of them may silently write
+//! values under the wrong property.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_ply::{PlyFormat, SerializeOptions};
+use std::collections::BTreeMap;
+
+const FORMATS: [PlyFormat; 3] = [
+    PlyFormat::Ascii,
+    PlyFormat::BinaryLittleEndian,
+    PlyFormat::BinaryBigEndian,
+];
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Ply<T> {
+    vertex: Vec<T>,
+}
+
+fn write<T: Serialize>(rows: Vec<T>, format: PlyFormat) -> Result<Vec<u8>, String> {
+    serde_ply::to_bytes(&Ply { vertex: rows }, SerializeOptions::new(format))
+        .map_err(|e| e.to_string())
+}
+
+fn read<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Result<Vec<T>, String> {
+    serde_ply::from_bytes::<Ply<T>>(bytes)
+        .map(|ply| ply.vertex)
+        .map_err(|e| e.to_string())
+}
+
+fn header(bytes: &[u8]) -> String {
+    let end = serde_ply::find_data_start(bytes).unwrap();
+    String::from_utf8(bytes[..end].to_vec()).unwrap()
+}
+
+#[test]
+fn rename_works() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Row {
+        #[serde(rename = "x")]
+        position_x: f32,
+        y: u8,
+    }
+
+    for format in FORMATS {
+        let bytes = write(
+            vec![Row {
+                position_x: 1.5,
+                y: 2,
+            }],
+            format,
+        )
+        .unwrap();
+        assert!(header(&bytes).contains("property float x\n"));
+        assert_eq!(
+            read::<Row>(&bytes).unwrap(),
+            [Row {
+                position_x: 1.5,
+                y: 2
+            }]
+        );
+    }
+}
+
+#[test]
+fn alias_works_on_read() {
+    #[derive(Serialize, Debug)]
+    struct Written {
+        pos_y: f32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Row {
+        #[serde(alias = "pos_y")]
+        y: f32,
+    }
+
+    for format in FORMATS {
+        let bytes = write(vec![Written { pos_y: 3.0 }], format).unwrap();
+        assert_eq!(read::<Row>(&bytes).unwrap(), [Row { y: 3.0 }]);
+    }
+}
+
+#[test]
+fn default_works_for_absent_properties() {
+    #[derive(Serialize, Debug)]
+    struct Written {
+        x: f32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Row {
+        x: f32,
+        #[serde(default)]
+        confidence: u8,
+    }
+
+    for format in FORMATS {
+        let bytes = write(vec![Written { x: 1.0 }], format).unwrap();
+        assert_eq!(
+            read::<Row>(&bytes).unwrap(),
+            [Row {
+                x: 1.0,
+                confidence: 0
+            }]
+        );
+    }
+}
+
+#[test]
+fn skip_works() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Row {
+        x: f32,
+        #[serde(skip)]
+        cached: u32,
+        y: f32,
+    }
+
+    for format in FORMATS {
+        let rows = vec![
+            Row {
+                x: 1.0,
+                cached: 7,
+                y: 2.0,
+            },
+            Row {
+                x: 3.0,
+                cached: 8,
+                y: 4.0,
+            },
+        ];
+        let bytes = write(rows, format).unwrap();
+        assert!(!header(&bytes).contains("cached"));
+        assert_eq!(
+            read::<Row>(&bytes).unwrap(),
+            [
+                Row {
+                    x: 1.0,
+                    cached: 0,
+                    y: 2.0
+                },
+                Row {
+                    x: 3.0,
+                    cached: 0,
+                    y: 4.0
+                }
+            ]
+        );
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct MaybeRow {
+    x: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<f32>,
+    y: f32,
+}
+
+#[test]
+fn skip_serializing_if_works_when_every_row_agrees() {
+    for format in FORMATS {
+        let rows = vec![
+            MaybeRow {
+                x: 1.0,
+                weight: None,
+                y: 2.0,
+            },
+            MaybeRow {
+                x: 3.0,
+                weight: None,
+                y: 4.0,
+            },
+        ];
+        let bytes = write(rows, format).unwrap();
+        assert!(!header(&bytes).contains("weight"));
+        assert_eq!(read::<MaybeRow>(&bytes).unwrap()[1].y, 4.0);
+
+        let rows = vec![
+            MaybeRow {
+                x: 1.0,
+                weight: Some(0.5),
+                y: 2.0,
+            },
+            MaybeRow {
+                x: 3.0,
+                weight: Some(0.25),
+                y: 4.0,
+            },
+        ];
+        let bytes = write(rows, format).unwrap();
+        assert_eq!(read::<MaybeRow>(&bytes).unwrap()[1].weight, Some(0.25));
+    }
+}
+
+#[test]
+fn skip_serializing_if_errors_when_rows_differ() {
+    for format in FORMATS {
+        // A later row skips a property of the header.
+        let rows = vec![
+            MaybeRow {
+                x: 1.0,
+                weight: Some(0.5),
+                y: 2.0,
+            },
+            MaybeRow {
+                x: 3.0,
+                weight: None,
+                y: 4.0,
+            },
+        ];
+        let err = write(rows, format).unwrap_err();
+        assert!(err.contains("element 'vertex'"), "{err}");
+        assert!(err.contains("'y' where the header has 'weight'"), "{err}");
+
+        // A later row has a property the header doesn't.
+        let rows = vec![
+            MaybeRow {
+                x: 1.0,
+                weight: None,
+                y: 2.0,
+            },
+            MaybeRow {
+                x: 3.0,
+                weight: Some(0.5),
+                y: 4.0,
+            },
+        ];
+        let err = write(rows, format).unwrap_err();
+        assert!(err.contains("'weight' where the header has 'y'"), "{err}");
+    }
+}
+
+#[test]
+fn skip_serializing_if_errors_when_a_trailing_property_is_skipped() {
+    #[derive(Serialize, Debug)]
+    struct Row {
+        x: f32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        weight: Option<f32>,
+    }
+
+    for format in FORMATS {
+        let rows = vec![
+            Row {
+                x: 1.0,
+                weight: Some(0.5),
+            },
+            Row {
+                x: 3.0,
+                weight: None,
+            },
+        ];
+        let err = write(rows, format).unwrap_err();
+        assert!(err.contains("doesn't write property 'weight'"), "{err}");
+
+        let rows = vec![
+            Row {
+                x: 1.0,
+                weight: None,
+            },
+            Row {
+                x: 3.0,
+                weight: Some(0.5),
+            },
+        ];
+        let err = write(rows, format).unwrap_err();
+        assert!(err.contains("which the first row doesn't have"), "{err}");
+    }
+}
+
+#[test]
+fn option_none_errors_on_write() {
+    #[derive(Serialize, Debug)]
+    struct Row {
+        x: Option<f32>,
+    }
+
+    for format in FORMATS {
+        assert!(write(vec![Row { x: Some(1.0) }, Row { x: None }], format).is_err());
+    }
+}
+
+#[test]
+fn flatten_struct_works() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Color {
+        red: u8,
+        green: u8,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Row {
+        x: f32,
+        #[serde(flatten)]
+        color: Color,
+    }
+
+    for format in FORMATS {
+        let rows = vec![
+            Row {
+                x: 1.0,
+                color: Color { red: 1, green: 2 },
+            },
+            Row {
+                x: 2.0,
+                color: Color { red: 3, green: 4 },
+            },
+        ];
+        let bytes = write(rows, format).unwrap();
+        assert!(header(&bytes).contains("property uchar red\nproperty uchar green\n"));
+        assert_eq!(
+            read::<Row>(&bytes).unwrap()[1].color,
+            Color { red: 3, green: 4 }
+        );
+    }
+}
+
+#[test]
+fn flatten_map_works() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Row {
+        x: f32,
+        #[serde(flatten)]
+        extra: BTreeMap<String, f32>,
+    }
+
+    for format in FORMATS {
+        let extra = |a, b| BTreeMap::from([("a".to_string(), a), ("b".to_string(), b)]);
+        let rows = vec![
+            Row {
+                x: 1.0,
+                extra: extra(2.0, 3.0),
+            },
+            Row {
+                x: 4.0,
+                extra: extra(5.0, 6.0),
+            },
+        ];
+        let bytes = write(rows, format).unwrap();
+        assert_eq!(read::<Row>(&bytes).unwrap()[1].extra, extra(5.0, 6.0));
+    }
+}
+
+#[test]
+fn flatten_map_errors_when_keys_differ() {
+    #[derive(Serialize, Debug)]
+    struct Row {
+        x: f32,
+        #[serde(flatten)]
+        extra: BTreeMap<String, f32>,
+    }
+
+    for format in FORMATS {
+        let rows = vec![
+            Row {
+                x: 1.0,
+                extra: BTreeMap::from([("a".to_string(), 2.0)]),
+            },
+            Row {
+                x: 4.0,
+                extra: BTreeMap::from([("b".to_string(), 5.0)]),
+            },
+        ];
+        let err = write(rows, format).unwrap_err();
+        assert!(err.contains("'b' where the header has 'a'"), "{err}");
+    }
+}
+
+#[test]
+fn nested_struct_without_flatten_errors() {
+    #[derive(Serialize, Debug)]
+    struct Inner {
+        a: f32,
+    }
+
+    #[derive(Serialize, Debug)]
+    struct Row {
+        inner: Inner,
+    }
+
+    for format in FORMATS {
+        let err = write(
+            vec![Row {
+                inner: Inner { a: 1.0 },
+            }],
+            format,
+        )
+        .unwrap_err();
+        assert!(err.contains("#[serde(flatten)]"), "{err}");
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(transparent)]
+struct VertexId(u32);
+
+#[test]
+fn transparent_scalar_works() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Row {
+        id: VertexId,
+    }
+
+    for format in FORMATS {
+        let bytes = write(vec![Row { id: VertexId(42) }], format).unwrap();
+        assert!(header(&bytes).contains("property uint id\n"));
+        assert_eq!(read::<Row>(&bytes).unwrap(), [Row { id: VertexId(42) }]);
+    }
+}
+
+#[test]
+fn transparent_in_list_works() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Row {
+        vertex_indices: Vec<VertexId>,
+    }
+
+    for format in FORMATS {
+        let rows = vec![Row {
+            vertex_indices: vec![VertexId(0), VertexId(1), VertexId(2)],
+        }];
+        let bytes = write(rows, format).unwrap();
+        assert!(header(&bytes).contains("property list uint8 uint32 vertex_indices\n"));
+        assert_eq!(
+            read::<Row>(&bytes).unwrap()[0].vertex_indices,
+            [VertexId(0), VertexId(1), VertexId(2)]
+        );
+    }
+}
+
+#[test]
+fn nested_list_errors() {
+    #[derive(Serialize, Debug)]
+    struct Row {
+        rings: Vec<Vec<u32>>,
+    }
+
+    for format in FORMATS {
+        let err = write(
+            vec![Row {
+                rings: vec![vec![1]],
+            }],
+            format,
+        )
+        .unwrap_err();
+        assert!(err.contains("PLY lists can only hold scalars"), "{err}");
+    }
+}
+
+#[test]
+fn deny_unknown_fields_errors_on_extra_properties() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Written {
+        x: f32,
+        extra: f32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Row {
+        x: f32,
+    }
+
+    for format in FORMATS {
+        let bytes = write(vec![Written { x: 1.0, extra: 2.0 }], format).unwrap();
+        let err = read::<Row>(&bytes).unwrap_err();
+        assert!(err.contains("unknown field `extra`"), "{err}");
+
+        // Without unknown properties it reads as usual.
+        assert_eq!(
+            read::<Written>(&bytes).unwrap(),
+            [Written { x: 1.0, extra: 2.0 }]
+        );
+    }
+}
+
+fn to_u8<S: Serializer>(v: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u8((v * 255.0).round() as u8)
+}
+
+fn from_u8<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    Ok(u8::deserialize(deserializer)? as f32 / 255.0)
+}
+
+#[test]
+fn serialize_with_and_deserialize_with_work() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Row {
+        #[serde(serialize_with = "to_u8", deserialize_with = "from_u8")]
+        red: f32,
+    }
+
+    for format in FORMATS {
+        let bytes = write(vec![Row { red: 1.0 }, Row { red: 0.0 }], format).unwrap();
+        assert!(header(&bytes).contains("property uchar red\n"));
+        assert_eq!(
+            read::<Row>(&bytes).unwrap(),
+            [Row { red: 1.0 }, Row { red: 0.0 }]
+        );
+    }
+}
+
+/// Read rows with `options` through the streaming and the chunked reader, which must agree.
+fn read_with<T: for<'de> Deserialize<'de> + PartialEq + std::fmt::Debug>(
+    bytes: &[u8],
+    options: serde_ply::DeserializeOptions,
+) -> Result<Vec<T>, String> {
+    let mut reader = serde_ply::PlyReader::from_reader(bytes)
+        .unwrap()
+        .with_options(options.clone());
+    let streamed = reader.next_element::<Vec<T>>().map_err(|e| e.to_string());
+
+    let mut chunked = serde_ply::PlyChunkedReader::new().with_options(options);
+    chunked.buffer_mut().extend_from_slice(bytes);
+    let chunk = chunked.next_chunk::<Vec<T>>().map_err(|e| e.to_string());
+    assert_eq!(streamed, chunk);
+    streamed
+}
+
+#[test]
+fn extra_property_precedence() {
+    #[derive(Serialize)]
+    struct Written {
+        x: f32,
+        nx: f64,
+        vertex_indices: Vec<u32>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Plain {
+        x: f32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Aliased {
+        #[serde(alias = "vertex_indices")]
+        indices: Vec<u32>,
+        x: f32,
+        nx: f64,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Denied {
+        x: f32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Position {
+        x: f32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct FlattenStruct {
+        #[serde(flatten)]
+        pos: Position,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct FlattenMap {
+        x: f32,
+        #[serde(flatten)]
+        extra: BTreeMap<String, serde::de::IgnoredAny>,
+    }
+
+    let lenient = serde_ply::DeserializeOptions::new;
+    let strict = || lenient().deny_extra_properties(true);
+    let extras =
+        "element 'vertex' has properties without a field in the row type: 'nx', 'vertex_indices'";
+
+    for format in FORMATS {
+        let rows = vec![
+            Written {
+                x: 1.0,
+                nx: 0.5,
+                vertex_indices: vec![1, 2],
+            },
+            Written {
+                x: 2.0,
+                nx: -0.5,
+                vertex_indices: vec![],
+            },
+        ];
+        let bytes = write(rows, format).unwrap();
+
+        // (1) Every property matches a field, directly or through an alias.
+        for options in [lenient(), strict()] {
+            let rows = read_with::<Aliased>(&bytes, options).unwrap();
+            assert_eq!(rows[0].indices, [1, 2]);
+            assert_eq!(rows[1].nx, -0.5);
+        }
+
+        // (2) A flattened map takes the other properties, a flattened struct ignores them.
+        for options in [lenient(), strict()] {
+            let rows = read_with::<FlattenMap>(&bytes, options.clone()).unwrap();
+            assert_eq!(rows[1].x, 2.0);
+            assert_eq!(
+                rows[0].extra.keys().collect::<Vec<_>>(),
+                ["nx", "vertex_indices"]
+            );
+            let rows = read_with::<FlattenStruct>(&bytes, options).unwrap();
+            assert_eq!(rows[1].pos, Position { x: 2.0 });
+        }
+
+        // (3) Other properties are skipped, unless the row type denies unknown fields.
+        assert_eq!(
+            read_with::<Plain>(&bytes, lenient()).unwrap(),
+            [Plain { x: 1.0 }, Plain { x: 2.0 }]
+        );
+        let err = read_with::<Denied>(&bytes, lenient()).unwrap_err();
+        assert!(err.contains("unknown field `nx`"), "{err}");
+
+        // (4) The option lists all of them, and takes precedence over serde's error.
+        for err in [
+            read_with::<Plain>(&bytes, strict()).unwrap_err(),
+            read_with::<Denied>(&bytes, strict()).unwrap_err(),
+        ] {
+            assert!(err.contains(extras), "{err}");
+        }
+    }
+
+    // Skipped lists of any length keep the rows after them aligned.
+    let ply = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float nx\nproperty list uchar int vertex_indices\nend_header\n1 1e3 3 0 1 2\n2 -0 0\n";
+    assert_eq!(
+        read_with::<Plain>(ply.as_bytes(), lenient()).unwrap(),
+        [Plain { x: 1.0 }, Plain { x: 2.0 }]
+    );
+}
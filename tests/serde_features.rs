@@ -375,6 +375,40 @@ end_header
     assert_eq!(vertices[0].indices, vec![1.0, 2.0, 3.0]);
 }
 
+#[test]
+fn test_list_data_type_mismatch() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct FloatRow {
+        data: Vec<f32>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[allow(unused)]
+    struct IntRow {
+        data: Vec<i32>,
+    }
+
+    let mut binary = b"ply\nformat binary_little_endian 1.0\nelement point 1\nproperty list uchar float data\nend_header\n\x02".to_vec();
+    binary.extend_from_slice(&1.5f32.to_le_bytes());
+    binary.extend_from_slice(&2.0f32.to_le_bytes());
+    let ascii = b"ply\nformat ascii 1.0\nelement point 1\nproperty list uchar float data\nend_header\n2 1.5 2\n";
+
+    for data in [&binary[..], &ascii[..]] {
+        let mut file = serde_ply::PlyReader::from_reader(Cursor::new(data)).unwrap();
+        let rows: Vec<FloatRow> = file.next_element().unwrap();
+        assert_eq!(rows[0].data, vec![1.5, 2.0]);
+
+        // Float data isn't truncated into integers.
+        let mut file = serde_ply::PlyReader::from_reader(Cursor::new(data)).unwrap();
+        let err = file.next_element::<Vec<IntRow>>().unwrap_err().to_string();
+        assert!(
+            err.contains("property 'data' of element 'point' is a list of float"),
+            "{err}"
+        );
+        assert!(err.contains("expected i32"), "{err}");
+    }
+}
+
 #[test]
 fn test_field_order_independence() {
     #[derive(Deserialize, Debug, PartialEq)]
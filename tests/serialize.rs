@@ -241,3 +241,1945 @@ fn test_list_count_types() {
     );
     assert_eq!(test_data.face[0].large_list.0, parsed.face[0].large_list.0);
 }
+
+#[test]
+fn test_list_count_u8() {
+    use serde_ply::{ListCountU8, ScalarType};
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Face {
+        vertex_indices: ListCountU8<Vec<u32>>,
+        normals: Vec<u32>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Mesh {
+        face: Vec<Face>,
+    }
+
+    let mesh = Mesh {
+        face: vec![Face {
+            vertex_indices: ListCountU8(vec![0, 1, 2]),
+            normals: vec![3],
+        }],
+    };
+    // The wrapper keeps a u8 count when the default is wider.
+    let options = SerializeOptions::binary_le().default_list_count_type(ScalarType::U32);
+    let bytes = to_bytes(&mesh, options).unwrap();
+    let header = String::from_utf8_lossy(&bytes);
+    assert!(header.contains("property list uint8 uint32 vertex_indices\n"));
+    assert!(header.contains("property list uint32 uint32 normals\n"));
+
+    let parsed: Mesh = from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(parsed.face[0].vertex_indices.0, [0, 1, 2]);
+    assert_eq!(parsed.face[0].normals, [3]);
+
+    // More entries than a u8 count holds.
+    let mesh = Mesh {
+        face: vec![Face {
+            vertex_indices: ListCountU8(vec![0; 256]),
+            normals: vec![3],
+        }],
+    };
+    let err = to_bytes(&mesh, SerializeOptions::binary_le()).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("List length 256 exceeds maximum for U8"));
+}
+
+#[test]
+fn test_ply_writer_matches_to_writer() {
+    use serde_ply::{ListCountU16, ListCountU32, PlyReader, PlyWriter};
+
+    #[derive(Serialize)]
+    struct Face {
+        vertex_indices: ListCountU16<Vec<u32>>,
+        uvs: ListCountU32<Vec<f32>>,
+        flags: Vec<u8>,
+    }
+
+    #[derive(Serialize)]
+    struct Mesh {
+        vertex: Vec<Vertex>,
+        face: Vec<Face>,
+    }
+
+    let mesh = Mesh {
+        vertex: (0..4)
+            .map(|i| Vertex {
+                x: i as f32,
+                y: 0.5,
+                z: -1.0,
+            })
+            .collect(),
+        face: vec![
+            Face {
+                vertex_indices: ListCountU16(vec![0, 1, 2]),
+                uvs: ListCountU32(vec![0.0, 1.0]),
+                flags: vec![7],
+            },
+            Face {
+                vertex_indices: ListCountU16(vec![1, 2, 3]),
+                uvs: ListCountU32(vec![]),
+                flags: vec![],
+            },
+        ],
+    };
+    for options in [
+        SerializeOptions::ascii(),
+        SerializeOptions::binary_le(),
+        SerializeOptions::binary_be(),
+    ] {
+        let options = options.with_comments(vec!["made by hand".to_string()]);
+        let expected = to_bytes(&mesh, options.clone()).unwrap();
+        let reader = PlyReader::from_reader(Cursor::new(&expected)).unwrap();
+
+        let mut writer = PlyWriter::new(Vec::new(), reader.header()).unwrap();
+        writer.write_rows(&mesh.vertex).unwrap();
+        writer.next_element().unwrap();
+        for face in &mesh.face {
+            writer.write_row(face).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), expected);
+
+        // The same elements, with the metadata of the options.
+        let elements = reader.header().elem_defs.clone();
+        let mut writer = PlyWriter::with_options(Vec::new(), elements, options).unwrap();
+        writer.write_rows(&mesh.vertex).unwrap();
+        writer.next_element().unwrap();
+        writer.write_rows(&mesh.face).unwrap();
+        assert_eq!(writer.finish().unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_ply_writer_checks_row_counts() {
+    use serde_ply::{PlyReader, PlyWriter};
+
+    let header = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\n\
+                  property float z\nelement face 1\nproperty list uchar uint vertex_indices\n\
+                  element edge 0\nproperty int vertex1\nend_header\n";
+    let reader = PlyReader::from_reader(header.as_bytes()).unwrap();
+    let header = reader.header();
+    let vertex = Vertex {
+        x: 0.0,
+        y: 1.0,
+        z: 2.0,
+    };
+    let face = Face {
+        vertex_indices: vec![0, 1],
+    };
+
+    // Too few rows, to the current or a later element.
+    let mut writer = PlyWriter::new(Vec::new(), header).unwrap();
+    writer.write_row(&vertex).unwrap();
+    let err = writer.next_element().unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("element 'vertex' has 1 of its 2 rows"));
+    assert_eq!(writer.element().unwrap().name, "vertex");
+    writer.write_row(&vertex).unwrap();
+    let err = writer.finish().unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("element 'face' has 0 of its 1 rows"));
+
+    // Too many rows.
+    let mut writer = PlyWriter::new(Vec::new(), header).unwrap();
+    writer.write_rows([&vertex, &vertex]).unwrap();
+    let err = writer.write_row(&vertex).unwrap_err();
+    assert!(err.to_string().contains("already has all its 2 rows"));
+
+    // A row of the wrong element.
+    writer.next_element().unwrap();
+    assert!(writer.write_row(&vertex).is_err());
+
+    // Trailing elements without rows don't need to be visited.
+    let mut writer = PlyWriter::new(Vec::new(), header).unwrap();
+    writer.write_rows([&vertex, &vertex]).unwrap();
+    writer.next_element().unwrap();
+    writer.write_row(&face).unwrap();
+    assert_eq!(writer.rows_written(), 1);
+    let text = String::from_utf8(writer.finish().unwrap()).unwrap();
+    assert!(text.ends_with("end_header\n0 1 2\n0 1 2\n2 0 1\n"));
+
+    // Nothing past the last element.
+    let mut writer = PlyWriter::new(Vec::new(), header).unwrap();
+    writer.write_rows([&vertex, &vertex]).unwrap();
+    writer.next_element().unwrap();
+    writer.write_row(&face).unwrap();
+    writer.next_element().unwrap();
+    let err = writer.next_element().unwrap_err();
+    assert!(err.to_string().contains("'edge' is the last element"));
+}
+
+#[test]
+fn test_ply_writer_unbounded_elements() {
+    use serde_ply::{PlyReader, PlyWriter};
+
+    let header = "ply\nformat binary_little_endian 1.0\nelement vertex 0\nproperty float x\n\
+                  property float y\nproperty float z\nelement face 1\n\
+                  property list uchar uint vertex_indices\nend_header\n";
+    let elements = PlyReader::from_reader(header.as_bytes())
+        .unwrap()
+        .header()
+        .elem_defs
+        .clone();
+    // A number of rows the writer doesn't know up front.
+    let vertices = || {
+        (0..7).map(|i| Vertex {
+            x: i as f32,
+            y: 0.0,
+            z: 1.0,
+        })
+    };
+    let face = Face {
+        vertex_indices: vec![0, 1, 2],
+    };
+    let mesh = Mesh {
+        vertex: vertices().collect(),
+        face: vec![Face {
+            vertex_indices: vec![0, 1, 2],
+        }],
+    };
+    let expected = to_bytes(&mesh, SerializeOptions::binary_le()).unwrap();
+
+    // Buffered in memory, with the final counts in the header.
+    let mut writer =
+        PlyWriter::unbounded(Vec::new(), elements.clone(), SerializeOptions::binary_le()).unwrap();
+    writer.start_element_unbounded("vertex").unwrap();
+    writer.write_rows(vertices()).unwrap();
+    writer.next_element().unwrap();
+    writer.write_row(&face).unwrap();
+    assert_eq!(writer.finish().unwrap(), expected);
+
+    // Placeholders patched in place, after some bytes already in the output.
+    let mut out = Cursor::new(b"prefix".to_vec());
+    out.set_position(6);
+    let mut writer =
+        PlyWriter::unbounded_seekable(out, elements.clone(), SerializeOptions::binary_le())
+            .unwrap();
+    writer.start_element_unbounded("vertex").unwrap();
+    writer.write_rows(vertices()).unwrap();
+    // A declared count can be ignored too.
+    writer.start_element_unbounded("face").unwrap();
+    writer.write_rows([&face, &face]).unwrap();
+    let bytes = writer.finish().unwrap().into_inner();
+    let text = String::from_utf8_lossy(&bytes);
+    assert!(text.starts_with("prefixply\n"));
+    assert!(text.contains(&format!("element vertex {}7\n", "0".repeat(19))));
+    assert!(text.contains(&format!("element face {}2\n", "0".repeat(19))));
+    let mut reader = PlyReader::from_reader(&bytes[6..]).unwrap();
+    assert_eq!(reader.header().elem_defs[0].count, 7);
+    let read: Vec<Vertex> = reader.next_element().unwrap();
+    assert_eq!(read, mesh.vertex);
+    let read: Vec<Face> = reader.next_element().unwrap();
+    assert_eq!(read.len(), 2);
+    assert_eq!(read[1], face);
+
+    // Writers with declared counts write the counts up front.
+    let mut writer =
+        PlyWriter::with_options(Vec::new(), elements, SerializeOptions::binary_le()).unwrap();
+    let err = writer.start_element_unbounded("vertex").unwrap_err();
+    assert!(err.to_string().contains("PlyWriter::unbounded"));
+}
+
+#[test]
+fn test_ply_writer_flush() {
+    use serde_ply::{PlyReader, PlyWriter};
+    use std::{io::Write, sync::mpsc};
+
+    /// Sends the bytes written since the last flush over a channel on every flush.
+    struct Channel {
+        pending: Vec<u8>,
+        sender: mpsc::Sender<Vec<u8>>,
+    }
+
+    impl Write for Channel {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.pending.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            if !self.pending.is_empty() {
+                self.sender.send(std::mem::take(&mut self.pending)).unwrap();
+            }
+            Ok(())
+        }
+    }
+
+    let header = "ply\nformat binary_little_endian 1.0\nelement vertex 10\nproperty float x\n\
+                  property float y\nproperty float z\nelement face 3\n\
+                  property list uchar int vertex_indices\nend_header\n";
+    let header = PlyReader::from_reader(header.as_bytes())
+        .unwrap()
+        .header()
+        .clone();
+    let vertices: Vec<Vertex> = (0..10)
+        .map(|i| Vertex {
+            x: i as f32,
+            y: 0.5,
+            z: -1.0,
+        })
+        .collect();
+    let face = Face {
+        vertex_indices: vec![0, 1, 2],
+    };
+    let write = |mut writer: PlyWriter<Channel>| {
+        writer.write_rows(&vertices).unwrap();
+        writer.next_element().unwrap();
+        writer.write_rows([&face, &face, &face]).unwrap();
+        writer.finish().unwrap();
+    };
+    let channel = || {
+        let (sender, receiver) = mpsc::channel();
+        let pending = Vec::new();
+        (Channel { pending, sender }, receiver)
+    };
+
+    let mut expected = PlyWriter::new(Vec::new(), &header).unwrap();
+    expected.write_rows(&vertices).unwrap();
+    expected.next_element().unwrap();
+    expected.write_rows([&face, &face, &face]).unwrap();
+    let expected = expected.finish().unwrap();
+    // Vertex rows are 12 bytes, face rows 13.
+    let header_len = expected.len() - 10 * 12 - 3 * 13;
+
+    // Every 4 rows, and the rest on finish. Flushes cross from one element to the next.
+    let (sink, receiver) = channel();
+    write(PlyWriter::new(sink, &header).unwrap().flush_every_rows(4));
+    let chunks: Vec<Vec<u8>> = receiver.iter().collect();
+    let lens: Vec<usize> = chunks.iter().map(Vec::len).collect();
+    assert_eq!(lens, [header_len + 4 * 12, 4 * 12, 2 * 12 + 2 * 13, 13]);
+    assert_eq!(chunks.concat(), expected);
+
+    // Once 30 bytes of rows are written, so after whole rows rather than at 30 bytes.
+    let (sink, receiver) = channel();
+    write(PlyWriter::new(sink, &header).unwrap().flush_every_bytes(30));
+    let chunks: Vec<Vec<u8>> = receiver.iter().collect();
+    let lens: Vec<usize> = chunks.iter().map(Vec::len).collect();
+    let rows = 3 * 12;
+    assert_eq!(lens, [header_len + rows, rows, rows, 12 + 2 * 13, 13]);
+    assert_eq!(chunks.concat(), expected);
+
+    // An explicit flush gets the rows written so far to the other end right away.
+    let (sink, receiver) = channel();
+    let mut writer = PlyWriter::new(sink, &header).unwrap();
+    writer.write_row(&vertices[0]).unwrap();
+    assert!(receiver.try_recv().is_err());
+    writer.flush().unwrap();
+    assert_eq!(receiver.try_recv().unwrap(), &expected[..header_len + 12]);
+
+    // Unbounded writers keep the rows until the header is written on finish.
+    let (sink, receiver) = channel();
+    let mut writer = PlyWriter::unbounded(
+        sink,
+        header.elem_defs.clone(),
+        SerializeOptions::binary_le(),
+    )
+    .unwrap()
+    .flush_every_rows(1);
+    writer.start_element_unbounded("vertex").unwrap();
+    writer.write_rows(&vertices).unwrap();
+    writer.flush().unwrap();
+    assert!(receiver.try_recv().is_err());
+    writer.start_element_unbounded("face").unwrap();
+    writer.write_rows([&face, &face, &face]).unwrap();
+    drop(writer.finish().unwrap());
+    let chunks: Vec<Vec<u8>> = receiver.iter().collect();
+    assert_eq!(chunks, [expected]);
+}
+
+#[test]
+fn test_property_name_transform() {
+    let mesh = create_test_mesh();
+    let options = SerializeOptions::ascii().property_name_transform(|name| name.to_uppercase());
+    let output = serde_ply::to_string(&mesh, options).unwrap();
+
+    assert!(
+        output.contains("element vertex 3\nproperty float X\nproperty float Y\nproperty float Z\n")
+    );
+    assert!(output.contains("element face 1\nproperty list uint8 uint32 VERTEX_INDICES\n"));
+
+    // Only the header is affected, the data matches a regular write.
+    let plain = serde_ply::to_string(&mesh, SerializeOptions::ascii()).unwrap();
+    let data = |s: &str| s.split("end_header\n").nth(1).unwrap().to_string();
+    assert_eq!(data(&output), data(&plain));
+
+    // Options with a transform can still be moved to another thread.
+    let options = SerializeOptions::ascii().property_name_transform(|name| name.to_uppercase());
+    let threaded = std::thread::spawn(move || serde_ply::to_string(&mesh, options).unwrap());
+    assert_eq!(threaded.join().unwrap(), output);
+}
+
+#[test]
+fn test_schema_preserving_roundtrip() {
+    use serde_ply::PlyReader;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Point {
+        x: f64,
+        intensity: u32,
+        labels: Vec<u32>,
+    }
+
+    #[derive(Serialize)]
+    struct Cloud {
+        point: Vec<Point>,
+    }
+
+    let source = "ply
+format ascii 1.0
+element point 2
+property float x
+property ushort intensity
+property list uchar uchar labels
+end_header
+0.5 300 2 1 2
+1.5 65535 0
+";
+
+    let mut reader = PlyReader::from_reader(Cursor::new(source)).unwrap();
+    let (mut point, def) = reader.next_element_preserving::<Point>().unwrap();
+    assert_eq!(point[1].intensity, 65535);
+    point[0].intensity += 1;
+
+    for options in [SerializeOptions::ascii(), SerializeOptions::binary_le()] {
+        let options = options.with_element_def(def.clone());
+        let cloud = Cloud {
+            point: point
+                .iter()
+                .map(|p| Point {
+                    x: p.x,
+                    intensity: p.intensity,
+                    labels: p.labels.clone(),
+                })
+                .collect(),
+        };
+        let bytes = to_bytes(&cloud, options).unwrap();
+        let header = String::from_utf8_lossy(&bytes);
+        assert!(header.contains(
+            "property float x\nproperty ushort intensity\nproperty list uint8 uint8 labels\n"
+        ));
+
+        let mut reader = PlyReader::from_reader(Cursor::new(bytes)).unwrap();
+        let read_back: Vec<Point> = reader.next_element().unwrap();
+        assert_eq!(read_back, cloud.point);
+    }
+
+    // Values that don't fit the declared type are rejected rather than truncated.
+    let cloud = Cloud {
+        point: vec![Point {
+            x: 0.0,
+            intensity: 70000,
+            labels: vec![],
+        }],
+    };
+    let err = to_bytes(&cloud, SerializeOptions::binary_le().with_element_def(def)).unwrap_err();
+    assert!(err.to_string().contains("out of range for uint16"));
+}
+
+#[test]
+fn test_list_count_wrapper_misuse() {
+    use serde_ply::{ListCountU16, ListCountU32};
+
+    #[derive(Serialize)]
+    struct Row<T> {
+        values: T,
+    }
+
+    #[derive(Serialize)]
+    struct Rows<T> {
+        row: Vec<Row<T>>,
+    }
+
+    fn err<T: Serialize>(values: T) -> String {
+        let rows = Rows {
+            row: vec![Row { values }],
+        };
+        to_bytes(&rows, SerializeOptions::ascii())
+            .unwrap_err()
+            .to_string()
+    }
+
+    assert!(err(ListCountU32(ListCountU16(vec![1u32]))).contains(
+        "property 'values' nests ListCountU16 inside ListCountU32, use a single ListCount wrapper"
+    ));
+    assert!(err(ListCountU16(1.0f32)).contains(
+        "ListCountU16 on property 'values' must wrap a sequence, found a scalar (float32)"
+    ));
+    assert!(err(ListCountU32(Vertex {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0
+    }))
+    .contains("ListCountU32 on property 'values' must wrap a sequence, found a struct"));
+
+    // The count type is known from the wrapper, only the data type is missing.
+    let empty = err(ListCountU16(Vec::<f64>::new()));
+    assert!(empty.contains(
+        "Cannot infer the data type of list property 'values' of element 'row', the list in the first row is empty"
+    ));
+    assert!(!empty.contains("count"));
+}
+
+#[test]
+fn test_default_list_count_type() {
+    use serde_ply::{ListCountU16, PlyReader, ScalarType};
+
+    #[derive(Serialize, Deserialize)]
+    struct Face {
+        vertex_indices: Vec<u32>,
+        uvs: ListCountU16<Vec<f32>>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Mesh {
+        face: Vec<Face>,
+    }
+
+    let mesh = Mesh {
+        face: vec![
+            Face {
+                vertex_indices: vec![0, 1, 2],
+                uvs: ListCountU16::from(vec![0.5]),
+            },
+            Face {
+                vertex_indices: (0..300).collect(),
+                uvs: ListCountU16::from(vec![]),
+            },
+        ],
+    };
+
+    let bytes = to_bytes(
+        &mesh,
+        SerializeOptions::binary_le().default_list_count_type(ScalarType::U32),
+    )
+    .unwrap();
+
+    let reader = PlyReader::from_reader(Cursor::new(&bytes)).unwrap();
+    let face = &reader.header().elem_defs[0];
+    assert_eq!(
+        face.properties[0].property_type,
+        serde_ply::PropertyType::List {
+            count_type: ScalarType::U32,
+            data_type: ScalarType::U32,
+        }
+    );
+    // The per-property wrapper wins over the global default.
+    assert_eq!(
+        face.properties[1].property_type,
+        serde_ply::PropertyType::List {
+            count_type: ScalarType::U16,
+            data_type: ScalarType::F32,
+        }
+    );
+
+    let parsed: Mesh = from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(parsed.face[0].vertex_indices, vec![0, 1, 2]);
+    assert_eq!(parsed.face[0].uvs.0, vec![0.5]);
+    assert_eq!(parsed.face[1].vertex_indices.len(), 300);
+}
+
+#[test]
+fn test_raw_identifier_property_names() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Extra {
+        r#ref: u8,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Point {
+        r#type: f32,
+        r#loop: Vec<u32>,
+        #[serde(flatten)]
+        extra: Extra,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Cloud {
+        point: Vec<Point>,
+    }
+
+    let cloud = Cloud {
+        point: vec![Point {
+            r#type: 1.5,
+            r#loop: vec![1, 2],
+            extra: Extra { r#ref: 7 },
+        }],
+    };
+
+    for options in [
+        SerializeOptions::ascii,
+        SerializeOptions::binary_le,
+        SerializeOptions::binary_be,
+    ] {
+        let bytes = to_bytes(&cloud, options()).unwrap();
+        let header = String::from_utf8_lossy(&bytes);
+        assert!(header.contains("property float type\n"));
+        assert!(header.contains("property list uint8 uint32 loop\n"));
+        assert!(header.contains("property uchar ref\n"));
+        let parsed: Cloud = from_reader(Cursor::new(bytes)).unwrap();
+        assert_eq!(parsed, cloud);
+    }
+
+    // Renaming a field to a raw identifier writes a wrong property name.
+    #[derive(Serialize)]
+    struct Renamed {
+        #[serde(rename = "r#type")]
+        kind: f32,
+    }
+
+    #[derive(Serialize)]
+    struct RenamedCloud {
+        point: Vec<Renamed>,
+    }
+
+    let err = to_bytes(
+        &RenamedCloud {
+            point: vec![Renamed { kind: 1.0 }],
+        },
+        SerializeOptions::ascii(),
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("property name 'r#type' starts with 'r#'"));
+}
+
+#[test]
+fn test_bool_list_roundtrip() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Face {
+        vertex_indices: Vec<u32>,
+        mask: Vec<bool>,
+        visible: bool,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Mesh {
+        face: Vec<Face>,
+    }
+
+    let mesh = Mesh {
+        face: vec![
+            Face {
+                vertex_indices: vec![0, 1, 2],
+                mask: vec![true, false, true],
+                visible: true,
+            },
+            Face {
+                vertex_indices: vec![2, 3, 0],
+                mask: vec![],
+                visible: false,
+            },
+        ],
+    };
+
+    for options in [SerializeOptions::ascii, SerializeOptions::binary_le] {
+        let bytes = to_bytes(&mesh, options()).unwrap();
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(text.contains("property list uint8 uint8 mask\n"));
+        assert!(text.contains("property uchar visible\n"));
+        let parsed: Mesh = from_reader(Cursor::new(bytes)).unwrap();
+        assert_eq!(parsed, mesh);
+    }
+
+    let ascii = serde_ply::to_string(&mesh, SerializeOptions::ascii()).unwrap();
+    assert!(ascii.ends_with("3 0 1 2 3 1 0 1 1\n3 2 3 0 0 0\n"));
+
+    // Any nonzero value reads as true.
+    let ply_data = "ply\nformat ascii 1.0\nelement face 1\nproperty list uchar uint vertex_indices\nproperty list uchar float mask\nproperty int visible\nend_header\n0 2 0.5 0 -3\n";
+    let parsed: Mesh = serde_ply::from_str(ply_data).unwrap();
+    assert_eq!(parsed.face[0].mask, vec![true, false]);
+    assert!(parsed.face[0].visible);
+}
+
+#[test]
+fn test_map_element() {
+    use serde_ply::{map_element, PlyReader};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Position {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Face {
+        vertex_indices: Vec<u32>,
+    }
+
+    let mut file = b"ply\nformat binary_little_endian 1.0\ncomment source\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\nelement face 2\nproperty list uchar int vertex_indices\nend_header\n".to_vec();
+    for v in [1.0f32, 2.0, 3.0, 3.0, 4.0, 5.0, 5.0, 6.0, 7.0] {
+        file.extend_from_slice(&v.to_le_bytes());
+    }
+    let face_start = file.len();
+    for face in [[0i32, 1, 2], [2, 1, 0]] {
+        file.push(3);
+        for i in face {
+            file.extend_from_slice(&i.to_le_bytes());
+        }
+    }
+    let face_bytes = file[face_start..].to_vec();
+
+    let recenter = |v: Position| Position {
+        x: v.x - 3.0,
+        y: v.y - 4.0,
+        z: v.z - 5.0,
+    };
+
+    // Same format: faces are copied byte for byte.
+    let mut output = Vec::new();
+    let stats = map_element(
+        Cursor::new(&file),
+        &mut output,
+        "vertex",
+        recenter,
+        SerializeOptions::binary_le(),
+    )
+    .unwrap();
+    assert_eq!(stats.rows_mapped, 3);
+    assert_eq!(stats.elements_copied, 1);
+    assert!(output.ends_with(&face_bytes));
+    assert!(String::from_utf8_lossy(&output).contains("comment source\n"));
+
+    let mut reader = PlyReader::from_reader(Cursor::new(&output)).unwrap();
+    let vertices: Vec<Position> = reader.next_element().unwrap();
+    assert_eq!(
+        vertices[0],
+        Position {
+            x: -2.0,
+            y: -2.0,
+            z: -2.0
+        }
+    );
+    assert_eq!(
+        vertices[2],
+        Position {
+            x: 2.0,
+            y: 2.0,
+            z: 2.0
+        }
+    );
+    let faces: Vec<Face> = reader.next_element().unwrap();
+    assert_eq!(faces[1].vertex_indices, vec![2, 1, 0]);
+
+    // Each row is written before the next one is mapped, nothing is collected.
+    struct Shared(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+    impl std::io::Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let written = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let mut seen = Vec::new();
+    map_element(
+        Cursor::new(&file),
+        Shared(written.clone()),
+        "vertex",
+        |v: Position| {
+            seen.push(written.borrow().len());
+            v
+        },
+        SerializeOptions::binary_le(),
+    )
+    .unwrap();
+    assert_eq!([seen[1] - seen[0], seen[2] - seen[1]], [12, 12]);
+    let data = &file[face_start - 3 * 12..];
+    assert!(written.borrow().ends_with(data));
+
+    // Other format: faces are transcoded, and keep their int indices.
+    let mut output = Vec::new();
+    let stats = map_element(
+        Cursor::new(&file),
+        &mut output,
+        "vertex",
+        recenter,
+        SerializeOptions::ascii(),
+    )
+    .unwrap();
+    assert_eq!(stats.elements_transcoded, 1);
+    let text = String::from_utf8(output).unwrap();
+    assert!(text.contains("property list uint8 int32 vertex_indices\n"));
+    assert!(text.ends_with("-2 -2 -2\n0 0 0\n2 2 2\n3 0 1 2\n3 2 1 0\n"));
+
+    // A row type that doesn't model every property would drop data.
+    #[derive(Serialize, Deserialize)]
+    struct Flat {
+        x: f32,
+        y: f32,
+    }
+    let err = map_element(
+        Cursor::new(&file),
+        Vec::new(),
+        "vertex",
+        |v: Flat| v,
+        SerializeOptions::binary_le(),
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("property 'z' of element 'vertex' has no field in the row type"));
+
+    let err = map_element(
+        Cursor::new(&file),
+        Vec::new(),
+        "normal",
+        recenter,
+        SerializeOptions::binary_le(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("header has no element 'normal'"));
+}
+
+#[test]
+fn test_map_elements_streams() {
+    use serde_ply::{map_elements, PlyReader};
+
+    #[derive(Deserialize)]
+    struct Position {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    let mut file = b"ply\nformat binary_little_endian 1.0\nelement face 1\nproperty list uchar int vertex_indices\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\nend_header\n".to_vec();
+    file.push(3);
+    for i in [0i32, 1, 2] {
+        file.extend_from_slice(&i.to_le_bytes());
+    }
+    for v in [1.0f32, 2.0, 3.0, 3.0, 4.0, 5.0, 5.0, 6.0, 7.0] {
+        file.extend_from_slice(&v.to_le_bytes());
+    }
+
+    let scale = |v: Position| Vertex {
+        x: v.x * 2.0,
+        y: v.y * 2.0,
+        z: v.z * 2.0,
+    };
+    for options in [SerializeOptions::binary_le, SerializeOptions::ascii] {
+        let mut output = Vec::new();
+        let rows =
+            map_elements(Cursor::new(&file), &mut output, "vertex", scale, options()).unwrap();
+        assert_eq!(rows, 3);
+
+        // Only the mapped element is written, the face before it is skipped.
+        let mut reader = PlyReader::from_reader(Cursor::new(&output)).unwrap();
+        assert_eq!(reader.header().elem_defs.len(), 1);
+        let vertices: Vec<Vertex> = reader.next_element().unwrap();
+        assert_eq!(
+            vertices,
+            [
+                Vertex {
+                    x: 2.0,
+                    y: 4.0,
+                    z: 6.0
+                },
+                Vertex {
+                    x: 6.0,
+                    y: 8.0,
+                    z: 10.0
+                },
+                Vertex {
+                    x: 10.0,
+                    y: 12.0,
+                    z: 14.0
+                },
+            ]
+        );
+    }
+
+    // A read error partway through is returned as is.
+    let truncated = &file[..file.len() - 2];
+    let err = map_elements(
+        Cursor::new(truncated),
+        Vec::new(),
+        "vertex",
+        scale,
+        SerializeOptions::binary_le(),
+    )
+    .unwrap_err();
+    assert_eq!(err.0.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    let err = map_elements(
+        Cursor::new(&file),
+        Vec::new(),
+        "edge",
+        scale,
+        SerializeOptions::binary_le(),
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("header has no element 'edge'"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_preserve_type_spelling() {
+    use serde_ply::{map_element, PlyReader, PropertyType, ScalarType};
+
+    #[derive(Serialize, Deserialize)]
+    struct Point {
+        label: i8,
+        flags: u8,
+        neighbors: Vec<i32>,
+    }
+
+    #[derive(Serialize)]
+    struct Cloud {
+        point: Vec<Point>,
+    }
+
+    let source = "ply\nformat ascii 1.0\nelement point 1\nproperty int8 label\nproperty uint8 flags\nproperty list uint8 int neighbors\nend_header\n-3 7 2 4 5\n";
+    let read = |options: SerializeOptions| {
+        let mut reader = PlyReader::from_reader(Cursor::new(source)).unwrap();
+        let (point, def) = reader.next_element_preserving::<Point>().unwrap();
+        assert_eq!(def.properties[0].type_spelling(), Some("int8"));
+        serde_ply::to_string(&Cloud { point }, options.with_element_def(def)).unwrap()
+    };
+
+    let preserved = read(SerializeOptions::ascii().preserve_type_spelling(true));
+    assert_eq!(preserved, source);
+
+    let canonical = read(SerializeOptions::ascii());
+    assert!(canonical.contains("property char label\n"));
+    assert!(canonical.contains("property uchar flags\n"));
+    assert!(canonical.contains("property list uint8 int32 neighbors\n"));
+
+    // A changed type isn't written with the old spelling.
+    let mut reader = PlyReader::from_reader(Cursor::new(source)).unwrap();
+    let (point, mut def) = reader.next_element_preserving::<Point>().unwrap();
+    def.properties[0].property_type = PropertyType::Scalar(ScalarType::I16);
+    let options = SerializeOptions::ascii()
+        .with_element_def(def)
+        .preserve_type_spelling(true);
+    let changed = serde_ply::to_string(&Cloud { point }, options).unwrap();
+    assert!(changed.contains("property short label\n"));
+    assert!(changed.contains("property uint8 flags\n"));
+
+    let mut output = Vec::new();
+    map_element(
+        Cursor::new(source),
+        &mut output,
+        "point",
+        |p: Point| p,
+        SerializeOptions::ascii().preserve_type_spelling(true),
+    )
+    .unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), source);
+}
+
+#[test]
+fn test_element_pairs_keep_file_order() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Camera {
+        x: f32,
+        y: f32,
+        focal: f64,
+    }
+
+    // Photogrammetry exports name elements camera_0, camera_1, ... with one schema.
+    let mut source = "ply\nformat ascii 1.0\n".to_string();
+    let names = ["camera_3", "camera_0", "camera-1", "camera_10"];
+    for name in names {
+        source.push_str(&format!(
+            "element {name} 2\nproperty float x\nproperty float y\nproperty double focal\n"
+        ));
+    }
+    source.push_str("end_header\n");
+    for i in 0..names.len() {
+        source.push_str(&format!("{i} 0.5 35\n-1.25 {i} 50.5\n"));
+    }
+
+    let cameras: Vec<(String, Vec<Camera>)> = serde_ply::from_str(&source).unwrap();
+    let read_names: Vec<&str> = cameras.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(read_names, names);
+    assert_eq!(
+        cameras[2].1[1],
+        Camera {
+            x: -1.25,
+            y: 2.0,
+            focal: 50.5
+        }
+    );
+
+    let written = serde_ply::to_string(&cameras, SerializeOptions::ascii()).unwrap();
+    assert_eq!(written, source);
+
+    let bytes = to_bytes(&cameras, SerializeOptions::binary_be()).unwrap();
+    let reread: Vec<(String, Vec<Camera>)> = serde_ply::from_bytes(&bytes).unwrap();
+    assert_eq!(reread, cameras);
+
+    // Anything other than (name, rows) pairs is rejected.
+    let bad = vec![("camera_0".to_string(), vec![(1.0f32,)], 3)];
+    assert!(serde_ply::to_string(&bad, SerializeOptions::ascii()).is_err());
+}
+
+#[test]
+fn test_nested_struct_columns() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Position {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Rgb {
+        red: u8,
+        green: u8,
+        blue: u8,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Vertex {
+        #[serde(flatten)]
+        pos: Position,
+        #[serde(flatten)]
+        color: Rgb,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Mesh {
+        vertex: Vec<Vertex>,
+    }
+
+    let mesh = Mesh {
+        vertex: (0..3)
+            .map(|i| Vertex {
+                pos: Position {
+                    x: i as f32,
+                    y: i as f32 + 0.5,
+                    z: -(i as f32),
+                },
+                color: Rgb {
+                    red: i,
+                    green: 10 + i,
+                    blue: 20 + i,
+                },
+            })
+            .collect(),
+    };
+
+    for options in [
+        SerializeOptions::ascii,
+        SerializeOptions::binary_le,
+        SerializeOptions::binary_be,
+    ] {
+        let bytes = to_bytes(&mesh, options()).unwrap();
+        let header = String::from_utf8_lossy(&bytes);
+        assert!(header.contains(
+            "property float x\nproperty float y\nproperty float z\n\
+             property uchar red\nproperty uchar green\nproperty uchar blue\n"
+        ));
+        let parsed: Mesh = from_reader(Cursor::new(bytes)).unwrap();
+        assert_eq!(parsed, mesh);
+    }
+
+    // Without flatten the error points at the field.
+    #[derive(Serialize)]
+    struct NestedVertex {
+        pos: Position,
+    }
+
+    #[derive(Serialize)]
+    struct NestedMesh {
+        vertex: Vec<NestedVertex>,
+    }
+
+    let err = to_bytes(
+        &NestedMesh {
+            vertex: vec![NestedVertex {
+                pos: Position {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+            }],
+        },
+        SerializeOptions::ascii(),
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("'pos'"), "{err}");
+    assert!(err.contains("#[serde(flatten)]"), "{err}");
+}
+
+#[test]
+fn test_options_with_format() {
+    use serde_ply::PlyFormat;
+
+    #[derive(Serialize)]
+    struct Vertex {
+        x: f32,
+    }
+
+    #[derive(Serialize)]
+    struct Mesh {
+        vertex: Vec<Vertex>,
+    }
+
+    let options = SerializeOptions::ascii()
+        .with_comments(vec!["kept".to_string()])
+        .with_format(PlyFormat::BinaryBigEndian);
+    assert_eq!(options.format(), PlyFormat::BinaryBigEndian);
+    assert_eq!(
+        SerializeOptions::binary_le().format(),
+        PlyFormat::BinaryLittleEndian
+    );
+
+    let mesh = Mesh {
+        vertex: vec![Vertex { x: 1.0 }],
+    };
+    let bytes = to_bytes(&mesh, options).unwrap();
+    let header_end = bytes.len() - 4;
+    let header = std::str::from_utf8(&bytes[..header_end]).unwrap();
+    assert!(header.contains("format binary_big_endian 1.0\ncomment kept\n"));
+    assert_eq!(&bytes[header_end..], 1.0f32.to_be_bytes());
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn test_array2_roundtrip() {
+    let array = ndarray::array![[0.5, -1.0, 2.25], [3.0, 4.5, -5.75], [6.0, 7.0, 8.0]];
+
+    for options in [SerializeOptions::ascii(), SerializeOptions::binary_be()] {
+        let mut buffer = Vec::new();
+        serde_ply::to_writer_array2(&mut buffer, options, "vertex", &["x", "y", "z"], &array)
+            .unwrap();
+        let mut reader = serde_ply::PlyReader::from_reader(Cursor::new(buffer)).unwrap();
+        assert_eq!(reader.header().elem_defs[0].properties.len(), 3);
+        assert_eq!(reader.element_array2("vertex").unwrap(), array);
+    }
+
+    let err = serde_ply::to_writer_array2(
+        Vec::new(),
+        SerializeOptions::ascii(),
+        "vertex",
+        &["x", "y"],
+        &array,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("3 columns"), "{err}");
+}
+
+#[test]
+fn test_columns_roundtrip() {
+    #[derive(Serialize)]
+    struct Cols {
+        x: Vec<f32>,
+        y: Vec<f32>,
+        red: Vec<u8>,
+    }
+
+    #[derive(Serialize)]
+    struct ColumnPly<'a> {
+        vertex: serde_ply::Columns<&'a Cols>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Row {
+        x: f32,
+        y: f32,
+        red: u8,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct RowPly {
+        vertex: Vec<Row>,
+    }
+
+    let cols = Cols {
+        x: vec![0.0, 1.5, -2.0],
+        y: vec![3.0, 4.25, 5.0],
+        red: vec![0, 128, 255],
+    };
+    let rows = RowPly {
+        vertex: (0..3)
+            .map(|i| Row {
+                x: cols.x[i],
+                y: cols.y[i],
+                red: cols.red[i],
+            })
+            .collect(),
+    };
+
+    for options in [SerializeOptions::ascii, SerializeOptions::binary_le] {
+        let bytes = to_bytes(
+            &ColumnPly {
+                vertex: serde_ply::Columns(&cols),
+            },
+            options(),
+        )
+        .unwrap();
+        // Writes the same file as the equivalent rows.
+        assert_eq!(bytes, to_bytes(&rows, options()).unwrap());
+        let read: RowPly = from_reader(Cursor::new(bytes)).unwrap();
+        assert_eq!(read, rows);
+    }
+
+    let cols = Cols {
+        x: vec![0.0, 1.0, 2.0],
+        y: vec![0.0, 1.0, 2.0],
+        red: vec![0, 1],
+    };
+    let err = to_bytes(
+        &ColumnPly {
+            vertex: serde_ply::Columns(&cols),
+        },
+        SerializeOptions::ascii(),
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("column 'red' has 2 values, but column 'x' has 3"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_options_from_header_roundtrip() {
+    #[derive(Serialize)]
+    struct Mesh {
+        vertex: Vec<Vertex>,
+    }
+
+    let mesh = Mesh {
+        vertex: vec![Vertex {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        }],
+    };
+    let options = SerializeOptions::binary_be()
+        .with_comments(vec!["made by test".to_string(), "second".to_string()])
+        .with_obj_info(vec!["units mm".to_string()]);
+    let expected = options.clone();
+    assert_eq!(options, expected);
+    let first = to_bytes(&mesh, options).unwrap();
+
+    let reader = serde_ply::PlyReader::from_reader(Cursor::new(&first)).unwrap();
+    let options = SerializeOptions::from_header(reader.header());
+    assert_eq!(options, expected);
+    let second = to_bytes(&mesh, options).unwrap();
+    assert_eq!(second, first);
+}
+
+#[test]
+fn test_metadata_order() {
+    use serde_ply::MetaOrder;
+
+    #[derive(Serialize)]
+    struct Cloud {
+        vertex: Vec<Vertex>,
+    }
+
+    let cloud = Cloud {
+        vertex: vec![Vertex {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        }],
+    };
+    let options = || {
+        SerializeOptions::ascii()
+            .with_comments(vec!["a".to_string(), "b".to_string()])
+            .with_obj_info(vec!["units mm".to_string()])
+    };
+    let comments_first = "comment a\ncomment b\nobj_info units mm\nelement";
+    let obj_info_first = "obj_info units mm\ncomment a\ncomment b\nelement";
+
+    let ply = serde_ply::to_string(&cloud, options()).unwrap();
+    assert!(ply.contains(comments_first), "{ply}");
+    let options_comments = options().metadata_order(MetaOrder::CommentsFirst);
+    let ply = serde_ply::to_string(&cloud, options_comments).unwrap();
+    assert!(ply.contains(comments_first), "{ply}");
+    let options_obj_info = options().metadata_order(MetaOrder::ObjInfoFirst);
+    let ply = serde_ply::to_string(&cloud, options_obj_info.clone()).unwrap();
+    assert!(ply.contains(obj_info_first), "{ply}");
+
+    // map_element writes the source metadata in the requested order too.
+    let source = "ply\nformat ascii 1.0\ncomment src\nobj_info scale 1\nelement vertex 1\nproperty float x\nproperty float y\nproperty float z\nend_header\n1 2 3\n";
+    let mut output = Vec::new();
+    serde_ply::map_element(
+        Cursor::new(source),
+        &mut output,
+        "vertex",
+        |v: Vertex| v,
+        options_obj_info,
+    )
+    .unwrap();
+    let ply = String::from_utf8(output).unwrap();
+    assert!(
+        ply.contains("obj_info scale 1\nobj_info units mm\ncomment src\ncomment a\ncomment b\n"),
+        "{ply}"
+    );
+}
+
+#[test]
+fn test_ascii_tab_separator() {
+    let mesh = Mesh {
+        vertex: vec![
+            Vertex {
+                x: 0.0,
+                y: 1.5,
+                z: -2.0,
+            },
+            Vertex {
+                x: 3.0,
+                y: 4.0,
+                z: 5.25,
+            },
+        ],
+        face: vec![Face {
+            vertex_indices: vec![0, 1, 1],
+        }],
+    };
+
+    let bytes = to_bytes(&mesh, SerializeOptions::ascii().ascii_separator('\t')).unwrap();
+    let text = String::from_utf8(bytes.clone()).unwrap();
+    assert!(text.ends_with("end_header\n0\t1.5\t-2\n3\t4\t5.25\n3\t0\t1\t1\n"));
+
+    let read: Mesh = from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(read, mesh);
+
+    // Line breaks would split rows.
+    let err = to_bytes(&mesh, SerializeOptions::ascii().ascii_separator('\n')).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("ASCII separator '\\n' is not a space, tab or form feed"));
+}
+
+#[test]
+fn test_binary_scalar_and_list_row() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Face {
+        material: u16,
+        indices: Vec<u32>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Mesh {
+        face: Vec<Face>,
+    }
+
+    let mesh = Mesh {
+        face: vec![
+            Face {
+                material: 7,
+                indices: vec![0, 1, 2],
+            },
+            Face {
+                material: 65535,
+                indices: vec![],
+            },
+            Face {
+                material: 1,
+                indices: vec![3, 4, 5, 100_000],
+            },
+        ],
+    };
+
+    for (options, material) in [
+        (SerializeOptions::binary_le(), 7u16.to_le_bytes()),
+        (SerializeOptions::binary_be(), 7u16.to_be_bytes()),
+    ] {
+        let bytes = to_bytes(&mesh, options).unwrap();
+        let data = &bytes[serde_ply::find_data_start(&bytes).unwrap()..];
+        // Each row is the scalar, then the list count and entries.
+        assert_eq!(data[..2], material);
+        assert_eq!(data[2], 3);
+        assert_eq!(data.len(), (2 + 1 + 3 * 4) + (2 + 1) + (2 + 1 + 4 * 4));
+
+        let read: Mesh = from_reader(Cursor::new(bytes)).unwrap();
+        assert_eq!(read, mesh);
+    }
+}
+
+#[test]
+fn test_to_slice() {
+    let mesh = create_test_mesh();
+    for options in [
+        SerializeOptions::ascii(),
+        SerializeOptions::binary_le(),
+        SerializeOptions::binary_be(),
+    ] {
+        let expected = to_bytes(&mesh, options.clone()).unwrap();
+        let len = expected.len();
+
+        // An exact fit writes everything.
+        let mut out = vec![0xAA; len];
+        assert_eq!(
+            serde_ply::to_slice(&mesh, options.clone(), &mut out).unwrap(),
+            len
+        );
+        assert_eq!(out, expected);
+
+        // A larger buffer keeps the bytes after the output.
+        let mut out = vec![0xAA; len + 16];
+        let written = serde_ply::to_slice(&mesh, options.clone(), &mut out).unwrap();
+        assert_eq!(written, len);
+        assert!(out[len..].iter().all(|&b| b == 0xAA));
+        let back: Mesh = serde_ply::from_bytes(&out[..written]).unwrap();
+        assert_eq!(back, mesh);
+
+        // One byte short fails without writing anything.
+        let mut out = vec![0xAA; len - 1];
+        let err = serde_ply::to_slice(&mesh, options, &mut out).unwrap_err();
+        assert_eq!(
+            err.as_buffer_too_small(),
+            Some(&serde_ply::BufferTooSmall {
+                needed: len,
+                available: len - 1,
+            })
+        );
+        assert!(out.iter().all(|&b| b == 0xAA));
+    }
+}
+
+#[test]
+fn test_map_with_empty_element() {
+    use std::collections::BTreeMap;
+
+    let elements = BTreeMap::from([
+        ("face".to_string(), vec![]),
+        (
+            "vertex".to_string(),
+            vec![Vertex {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+            }],
+        ),
+    ]);
+    let face_def = serde_ply::ElementDef {
+        name: "face".to_string(),
+        count: 0,
+        properties: vec![serde_ply::PlyProperty::new(
+            "vertex_indices",
+            serde_ply::PropertyType::List {
+                count_type: serde_ply::ScalarType::U8,
+                data_type: serde_ply::ScalarType::U32,
+            },
+        )],
+    };
+
+    for format in [
+        SerializeOptions::ascii,
+        SerializeOptions::binary_le,
+        SerializeOptions::binary_be,
+    ] {
+        // Without a definition the empty element has no properties.
+        let bytes = to_bytes(&elements, format()).unwrap();
+        let (header, _) = serde_ply::read_header_prefix(&bytes).unwrap();
+        assert_eq!(header.elem_defs[0].name, "face");
+        assert_eq!(header.elem_defs[0].count, 0);
+        assert!(header.elem_defs[0].properties.is_empty());
+
+        // A definition declares them.
+        let options = format().with_element_def(face_def.clone());
+        let bytes = to_bytes(&elements, options).unwrap();
+        let (header, _) = serde_ply::read_header_prefix(&bytes).unwrap();
+        let face = &header.elem_defs[0];
+        assert_eq!((face.name.as_str(), face.count), ("face", 0));
+        assert_eq!(face.properties[0].name, "vertex_indices");
+        assert_eq!(
+            face.properties[0].property_type,
+            face_def.properties[0].property_type
+        );
+        assert_eq!(header.elem_defs[1].count, 1);
+        assert_eq!(header.elem_defs[1].properties.len(), 3);
+        let mesh: Mesh = serde_ply::from_bytes(&bytes).unwrap();
+        assert!(mesh.face.is_empty());
+        assert_eq!(mesh.vertex, elements["vertex"]);
+
+        // Or the element is left out.
+        let bytes = to_bytes(&elements, format().skip_empty_elements(true)).unwrap();
+        let back: BTreeMap<String, Vec<Vertex>> = serde_ply::from_bytes(&bytes).unwrap();
+        assert_eq!(back.keys().collect::<Vec<_>>(), ["vertex"]);
+        assert_eq!(back["vertex"], elements["vertex"]);
+    }
+}
+
+#[test]
+fn test_map_key_names_checked() {
+    use std::collections::{BTreeMap, HashMap};
+
+    let cases = [
+        ("my element", "contains whitespace"),
+        ("my\telement", "contains whitespace"),
+        ("my\nelement", "contains whitespace"),
+        ("", "is empty"),
+        ("my\0element", "contains a control character"),
+    ];
+    for (name, problem) in cases {
+        // As element name.
+        let elements = HashMap::from([(
+            name.to_string(),
+            vec![Vertex {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+            }],
+        )]);
+        let err = to_bytes(&elements, SerializeOptions::ascii())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains(&format!("element name {name:?} {problem}")),
+            "{err}"
+        );
+
+        // As property name of map rows.
+        let rows = vec![BTreeMap::from([
+            ("x".to_string(), 1.0f32),
+            (name.to_string(), 2.0),
+        ])];
+        let elements = HashMap::from([("vertex".to_string(), rows)]);
+        let err = to_bytes(&elements, SerializeOptions::binary_le())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains(&format!("property name {name:?} {problem}")),
+            "{err}"
+        );
+    }
+
+    // The escapes make the offending character visible.
+    let elements = HashMap::from([("a\nb".to_string(), Vec::<Vertex>::new())]);
+    let err = to_bytes(&elements, SerializeOptions::ascii()).unwrap_err();
+    assert!(err.to_string().contains(r#""a\nb""#), "{err}");
+
+    // A name transform can make a key valid.
+    let rows = vec![BTreeMap::from([("my x".to_string(), 1.0f32)])];
+    let elements = HashMap::from([("vertex".to_string(), rows)]);
+    let options = SerializeOptions::ascii().property_name_transform(|name| name.replace(" ", "_"));
+    let bytes = to_bytes(&elements, options).unwrap();
+    assert!(String::from_utf8(bytes)
+        .unwrap()
+        .contains("property float my_x\n"));
+}
+
+#[test]
+fn test_to_writer_flushes() {
+    use std::io::{BufWriter, Write};
+
+    /// Accepts writes, but fails to flush them.
+    struct FailingFlush(Vec<u8>);
+
+    impl Write for FailingFlush {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Err(std::io::Error::other("disk full"))
+        }
+    }
+
+    let mesh = create_test_mesh();
+    for options in [SerializeOptions::ascii(), SerializeOptions::binary_le()] {
+        let expected = to_bytes(&mesh, options.clone()).unwrap();
+
+        // Everything reaches the inner writer without flushing the BufWriter by hand.
+        let mut writer = BufWriter::new(Vec::new());
+        serde_ply::to_writer(&mesh, options.clone(), &mut writer).unwrap();
+        assert!(writer.buffer().is_empty());
+        assert_eq!(writer.get_ref(), &expected);
+
+        let err =
+            serde_ply::to_writer(&mesh, options.clone(), FailingFlush(Vec::new())).unwrap_err();
+        assert_eq!(err.0.to_string(), "disk full");
+        let err = serde_ply::to_writer(&mesh, options, BufWriter::new(FailingFlush(Vec::new())))
+            .unwrap_err();
+        assert_eq!(err.0.to_string(), "disk full");
+    }
+
+    let ply_data = "ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nproperty float y\nproperty float z\nend_header\n1 2 3\n";
+    let mut writer = BufWriter::new(Vec::new());
+    serde_ply::map_element(
+        Cursor::new(ply_data),
+        &mut writer,
+        "vertex",
+        |v: Vertex| v,
+        SerializeOptions::ascii(),
+    )
+    .unwrap();
+    assert!(writer.buffer().is_empty());
+    assert!(writer.get_ref().ends_with(b"1 2 3\n"));
+}
+
+#[test]
+fn test_buffer_hint() {
+    use std::io::Write;
+
+    /// Counts the writes that reach it.
+    struct CountingWriter {
+        bytes: Vec<u8>,
+        writes: usize,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.writes += 1;
+            self.bytes.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mesh = Mesh {
+        vertex: (0..100)
+            .map(|i| Vertex {
+                x: i as f32,
+                y: 0.5,
+                z: -1.0,
+            })
+            .collect(),
+        face: vec![Face {
+            vertex_indices: vec![0, 1, 2],
+        }],
+    };
+
+    for format in [
+        SerializeOptions::ascii,
+        SerializeOptions::binary_le,
+        SerializeOptions::binary_be,
+    ] {
+        let expected = to_bytes(&mesh, format()).unwrap();
+        let mut direct = CountingWriter {
+            bytes: Vec::new(),
+            writes: 0,
+        };
+        serde_ply::to_writer(&mesh, format(), &mut direct).unwrap();
+        assert_eq!(direct.bytes, expected);
+
+        for capacity in [1, 7, 64 * 1024] {
+            let mut buffered = CountingWriter {
+                bytes: Vec::new(),
+                writes: 0,
+            };
+            serde_ply::to_writer(&mesh, format().buffer_hint(capacity), &mut buffered).unwrap();
+            assert_eq!(buffered.bytes, expected, "capacity {capacity}");
+            if capacity == 64 * 1024 {
+                assert_eq!(buffered.writes, 1);
+                assert!(direct.writes > 100);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_float_list_count_type_rejected() {
+    use serde_ply::{ElementDef, PlyProperty, PropertyType, ScalarType};
+
+    #[derive(Serialize)]
+    struct Face {
+        vertex_indices: Vec<u32>,
+    }
+
+    #[derive(Serialize)]
+    struct Mesh {
+        face: Vec<Face>,
+    }
+
+    let mesh = Mesh {
+        face: vec![Face {
+            vertex_indices: vec![0, 1, 2],
+        }],
+    };
+
+    for count_type in [ScalarType::F32, ScalarType::F64] {
+        let err = to_bytes(
+            &mesh,
+            SerializeOptions::ascii().default_list_count_type(count_type),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains(&format!(
+                "List count type {count_type:?} is not an integer type"
+            )),
+            "{err}"
+        );
+
+        let def = ElementDef {
+            name: "face".to_string(),
+            count: 1,
+            properties: vec![PlyProperty::new(
+                "vertex_indices",
+                PropertyType::List {
+                    count_type,
+                    data_type: ScalarType::U32,
+                },
+            )],
+        };
+        let mut buf = Vec::new();
+        // Nothing is written, not even the header.
+        let err = serde_ply::to_writer(
+            &mesh,
+            SerializeOptions::binary_le().with_element_def(def),
+            &mut buf,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("use ScalarType::U8, U16 or U32"));
+        assert!(buf.is_empty());
+    }
+}
+
+#[test]
+fn test_error_conversion() {
+    use serde_ply::{DeserializeError, SerializeError};
+
+    let ply = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nend_header\n";
+    let mut reader = serde_ply::PlyReader::from_reader(ply.as_bytes()).unwrap();
+    let err = reader.next_element::<Vec<Vertex>>().unwrap_err();
+    let message = err.0.to_string();
+    let err = SerializeError::from(err);
+    assert_eq!(
+        err.to_string(),
+        format!("Error while serializing ply: {message}")
+    );
+    // The cause survives the round trip.
+    let err = DeserializeError::from(err);
+    assert_eq!(err.as_missing_data().unwrap().expected_rows, 2);
+
+    let mut small = [0u8; 4];
+    let err = serde_ply::to_slice(&create_test_mesh(), SerializeOptions::ascii(), &mut small)
+        .unwrap_err();
+    let err = DeserializeError::from(err);
+    assert_eq!(err.0.kind(), std::io::ErrorKind::WriteZero);
+    assert!(SerializeError::from(err).as_buffer_too_small().is_some());
+}
+
+#[test]
+fn test_array_as_list_or_columns() {
+    use serde_ply::{ElementDef, PlyProperty, PropertyType, ScalarType};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Vertex {
+        x: f32,
+        rgba: [u8; 4],
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Cloud {
+        vertex: Vec<Vertex>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct ColumnVertex {
+        x: f32,
+        r: u8,
+        g: u8,
+        b: u8,
+        a: u8,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct ColumnCloud {
+        vertex: Vec<ColumnVertex>,
+    }
+
+    let cloud = Cloud {
+        vertex: vec![
+            Vertex {
+                x: 1.0,
+                rgba: [255, 128, 0, 255],
+            },
+            Vertex {
+                x: 2.0,
+                rgba: [1, 2, 3, 4],
+            },
+        ],
+    };
+
+    for options in [SerializeOptions::ascii(), SerializeOptions::binary_le()] {
+        // By default an array is a list.
+        let bytes = serde_ply::to_bytes(&cloud, options.clone()).unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains("property list uint8 uint8 rgba\n"));
+        let read: Cloud = serde_ply::from_bytes(&bytes).unwrap();
+        assert_eq!(read, cloud);
+
+        let options = options.array_as_columns("vertex", "rgba", ["r", "g", "b", "a"]);
+        let bytes = serde_ply::to_bytes(&cloud, options).unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains(
+            "property float x\nproperty uchar r\nproperty uchar g\nproperty uchar b\nproperty uchar a\nend_header\n"
+        ));
+        let read: ColumnCloud = serde_ply::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            read.vertex[1],
+            ColumnVertex {
+                x: 2.0,
+                r: 1,
+                g: 2,
+                b: 3,
+                a: 4
+            }
+        );
+    }
+
+    // An element definition still sets the types of the columns.
+    let def = ElementDef {
+        name: "vertex".to_string(),
+        count: 0,
+        properties: vec![PlyProperty::new("a", PropertyType::Scalar(ScalarType::F32))],
+    };
+    let options = SerializeOptions::ascii()
+        .array_as_columns("vertex", "rgba", ["r", "g", "b", "a"])
+        .with_element_def(def);
+    let ply = serde_ply::to_string(&cloud, options).unwrap();
+    assert!(ply.contains("property uchar b\nproperty float a\n"));
+    assert!(ply.ends_with("end_header\n1 255 128 0 255\n2 1 2 3 4\n"));
+
+    // The number of columns has to match the array.
+    let options = SerializeOptions::ascii().array_as_columns("vertex", "rgba", ["r", "g", "b"]);
+    let err = serde_ply::to_string(&cloud, options).unwrap_err();
+    assert!(err.to_string().contains(
+        "property 'rgba' of element 'vertex' has 4 entries, but is written as 3 columns"
+    ));
+}
+
+#[test]
+fn test_duplicate_element_names() {
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Point {
+        x: f32,
+    }
+
+    #[derive(Serialize)]
+    struct Scan {
+        vertex: Vec<Point>,
+        #[serde(rename = "vertex")]
+        extra: Vec<Point>,
+    }
+
+    let scan = Scan {
+        vertex: vec![Point { x: 1.0 }],
+        extra: vec![Point { x: 2.0 }],
+    };
+    let mut buf = Vec::new();
+    let err = serde_ply::to_writer(&scan, SerializeOptions::ascii(), &mut buf).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("element 'vertex' is written twice, element names must be unique"));
+    // Caught before the second element is declared.
+    assert!(!String::from_utf8_lossy(&buf).contains("element vertex 1\nproperty float x\nelement"));
+
+    // Empty elements left out of the file still count.
+    let scan = Scan {
+        vertex: vec![],
+        extra: vec![Point { x: 2.0 }],
+    };
+    let options = SerializeOptions::ascii().skip_empty_elements(true);
+    let err = serde_ply::to_string(&scan, options).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("element 'vertex' is written twice"));
+
+    // Entries given as a sequence can repeat a name too.
+    let entries = vec![("face", vec![Point { x: 1.0 }]), ("face", vec![])];
+    let err = serde_ply::to_string(&entries, SerializeOptions::ascii()).unwrap_err();
+    assert!(err.to_string().contains("element 'face' is written twice"));
+
+    // Names are case sensitive.
+    let elements = BTreeMap::from([
+        ("Vertex", vec![Point { x: 1.0 }]),
+        ("vertex", vec![Point { x: 2.0 }]),
+    ]);
+    let ply = serde_ply::to_string(&elements, SerializeOptions::ascii()).unwrap();
+    assert!(ply.contains("element Vertex 1\nproperty float x\nelement vertex 1\n"));
+    assert!(ply.ends_with("end_header\n1\n2\n"));
+}
+
+#[test]
+fn test_default_array_mode() {
+    use serde_ply::{ArrayMode, ListCountU16};
+
+    #[derive(Serialize)]
+    struct Vertex {
+        position: [f32; 3],
+        rgb: [u8; 3],
+        uv: ListCountU16<[f32; 2]>,
+    }
+
+    #[derive(Serialize)]
+    struct Cloud {
+        vertex: Vec<Vertex>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Point {
+        position_0: f32,
+        position_1: f32,
+        position_2: f32,
+        rgb: Vec<u8>,
+        uv: Vec<f32>,
+    }
+
+    #[derive(Deserialize)]
+    struct Points {
+        vertex: Vec<Point>,
+    }
+
+    let cloud = Cloud {
+        vertex: vec![
+            Vertex {
+                position: [1.0, 2.0, 3.0],
+                rgb: [255, 0, 0],
+                uv: ListCountU16([0.5, 0.25]),
+            },
+            Vertex {
+                position: [4.0, 5.0, 6.0],
+                rgb: [0, 255, 0],
+                uv: ListCountU16([1.0, 0.0]),
+            },
+        ],
+    };
+
+    let ply = serde_ply::to_string(
+        &cloud,
+        SerializeOptions::ascii().default_array_mode(ArrayMode::List),
+    )
+    .unwrap();
+    assert!(ply.contains(
+        "property list uint8 float32 position\nproperty list uint8 uint8 rgb\nproperty list uint16 float32 uv\n"
+    ));
+    assert!(ply.ends_with("end_header\n3 1 2 3 3 255 0 0 2 0.5 0.25\n3 4 5 6 3 0 255 0 2 1 0\n"));
+
+    // Columns everywhere, except where a property asks for a list.
+    for options in [SerializeOptions::ascii(), SerializeOptions::binary_le()] {
+        let options = options
+            .default_array_mode(ArrayMode::Columns)
+            .array_as_list("vertex", "rgb");
+        let bytes = serde_ply::to_bytes(&cloud, options).unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains(
+            "property float position_0\nproperty float position_1\nproperty float position_2\n\
+             property list uint8 uint8 rgb\nproperty list uint16 float32 uv\n"
+        ));
+
+        let read: Points = serde_ply::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            read.vertex[1],
+            Point {
+                position_0: 4.0,
+                position_1: 5.0,
+                position_2: 6.0,
+                rgb: vec![0, 255, 0],
+                uv: vec![1.0, 0.0],
+            }
+        );
+    }
+}
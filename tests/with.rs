@@ -0,0 +1,115 @@
+#![cfg(feature = "with")]
+
+use serde::{Deserialize, Serialize};
+use serde_ply::{from_bytes, to_bytes, SerializeOptions};
+
+const FORMATS: [fn() -> SerializeOptions; 3] = [
+    SerializeOptions::ascii,
+    SerializeOptions::binary_le,
+    SerializeOptions::binary_be,
+];
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Stored {
+    red: u8,
+    depth: u16,
+    color: u32,
+    scale: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Transformed {
+    #[serde(with = "serde_ply::with::u8_normalized")]
+    red: f32,
+    #[serde(with = "serde_ply::with::u16_normalized")]
+    depth: f32,
+    #[serde(with = "serde_ply::with::rgba_u32")]
+    color: [f32; 4],
+    #[serde(with = "serde_ply::with::log_scale")]
+    scale: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Ply<T> {
+    vertex: Vec<T>,
+}
+
+#[test]
+fn test_stored_values_round_trip() {
+    let stored = Ply {
+        vertex: vec![
+            Stored {
+                red: 0,
+                depth: 0,
+                color: 0,
+                scale: 0.0,
+            },
+            Stored {
+                red: 255,
+                depth: 65535,
+                color: 0xFFFF_FFFF,
+                scale: -2.5,
+            },
+            Stored {
+                red: 77,
+                depth: 12345,
+                color: 0x12_80_FE_01,
+                scale: 1.25,
+            },
+        ],
+    };
+
+    for format in FORMATS {
+        let bytes = to_bytes(&stored, format()).unwrap();
+        let transformed: Ply<Transformed> = from_bytes(&bytes).unwrap();
+        let row = &transformed.vertex[2];
+        assert_eq!(row.red, 77.0 / 255.0);
+        assert_eq!(row.depth, 12345.0 / 65535.0);
+        assert_eq!(
+            row.color,
+            [18.0 / 255.0, 128.0 / 255.0, 254.0 / 255.0, 1.0 / 255.0]
+        );
+        assert_eq!(row.scale, 1.25f32.exp());
+
+        // Writing the transformed rows gives the same properties, with the integers exact.
+        let bytes = to_bytes(&transformed, format()).unwrap();
+        let header = String::from_utf8_lossy(&bytes[..serde_ply::find_data_start(&bytes).unwrap()]);
+        assert!(header.contains(
+            "property uchar red\nproperty ushort depth\nproperty uint color\nproperty float scale\n"
+        ));
+        let back: Ply<Stored> = from_bytes(&bytes).unwrap();
+        for (back, stored) in back.vertex.iter().zip(&stored.vertex) {
+            assert_eq!(
+                (back.red, back.depth, back.color),
+                (stored.red, stored.depth, stored.color)
+            );
+            assert!((back.scale - stored.scale).abs() <= 1e-6);
+        }
+    }
+}
+
+#[test]
+fn test_normalized_values_clamp_and_round() {
+    let transformed = Ply {
+        vertex: vec![Transformed {
+            red: 1.5,
+            depth: 0.5,
+            color: [-1.0, 0.5, 0.502, 2.0],
+            scale: 1.0,
+        }],
+    };
+
+    for format in FORMATS {
+        let bytes = to_bytes(&transformed, format()).unwrap();
+        let stored: Ply<Stored> = from_bytes(&bytes).unwrap();
+        assert_eq!(
+            stored.vertex[0],
+            Stored {
+                red: 255,
+                depth: 32768,
+                color: 0x00_80_80_FF,
+                scale: 0.0,
+            }
+        );
+    }
+}